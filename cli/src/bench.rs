@@ -0,0 +1,264 @@
+use crate::config::CliConfig;
+use crate::send::local_platform;
+use clap::Args;
+use multi_push::{Message, MessageType, PushError, PushPlatformCapabilities, PushResult};
+use multi_push_client::Client;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 本地模式下未配置该通道时，使用进程内丢弃消息的控制台通道
+const CONSOLE_CHANNEL: &str = "console";
+
+/// `bench` 子命令参数
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// 目标通道
+    #[arg(short, long)]
+    channel: String,
+    /// 每秒发送的消息数
+    #[arg(long, default_value_t = 100)]
+    rate: u32,
+    /// 持续时间，如 `60s`、`5m`
+    #[arg(long, value_parser = parse_duration, default_value = "10s")]
+    duration: Duration,
+    /// 最大并发请求数，达到后按实际完成速度发送
+    #[arg(long, default_value_t = 64)]
+    concurrency: usize,
+    /// 消息内容
+    #[arg(long, default_value = "multi_push bench")]
+    text: String,
+    /// 服务端地址，覆盖配置文件中的 `server_url`
+    #[arg(long, env = "MULTI_PUSH_URL")]
+    server: Option<String>,
+    /// 服务端 API Key，覆盖配置文件中的 `api_key`
+    #[arg(long, env = "MULTI_PUSH_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    /// 不经过服务端，在进程内直接调用平台
+    #[arg(long)]
+    local: bool,
+}
+
+/// 压测目标
+enum Target {
+    Remote {
+        client: Client,
+        channel: String,
+    },
+    Local(Box<dyn PushPlatformCapabilities>),
+    /// 只序列化消息，用于测量客户端自身的开销
+    Console,
+}
+
+impl Target {
+    async fn send(&self, message: Message) -> Result<PushResult, PushError> {
+        match self {
+            Self::Remote { client, channel } => client.push_to_channel(channel, &message).await,
+            Self::Local(platform) => platform.send_message(message).await,
+            Self::Console => {
+                serde_json::to_string(&message)
+                    .map_err(|e| PushError::MessageError(e.to_string()))?;
+                Ok(PushResult::default())
+            }
+        }
+    }
+}
+
+/// 压测结果
+#[derive(Debug)]
+pub struct Report {
+    pub elapsed: Duration,
+    pub failed: usize,
+    /// 成功请求的耗时，升序
+    pub latencies: Vec<Duration>,
+    /// 第一个错误
+    pub first_error: Option<PushError>,
+}
+
+impl Report {
+    pub fn sent(&self) -> usize {
+        self.latencies.len() + self.failed
+    }
+
+    /// 每秒完成的请求数
+    pub fn throughput(&self) -> f64 {
+        self.sent() as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// 成功请求耗时的分位数，`q` 取 0 到 1
+    pub fn percentile(&self, q: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let rank = (q * self.latencies.len() as f64).ceil() as usize;
+        Some(self.latencies[rank.clamp(1, self.latencies.len()) - 1])
+    }
+}
+
+/// 执行 `bench` 子命令
+pub async fn run(args: BenchArgs, config: CliConfig) -> Result<Report, PushError> {
+    if args.rate == 0 || args.concurrency == 0 {
+        return Err(PushError::ConfigError(
+            "--rate and --concurrency must be positive".to_string(),
+        ));
+    }
+    let target = if args.local {
+        if args.channel == CONSOLE_CHANNEL && !config.push.channels.contains_key(CONSOLE_CHANNEL) {
+            Target::Console
+        } else {
+            Target::Local(local_platform(&config.push, &args.channel).await?)
+        }
+    } else {
+        let server = args.server.or(config.server_url).ok_or_else(|| {
+            PushError::ConfigError(
+                "No server configured, set --server, MULTI_PUSH_URL or use --local".to_string(),
+            )
+        })?;
+        let mut client = Client::new(server);
+        if let Some(api_key) = args.api_key.or(config.api_key) {
+            client = client.with_api_key(api_key);
+        }
+        Target::Remote {
+            client,
+            channel: args.channel.clone(),
+        }
+    };
+    let message = Message::new(MessageType::Text(args.text));
+    Ok(bench(
+        Arc::new(target),
+        message,
+        args.rate,
+        args.duration,
+        args.concurrency,
+    )
+    .await)
+}
+
+/// 按 `rate` 匀速发送 `duration`，并发超过 `concurrency` 时等待已有请求完成
+async fn bench(
+    target: Arc<Target>,
+    message: Message,
+    rate: u32,
+    duration: Duration,
+    concurrency: usize,
+) -> Report {
+    let mut ticker = tokio::time::interval(Duration::from_secs(1) / rate);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    let mut report = Report {
+        elapsed: Duration::ZERO,
+        failed: 0,
+        latencies: Vec::new(),
+        first_error: None,
+    };
+    let started = Instant::now();
+    while started.elapsed() < duration {
+        ticker.tick().await;
+        let Ok(permit) = semaphore.clone().acquire_owned().await else {
+            break;
+        };
+        let target = target.clone();
+        let message = message.clone();
+        tasks.spawn(async move {
+            let sent = Instant::now();
+            let result = target.send(message).await;
+            drop(permit);
+            (sent.elapsed(), result)
+        });
+        while let Some(joined) = tasks.try_join_next() {
+            record(&mut report, joined);
+        }
+    }
+    while let Some(joined) = tasks.join_next().await {
+        record(&mut report, joined);
+    }
+    report.elapsed = started.elapsed();
+    report.latencies.sort();
+    report
+}
+
+fn record(
+    report: &mut Report,
+    joined: Result<(Duration, Result<PushResult, PushError>), tokio::task::JoinError>,
+) {
+    match joined {
+        Ok((latency, Ok(_))) => report.latencies.push(latency),
+        Ok((_, Err(e))) => {
+            report.failed += 1;
+            report.first_error.get_or_insert(e);
+        }
+        Err(e) => {
+            report.failed += 1;
+            report
+                .first_error
+                .get_or_insert(PushError::PlatformError(e.to_string()));
+        }
+    }
+}
+
+/// 解析 `30s`、`5m`、`1h` 形式的时长，不带单位时按秒计
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", value))?;
+    let secs = match unit {
+        "ms" => return Ok(Duration::from_millis(number)),
+        "s" => number,
+        "m" => number * 60,
+        "h" => number * 60 * 60,
+        _ => return Err(format!("invalid duration '{}', expected e.g. 60s", value)),
+    };
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("60s"), Ok(Duration::from_secs(60)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("5w").is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let report = Report {
+            elapsed: Duration::from_secs(2),
+            failed: 1,
+            latencies: (1..=100).map(Duration::from_millis).collect(),
+            first_error: None,
+        };
+        assert_eq!(report.sent(), 101);
+        assert_eq!(report.throughput(), 50.5);
+        assert_eq!(report.percentile(0.5), Some(Duration::from_millis(50)));
+        assert_eq!(report.percentile(0.99), Some(Duration::from_millis(99)));
+        assert_eq!(report.percentile(1.0), Some(Duration::from_millis(100)));
+    }
+
+    #[tokio::test]
+    async fn test_bench_console() {
+        let message = Message::new(MessageType::Text("bench".to_string()));
+        let report = bench(
+            Arc::new(Target::Console),
+            message,
+            200,
+            Duration::from_millis(100),
+            4,
+        )
+        .await;
+        assert_eq!(report.failed, 0);
+        assert!(report.sent() > 0);
+        assert!(report.percentile(0.5).is_some());
+    }
+}
@@ -0,0 +1,304 @@
+use clap::Args;
+use multi_push::{MessageType, MultiPushConfig, PlatformRegistry, PushError, parse_timezone};
+use serde_json::Value;
+use server::config::ServerConfig;
+use std::path::PathBuf;
+
+/// 服务端用于组合降级通道的伪平台
+const FALLBACK_PLATFORM: &str = "fallback";
+
+/// `validate` 子命令参数
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// 配置文件路径，未指定时使用 `--config`
+    pub path: Option<PathBuf>,
+}
+
+/// `doctor` 子命令参数
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// 配置文件路径，未指定时使用 `--config`
+    pub path: Option<PathBuf>,
+    /// 健康检查通过后向每个通道发送一条测试消息
+    #[arg(long)]
+    pub send_test: bool,
+}
+
+/// 配置问题，`location` 形如 `channels.ops`、`routes[0]`
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    pub location: String,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(location: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// 静态检查服务端配置：平台是否存在、通道配置是否符合平台 schema、引用的通道是否存在，
+/// 以及存储、去重、机器人命令等服务端专有的配置
+pub fn validate(config: &ServerConfig, registry: &PlatformRegistry) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let push = &config.push;
+    for (name, channel) in sorted(push) {
+        let location = format!("channels.{}", name);
+        if let Some(Err(e)) = channel.timezone.as_deref().map(parse_timezone) {
+            problems.push(Problem::new(&location, e.to_string()));
+        }
+        if channel.platform == FALLBACK_PLATFORM {
+            problems.extend(check_fallback(push, &channel.config, &location));
+            continue;
+        }
+        let Some(factory) = registry.get_factory(&channel.platform) else {
+            problems.push(Problem::new(
+                location,
+                format!("Platform '{}' not found", channel.platform),
+            ));
+            continue;
+        };
+        let schema_problems = check_schema(&factory.config_schema(), &channel.platform_config());
+        if !schema_problems.is_empty() {
+            problems.extend(
+                schema_problems
+                    .into_iter()
+                    .map(|message| Problem::new(&location, message)),
+            );
+            continue;
+        }
+        // schema 只覆盖字段和类型，其余约束由平台创建时校验
+        if let Err(e) = registry.create(&channel.platform, channel.platform_config()) {
+            problems.push(Problem::new(location, e.to_string()));
+        }
+    }
+
+    let mut platforms: Vec<&String> = push.defaults.keys().collect();
+    platforms.sort();
+    for platform in platforms {
+        if !registry.contains(platform) {
+            problems.push(Problem::new(
+                format!("defaults.{}", platform),
+                format!("Platform '{}' not found", platform),
+            ));
+        }
+    }
+    for (i, route) in push.routes.iter().enumerate() {
+        for channel in &route.channels {
+            if !push.channels.contains_key(channel) {
+                problems.push(Problem::new(
+                    format!("routes[{}]", i),
+                    format!("Channel '{}' not found", channel),
+                ));
+            }
+        }
+    }
+    for (section, e) in config.check() {
+        problems.push(Problem::new(section, e.to_string()));
+    }
+    problems
+}
+
+/// 初始化每个通道并执行健康检查，可选发送测试消息，返回每个通道的结果
+pub async fn doctor(
+    config: &MultiPushConfig,
+    registry: &PlatformRegistry,
+    send_test: bool,
+) -> Vec<(String, Result<(), PushError>)> {
+    let mut results = Vec::new();
+    for (name, channel) in sorted(config) {
+        // 降级通道由其成员通道各自检查
+        if channel.platform == FALLBACK_PLATFORM {
+            continue;
+        }
+        let result = async {
+            let mut platform = registry.create(&channel.platform, channel.platform_config())?;
+            platform.init().await?;
+            if !platform.health_check().await? {
+                return Err(PushError::PlatformError("Health check failed".to_string()));
+            }
+            if send_test {
+                let content = format!("multi_push doctor: test message for channel '{}'", name);
+                platform
+                    .send_message(MessageType::Text(content).into())
+                    .await?;
+            }
+            Ok(())
+        }
+        .await;
+        results.push((name.to_string(), result));
+    }
+    results
+}
+
+/// 按名称排序的通道，保证输出稳定
+fn sorted(config: &MultiPushConfig) -> Vec<(&str, &multi_push::ChannelConfig)> {
+    let mut channels: Vec<_> = config
+        .channels
+        .iter()
+        .map(|(name, channel)| (name.as_str(), channel))
+        .collect();
+    channels.sort_by_key(|(name, _)| *name);
+    channels
+}
+
+fn check_fallback(config: &MultiPushConfig, fallback: &Value, location: &str) -> Vec<Problem> {
+    let Some(channels) = fallback.get("channels").and_then(Value::as_array) else {
+        return vec![Problem::new(
+            location,
+            "Fallback channel requires a 'channels' array",
+        )];
+    };
+    channels
+        .iter()
+        .filter_map(|channel| {
+            let Some(channel) = channel.as_str() else {
+                return Some(Problem::new(location, "Fallback channels must be strings"));
+            };
+            match config.channels.get(channel) {
+                None => Some(Problem::new(
+                    location,
+                    format!("Channel '{}' not found", channel),
+                )),
+                Some(c) if c.platform == FALLBACK_PLATFORM => Some(Problem::new(
+                    location,
+                    format!("Fallback channel '{}' cannot be nested", channel),
+                )),
+                Some(_) => None,
+            }
+        })
+        .collect()
+}
+
+/// 按平台 schema 检查必填字段和字段类型
+fn check_schema(schema: &Value, config: &Value) -> Vec<String> {
+    let Value::Object(config) = config else {
+        return vec!["Platform config must be an object".to_string()];
+    };
+    let mut problems = Vec::new();
+    for key in schema["required"].as_array().into_iter().flatten() {
+        if let Some(key) = key.as_str().filter(|key| !config.contains_key(*key)) {
+            problems.push(format!("Missing required field '{}'", key));
+        }
+    }
+    for (key, value) in config {
+        let Some(kind) = schema["properties"][key]["type"].as_str() else {
+            continue;
+        };
+        if !value.is_null() && !matches_type(kind, value) {
+            problems.push(format!("Field '{}' should be {}", key, kind));
+        }
+    }
+    problems
+}
+
+fn matches_type(kind: &str, value: &Value) -> bool {
+    match kind {
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_push::{ConfigFormat, parse_config};
+
+    fn config(content: &str) -> ServerConfig {
+        parse_config(content, ConfigFormat::Toml).unwrap()
+    }
+
+    #[test]
+    fn test_check_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "key": { "type": "string" }, "timeout": { "type": "integer" } },
+            "required": ["key"],
+        });
+        assert!(check_schema(&schema, &serde_json::json!({"key": "k", "timeout": 3})).is_empty());
+        assert_eq!(
+            check_schema(&schema, &serde_json::json!({"timeout": "3"})),
+            vec![
+                "Missing required field 'key'".to_string(),
+                "Field 'timeout' should be integer".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let config = config(
+            r#"
+[channels.ops]
+platform = "wxwork"
+config = { token = "xxx" }
+timezone = "Asia/Shanghai"
+
+[channels.oncall]
+platform = "wxwork"
+config = { token = "xxx" }
+timezone = "Mars/Base"
+
+[channels.qa]
+platform = "wxwork"
+config = { proxy = 8080 }
+
+[channels.dev]
+platform = "unknown"
+config = {}
+
+[channels.backup]
+platform = "fallback"
+config = { channels = ["ops", "missing"] }
+
+[[routes]]
+channels = ["ops", "staging"]
+
+[storage]
+url = "mongodb://db/multi_push"
+
+[dedup]
+url = "redis://cache"
+"#,
+        );
+        let problems = validate(&config, &multi_push::default_registry());
+        assert_eq!(
+            problems,
+            vec![
+                Problem::new("channels.backup", "Channel 'missing' not found"),
+                Problem::new("channels.dev", "Platform 'unknown' not found"),
+                Problem::new(
+                    "channels.oncall",
+                    "Configuration error: Unknown timezone 'Mars/Base'"
+                ),
+                Problem::new("channels.qa", "Missing required field 'token'"),
+                Problem::new("channels.qa", "Field 'proxy' should be string"),
+                Problem::new("routes[0]", "Channel 'staging' not found"),
+                Problem::new(
+                    "storage",
+                    "Configuration error: Unsupported storage URL 'mongodb://db/multi_push'"
+                ),
+                Problem::new(
+                    "dedup",
+                    "Configuration error: Redis dedup store requires the `redis` feature"
+                ),
+            ]
+        );
+        // 服务端专有的配置同样按 schema 解析
+        assert!(
+            parse_config::<ServerConfig>(
+                "[validation]\nmax_content_bytes = \"64k\"",
+                ConfigFormat::Toml
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use multi_push::{MultiPushConfig, PushError, load_config};
+use serde::Deserialize;
+use std::path::Path;
+
+/// 命令行配置文件，通道配置与服务端相同，`--local` 模式下直接使用
+#[derive(Debug, Default, Deserialize)]
+pub struct CliConfig {
+    /// 服务端地址
+    #[serde(default)]
+    pub server_url: Option<String>,
+    /// 服务端 API Key
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// 通道、平台默认配置和路由规则
+    #[serde(flatten)]
+    pub push: MultiPushConfig,
+}
+
+impl CliConfig {
+    /// 读取配置文件，未指定路径时返回空配置
+    pub fn load(path: Option<&Path>) -> Result<Self, PushError> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+        let mut config: Self = load_config(path)?;
+        config.push.apply_defaults();
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_push::{ConfigFormat, parse_config};
+
+    #[test]
+    fn test_cli_config() {
+        let config: CliConfig = parse_config(
+            r#"
+server_url = "http://localhost:8888"
+
+[channels.ops]
+platform = "wxwork"
+config = { token = "xxx" }
+"#,
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(config.server_url.as_deref(), Some("http://localhost:8888"));
+        assert_eq!(config.api_key, None);
+        assert_eq!(config.push.channels["ops"].platform, "wxwork");
+    }
+}
@@ -0,0 +1,259 @@
+use clap::Args;
+use dialoguer::{Confirm, Input, Password, Select};
+use multi_push::{
+    ChannelConfig, ConfigFormat, MessageType, MultiPushConfig, PlatformRegistry, PushError,
+    default_registry, serialize_config,
+};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+/// 输入时不回显的字段名
+const SECRET_FIELDS: &[&str] = &["token", "secret", "key", "password", "api_key"];
+
+/// `init` 子命令参数
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// 写入的配置文件路径，格式按扩展名识别
+    #[arg(default_value = "multi_push.toml")]
+    pub path: PathBuf,
+    /// 覆盖已存在的文件
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// 交互式配置通道并写入配置文件
+pub async fn run(args: InitArgs) -> Result<(), PushError> {
+    if args.path.exists() && !args.force {
+        return Err(PushError::ConfigError(format!(
+            "{} already exists, use --force to overwrite",
+            args.path.display()
+        )));
+    }
+    let format = ConfigFormat::from_path(&args.path).ok_or_else(|| {
+        PushError::ConfigError(format!(
+            "Unsupported config format: {}",
+            args.path.display()
+        ))
+    })?;
+
+    let registry = default_registry();
+    let mut platforms = registry.list_platforms();
+    platforms.sort();
+    let mut config = MultiPushConfig::default();
+    loop {
+        let mut items: Vec<&str> = platforms.iter().map(String::as_str).collect();
+        items.push("Done");
+        let selected = Select::new()
+            .with_prompt("Platform to configure")
+            .items(&items)
+            .default(0)
+            .interact()
+            .map_err(prompt_error)?;
+        if selected == platforms.len() {
+            break;
+        }
+        let platform = &platforms[selected];
+        if let Some((name, channel)) = channel(&registry, &config, platform).await? {
+            config.channels.insert(name, channel);
+        }
+    }
+    if config.channels.is_empty() {
+        return Err(PushError::ConfigError("No channels configured".to_string()));
+    }
+
+    let content = serialize_config(&config, format)?;
+    std::fs::write(&args.path, content).map_err(|e| {
+        PushError::ConfigError(format!("Failed to write {}: {}", args.path.display(), e))
+    })?;
+    println!(
+        "Wrote {} channel(s) to {}",
+        config.channels.len(),
+        args.path.display()
+    );
+    Ok(())
+}
+
+/// 配置一个通道，用户放弃时返回 `None`
+async fn channel(
+    registry: &PlatformRegistry,
+    config: &MultiPushConfig,
+    platform: &str,
+) -> Result<Option<(String, ChannelConfig)>, PushError> {
+    let name: String = Input::new()
+        .with_prompt("Channel name")
+        .default(platform.to_string())
+        .validate_with(|name: &String| {
+            if config.channels.contains_key(name) {
+                Err(format!("Channel '{}' already exists", name))
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()
+        .map_err(prompt_error)?;
+
+    let schema = registry
+        .get_factory(platform)
+        .map(|factory| factory.config_schema())
+        .unwrap_or_default();
+    let mut platform_config = Map::new();
+    for (field, kind, required) in fields(&schema) {
+        let prompt = match schema["properties"][&field]["description"].as_str() {
+            Some(description) => format!("{} ({})", field, description),
+            None => field.clone(),
+        };
+        let input = if SECRET_FIELDS.contains(&field.as_str()) {
+            Password::new()
+                .with_prompt(prompt)
+                .allow_empty_password(!required)
+                .interact()
+        } else {
+            Input::new()
+                .with_prompt(prompt)
+                .allow_empty(!required)
+                .validate_with(|input: &String| parse_value(&kind, input).map(|_| ()))
+                .interact_text()
+        }
+        .map_err(prompt_error)?;
+        if !input.is_empty() {
+            let value = parse_value(&kind, &input).map_err(PushError::ConfigError)?;
+            platform_config.insert(field, value);
+        }
+    }
+
+    let channel = ChannelConfig {
+        platform: platform.to_string(),
+        config: Value::Object(platform_config),
+        proxy: None,
+        retry: None,
+        decorations: Default::default(),
+        capabilities: Default::default(),
+        timezone: None,
+        policy: None,
+        quiet_hours: None,
+        budget: None,
+        emoji: None,
+        locale: None,
+        markdown_image: None,
+        sms: None,
+    };
+    let mut instance = match registry.create(platform, channel.platform_config()) {
+        Ok(instance) => instance,
+        Err(e) => {
+            eprintln!("Invalid config for channel '{}': {}", name, e);
+            return Ok(None);
+        }
+    };
+    let send_test = Confirm::new()
+        .with_prompt("Send a test message?")
+        .default(true)
+        .interact()
+        .map_err(prompt_error)?;
+    if send_test {
+        let content = format!("multi_push init: channel '{}' is configured", name);
+        let result = match instance.init().await {
+            Ok(()) => {
+                instance
+                    .send_message(MessageType::Text(content).into())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(_) => println!("Test message sent to '{}'", name),
+            Err(e) => {
+                eprintln!("Test message failed: {}", e);
+                let keep = Confirm::new()
+                    .with_prompt("Keep this channel anyway?")
+                    .default(false)
+                    .interact()
+                    .map_err(prompt_error)?;
+                if !keep {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+    Ok(Some((name, channel)))
+}
+
+/// schema 中的字段，必填字段在前，每项为（字段名，类型，是否必填）
+fn fields(schema: &Value) -> Vec<(String, String, bool)> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let mut fields: Vec<(String, String, bool)> = schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(field, property)| {
+            let kind = property["type"].as_str().unwrap_or("string").to_string();
+            (field.clone(), kind, required.contains(&field.as_str()))
+        })
+        .collect();
+    fields.sort_by_key(|(_, _, required)| !required);
+    fields
+}
+
+/// 按 schema 类型转换输入
+fn parse_value(kind: &str, input: &str) -> Result<Value, String> {
+    if input.is_empty() {
+        return Ok(Value::Null);
+    }
+    match kind {
+        "integer" => input
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("'{}' is not an integer", input)),
+        "number" => input
+            .parse::<f64>()
+            .map(Value::from)
+            .map_err(|_| format!("'{}' is not a number", input)),
+        "boolean" => input
+            .parse::<bool>()
+            .map(Value::from)
+            .map_err(|_| format!("'{}' is not true or false", input)),
+        "array" | "object" => serde_json::from_str(input).map_err(|e| e.to_string()),
+        _ => Ok(Value::String(input.to_string())),
+    }
+}
+
+fn prompt_error(e: dialoguer::Error) -> PushError {
+    PushError::ConfigError(format!("Prompt failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fields() {
+        let schema = json!({
+            "properties": {
+                "proxy": { "type": "string" },
+                "timeout": { "type": "integer" },
+                "token": { "type": "string" }
+            },
+            "required": ["token"]
+        });
+        let fields: Vec<(String, String, bool)> = fields(&schema);
+        assert_eq!(fields[0], ("token".to_string(), "string".to_string(), true));
+        assert_eq!(
+            fields[2],
+            ("timeout".to_string(), "integer".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("integer", "30"), Ok(json!(30)));
+        assert_eq!(parse_value("boolean", "true"), Ok(json!(true)));
+        assert_eq!(parse_value("array", r#"["a"]"#), Ok(json!(["a"])));
+        assert_eq!(parse_value("string", "abc"), Ok(json!("abc")));
+        assert!(parse_value("integer", "abc").is_err());
+    }
+}
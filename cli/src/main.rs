@@ -0,0 +1,194 @@
+//! multi_push 命令行工具
+
+use clap::{Parser, Subcommand};
+use server::config::ServerConfig;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+mod bench;
+mod check;
+mod config;
+mod init;
+mod send;
+
+/// 通过 multi_push 服务端或直接调用平台发送通知
+#[derive(Debug, Parser)]
+#[command(name = "multi_push", version)]
+struct Cli {
+    /// 配置文件路径，支持 JSON、TOML 和 YAML
+    #[arg(long, global = true, env = "MULTI_PUSH_CONFIG")]
+    config: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 向一个或多个通道发送消息
+    Send(Box<send::SendArgs>),
+    /// 校验配置文件，适合在 CI 中运行
+    Validate(check::ValidateArgs),
+    /// 校验配置并对每个通道执行健康检查，可选发送测试消息
+    Doctor(check::DoctorArgs),
+    /// 交互式配置通道并生成配置文件
+    Init(init::InitArgs),
+    /// 按固定速率向通道发送消息，报告吞吐量和延迟分位数
+    Bench(bench::BenchArgs),
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let path = match &cli.command {
+        Command::Send(_) | Command::Init(_) | Command::Bench(_) => None,
+        Command::Validate(args) => args.path.as_deref(),
+        Command::Doctor(args) => args.path.as_deref(),
+    }
+    .or(cli.config.as_deref())
+    .map(Path::to_path_buf);
+
+    match cli.command {
+        Command::Send(args) => {
+            let config = match load_cli_config(path.as_deref()) {
+                Ok(config) => config,
+                Err(code) => return code,
+            };
+            send(*args, config).await
+        }
+        Command::Bench(args) => {
+            let config = match load_cli_config(path.as_deref()) {
+                Ok(config) => config,
+                Err(code) => return code,
+            };
+            bench(args, config).await
+        }
+        Command::Validate(_) => match load_server_config(path.as_deref()) {
+            Ok(config) => validate(&config),
+            Err(code) => code,
+        },
+        Command::Init(args) => match init::run(args).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Command::Doctor(args) => {
+            let config = match load_server_config(path.as_deref()) {
+                Ok(config) => config,
+                Err(code) => return code,
+            };
+            if validate(&config) == ExitCode::FAILURE {
+                return ExitCode::FAILURE;
+            }
+            let registry = multi_push::default_registry();
+            let mut code = ExitCode::SUCCESS;
+            for (channel, result) in check::doctor(&config.push, &registry, args.send_test).await {
+                match result {
+                    Ok(()) => println!("ok    {}", channel),
+                    Err(e) => {
+                        println!("fail  {}: {}", channel, e);
+                        code = ExitCode::FAILURE;
+                    }
+                }
+            }
+            code
+        }
+    }
+}
+
+/// 发送和压测只需要通道配置，未指定配置文件时使用空配置
+fn load_cli_config(path: Option<&Path>) -> Result<config::CliConfig, ExitCode> {
+    config::CliConfig::load(path).map_err(|e| {
+        eprintln!("Failed to load config: {}", e);
+        ExitCode::FAILURE
+    })
+}
+
+/// 校验和诊断按服务端配置的完整 schema 解析，必须指定配置文件
+fn load_server_config(path: Option<&Path>) -> Result<ServerConfig, ExitCode> {
+    let Some(path) = path else {
+        eprintln!("No config file given, pass a path or set --config");
+        return Err(ExitCode::FAILURE);
+    };
+    ServerConfig::read(path).map_err(|e| {
+        eprintln!("Failed to load config: {}", e);
+        ExitCode::FAILURE
+    })
+}
+
+async fn send(args: send::SendArgs, config: config::CliConfig) -> ExitCode {
+    match send::run(args, config).await {
+        Ok(results) => {
+            let mut code = ExitCode::SUCCESS;
+            for (channel, result) in results {
+                match result {
+                    Ok(results) => {
+                        println!("Sent {} message(s) to '{}'", results.len(), channel)
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to send to '{}': {}", channel, e);
+                        code = ExitCode::FAILURE;
+                    }
+                }
+            }
+            code
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn bench(args: bench::BenchArgs, config: config::CliConfig) -> ExitCode {
+    match bench::run(args, config).await {
+        Ok(report) => {
+            println!(
+                "Sent {} message(s) in {:.1}s: {:.1} msg/s, {} failed",
+                report.sent(),
+                report.elapsed.as_secs_f64(),
+                report.throughput(),
+                report.failed
+            );
+            let percentiles = [("p50", 0.5), ("p90", 0.9), ("p99", 0.99), ("max", 1.0)];
+            let latencies: Vec<String> = percentiles
+                .iter()
+                .filter_map(|(name, q)| {
+                    report
+                        .percentile(*q)
+                        .map(|latency| format!("{} {:.1}ms", name, latency.as_secs_f64() * 1000.0))
+                })
+                .collect();
+            if !latencies.is_empty() {
+                println!("Latency: {}", latencies.join(", "));
+            }
+            match report.first_error {
+                Some(e) => {
+                    eprintln!("First error: {}", e);
+                    ExitCode::FAILURE
+                }
+                None => ExitCode::SUCCESS,
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 打印配置问题，没有问题时返回成功
+fn validate(config: &ServerConfig) -> ExitCode {
+    let problems = check::validate(config, &multi_push::default_registry());
+    for problem in &problems {
+        eprintln!("{}: {}", problem.location, problem.message);
+    }
+    if problems.is_empty() {
+        println!("Config is valid: {} channel(s)", config.push.channels.len());
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("Found {} problem(s)", problems.len());
+        ExitCode::FAILURE
+    }
+}
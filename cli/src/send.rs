@@ -0,0 +1,299 @@
+use crate::config::CliConfig;
+use clap::{ArgGroup, Args};
+use multi_push::{
+    AttachmentSource, Message, MessageType, MultiPushConfig, Priority, PushError,
+    PushPlatformCapabilities, PushResult, default_registry,
+};
+use multi_push_client::Client;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// `send` 子命令参数
+#[derive(Debug, Args)]
+#[command(group(ArgGroup::new("content").args(["text", "markdown", "html"])))]
+pub struct SendArgs {
+    /// 目标通道，可重复指定
+    #[arg(short, long = "channel", required = true)]
+    channels: Vec<String>,
+    /// 纯文本内容，`-` 表示从标准输入读取
+    #[arg(long)]
+    text: Option<String>,
+    /// Markdown 内容，`-` 表示从标准输入读取
+    #[arg(long)]
+    markdown: Option<String>,
+    /// HTML 内容，`-` 表示从标准输入读取
+    #[arg(long)]
+    html: Option<String>,
+    /// 作为附件发送的文件，可重复指定
+    #[arg(short, long = "file")]
+    files: Vec<PathBuf>,
+    /// @提及的用户，可重复指定
+    #[arg(long = "mention")]
+    mentions: Vec<String>,
+    /// 标签，形如 `env=prod`，可重复指定
+    #[arg(long = "label", value_parser = parse_label)]
+    labels: Vec<(String, String)>,
+    /// 会话键，相同键的消息在支持会话的平台上发到同一会话
+    #[arg(long)]
+    thread_key: Option<String>,
+    /// 消息优先级：low、normal、high、urgent
+    #[arg(long, value_parser = parse_priority, default_value = "normal")]
+    priority: Priority,
+    /// 单次请求超时（毫秒），覆盖通道配置
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+    /// 重试次数，覆盖通道配置
+    #[arg(long)]
+    retry_count: Option<u32>,
+    /// 服务端地址，覆盖配置文件中的 `server_url`
+    #[arg(long, env = "MULTI_PUSH_URL")]
+    server: Option<String>,
+    /// 服务端 API Key，覆盖配置文件中的 `api_key`
+    #[arg(long, env = "MULTI_PUSH_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    /// 不经过服务端，按配置文件中的通道直接调用平台
+    #[arg(long)]
+    local: bool,
+}
+
+/// 执行 `send` 子命令，返回每个通道的结果
+pub async fn run(
+    args: SendArgs,
+    config: CliConfig,
+) -> Result<Vec<(String, Result<Vec<PushResult>, PushError>)>, PushError> {
+    let messages = messages(&args)?;
+    let mut results = Vec::with_capacity(args.channels.len());
+    if args.local {
+        for channel in &args.channels {
+            let result = send_local(&config.push, channel, &messages).await;
+            results.push((channel.clone(), result));
+        }
+        return Ok(results);
+    }
+
+    let server = args.server.or(config.server_url).ok_or_else(|| {
+        PushError::ConfigError(
+            "No server configured, set --server, MULTI_PUSH_URL or use --local".to_string(),
+        )
+    })?;
+    let mut client = Client::new(server);
+    if let Some(api_key) = args.api_key.or(config.api_key) {
+        client = client.with_api_key(api_key);
+    }
+    for channel in &args.channels {
+        let result = send_remote(&client, channel, &messages).await;
+        results.push((channel.clone(), result));
+    }
+    Ok(results)
+}
+
+/// 由参数组装待发送的消息：正文在前，附件依次在后
+fn messages(args: &SendArgs) -> Result<Vec<Message>, PushError> {
+    let content = match (&args.text, &args.markdown, &args.html) {
+        (Some(text), _, _) => Some(MessageType::Text(read_content(text)?)),
+        (_, Some(markdown), _) => Some(MessageType::Markdown(read_content(markdown)?)),
+        (_, _, Some(html)) => Some(MessageType::Html(read_content(html)?)),
+        // 未指定内容时读取管道输入，交互终端下只发送附件
+        _ if !std::io::stdin().is_terminal() => {
+            let text = read_content("-")?;
+            (!text.is_empty()).then_some(MessageType::Text(text))
+        }
+        _ => None,
+    };
+
+    let mut contents: Vec<MessageType> = content.into_iter().collect();
+    for path in &args.files {
+        contents.push(attachment(path, args.local)?);
+    }
+    if contents.is_empty() {
+        return Err(PushError::MessageError("Nothing to send".to_string()));
+    }
+
+    let mut messages: Vec<Message> = contents
+        .into_iter()
+        .map(|content| {
+            let mut message = Message::new(content);
+            message.priority = args.priority;
+            message.labels = args.labels.iter().cloned().collect();
+            message.thread_key = args.thread_key.clone();
+            message.timeout_ms = args.timeout_ms;
+            message.retry_count = args.retry_count;
+            message
+        })
+        .collect();
+    messages[0].mentions = args.mentions.iter().map(|m| m.as_str().into()).collect();
+    Ok(messages)
+}
+
+/// `-` 表示从标准输入读取
+fn read_content(value: &str) -> Result<String, PushError> {
+    if value != "-" {
+        return Ok(value.to_string());
+    }
+    let mut content = String::new();
+    std::io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|e| PushError::MessageError(format!("Failed to read stdin: {}", e)))?;
+    Ok(content.trim_end().to_string())
+}
+
+/// 本地模式由平台直接读取文件，经服务端发送时内联文件内容
+fn attachment(path: &Path, local: bool) -> Result<MessageType, PushError> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| PushError::MessageError(format!("Invalid file: {}", path.display())))?;
+    let source = if local {
+        AttachmentSource::Path(path.to_path_buf())
+    } else {
+        let bytes = std::fs::read(path).map_err(|e| {
+            PushError::MessageError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        AttachmentSource::Bytes(bytes)
+    };
+    Ok(MessageType::File {
+        name,
+        mime: None,
+        source,
+    })
+}
+
+async fn send_remote(
+    client: &Client,
+    channel: &str,
+    messages: &[Message],
+) -> Result<Vec<PushResult>, PushError> {
+    let mut results = Vec::with_capacity(messages.len());
+    for message in messages {
+        results.push(client.push_to_channel(channel, message).await?);
+    }
+    Ok(results)
+}
+
+async fn send_local(
+    config: &MultiPushConfig,
+    channel: &str,
+    messages: &[Message],
+) -> Result<Vec<PushResult>, PushError> {
+    let platform = local_platform(config, channel).await?;
+    let mut results = Vec::with_capacity(messages.len());
+    for message in messages {
+        results.push(platform.send_message(message.clone()).await?);
+    }
+    Ok(results)
+}
+
+/// 按配置文件中的通道创建并初始化平台
+pub async fn local_platform(
+    config: &MultiPushConfig,
+    channel: &str,
+) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+    let channel_config = config
+        .channels
+        .get(channel)
+        .ok_or_else(|| PushError::ConfigError(format!("Channel '{}' not found", channel)))?;
+    let mut platform =
+        default_registry().create(&channel_config.platform, channel_config.platform_config())?;
+    platform.init().await?;
+    Ok(channel_config.decorate(channel, platform))
+}
+
+/// 解析 `key=value` 形式的标签参数
+fn parse_label(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid label '{}', expected key=value", value)),
+    }
+}
+
+/// 解析优先级参数
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase())).map_err(|_| {
+        format!(
+            "invalid priority '{}', expected low, normal, high or urgent",
+            value
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cli;
+    use clap::Parser;
+
+    fn send_args(args: &[&str]) -> SendArgs {
+        let args = [&["multi_push", "send"], args].concat();
+        match Cli::try_parse_from(args).unwrap().command {
+            crate::Command::Send(args) => *args,
+            command => panic!("unexpected command: {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_messages() {
+        let args = send_args(&[
+            "--channel",
+            "ops",
+            "--markdown",
+            "deploy **done**",
+            "--mention",
+            "alice",
+            "--priority",
+            "HIGH",
+            "--label",
+            "env=prod",
+            "--thread-key",
+            "deploy-42",
+            "--timeout-ms",
+            "2000",
+            "--retry-count",
+            "0",
+        ]);
+        let messages = messages(&args).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(
+            &messages[0].content,
+            MessageType::Markdown(m) if m == "deploy **done**"
+        ));
+        assert_eq!(messages[0].priority, Priority::High);
+        assert_eq!(messages[0].mentions.len(), 1);
+        assert_eq!(messages[0].labels["env"], "prod");
+        assert_eq!(messages[0].thread_key.as_deref(), Some("deploy-42"));
+        assert_eq!(
+            messages[0].timeout(),
+            Some(std::time::Duration::from_secs(2))
+        );
+        assert_eq!(messages[0].retry_count, Some(0));
+        assert!(parse_label("=prod").is_err());
+
+        assert!(Cli::try_parse_from(["multi_push", "send", "--text", "hi"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "multi_push",
+                "send",
+                "-c",
+                "ops",
+                "--text",
+                "a",
+                "--html",
+                "b"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_attachment() {
+        let path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+        assert!(matches!(
+            attachment(&path, true).unwrap(),
+            MessageType::File { name, source: AttachmentSource::Path(_), .. } if name == "Cargo.toml"
+        ));
+        assert!(matches!(
+            attachment(&path, false).unwrap(),
+            MessageType::File { source: AttachmentSource::Bytes(b), .. } if !b.is_empty()
+        ));
+        assert!(attachment(Path::new("missing.txt"), false).is_err());
+    }
+}
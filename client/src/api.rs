@@ -0,0 +1,256 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use common::{
+    DeliveryStatus, Mention, Message, MessageType, MetricSeries, PlatformInfo, Priority, PushResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// 推送请求体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushRequest {
+    /// 目标平台
+    pub platform: String,
+    /// 平台的配置信息
+    pub config: Value,
+    /// 消息内容
+    pub message: MessageType,
+    /// 优先级
+    #[serde(default)]
+    pub priority: Priority,
+    /// @提及列表
+    #[serde(default)]
+    pub mentions: Vec<Mention>,
+    /// 附加元数据
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// 标签，如 `service`、`env`、`team`
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// 会话键，相同键的后续消息发到同一会话
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_key: Option<String>,
+    /// 消息语言，如 `zh-CN`，用于选择模板的语言版本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// 指标趋势，随消息附带趋势图
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chart: Option<MetricSeries>,
+    /// 事件标记，服务端启用状态页时会被记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incident: Option<IncidentUpdate>,
+    /// 演练，服务端照常渲染但不实际发送
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// 单次请求超时（毫秒），覆盖平台配置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// 重试次数，覆盖平台配置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u32>,
+}
+
+impl PushRequest {
+    /// 向指定平台推送消息
+    pub fn new(platform: impl Into<String>, config: Value, message: impl Into<Message>) -> Self {
+        let message = message.into();
+        Self {
+            platform: platform.into(),
+            config,
+            message: message.content,
+            priority: message.priority,
+            mentions: message.mentions,
+            metadata: message.metadata,
+            labels: message.labels,
+            thread_key: message.thread_key,
+            locale: None,
+            chart: message.chart,
+            incident: None,
+            dry_run: false,
+            timeout_ms: message.timeout_ms,
+            retry_count: message.retry_count,
+        }
+    }
+
+    /// 设置消息语言
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// 附带事件标记
+    pub fn with_incident(mut self, incident: IncidentUpdate) -> Self {
+        self.incident = Some(incident);
+        self
+    }
+
+    /// 演练，结果的 `response` 为渲染后的请求载荷
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+/// 推送响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushResponse {
+    /// 推送结果
+    pub result: PushResult,
+}
+
+/// 消息在一个通道上的预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPreview {
+    pub channel: String,
+    /// 平台名称
+    pub platform: String,
+    /// 平台会收到的请求载荷，超长消息拆分后每条一个
+    pub payloads: Vec<Value>,
+    /// 渲染失败的原因
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// 通道测试响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelTestResponse {
+    pub channel: String,
+    pub success: bool,
+    /// 发送结果，包含上游响应体和 HTTP 状态码
+    #[serde(default)]
+    pub result: Option<PushResult>,
+    /// 发送失败的原因
+    #[serde(default)]
+    pub error: Option<ErrorBody>,
+}
+
+/// 付费通道当日的预算使用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub channel: String,
+    /// 统计日期，按 UTC 计算
+    pub date: NaiveDate,
+    /// 每条消息的费用
+    pub cost_per_message: f64,
+    /// 每日预算
+    pub limit: f64,
+    /// 当日已花费
+    pub spent: f64,
+    /// 当日剩余预算
+    pub remaining: f64,
+    /// 当日发送成功的消息数
+    pub sent: u64,
+    /// 当日因预算用尽被拒绝或改道的消息数
+    pub refused: u64,
+    /// 预算是否已不足以再发送一条
+    pub exhausted: bool,
+}
+
+/// 确认响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AckResponse {
+    /// 被确认消息的请求 ID
+    pub id: String,
+    /// 确认前已发送的次数
+    pub attempts: u32,
+}
+
+/// 多目标投递的结果汇总，编辑和撤回消息时返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryReport {
+    /// 来源系统的分组键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_key: Option<String>,
+    /// 每个通道或平台的结果
+    pub results: BTreeMap<String, PushResult>,
+}
+
+/// 投递状态响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptsResponse {
+    /// 推送时的请求 ID
+    pub id: String,
+    /// 每个通道或平台的最新投递状态
+    pub statuses: BTreeMap<String, DeliveryStatus>,
+}
+
+/// 服务端错误响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// 机器可读的错误码，如 `channel_not_found`、`rate_limited`
+    pub code: String,
+    /// 错误描述
+    pub message: String,
+    /// 附加信息，如校验失败时的 `violations`
+    #[serde(default)]
+    pub details: Value,
+    /// 稍后重试是否可能成功
+    #[serde(default)]
+    pub retryable: bool,
+}
+
+/// 平台发现条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformDescriptor {
+    /// 平台名称
+    pub name: String,
+    /// 平台信息，平台无法脱离配置提供时为空
+    pub info: Option<PlatformInfo>,
+    /// 平台配置的 JSON Schema
+    pub config_schema: Value,
+}
+
+/// 事件状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentStatus {
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+/// 推送请求中携带的事件标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentUpdate {
+    /// 事件 ID，同一 ID 的推送归为同一事件
+    pub id: String,
+    /// 事件标题
+    pub title: Option<String>,
+    /// 当前状态
+    pub status: IncidentStatus,
+    /// 受影响的组件
+    pub component: Option<String>,
+}
+
+/// 状态页摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusSummary {
+    /// 页面标题
+    pub title: String,
+    /// 是否没有未解决的事件
+    pub operational: bool,
+    /// 最近的事件
+    pub incidents: Vec<Incident>,
+}
+
+/// 状态页中的事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub title: String,
+    pub component: Option<String>,
+    pub status: IncidentStatus,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub updates: Vec<IncidentEntry>,
+}
+
+/// 事件的一次更新
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentEntry {
+    pub status: IncidentStatus,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
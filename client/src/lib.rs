@@ -0,0 +1,488 @@
+//! multi_push 服务端 API 的异步客户端
+
+mod api;
+
+pub use api::{
+    AckResponse, BudgetUsage, ChannelPreview, ChannelTestResponse, DeliveryReport, ErrorBody,
+    Incident, IncidentEntry, IncidentStatus, IncidentUpdate, PlatformDescriptor, PushRequest,
+    PushResponse, ReceiptsResponse, StatusSummary,
+};
+pub use common::PushError;
+
+use common::{Message, MessageKind, PushResult, REQUEST_ID_KEY};
+use futures::future::join_all;
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 默认首次重试间隔
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 重试间隔上限
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 请求 ID 头，同一次推送的重试携带相同的 ID，服务端据此去重
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// multi_push 服务端客户端，克隆开销很小且共用连接池
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http_client: reqwest::Client,
+    api_key: Option<String>,
+    retry_count: u32,
+    backoff: Duration,
+}
+
+impl Client {
+    /// 创建客户端，`base_url` 形如 `http://localhost:8888`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http_client: reqwest::Client::new(),
+            api_key: None,
+            retry_count: 3,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+
+    /// 使用自定义的 HTTP 客户端，如需配置超时、代理或 TLS
+    pub fn with_http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// 设置 API Key，以 `Authorization: Bearer` 头发送
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// 设置网络错误、服务端错误和限流时的重试次数
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = retry_count;
+        self
+    }
+
+    /// 设置首次重试间隔，之后每次翻倍
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// 推送一条消息，平台返回失败时转换为错误
+    pub async fn push(&self, request: &PushRequest) -> Result<PushResult, PushError> {
+        let request_id = request_id(&request.metadata);
+        let response: PushResponse = self
+            .send(|| {
+                self.request(Method::POST, "/push")
+                    .header(REQUEST_ID_HEADER, &request_id)
+                    .json(request)
+            })
+            .await?;
+        into_result(response)
+    }
+
+    /// 推送到服务端配置的通道，通道不存在时返回配置错误
+    pub async fn push_to_channel(
+        &self,
+        channel: &str,
+        message: &Message,
+    ) -> Result<PushResult, PushError> {
+        let path = format!("/push/{}", channel);
+        let request_id = request_id(&message.metadata);
+        let response: PushResponse = self
+            .send(|| {
+                self.request(Method::POST, &path)
+                    .header(REQUEST_ID_HEADER, &request_id)
+                    .json(message)
+            })
+            .await?;
+        into_result(response)
+    }
+
+    /// 预览消息在各通道上平台会收到的请求载荷，不发送；`channels` 为空时按路由规则选择
+    pub async fn preview(
+        &self,
+        message: &Message,
+        channels: &[String],
+    ) -> Result<Vec<ChannelPreview>, PushError> {
+        let body = serde_json::json!({"message": message, "channels": channels});
+        self.send(|| self.request(Method::POST, "/preview").json(&body))
+            .await
+    }
+
+    /// 向通道发送诊断消息，发送失败时同样返回响应，失败原因在 `error` 中
+    pub async fn test_channel(&self, channel: &str) -> Result<ChannelTestResponse, PushError> {
+        let path = format!("/admin/channels/{}/test", channel);
+        self.send(|| self.request(Method::POST, &path)).await
+    }
+
+    /// 获取付费通道当日的预算使用情况
+    pub async fn budgets(&self) -> Result<Vec<BudgetUsage>, PushError> {
+        self.send(|| self.request(Method::GET, "/budgets")).await
+    }
+
+    /// 确认 `require_ack` 的消息，停止重复提醒，`id` 为推送结果中的请求 ID
+    pub async fn ack(&self, id: &str) -> Result<AckResponse, PushError> {
+        let path = format!("/ack/{}", id);
+        self.send(|| self.request(Method::POST, &path)).await
+    }
+
+    /// 编辑之前推送的消息，`id` 为推送结果中的请求 ID，平台需支持 `edit` 特性
+    pub async fn update_message(
+        &self,
+        id: &str,
+        message: &Message,
+    ) -> Result<DeliveryReport, PushError> {
+        let path = format!("/push/{}", id);
+        self.send(|| self.request(Method::PATCH, &path).json(message))
+            .await
+    }
+
+    /// 撤回之前推送的消息，平台需支持 `delete` 特性
+    pub async fn delete_message(&self, id: &str) -> Result<DeliveryReport, PushError> {
+        let path = format!("/push/{}", id);
+        self.send(|| self.request(Method::DELETE, &path)).await
+    }
+
+    /// 查询推送的投递状态，只包含支持回执的通道
+    pub async fn get_receipts(&self, id: &str) -> Result<ReceiptsResponse, PushError> {
+        let path = format!("/push/{}/receipts", id);
+        self.send(|| self.request(Method::GET, &path)).await
+    }
+
+    /// 并发推送多条消息，结果与请求一一对应
+    pub async fn push_batch(&self, requests: &[PushRequest]) -> Vec<Result<PushResult, PushError>> {
+        join_all(requests.iter().map(|request| self.push(request))).await
+    }
+
+    /// 获取状态页摘要，服务端未启用状态页时返回错误
+    pub async fn get_status(&self) -> Result<StatusSummary, PushError> {
+        self.send(|| self.request(Method::GET, "/status.json"))
+            .await
+    }
+
+    /// 列出服务端支持的平台
+    pub async fn list_platforms(&self) -> Result<Vec<PlatformDescriptor>, PushError> {
+        self.send(|| self.request(Method::GET, "/platforms")).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http_client
+            .request(method, format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        }
+    }
+
+    /// 发送请求并解析响应，可重试的错误按指数退避重试
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<T, PushError> {
+        let mut attempt = 0;
+        let mut backoff = self.backoff;
+        loop {
+            attempt += 1;
+            match self.send_once(request()).await {
+                Err(e) if e.is_retryable() && attempt <= self.retry_count => {
+                    let delay = e.retry_after().unwrap_or(backoff).min(MAX_BACKOFF);
+                    tokio::time::sleep(delay).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_once<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<T, PushError> {
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                PushError::Timeout(e.to_string())
+            } else {
+                PushError::NetworkError(e.to_string())
+            }
+        })?;
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
+        let body = response
+            .text()
+            .await
+            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+        if status.is_success() {
+            return serde_json::from_str(&body)
+                .map_err(|e| PushError::PlatformError(format!("Invalid response: {}", e)));
+        }
+
+        // 优先按结构化错误码区分，旧版服务端在推送结果中给出原因
+        if let Ok(error) = serde_json::from_str::<ErrorBody>(&body) {
+            return Err(from_error_body(error, status, retry_after));
+        }
+        let message = serde_json::from_str::<PushResponse>(&body)
+            .ok()
+            .and_then(|r| r.result.response)
+            .unwrap_or(body);
+        let message = format!("Request failed with status: {}, body: {}", status, message);
+        Err(match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => PushError::AuthError(message),
+            StatusCode::TOO_MANY_REQUESTS => PushError::RateLimited {
+                message,
+                retry_after,
+            },
+            StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => PushError::ConfigError(message),
+            s if s.is_server_error() => PushError::NetworkError(message),
+            _ => PushError::PlatformError(message),
+        })
+    }
+}
+
+/// 一次推送的请求 ID，元数据中已有时沿用，否则生成新的 ID；重试时不再重新生成
+fn request_id(metadata: &HashMap<String, String>) -> String {
+    metadata
+        .get(REQUEST_ID_KEY)
+        .cloned()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// `unsupported_message_type` 错误的附加信息
+#[derive(serde::Deserialize)]
+struct UnsupportedDetails {
+    platform: String,
+    message_type: MessageKind,
+    suggestion: Option<MessageKind>,
+}
+
+/// 将服务端错误码转换为对应的错误类型，未知错误码按状态码区分
+fn from_error_body(
+    error: ErrorBody,
+    status: StatusCode,
+    retry_after: Option<Duration>,
+) -> PushError {
+    let message = format!("{} ({})", error.message, error.code);
+    match error.code.as_str() {
+        "unauthorized" | "forbidden" | "platform_auth_error" => PushError::AuthError(message),
+        "invalid_body" | "validation_failed" | "platform_not_found" | "channel_not_found"
+        | "ack_not_found" | "message_not_found" | "config_error" => PushError::ConfigError(message),
+        "message_error" => PushError::MessageError(message),
+        "unsupported_message_type" => {
+            match serde_json::from_value::<UnsupportedDetails>(error.details) {
+                Ok(details) => PushError::Unsupported {
+                    platform: details.platform,
+                    message_type: details.message_type,
+                    suggestion: details.suggestion,
+                },
+                Err(_) => PushError::MessageError(message),
+            }
+        }
+        "payload_too_large" => PushError::PayloadTooLarge(message),
+        "rate_limited" => PushError::RateLimited {
+            message,
+            retry_after,
+        },
+        "network_error" => PushError::NetworkError(message),
+        "timeout" => PushError::Timeout(message),
+        _ if error.retryable || status.is_server_error() => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+/// 平台返回失败时转换为错误
+fn into_result(response: PushResponse) -> Result<PushResult, PushError> {
+    if response.result.success {
+        Ok(response.result)
+    } else {
+        Err(PushError::PlatformError(
+            response.result.response.unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MessageType;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 依次以给定的状态码和响应体应答请求，返回服务地址
+    async fn serve(responses: Vec<(u16, String)>) -> String {
+        serve_recording(responses).await.0
+    }
+
+    /// 同 [`serve`]，并记录收到的请求
+    async fn serve_recording(responses: Vec<(u16, String)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let len = socket.read(&mut buf).await.unwrap();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&buf[..len]).into_owned());
+                let head = format!(
+                    "HTTP/1.1 {} X\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                socket.write_all(head.as_bytes()).await.unwrap();
+                socket.write_all(body.as_bytes()).await.unwrap();
+            }
+        });
+        (format!("http://{}", addr), received)
+    }
+
+    /// 请求中的请求 ID 头
+    fn request_id_header(request: &str) -> Option<String> {
+        request.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case(REQUEST_ID_HEADER)
+                .then(|| value.trim().to_string())
+        })
+    }
+
+    fn result(success: bool, response: &str) -> String {
+        json!({"result": {
+            "success": success,
+            "response": response,
+            "timestamp": "2024-01-01T00:00:00Z"
+        }})
+        .to_string()
+    }
+
+    fn request() -> PushRequest {
+        PushRequest::new(
+            "wxwork",
+            json!({"token": "t"}),
+            MessageType::Text("hi".to_string()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_push_retries_server_errors() {
+        let (url, received) = serve_recording(vec![
+            (503, "unavailable".to_string()),
+            (200, result(true, "ok")),
+            (200, result(true, "ok")),
+        ])
+        .await;
+        let client = Client::new(url)
+            .with_api_key("key")
+            .with_backoff(Duration::from_millis(1));
+        let result = client.push(&request()).await.unwrap();
+        assert_eq!(result.response.as_deref(), Some("ok"));
+
+        // 重试携带相同的请求 ID，服务端据此去重
+        let ids: Vec<_> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| request_id_header(r).unwrap())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], ids[1]);
+
+        // 消息元数据中已有请求 ID 时沿用
+        let mut request = request();
+        request
+            .metadata
+            .insert(REQUEST_ID_KEY.to_string(), "deploy-42".to_string());
+        client.push(&request).await.unwrap();
+        let last = received.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(request_id_header(&last).as_deref(), Some("deploy-42"));
+    }
+
+    #[tokio::test]
+    async fn test_push_reports_failures() {
+        let url = serve(vec![
+            (400, result(false, "Platform 'x' not found")),
+            (200, result(false, "bad token")),
+        ])
+        .await;
+        let client = Client::new(url).with_retry_count(0);
+        assert!(matches!(
+            client.push(&request()).await,
+            Err(PushError::ConfigError(m)) if m.contains("Platform 'x' not found")
+        ));
+        assert!(matches!(
+            client.push(&request()).await,
+            Err(PushError::PlatformError(m)) if m == "bad token"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_codes() {
+        let error = |code: &str, retryable: bool| {
+            json!({"code": code, "message": "m", "retryable": retryable}).to_string()
+        };
+        let url = serve(vec![
+            (404, error("channel_not_found", false)),
+            (429, error("rate_limited", true)),
+            (200, result(true, "ok")),
+            (422, error("validation_failed", false)),
+            (
+                422,
+                json!({
+                    "code": "unsupported_message_type",
+                    "message": "m",
+                    "details": {"platform": "voice", "message_type": "image", "suggestion": "text"},
+                })
+                .to_string(),
+            ),
+        ])
+        .await;
+        let client = Client::new(url).with_backoff(Duration::from_millis(1));
+        let message = Message::new(MessageType::Text("hi".to_string()));
+        assert!(matches!(
+            client.push_to_channel("x", &message).await,
+            Err(PushError::ConfigError(m)) if m == "m (channel_not_found)"
+        ));
+        assert!(client.push_to_channel("ops", &message).await.is_ok());
+        assert!(matches!(
+            client.push_to_channel("ops", &message).await,
+            Err(PushError::ConfigError(_))
+        ));
+        assert!(matches!(
+            client.push_to_channel("ops", &message).await,
+            Err(PushError::Unsupported {
+                message_type: MessageKind::Image,
+                suggestion: Some(MessageKind::Text),
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_push_to_channel() {
+        let url = serve(vec![
+            (200, result(true, "ok")),
+            (400, result(false, "Channel 'x' not found")),
+        ])
+        .await;
+        let client = Client::new(url).with_retry_count(0);
+        let message = Message::new(MessageType::Markdown("deploy done".to_string()));
+        assert!(client.push_to_channel("ops", &message).await.is_ok());
+        assert!(matches!(
+            client.push_to_channel("x", &message).await,
+            Err(PushError::ConfigError(m)) if m.contains("Channel 'x' not found")
+        ));
+    }
+}
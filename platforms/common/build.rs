@@ -0,0 +1,17 @@
+use std::process::Command;
+
+/// 记录编译器版本，插件与宿主的编译器不一致时拒绝加载
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!(
+        "cargo:rustc-env=MULTI_PUSH_RUSTC_VERSION={}",
+        version.trim()
+    );
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
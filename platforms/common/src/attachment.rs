@@ -0,0 +1,182 @@
+use crate::PushError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 附件来源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum AttachmentSource {
+    /// 内联内容，JSON 中以 base64 表示
+    Bytes(#[serde(with = "base64_bytes")] Vec<u8>),
+    /// 本地文件路径
+    Path(PathBuf),
+    /// 远程地址，由平台自行下载或直接引用；`data:` URL 按内联内容处理
+    Url(String),
+}
+
+impl AttachmentSource {
+    /// 读取内联或本地文件内容，远程地址返回错误
+    pub async fn read_local(&self) -> Result<Vec<u8>, PushError> {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes.clone()),
+            Self::Url(url) if url.starts_with("data:") => decode_data_url(url),
+            Self::Path(path) => tokio::fs::read(path).await.map_err(|e| {
+                PushError::MessageError(format!("Failed to read {}: {}", path.display(), e))
+            }),
+            Self::Url(url) => Err(PushError::MessageError(format!(
+                "Attachment {} must be downloaded first",
+                url
+            ))),
+        }
+    }
+
+    /// 读取附件内容，远程地址用 `client` 流式下载；超过 `max_bytes` 时立即停止，不会整体读入内存
+    pub async fn read(
+        &self,
+        client: &reqwest::Client,
+        name: &str,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, PushError> {
+        let too_large = || {
+            PushError::PayloadTooLarge(format!("{} exceeds the limit of {} bytes", name, max_bytes))
+        };
+        let network = |e: reqwest::Error| PushError::NetworkError(e.to_string());
+        let Some(url) = self.url() else {
+            if self.known_len().is_some_and(|len| len > max_bytes) {
+                return Err(too_large());
+            }
+            let bytes = self.read_local().await?;
+            return if bytes.len() > max_bytes {
+                Err(too_large())
+            } else {
+                Ok(bytes)
+            };
+        };
+        let mut response = client
+            .get(crate::endpoint(url))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(network)?;
+        if response
+            .content_length()
+            .is_some_and(|len| len > max_bytes as u64)
+        {
+            return Err(too_large());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(network)? {
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// 远程地址，`data:` URL 不算远程地址
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            Self::Url(url) if !url.starts_with("data:") => Some(url),
+            _ => None,
+        }
+    }
+
+    /// 无需下载即可得知的内容大小，远程地址返回 `None`
+    pub fn known_len(&self) -> Option<usize> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes.len()),
+            Self::Path(path) => std::fs::metadata(path).ok().map(|meta| meta.len() as usize),
+            Self::Url(_) => None,
+        }
+    }
+}
+
+/// 解码 `data:[<mime>];base64,<data>` 形式的 URL
+fn decode_data_url(url: &str) -> Result<Vec<u8>, PushError> {
+    use base64::Engine;
+    let invalid = || PushError::MessageError("Invalid data URL".to_string());
+    let (header, data) = url.split_once(',').ok_or_else(invalid)?;
+    if !header.ends_with(";base64") {
+        return Err(invalid());
+    }
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|_| invalid())
+}
+
+mod base64_bytes {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+
+    #[test]
+    fn test_file_message_serde() {
+        let message: MessageType = serde_json::from_str(
+            r#"{"type": "File", "payload": {"name": "a.txt",
+                "source": {"type": "bytes", "value": "aGVsbG8="}}}"#,
+        )
+        .unwrap();
+        match &message {
+            MessageType::File { name, mime, source } => {
+                assert_eq!(name, "a.txt");
+                assert!(mime.is_none());
+                assert!(matches!(source, AttachmentSource::Bytes(b) if b == b"hello"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["payload"]["source"]["value"], "aGVsbG8=");
+    }
+
+    #[tokio::test]
+    async fn test_read_local() {
+        let bytes = AttachmentSource::Bytes(b"x".to_vec());
+        assert_eq!(bytes.read_local().await.unwrap(), b"x");
+        let url = AttachmentSource::Url("https://example.com/a.pdf".to_string());
+        assert!(url.read_local().await.is_err());
+        assert_eq!(url.url(), Some("https://example.com/a.pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_read_stops_at_limit() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"0123456789".to_vec()))
+            .mount(&server)
+            .await;
+        let client = reqwest::Client::new();
+        let remote = AttachmentSource::Url(format!("{}/report.pdf", server.uri()));
+        assert_eq!(
+            remote.read(&client, "report.pdf", 10).await.unwrap(),
+            b"0123456789"
+        );
+        let error = remote.read(&client, "report.pdf", 4).await.unwrap_err();
+        assert!(matches!(error, PushError::PayloadTooLarge(m) if m.contains("report.pdf")));
+
+        let bytes = AttachmentSource::Bytes(b"hello".to_vec());
+        assert!(matches!(
+            bytes.read(&client, "a.txt", 4).await,
+            Err(PushError::PayloadTooLarge(_))
+        ));
+        assert_eq!(bytes.read(&client, "a.txt", 5).await.unwrap(), b"hello");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Message, PushError, PushPlatformCapabilities, PushResult};
+use futures::stream::{self, StreamExt};
+
+/// 以至多 `limit` 个并发向同一平台发送多条消息，结果顺序与输入一致
+pub async fn send_concurrent<P>(
+    platform: &P,
+    messages: Vec<Message>,
+    limit: usize,
+) -> Vec<Result<PushResult, PushError>>
+where
+    P: PushPlatformCapabilities + ?Sized,
+{
+    stream::iter(messages)
+        .map(|message| platform.send_message(message))
+        .buffered(limit.max(1))
+        .collect()
+        .await
+}
+
+/// 以至多 `limit` 个并发向多个平台发送同一条消息，结果顺序与输入一致
+pub async fn send_to_many<P>(
+    platforms: &[P],
+    message: &Message,
+    limit: usize,
+) -> Vec<Result<PushResult, PushError>>
+where
+    P: PushPlatformCapabilities,
+{
+    stream::iter(platforms)
+        .map(|platform| platform.send_message(message.clone()))
+        .buffered(limit.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+    use crate::testing::MockPlatform;
+    use std::time::Duration;
+
+    fn text(content: &str) -> Message {
+        MessageType::Text(content.to_string()).into()
+    }
+
+    fn slow_platform() -> MockPlatform {
+        MockPlatform::new("slow").with_delay(Duration::from_millis(10))
+    }
+
+    #[tokio::test]
+    async fn test_send_batch_default_is_sequential() {
+        let platform = slow_platform();
+        let results = platform
+            .send_batch(vec![text("a"), text("fail"), text("b")])
+            .await;
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_err());
+        assert_eq!(platform.sent(), vec!["a", "b"]);
+        assert_eq!(platform.peak(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_concurrent_is_bounded_and_ordered() {
+        let platform = slow_platform();
+        let messages = (0..6).map(|i| text(&i.to_string())).collect();
+        let results = send_concurrent(&platform, messages, 2).await;
+        let responses: Vec<String> = results
+            .into_iter()
+            .map(|r| r.unwrap().response.unwrap())
+            .collect();
+        assert_eq!(responses, vec!["0", "1", "2", "3", "4", "5"]);
+        assert_eq!(platform.peak(), 2);
+    }
+}
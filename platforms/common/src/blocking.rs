@@ -0,0 +1,110 @@
+//! 同步接口，内部自带 tokio 运行时，供 CLI 工具和非异步程序使用
+//!
+//! 不要在异步上下文中调用，否则会因为嵌套运行时而 panic
+
+use crate::{Message, PlatformInfo, PushError, PushPlatformCapabilities, PushResult};
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+fn runtime() -> Result<Runtime, PushError> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| PushError::PlatformError(format!("Failed to start runtime: {}", e)))
+}
+
+/// 在临时运行时中执行一次异步调用
+fn block_on<F: Future>(future: F) -> Result<F::Output, PushError> {
+    Ok(runtime()?.block_on(future))
+}
+
+/// 同步发送纯文本
+pub fn send_text(
+    platform: &dyn PushPlatformCapabilities,
+    content: &str,
+) -> Result<PushResult, PushError> {
+    block_on(platform.send_text(content))?
+}
+
+/// 同步发送消息
+pub fn send_message(
+    platform: &dyn PushPlatformCapabilities,
+    message: Message,
+) -> Result<PushResult, PushError> {
+    block_on(platform.send_message(message))?
+}
+
+/// 持有独立运行时的同步平台，多次发送时复用运行时和连接
+pub struct BlockingPlatform {
+    inner: Box<dyn PushPlatformCapabilities>,
+    runtime: Runtime,
+}
+
+impl BlockingPlatform {
+    /// 包装异步平台
+    pub fn new(inner: Box<dyn PushPlatformCapabilities>) -> Result<Self, PushError> {
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// 初始化平台
+    pub fn init(&mut self) -> Result<(), PushError> {
+        self.runtime.block_on(self.inner.init())
+    }
+
+    /// 发送纯文本
+    pub fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.runtime.block_on(self.inner.send_text(content))
+    }
+
+    /// 发送 Markdown
+    pub fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.runtime.block_on(self.inner.send_markdown(content))
+    }
+
+    /// 发送消息，按平台能力降级
+    pub fn send_message(&self, message: impl Into<Message>) -> Result<PushResult, PushError> {
+        self.runtime
+            .block_on(self.inner.send_message(message.into()))
+    }
+
+    /// 检查平台健康状态
+    pub fn health_check(&self) -> Result<bool, PushError> {
+        self.runtime.block_on(self.inner.health_check())
+    }
+
+    /// 获取平台信息
+    pub fn platform_info(&self) -> PlatformInfo {
+        self.inner.platform_info()
+    }
+
+    /// 内部的异步平台
+    pub fn inner(&self) -> &dyn PushPlatformCapabilities {
+        self.inner.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+    use crate::testing::MockPlatform;
+
+    #[test]
+    fn test_blocking_send() {
+        let platform = BlockingPlatform::new(Box::new(MockPlatform::new("mock"))).unwrap();
+        let result = platform.send_text("hello").unwrap();
+        assert_eq!(result.response.as_deref(), Some("hello"));
+        assert!(
+            platform
+                .send_message(MessageType::Text("fail".to_string()))
+                .is_err()
+        );
+
+        let mock = MockPlatform::new("mock");
+        send_text(&mock, "once").unwrap();
+        assert_eq!(mock.sent(), vec!["once"]);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Mention, MessageType};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 消息类型，与 [`MessageType`] 的变体一一对应，用于声明平台原生支持的消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    Text,
+    Markdown,
+    Html,
+    Rich,
+    Image,
+    Link,
+    File,
+    Card,
+    Template,
+    Audio,
+    Video,
+    Location,
+}
+
+impl MessageKind {
+    /// 全部消息类型
+    pub const ALL: [MessageKind; 12] = [
+        MessageKind::Text,
+        MessageKind::Markdown,
+        MessageKind::Html,
+        MessageKind::Rich,
+        MessageKind::Image,
+        MessageKind::Link,
+        MessageKind::File,
+        MessageKind::Card,
+        MessageKind::Template,
+        MessageKind::Audio,
+        MessageKind::Video,
+        MessageKind::Location,
+    ];
+
+    /// 消息类型名称，与 `MessageType` 序列化时的 `type` 字段一致
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageKind::Text => "Text",
+            MessageKind::Markdown => "Markdown",
+            MessageKind::Html => "Html",
+            MessageKind::Rich => "Rich",
+            MessageKind::Image => "Image",
+            MessageKind::Link => "Link",
+            MessageKind::File => "File",
+            MessageKind::Card => "Card",
+            MessageKind::Template => "Template",
+            MessageKind::Audio => "Audio",
+            MessageKind::Video => "Video",
+            MessageKind::Location => "Location",
+        }
+    }
+}
+
+impl fmt::Display for MessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<&MessageType> for MessageKind {
+    fn from(message: &MessageType) -> Self {
+        match message {
+            MessageType::Text(_) => MessageKind::Text,
+            MessageType::Markdown(_) => MessageKind::Markdown,
+            MessageType::Html(_) => MessageKind::Html,
+            MessageType::Rich { .. } => MessageKind::Rich,
+            MessageType::Image { .. } => MessageKind::Image,
+            MessageType::Link { .. } => MessageKind::Link,
+            MessageType::File { .. } => MessageKind::File,
+            MessageType::Card { .. } => MessageKind::Card,
+            MessageType::Template { .. } => MessageKind::Template,
+            MessageType::Audio { .. } => MessageKind::Audio,
+            MessageType::Video { .. } => MessageKind::Video,
+            MessageType::Location { .. } => MessageKind::Location,
+        }
+    }
+}
+
+/// 平台能原生送达的@提醒方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MentionSupport {
+    /// 提醒所有人
+    pub all: bool,
+    /// 按平台用户 ID 提醒
+    pub user_id: bool,
+    /// 按手机号提醒
+    pub phone: bool,
+    /// 按邮箱提醒
+    pub email: bool,
+}
+
+impl MentionSupport {
+    /// 平台能否送达该提醒
+    pub fn supports(&self, mention: &Mention) -> bool {
+        match mention {
+            Mention::All => self.all,
+            Mention::UserId(_) => self.user_id,
+            Mention::Phone(_) => self.phone,
+            Mention::Email(_) => self.email,
+        }
+    }
+
+    /// 是否支持任意一种提醒
+    pub fn any(&self) -> bool {
+        self.all || self.user_id || self.phone || self.email
+    }
+
+    /// 两个平台都支持的提醒方式
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            all: self.all && other.all,
+            user_id: self.user_id && other.user_id,
+            phone: self.phone && other.phone,
+            email: self.email && other.email,
+        }
+    }
+}
+
+/// 平台公布的发送频率限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// 窗口内最多请求数
+    pub requests: u32,
+    /// 窗口长度（秒）
+    pub per_secs: u32,
+}
+
+impl RateLimit {
+    /// 平均每秒可发送的请求数，用于比较两个限制的严格程度
+    pub fn per_second(&self) -> f64 {
+        self.requests as f64 / self.per_secs.max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_kind_matches_serialized_type() {
+        let message = MessageType::Link {
+            title: "Release".to_string(),
+            description: String::new(),
+            url: "http://ci/1".to_string(),
+            image_url: None,
+        };
+        let kind = MessageKind::from(&message);
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["type"], kind.name());
+        assert_eq!(serde_json::to_value(kind).unwrap(), "link");
+        assert!(MessageKind::ALL.contains(&kind));
+    }
+
+    #[test]
+    fn test_mention_support() {
+        let support = MentionSupport {
+            all: true,
+            phone: true,
+            ..Default::default()
+        };
+        assert!(support.supports(&Mention::All));
+        assert!(support.supports(&Mention::from("+8613800000000")));
+        assert!(!support.supports(&Mention::from("alice")));
+        assert!(!support.intersect(&MentionSupport::default()).any());
+    }
+}
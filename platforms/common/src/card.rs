@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+/// 卡片中的一个段落
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CardSection {
+    /// 段落标题
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 段落内容（Markdown）
+    pub content: String,
+    /// 突出显示，如告警的关键指标
+    #[serde(default)]
+    pub emphasis: bool,
+    /// 段落配图
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
+/// 卡片按钮
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardButton {
+    /// 按钮文字
+    pub label: String,
+    /// 点击行为
+    pub action: CardAction,
+}
+
+/// 按钮点击行为
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardAction {
+    /// 打开链接
+    Url(String),
+    /// 回调到发送方，携带回调 ID
+    Callback(String),
+}
+
+impl CardButton {
+    /// 链接按钮
+    pub fn url(label: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            action: CardAction::Url(url.into()),
+        }
+    }
+
+    /// 回调按钮
+    pub fn callback(label: impl Into<String>, id: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            action: CardAction::Callback(id.into()),
+        }
+    }
+
+    /// 链接按钮的地址
+    pub fn link(&self) -> Option<&str> {
+        match &self.action {
+            CardAction::Url(url) => Some(url),
+            CardAction::Callback(_) => None,
+        }
+    }
+}
+
+/// 将卡片渲染为 Markdown，回调按钮无法在 Markdown 中表达，直接省略
+pub fn card_to_markdown(title: &str, sections: &[CardSection], buttons: &[CardButton]) -> String {
+    let mut blocks = vec![format!("**{}**", title)];
+    for section in sections {
+        let content = if section.emphasis {
+            format!("**{}**", section.content)
+        } else {
+            section.content.clone()
+        };
+        let mut block = match &section.title {
+            Some(heading) => format!("**{}**\n{}", heading, content),
+            None => content,
+        };
+        if let Some(url) = &section.image_url {
+            block.push_str(&format!("\n![]({})", url));
+        }
+        blocks.push(block);
+    }
+    let links: Vec<String> = buttons
+        .iter()
+        .filter_map(|b| b.link().map(|url| format!("[{}]({})", b.label, url)))
+        .collect();
+    if !links.is_empty() {
+        blocks.push(links.join(" | "));
+    }
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_to_markdown() {
+        let sections = vec![
+            CardSection {
+                title: Some("Service".to_string()),
+                content: "api".to_string(),
+                ..Default::default()
+            },
+            CardSection {
+                content: "p99 > 1s".to_string(),
+                emphasis: true,
+                ..Default::default()
+            },
+        ];
+        let buttons = vec![
+            CardButton::url("Dashboard", "http://grafana/d/1"),
+            CardButton::callback("Ack", "ack-1"),
+        ];
+        assert_eq!(
+            card_to_markdown("High latency", &sections, &buttons),
+            "**High latency**\n\n**Service**\napi\n\n**p99 > 1s**\n\n[Dashboard](http://grafana/d/1)"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{MessageType, PushError};
+use serde::{Deserialize, Serialize};
+
+/// 最多绘制的数据点数
+pub const MAX_CHART_POINTS: usize = 1000;
+
+/// 指标的近期数据，随告警消息附带趋势图
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSeries {
+    /// 指标名称，如 `cpu_usage`
+    pub name: String,
+    /// 按时间顺序排列的取值
+    pub points: Vec<f64>,
+    /// 告警阈值，绘制为水平线
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
+    /// 单位，如 `%`、`ms`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+impl MetricSeries {
+    /// 数据不足两个点或含有非有限值时返回错误
+    pub fn validate(&self) -> Result<(), String> {
+        if self.points.len() < 2 {
+            return Err("At least 2 points are required".to_string());
+        }
+        if self.points.len() > MAX_CHART_POINTS {
+            return Err(format!("At most {} points are allowed", MAX_CHART_POINTS));
+        }
+        if !self
+            .points
+            .iter()
+            .chain(&self.threshold)
+            .all(|v| v.is_finite())
+        {
+            return Err("Points and threshold must be finite numbers".to_string());
+        }
+        Ok(())
+    }
+
+    /// 一行文字摘要：最新值、最小值和最大值，作为图片说明或无法发送图片时的替代
+    pub fn summary(&self) -> String {
+        let unit = self.unit.as_deref().unwrap_or_default();
+        let fold = |init: f64, f: fn(f64, f64) -> f64| self.points.iter().copied().fold(init, f);
+        let mut summary = format!(
+            "{}: {}{} (min {}{}, max {}{})",
+            self.name,
+            self.points.last().copied().unwrap_or_default(),
+            unit,
+            fold(f64::INFINITY, f64::min),
+            unit,
+            fold(f64::NEG_INFINITY, f64::max),
+            unit
+        );
+        if let Some(threshold) = self.threshold {
+            summary.push_str(&format!(", threshold {}{}", threshold, unit));
+        }
+        summary
+    }
+}
+
+/// 趋势图图片消息，图片以 `data:` URL 内联；未启用 `charts` 特性时返回配置错误
+pub fn chart_image(series: &MetricSeries) -> Result<MessageType, PushError> {
+    use base64::Engine;
+    series.validate().map_err(PushError::MessageError)?;
+    let png = render::render_chart(series)?;
+    Ok(MessageType::Image {
+        url: format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(png)
+        ),
+        caption: Some(series.summary()),
+    })
+}
+
+#[cfg(feature = "charts")]
+mod render {
+    use super::MetricSeries;
+    use crate::PushError;
+    use plotters::prelude::*;
+
+    const WIDTH: u32 = 600;
+    const HEIGHT: u32 = 240;
+
+    fn chart_error(e: impl std::fmt::Debug) -> PushError {
+        PushError::MessageError(format!("Failed to render chart: {:?}", e))
+    }
+
+    /// 绘制折线面积图和阈值线，不绘制文字，数值由图片说明给出
+    pub fn render_chart(series: &MetricSeries) -> Result<Vec<u8>, PushError> {
+        let values = series.points.iter().chain(&series.threshold);
+        let low = values.clone().copied().fold(f64::INFINITY, f64::min);
+        let high = values.copied().fold(f64::NEG_INFINITY, f64::max);
+        let margin = ((high - low) * 0.1).max(1e-9);
+        let (low, high) = (low - margin, high + margin);
+        let last = (series.points.len() - 1) as f64;
+
+        let mut pixels = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut pixels, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&WHITE).map_err(chart_error)?;
+            let mut chart = ChartBuilder::on(&root)
+                .margin(12)
+                .build_cartesian_2d(0f64..last, low..high)
+                .map_err(chart_error)?;
+            let points = series
+                .points
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i as f64, *v));
+            chart
+                .draw_series(
+                    AreaSeries::new(points, low, BLUE.mix(0.2)).border_style(BLUE.stroke_width(2)),
+                )
+                .map_err(chart_error)?;
+            if let Some(threshold) = series.threshold {
+                chart
+                    .draw_series(LineSeries::new(
+                        [(0.0, threshold), (last, threshold)],
+                        RED.stroke_width(2),
+                    ))
+                    .map_err(chart_error)?;
+            }
+            root.present().map_err(chart_error)?;
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(chart_error)?;
+        Ok(png)
+    }
+}
+
+#[cfg(not(feature = "charts"))]
+mod render {
+    use super::MetricSeries;
+    use crate::PushError;
+
+    pub fn render_chart(_series: &MetricSeries) -> Result<Vec<u8>, PushError> {
+        Err(PushError::ConfigError(
+            "Chart rendering requires the `charts` feature".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series() -> MetricSeries {
+        MetricSeries {
+            name: "cpu".to_string(),
+            points: vec![12.0, 30.5, 97.0, 88.0],
+            threshold: Some(90.0),
+            unit: Some("%".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(
+            series().summary(),
+            "cpu: 88% (min 12%, max 97%), threshold 90%"
+        );
+        let mut invalid = series();
+        invalid.points.push(f64::NAN);
+        assert!(invalid.validate().is_err());
+        invalid.points = vec![1.0];
+        assert!(invalid.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_summary_sent_when_images_unsupported() {
+        use crate::testing::MockPlatform;
+        use crate::{Message, PushPlatformCapabilities};
+        let mock = MockPlatform::new("mock");
+        let message = Message::new(MessageType::Text("CPU high".to_string())).with_chart(series());
+        mock.send_message(message).await.unwrap();
+        assert_eq!(
+            mock.sent(),
+            vec!["CPU high".to_string(), series().summary()]
+        );
+    }
+
+    #[cfg(feature = "charts")]
+    #[test]
+    fn test_chart_image() {
+        let MessageType::Image { url, caption } = chart_image(&series()).unwrap() else {
+            unreachable!()
+        };
+        assert!(url.starts_with("data:image/png;base64,iVBOR"));
+        assert_eq!(caption.unwrap(), series().summary());
+    }
+}
@@ -0,0 +1,571 @@
+use crate::{
+    CapabilityMode, ContentPolicy, Decorations, EmojiShortcodes, MarkdownImageConfig, Message,
+    Priority, PushError, PushPlatformCapabilities, RetryPolicy, SmsConfig,
+};
+use chrono::{DateTime, FixedOffset, Local, NaiveTime, Offset, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    /// 需要启用 `toml` feature
+    Toml,
+    /// 需要启用 `yaml` feature
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// 按文件扩展名识别格式
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// 解析配置文本，解析前先替换其中的环境变量引用
+pub fn parse_config<T: DeserializeOwned>(
+    content: &str,
+    format: ConfigFormat,
+) -> Result<T, PushError> {
+    let content = interpolate_env(content, |name| std::env::var(name).ok())?;
+    let error = |e: String| PushError::ConfigError(format!("Invalid config: {}", e));
+    match format {
+        ConfigFormat::Json => serde_json::from_str(&content).map_err(|e| error(e.to_string())),
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => toml::from_str(&content).map_err(|e| error(e.to_string())),
+        #[cfg(feature = "yaml")]
+        ConfigFormat::Yaml => serde_yaml::from_str(&content).map_err(|e| error(e.to_string())),
+        #[allow(unreachable_patterns)]
+        format => Err(PushError::ConfigError(format!(
+            "{:?} config support is not enabled",
+            format
+        ))),
+    }
+}
+
+/// 将配置序列化为指定格式的文本
+///
+/// 文本中的 `$` 转义为 `$$`，重新加载时不会被当作环境变量引用
+pub fn serialize_config<T: Serialize>(
+    value: &T,
+    format: ConfigFormat,
+) -> Result<String, PushError> {
+    let error = |e: String| PushError::ConfigError(format!("Failed to serialize config: {}", e));
+    let content = match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|e| error(e.to_string())),
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|e| error(e.to_string())),
+        #[cfg(feature = "yaml")]
+        ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|e| error(e.to_string())),
+        #[allow(unreachable_patterns)]
+        format => Err(PushError::ConfigError(format!(
+            "{:?} config support is not enabled",
+            format
+        ))),
+    }?;
+    Ok(content.replace('$', "$$"))
+}
+
+/// 读取并解析配置文件，格式按扩展名识别，无法识别时按 JSON 解析
+pub fn load_config<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, PushError> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| PushError::ConfigError(format!("Failed to read {}: {}", path.display(), e)))?;
+    parse_config(
+        &content,
+        ConfigFormat::from_path(path).unwrap_or(ConfigFormat::Json),
+    )
+}
+
+/// 替换 `${VAR}` 和 `${VAR:-default}` 形式的环境变量引用，`$$` 表示字面量 `$`
+///
+/// 替换发生在解析之前，数字、布尔值等非字符串配置也可以引用环境变量
+pub fn interpolate_env(
+    content: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, PushError> {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find('$') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            output.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after.find('}').ok_or_else(|| {
+                PushError::ConfigError("Unterminated environment variable reference".to_string())
+            })?;
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            let value = lookup(name)
+                .or_else(|| default.map(str::to_string))
+                .ok_or_else(|| {
+                    PushError::ConfigError(format!("Environment variable {} is not set", name))
+                })?;
+            output.push_str(&value);
+            rest = &after[end + 1..];
+        } else {
+            output.push('$');
+            rest = &rest[1..];
+        }
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// 通道配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    /// 目标平台
+    pub platform: String,
+    /// 平台的配置信息
+    pub config: Value,
+    /// 出站代理，覆盖平台配置中未设置的 `proxy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// 重试策略，覆盖平台配置中未设置的 `retry`，如按条计费的短信通道减少重试
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// 自动加到每条消息上的前缀、后缀和页脚
+    #[serde(default, skip_serializing_if = "Decorations::is_empty")]
+    pub decorations: Decorations,
+    /// 消息超出平台能力时自动改写（`lenient`）还是拒绝发送（`strict`）
+    #[serde(default, skip_serializing_if = "is_lenient")]
+    pub capabilities: CapabilityMode,
+    /// 时区，如 `Asia/Shanghai` 或 `+08:00`，模板和装饰中的时间按该时区显示
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// 语言，如 `zh-CN`、`en`、`ja`，用于选择模板的语言版本和内置文字的语言
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// 内容策略，违反时拒绝或修正消息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<ContentPolicy>,
+    /// 静默时段，由服务端在时段内暂存低优先级消息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+    /// 付费通道的每日预算，用尽后拒绝发送或改用更便宜的通道
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
+    /// 展开 `:rocket:` 形式的 emoji shortcode，平台原生支持时不展开
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<EmojiShortcodes>,
+    /// 平台不支持 Markdown 但支持图片时，将 Markdown 消息渲染为图片发送
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown_image: Option<MarkdownImageConfig>,
+    /// 短信内容预算，控制条数并可缩短链接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sms: Option<SmsConfig>,
+}
+
+fn is_lenient(mode: &CapabilityMode) -> bool {
+    *mode == CapabilityMode::Lenient
+}
+
+impl ChannelConfig {
+    /// 合并通道级设置后的平台配置
+    pub fn platform_config(&self) -> Value {
+        let mut config = self.config.clone();
+        if let (Some(proxy), Value::Object(map)) = (&self.proxy, &mut config) {
+            map.entry("proxy")
+                .or_insert_with(|| Value::String(proxy.clone()));
+        }
+        if let (Some(retry), Value::Object(map)) = (&self.retry, &mut config) {
+            map.entry("retry")
+                .or_insert_with(|| serde_json::to_value(retry).unwrap_or_default());
+        }
+        config
+    }
+
+    /// 为该通道的平台实例套上内容策略、消息装饰、时区、语言和 emoji 展开，`name` 为通道名称
+    pub fn decorate(
+        &self,
+        name: &str,
+        platform: Box<dyn PushPlatformCapabilities>,
+    ) -> Box<dyn PushPlatformCapabilities> {
+        crate::decoration::decorate(name, self, platform)
+    }
+}
+
+/// 短信、语音等按条计费通道的费用预算，费用单位由使用者自行约定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    /// 每条消息的费用
+    pub cost_per_message: f64,
+    /// 每日预算，按 UTC 日期重置
+    pub daily_limit: f64,
+    /// 预算用尽时改用的通道，未设置时拒绝发送
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downgrade_to: Option<String>,
+}
+
+impl Budget {
+    /// 已花费 `spent` 后是否还能再发送一条
+    pub fn allows(&self, spent: f64) -> bool {
+        // 容忍浮点累加误差，避免 0.1 累加三次后超出 0.3
+        spent + self.cost_per_message <= self.daily_limit + 1e-9
+    }
+}
+
+/// 静默时段，如 23:00 到次日 08:00
+///
+/// 时段内优先级低于 `bypass_priority` 的消息被暂存，时段结束后合并为一条摘要发送
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// 开始时间，如 `23:00`
+    pub start: NaiveTime,
+    /// 结束时间，早于开始时间表示跨越午夜
+    pub end: NaiveTime,
+    /// 时区偏移，如 `+08:00`，未设置时使用服务器本地时区
+    #[serde(default, with = "utc_offset", skip_serializing_if = "Option::is_none")]
+    pub utc_offset: Option<FixedOffset>,
+    /// 不低于该优先级的消息照常立即发送
+    #[serde(default = "default_bypass_priority")]
+    pub bypass_priority: Priority,
+    /// 每个通道最多暂存的消息数，超出后丢弃最早的消息
+    #[serde(default = "default_max_held")]
+    pub max_held: usize,
+    /// HTML 摘要布局模板文件（Jinja 语法），目标平台支持 HTML（如邮件）时摘要按该布局渲染，
+    /// 未设置时使用内置布局；内置平台都不支持 HTML，需配合支持 HTML 的第三方平台使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_template: Option<PathBuf>,
+}
+
+fn default_bypass_priority() -> Priority {
+    Priority::High
+}
+
+fn default_max_held() -> usize {
+    100
+}
+
+impl QuietHours {
+    /// 指定时刻是否处于静默时段，开始和结束相同时视为不静默
+    pub fn is_quiet(&self, now: DateTime<Utc>) -> bool {
+        let offset = self
+            .utc_offset
+            .unwrap_or_else(|| now.with_timezone(&Local).offset().fix());
+        let time = now.with_timezone(&offset).time();
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// 该消息在静默时段内是否需要暂存
+    pub fn holds(&self, message: &Message, now: DateTime<Utc>) -> bool {
+        message.priority < self.bypass_priority && self.is_quiet(now)
+    }
+}
+
+/// 以 `+08:00` 形式读写时区偏移
+mod utc_offset {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_str(&offset.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|offset| {
+                offset.parse().map_err(|_| {
+                    serde::de::Error::custom(format!("invalid UTC offset '{}'", offset))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// 消息路由规则，条件都满足时消息投递到规则中的通道
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Route {
+    /// 目标通道
+    pub channels: Vec<String>,
+    /// 最低优先级
+    #[serde(default)]
+    pub min_priority: Option<Priority>,
+    /// 只匹配这些优先级，为空时不限制，如 `["urgent"]`
+    #[serde(default)]
+    pub priorities: Vec<Priority>,
+    /// 需要匹配的元数据
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// 需要匹配的标签
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+impl Route {
+    /// 所有匹配的规则中的通道，按规则顺序去重
+    pub fn select<'a>(routes: &'a [Route], message: &Message) -> Vec<&'a str> {
+        let mut channels: Vec<&str> = Vec::new();
+        for route in routes.iter().filter(|r| r.matches(message)) {
+            for channel in &route.channels {
+                if !channels.contains(&channel.as_str()) {
+                    channels.push(channel);
+                }
+            }
+        }
+        channels
+    }
+
+    /// 消息是否匹配该规则
+    pub fn matches(&self, message: &Message) -> bool {
+        self.min_priority.is_none_or(|min| message.priority >= min)
+            && (self.priorities.is_empty() || self.priorities.contains(&message.priority))
+            && self
+                .metadata
+                .iter()
+                .all(|(key, value)| message.metadata.get(key) == Some(value))
+            && self
+                .labels
+                .iter()
+                .all(|(key, value)| message.labels.get(key) == Some(value))
+    }
+}
+
+/// 完整的多平台推送配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultiPushConfig {
+    /// 命名通道，键为通道名称
+    #[serde(default)]
+    pub channels: HashMap<String, ChannelConfig>,
+    /// 各平台的默认配置，键为平台名称，通道未设置的字段使用默认值
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub defaults: HashMap<String, Value>,
+    /// 路由规则，按顺序匹配
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<Route>,
+}
+
+impl MultiPushConfig {
+    /// 读取配置文件并合并平台默认配置
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PushError> {
+        let mut config: Self = load_config(path)?;
+        config.apply_defaults();
+        Ok(config)
+    }
+
+    /// 将平台默认配置合并到各通道中，通道自身的设置优先
+    pub fn apply_defaults(&mut self) {
+        for channel in self.channels.values_mut() {
+            let (Some(Value::Object(defaults)), Value::Object(config)) =
+                (self.defaults.get(&channel.platform), &mut channel.config)
+            else {
+                continue;
+            };
+            for (key, value) in defaults {
+                config.entry(key).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    /// 消息应投递的通道，按规则顺序去重
+    pub fn route(&self, message: &Message) -> Vec<&str> {
+        Route::select(&self.routes, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+    use serde_json::json;
+
+    #[test]
+    fn test_interpolate_env() {
+        let lookup = |name: &str| (name == "TOKEN").then(|| "secret".to_string());
+        assert_eq!(
+            interpolate_env("key=${TOKEN} port=${PORT:-8080} $$HOME $x", lookup).unwrap(),
+            "key=secret port=8080 $HOME $x"
+        );
+        assert!(matches!(
+            interpolate_env("${MISSING}", lookup),
+            Err(PushError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_serialized_secrets_round_trip() {
+        let config = json!({"token": "pa$$word${HOME}$x", "proxy": "${PROXY:-none}"});
+        let content = serialize_config(&config, ConfigFormat::Json).unwrap();
+        let parsed: Value = parse_config(&content, ConfigFormat::Json).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_defaults_and_routes() {
+        let mut config: MultiPushConfig = parse_config(
+            r#"{
+                "channels": {
+                    "ops": {"platform": "wxwork", "config": {"token": "a"}},
+                    "dev": {"platform": "wxwork", "config": {"token": "b", "proxy": "http://own:3128"}}
+                },
+                "defaults": {"wxwork": {"proxy": "socks5://127.0.0.1:1080"}},
+                "routes": [
+                    {"channels": ["ops"], "min_priority": "high"},
+                    {"channels": ["dev", "ops"], "metadata": {"team": "dev"}},
+                    {"channels": ["sre"], "labels": {"env": "prod", "service": "api"}}
+                ]
+            }"#,
+            ConfigFormat::Json,
+        )
+        .unwrap();
+        config.apply_defaults();
+        assert_eq!(
+            config.channels["ops"].config,
+            json!({"token": "a", "proxy": "socks5://127.0.0.1:1080"})
+        );
+        assert_eq!(config.channels["dev"].config["proxy"], "http://own:3128");
+
+        let mut message = Message::new(MessageType::Text("disk full".to_string()));
+        message.priority = Priority::Urgent;
+        message
+            .metadata
+            .insert("team".to_string(), "dev".to_string());
+        assert_eq!(config.route(&message), ["ops", "dev"]);
+        message.priority = Priority::Normal;
+        assert_eq!(config.route(&message), ["dev", "ops"]);
+        message = message.with_label("env", "prod");
+        assert_eq!(config.route(&message), ["dev", "ops"]);
+        message = message.with_label("service", "api");
+        assert_eq!(config.route(&message), ["dev", "ops", "sre"]);
+    }
+
+    #[test]
+    fn test_route_by_priority() {
+        let config: MultiPushConfig = serde_json::from_value(json!({
+            "routes": [
+                {"channels": ["sms-oncall", "wxwork-ops"], "priorities": ["urgent"]},
+                {"channels": ["wxwork-ops"], "priorities": ["normal", "high"]},
+                {"channels": ["log-file"], "priorities": ["low"]}
+            ]
+        }))
+        .unwrap();
+        let route = |priority| {
+            let mut message = Message::new(MessageType::Text("cpu".to_string()));
+            message.priority = priority;
+            config
+                .route(&message)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(route(Priority::Urgent), ["sms-oncall", "wxwork-ops"]);
+        assert_eq!(route(Priority::High), ["wxwork-ops"]);
+        assert_eq!(route(Priority::Normal), ["wxwork-ops"]);
+        assert_eq!(route(Priority::Low), ["log-file"]);
+    }
+
+    #[test]
+    fn test_channel_proxy_is_merged() {
+        let channel: ChannelConfig = serde_json::from_value(json!({
+            "platform": "wxwork",
+            "config": {"token": "t"},
+            "proxy": "socks5://127.0.0.1:1080"
+        }))
+        .unwrap();
+        assert_eq!(
+            channel.platform_config(),
+            json!({"token": "t", "proxy": "socks5://127.0.0.1:1080"})
+        );
+
+        let own = ChannelConfig {
+            config: json!({"token": "t", "proxy": "http://own:3128"}),
+            ..channel
+        };
+        assert_eq!(own.platform_config()["proxy"], "http://own:3128");
+    }
+
+    #[test]
+    fn test_channel_retry_is_merged() {
+        let channel: ChannelConfig = serde_json::from_value(json!({
+            "platform": "sms",
+            "config": {"token": "t"},
+            "retry": {"max_attempts": 1, "retry_on": ["timeout"]}
+        }))
+        .unwrap();
+        let config = channel.platform_config();
+        assert_eq!(config["retry"]["max_attempts"], 1);
+        assert_eq!(config["retry"]["retry_on"], json!(["timeout"]));
+        assert_eq!(config["retry"]["initial_backoff_ms"], 500);
+
+        let own = ChannelConfig {
+            config: json!({"token": "t", "retry": {"max_attempts": 5}}),
+            ..channel
+        };
+        assert_eq!(own.platform_config()["retry"], json!({"max_attempts": 5}));
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let quiet: QuietHours = serde_json::from_value(json!({
+            "start": "23:00",
+            "end": "08:00",
+            "utc_offset": "+08:00"
+        }))
+        .unwrap();
+        assert_eq!(quiet.bypass_priority, Priority::High);
+        let at = |time: &str| format!("2024-01-01T{}Z", time).parse().unwrap();
+        // 16:00 UTC 为 +08:00 的 00:00
+        assert!(quiet.is_quiet(at("16:00:00")));
+        assert!(quiet.is_quiet(at("23:59:00")));
+        assert!(!quiet.is_quiet(at("00:00:00")));
+        assert!(!quiet.is_quiet(at("14:59:00")));
+
+        let mut message = Message::new(MessageType::Text("backup done".to_string()));
+        assert!(quiet.holds(&message, at("16:00:00")));
+        message.priority = Priority::Urgent;
+        assert!(!quiet.holds(&message, at("16:00:00")));
+
+        assert_eq!(
+            serde_json::to_value(&quiet).unwrap()["utc_offset"],
+            "+08:00"
+        );
+        assert!(
+            serde_json::from_value::<QuietHours>(json!({
+                "start": "23:00", "end": "08:00", "utc_offset": "CST"
+            }))
+            .is_err()
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_parse_toml() {
+        let config: MultiPushConfig = parse_config(
+            "[channels.ops]\nplatform = \"wxwork\"\nconfig = { token = \"a\" }\n",
+            ConfigFormat::Toml,
+        )
+        .unwrap();
+        assert_eq!(config.channels["ops"].platform, "wxwork");
+
+        let content = serialize_config(&config, ConfigFormat::Toml).unwrap();
+        let parsed: MultiPushConfig = parse_config(&content, ConfigFormat::Toml).unwrap();
+        assert_eq!(parsed.channels["ops"].config, json!({"token": "a"}));
+        assert!(parsed.channels["ops"].proxy.is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{PushError, PushInitConfig};
+use reqwest::{Client, ClientBuilder, Proxy};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// 默认 User-Agent
+const USER_AGENT: &str = concat!("multi_push/", env!("CARGO_PKG_VERSION"));
+
+/// 创建 HTTP 客户端的设置，每次调用返回配置好的 builder
+type ClientSettings = Arc<dyn Fn() -> ClientBuilder + Send + Sync>;
+
+/// 平台创建上下文，在所有平台之间共享 HTTP 客户端
+///
+/// 连接池、代理、自定义 TLS 根证书和 User-Agent 都在客户端上配置一次即可；
+/// 默认客户端会读取 `HTTPS_PROXY` 等环境变量
+#[derive(Clone)]
+pub struct PlatformContext {
+    http_client: Client,
+    /// 配置了代理的平台在这些设置上加代理创建客户端
+    settings: ClientSettings,
+    /// 按代理地址缓存的客户端，配置了相同代理的平台共用连接池
+    proxied: Arc<Mutex<HashMap<String, Client>>>,
+}
+
+impl PlatformContext {
+    /// 使用自定义的 HTTP 客户端
+    ///
+    /// 已创建的客户端无法取回其设置，配置了代理的平台使用只设置了默认 User-Agent 的客户端；
+    /// 代理客户端需要沿用相同的 TLS 根证书、超时等设置时改用 [`PlatformContext::with_builder`]
+    pub fn new(http_client: Client) -> Self {
+        Self {
+            http_client,
+            settings: Arc::new(default_builder),
+            proxied: Arc::default(),
+        }
+    }
+
+    /// 按 `builder` 返回的设置创建共享客户端，配置了代理的平台在同样的设置上加代理
+    pub fn with_builder(
+        builder: impl Fn() -> ClientBuilder + Send + Sync + 'static,
+    ) -> Result<Self, PushError> {
+        let http_client = builder()
+            .build()
+            .map_err(|e| PushError::ConfigError(format!("Invalid HTTP client settings: {}", e)))?;
+        Ok(Self {
+            http_client,
+            settings: Arc::new(builder),
+            proxied: Arc::default(),
+        })
+    }
+
+    /// 共享的 HTTP 客户端，克隆开销很小且共用连接池
+    pub fn http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    /// 平台应使用的 HTTP 客户端：配置了代理时返回走该代理的客户端，否则返回共享客户端
+    pub fn client_for(&self, config: &dyn PushInitConfig) -> Result<Client, PushError> {
+        let Some(proxy) = config.proxy() else {
+            return Ok(self.http_client.clone());
+        };
+        let mut clients = self.proxied.lock().unwrap();
+        if let Some(client) = clients.get(proxy) {
+            return Ok(client.clone());
+        }
+        // 代理地址可能包含凭据，错误信息中不回显
+        let client = Proxy::all(proxy)
+            .and_then(|proxy| (self.settings)().proxy(proxy).build())
+            .map_err(|e| PushError::ConfigError(format!("Invalid proxy: {}", e)))?;
+        clients.insert(proxy.to_string(), client.clone());
+        Ok(client)
+    }
+}
+
+impl Default for PlatformContext {
+    fn default() -> Self {
+        Self::new(default_builder().build().unwrap_or_default())
+    }
+}
+
+impl fmt::Debug for PlatformContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlatformContext")
+            .field("http_client", &self.http_client)
+            .finish_non_exhaustive()
+    }
+}
+
+fn default_builder() -> ClientBuilder {
+    Client::builder().user_agent(USER_AGENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ProxyConfig(Option<&'static str>);
+
+    impl PushInitConfig for ProxyConfig {
+        fn platform_name(&self) -> &str {
+            "mock"
+        }
+
+        fn webhook_url(&self) -> String {
+            String::new()
+        }
+
+        fn secret(&self) -> Option<&str> {
+            None
+        }
+
+        fn timeout(&self) -> u64 {
+            30
+        }
+
+        fn retry_count(&self) -> u32 {
+            0
+        }
+
+        fn proxy(&self) -> Option<&str> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_client_for_proxy() {
+        let context = PlatformContext::default();
+        context.client_for(&ProxyConfig(None)).unwrap();
+        context
+            .client_for(&ProxyConfig(Some("socks5://127.0.0.1:1080")))
+            .unwrap();
+        context
+            .client_for(&ProxyConfig(Some("http://proxy.corp:3128")))
+            .unwrap();
+        assert_eq!(context.proxied.lock().unwrap().len(), 2);
+        assert!(matches!(
+            context.client_for(&ProxyConfig(Some("not a url"))),
+            Err(PushError::ConfigError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_proxied_client_keeps_builder_settings() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::any};
+
+        // 普通 HTTP 代理收到的是完整 URL 的请求，用模拟服务器充当代理
+        let proxy = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&proxy)
+            .await;
+        let context =
+            PlatformContext::with_builder(|| Client::builder().user_agent("ops-gateway/1.0"))
+                .unwrap();
+        let uri: &'static str = Box::leak(proxy.uri().into_boxed_str());
+        let client = context.client_for(&ProxyConfig(Some(uri))).unwrap();
+        client
+            .get("http://upstream.invalid/hook")
+            .send()
+            .await
+            .unwrap();
+
+        let requests = proxy.received_requests().await.unwrap();
+        assert_eq!(requests[0].headers["user-agent"], "ops-gateway/1.0");
+    }
+}
@@ -0,0 +1,296 @@
+use crate::sms::SmsHook;
+use crate::transform::{CapabilityCheck, TransformHook};
+use crate::{
+    CapabilityMode, CardSection, ChannelConfig, HookedPlatform, LOCALE_KEY, Message, MessageType,
+    Priority, PushError, PushPlatformCapabilities, SendHook, TIMEZONE_KEY, format_timestamp,
+    render_template_in,
+};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, OnceLock};
+
+/// 通道级消息装饰，发送前自动加到经过该通道的每条消息上
+///
+/// 前缀、后缀和页脚按 Jinja 语法渲染，可用变量：`hostname`、`timestamp`、
+/// `priority`、`channel`、`platform` 和消息的 `metadata`、`labels`；设置了通道时区时
+/// `timestamp` 和 `tz` 过滤器使用该时区
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Decorations {
+    /// 加在正文前，如 `[prod] `，需要的空格或换行写在配置里
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// 加在正文后
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    /// 页脚，与正文之间空一行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    /// 按优先级加在最前面的 emoji，如 `urgent = "🚨"`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub emoji: BTreeMap<Priority, String>,
+}
+
+#[derive(Serialize)]
+struct DecorationContext<'a> {
+    hostname: &'a str,
+    timestamp: String,
+    priority: Priority,
+    channel: &'a str,
+    platform: &'a str,
+    metadata: &'a HashMap<String, String>,
+    labels: &'a HashMap<String, String>,
+}
+
+impl Decorations {
+    /// 是否没有任何装饰
+    pub fn is_empty(&self) -> bool {
+        self.prefix.is_none()
+            && self.suffix.is_none()
+            && self.footer.is_none()
+            && self.emoji.is_empty()
+    }
+
+    /// 装饰消息内容
+    ///
+    /// 只处理带正文的消息：文本、Markdown、HTML、富文本、链接和卡片，其余类型原样发送
+    pub fn apply(
+        &self,
+        channel: &str,
+        platform: &str,
+        message: &mut Message,
+    ) -> Result<(), PushError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let timezone = message.metadata.get(TIMEZONE_KEY).map(String::as_str);
+        let timestamp = match timezone {
+            Some(timezone) => {
+                format_timestamp(jiff::Timestamp::now(), timezone, "%Y-%m-%d %H:%M:%S %Z")?
+            }
+            None => Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        };
+        let context = DecorationContext {
+            hostname: hostname(),
+            timestamp,
+            priority: message.priority,
+            channel,
+            platform,
+            metadata: &message.metadata,
+            labels: &message.labels,
+        };
+        let render = |template: &Option<String>| {
+            template
+                .as_deref()
+                .map(|template| render_template_in(template, &context, timezone))
+                .transpose()
+                .map(Option::unwrap_or_default)
+        };
+        let mut head = self
+            .emoji
+            .get(&message.priority)
+            .map(|emoji| format!("{} ", emoji))
+            .unwrap_or_default();
+        head.push_str(&render(&self.prefix)?);
+        let tail = render(&self.suffix)?;
+        let footer = render(&self.footer)?;
+
+        let decorate = |body: &mut String, separator: &str| {
+            *body = format!("{}{}{}", head, body, tail);
+            if !footer.is_empty() {
+                body.push_str(separator);
+                body.push_str(&footer);
+            }
+        };
+        match &mut message.content {
+            MessageType::Text(body) | MessageType::Markdown(body) => decorate(body, "\n\n"),
+            MessageType::Html(body) => decorate(body, "<br><br>"),
+            MessageType::Rich { content, .. } => decorate(content, "\n\n"),
+            MessageType::Link { description, .. } => decorate(description, "\n\n"),
+            MessageType::Card {
+                title, sections, ..
+            } => {
+                *title = format!("{}{}{}", head, title, tail);
+                if !footer.is_empty() {
+                    sections.push(CardSection {
+                        content: footer.clone(),
+                        ..Default::default()
+                    });
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// 本机主机名，依次读取 `HOSTNAME` 环境变量和 `/etc/hostname`
+fn hostname() -> &'static str {
+    static HOSTNAME: OnceLock<String> = OnceLock::new();
+    HOSTNAME.get_or_init(|| {
+        std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+}
+
+/// 发送前为消息设置通道时区、语言并加上通道装饰的拦截器
+struct ChannelDecorator {
+    channel: String,
+    decorations: Decorations,
+    timezone: Option<String>,
+    locale: Option<String>,
+}
+
+#[async_trait]
+impl SendHook for ChannelDecorator {
+    async fn before_send(&self, platform: &str, message: &mut Message) -> Result<(), PushError> {
+        // 消息自带的时区和语言优先
+        for (key, value) in [(TIMEZONE_KEY, &self.timezone), (LOCALE_KEY, &self.locale)] {
+            if let Some(value) = value {
+                message
+                    .metadata
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+        self.decorations.apply(&self.channel, platform, message)
+    }
+}
+
+/// 为通道的平台实例套上能力检查、内容策略、装饰、emoji 展开、Markdown 渲染和短信预算拦截器，
+/// 都没有配置时原样返回
+///
+/// 内容策略先于装饰执行，只检查调用方提供的内容；emoji 最后展开，装饰中的 shortcode 同样生效
+pub(crate) fn decorate(
+    name: &str,
+    channel: &ChannelConfig,
+    platform: Box<dyn PushPlatformCapabilities>,
+) -> Box<dyn PushPlatformCapabilities> {
+    let mut hooks: Vec<Arc<dyn SendHook>> = Vec::new();
+    if channel.capabilities == CapabilityMode::Strict {
+        hooks.push(Arc::new(CapabilityCheck {
+            target: platform.platform_info(),
+        }));
+    }
+    if let Some(policy) = &channel.policy {
+        hooks.push(Arc::new(policy.clone()));
+    }
+    if !channel.decorations.is_empty() || channel.timezone.is_some() || channel.locale.is_some() {
+        hooks.push(Arc::new(ChannelDecorator {
+            channel: name.to_string(),
+            decorations: channel.decorations.clone(),
+            timezone: channel.timezone.clone(),
+            locale: channel.locale.clone(),
+        }));
+    }
+    if let Some(emoji) = &channel.emoji {
+        hooks.push(Arc::new(TransformHook {
+            stage: emoji.clone(),
+            target: platform.platform_info(),
+        }));
+    }
+    if let Some(markdown_image) = &channel.markdown_image {
+        hooks.push(Arc::new(TransformHook {
+            stage: markdown_image.clone(),
+            target: platform.platform_info(),
+        }));
+    }
+    // 短信预算最后执行，按装饰和 emoji 展开后的最终内容计算长度
+    if let Some(sms) = &channel.sms {
+        hooks.push(Arc::new(SmsHook::new(
+            sms.clone(),
+            platform.platform_info(),
+        )));
+    }
+    if hooks.is_empty() {
+        return platform;
+    }
+    Box::new(HookedPlatform::new(platform, hooks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let decorations = Decorations {
+            prefix: Some("[{{ labels.env }}] ".to_string()),
+            suffix: None,
+            footer: Some("sent via {{ channel }} ({{ platform }})".to_string()),
+            emoji: BTreeMap::from([(Priority::Urgent, "🚨".to_string())]),
+        };
+        let mut message =
+            Message::new(MessageType::Text("disk full".to_string())).with_label("env", "prod");
+        message.priority = Priority::Urgent;
+        decorations.apply("ops", "wxwork", &mut message).unwrap();
+        assert!(matches!(
+            &message.content,
+            MessageType::Text(t) if t == "🚨 [prod] disk full\n\nsent via ops (wxwork)"
+        ));
+
+        let mut message = Message::new(MessageType::Card {
+            title: "deploy".to_string(),
+            sections: Vec::new(),
+            buttons: Vec::new(),
+        });
+        decorations.apply("ops", "wxwork", &mut message).unwrap();
+        let MessageType::Card {
+            title, sections, ..
+        } = &message.content
+        else {
+            unreachable!()
+        };
+        assert_eq!(title, "[] deploy");
+        assert_eq!(sections[0].content, "sent via ops (wxwork)");
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let decorations: Decorations = serde_json::from_value(serde_json::json!({
+            "footer": "{{ hostname }} {{ timestamp }}",
+            "emoji": {"high": "⚠️"}
+        }))
+        .unwrap();
+        assert_eq!(decorations.emoji[&Priority::High], "⚠️");
+
+        let mut message = Message::new(MessageType::Markdown("**ok**".to_string()));
+        decorations.apply("ops", "slack", &mut message).unwrap();
+        let MessageType::Markdown(body) = &message.content else {
+            unreachable!()
+        };
+        assert!(body.starts_with("**ok**\n\n"));
+        assert!(body.ends_with('Z'));
+
+        let mut message = Message::new(MessageType::Text("ok".to_string()))
+            .with_metadata(TIMEZONE_KEY, "Asia/Shanghai");
+        decorations.apply("ops", "slack", &mut message).unwrap();
+        let MessageType::Text(body) = &message.content else {
+            unreachable!()
+        };
+        assert!(body.ends_with(" CST"));
+    }
+
+    #[tokio::test]
+    async fn test_channel_locale() {
+        let decorator = ChannelDecorator {
+            channel: "ops".to_string(),
+            decorations: Decorations::default(),
+            timezone: None,
+            locale: Some("ja".to_string()),
+        };
+        let mut message = Message::new(MessageType::Text("ok".to_string()));
+        decorator.before_send("slack", &mut message).await.unwrap();
+        assert_eq!(message.locale(), Some("ja"));
+
+        let mut message = Message::new(MessageType::Text("ok".to_string())).with_locale("en");
+        decorator.before_send("slack", &mut message).await.unwrap();
+        assert_eq!(message.locale(), Some("en"));
+    }
+}
@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+
+/// 平台支持的 Markdown 方言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkdownDialect {
+    /// 通用 Markdown，原样发送
+    #[default]
+    Standard,
+    /// Telegram MarkdownV2，需要转义保留字符
+    TelegramV2,
+    /// Slack mrkdwn
+    Slack,
+    /// 企业微信，仅支持标题、加粗、链接、引用和行内代码
+    WxWork,
+    /// 纯文本
+    PlainText,
+}
+
+/// Telegram MarkdownV2 中需要转义的字符
+const TELEGRAM_RESERVED: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+/// 行内元素
+#[derive(Debug)]
+enum Inline {
+    Text(String),
+    Bold(Vec<Inline>),
+    Italic(Vec<Inline>),
+    Strike(Vec<Inline>),
+    Code(String),
+    Link { text: Vec<Inline>, url: String },
+}
+
+/// 将通用 Markdown 子集转换为目标方言
+///
+/// 支持标题、引用、列表、代码块，以及加粗、斜体、删除线、行内代码、链接和图片（按链接处理）
+pub fn convert_markdown(markdown: &str, dialect: MarkdownDialect) -> String {
+    if dialect == MarkdownDialect::Standard {
+        return markdown.to_string();
+    }
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            match dialect {
+                MarkdownDialect::PlainText => {}
+                // Slack 代码块不支持语言标注
+                MarkdownDialect::Slack => lines.push("```".to_string()),
+                _ => lines.push(trimmed.to_string()),
+            }
+        } else if in_fence {
+            lines.push(match dialect {
+                MarkdownDialect::TelegramV2 => escape_telegram_code(line),
+                MarkdownDialect::Slack => escape_slack(line),
+                _ => line.to_string(),
+            });
+        } else {
+            lines.push(convert_line(line, dialect));
+        }
+    }
+    lines.join("\n")
+}
+
+fn convert_line(line: &str, dialect: MarkdownDialect) -> String {
+    let trimmed = line.trim_start();
+    let heading = trimmed.trim_start_matches('#');
+    if heading.len() < trimmed.len()
+        && trimmed.len() - heading.len() <= 6
+        && heading.starts_with(' ')
+    {
+        let text = render(&parse_inline(heading.trim()), dialect);
+        return match dialect {
+            MarkdownDialect::TelegramV2 | MarkdownDialect::Slack => format!("*{}*", text),
+            MarkdownDialect::PlainText => text,
+            _ => format!("{} {}", &trimmed[..trimmed.len() - heading.len()], text),
+        };
+    }
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let text = render(&parse_inline(quote.trim_start()), dialect);
+        return match dialect {
+            MarkdownDialect::PlainText => text,
+            _ => format!("> {}", text),
+        };
+    }
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = trimmed.strip_prefix(bullet) {
+            let text = render(&parse_inline(item), dialect);
+            return match dialect {
+                MarkdownDialect::TelegramV2 | MarkdownDialect::Slack => format!("• {}", text),
+                _ => format!("- {}", text),
+            };
+        }
+    }
+    render(&parse_inline(line), dialect)
+}
+
+fn parse_inline(text: &str) -> Vec<Inline> {
+    let mut nodes = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    let mut prev_alnum = false;
+    while let Some(c) = rest.chars().next() {
+        if let Some((node, len)) = parse_span(rest, prev_alnum) {
+            if !plain.is_empty() {
+                nodes.push(Inline::Text(std::mem::take(&mut plain)));
+            }
+            nodes.push(node);
+            rest = &rest[len..];
+            prev_alnum = false;
+            continue;
+        }
+        plain.push(c);
+        prev_alnum = c.is_alphanumeric();
+        rest = &rest[c.len_utf8()..];
+    }
+    if !plain.is_empty() {
+        nodes.push(Inline::Text(plain));
+    }
+    nodes
+}
+
+/// 将子元素包装为强调元素的构造函数
+type Wrap = fn(Vec<Inline>) -> Inline;
+
+/// 强调标记及对应的元素，较长的标记优先匹配
+const DELIMITERS: [(&str, Wrap); 5] = [
+    ("**", Inline::Bold),
+    ("__", Inline::Bold),
+    ("~~", Inline::Strike),
+    ("*", Inline::Italic),
+    ("_", Inline::Italic),
+];
+
+/// 尝试在开头解析一个行内元素，返回元素和消耗的字节数
+fn parse_span(text: &str, prev_alnum: bool) -> Option<(Inline, usize)> {
+    if let Some(inner) = text.strip_prefix('`') {
+        let end = inner.find('`')?;
+        return Some((Inline::Code(inner[..end].to_string()), end + 2));
+    }
+    if let Some(image) = text.strip_prefix('!') {
+        let (node, len) = parse_link(image)?;
+        return Some((node, len + 1));
+    }
+    if text.starts_with('[') {
+        return parse_link(text);
+    }
+    for (delimiter, node) in DELIMITERS {
+        // 下划线出现在单词中间时（如 snake_case）不作为强调
+        if delimiter.starts_with('_') && prev_alnum {
+            continue;
+        }
+        let Some(inner) = text.strip_prefix(delimiter) else {
+            continue;
+        };
+        if inner.starts_with(char::is_whitespace) {
+            continue;
+        }
+        if let Some(end) = inner.find(delimiter).filter(|&end| end > 0) {
+            let len = end + delimiter.len() * 2;
+            return Some((node(parse_inline(&inner[..end])), len));
+        }
+    }
+    None
+}
+
+fn parse_link(text: &str) -> Option<(Inline, usize)> {
+    let mid = text.find("](")?;
+    let end = text[mid + 2..].find(')')? + mid + 2;
+    let node = Inline::Link {
+        text: parse_inline(&text[1..mid]),
+        url: text[mid + 2..end].to_string(),
+    };
+    Some((node, end + 1))
+}
+
+fn render(nodes: &[Inline], dialect: MarkdownDialect) -> String {
+    nodes
+        .iter()
+        .map(|node| render_node(node, dialect))
+        .collect()
+}
+
+fn render_node(node: &Inline, dialect: MarkdownDialect) -> String {
+    use MarkdownDialect::*;
+    match node {
+        Inline::Text(text) => match dialect {
+            TelegramV2 => escape_telegram(text),
+            Slack => escape_slack(text),
+            _ => text.clone(),
+        },
+        Inline::Bold(inner) => match dialect {
+            TelegramV2 | Slack => format!("*{}*", render(inner, dialect)),
+            PlainText => render(inner, dialect),
+            _ => format!("**{}**", render(inner, dialect)),
+        },
+        Inline::Italic(inner) => match dialect {
+            TelegramV2 | Slack => format!("_{}_", render(inner, dialect)),
+            Standard => format!("*{}*", render(inner, dialect)),
+            WxWork | PlainText => render(inner, dialect),
+        },
+        Inline::Strike(inner) => match dialect {
+            TelegramV2 | Slack => format!("~{}~", render(inner, dialect)),
+            Standard => format!("~~{}~~", render(inner, dialect)),
+            WxWork | PlainText => render(inner, dialect),
+        },
+        Inline::Code(code) => match dialect {
+            TelegramV2 => format!("`{}`", escape_telegram_code(code)),
+            Slack => format!("`{}`", escape_slack(code)),
+            PlainText => code.clone(),
+            _ => format!("`{}`", code),
+        },
+        Inline::Link { text, url } => {
+            let label = render(text, dialect);
+            match dialect {
+                TelegramV2 => format!(
+                    "[{}]({})",
+                    label,
+                    url.replace('\\', "\\\\").replace(')', "\\)")
+                ),
+                Slack => format!("<{}|{}>", url, label),
+                PlainText if label == *url => url.clone(),
+                PlainText => format!("{} ({})", label, url),
+                _ => format!("[{}]({})", label, url),
+            }
+        }
+    }
+}
+
+fn escape_telegram(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if TELEGRAM_RESERVED.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 代码中只需转义反引号和反斜杠
+fn escape_telegram_code(code: &str) -> String {
+    code.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str =
+        "# Deploy *v1.2*\n**api** is ~~down~~ up, see [logs](http://ci/1?a=1)\n- run `make test`";
+
+    #[test]
+    fn test_telegram_escaping() {
+        assert_eq!(
+            convert_markdown(SAMPLE, MarkdownDialect::TelegramV2),
+            "*Deploy _v1\\.2_*\n*api* is ~down~ up, see [logs](http://ci/1?a=1)\n• run `make test`"
+        );
+        assert_eq!(
+            convert_markdown("1. a_b (c)!", MarkdownDialect::TelegramV2),
+            "1\\. a\\_b \\(c\\)\\!"
+        );
+    }
+
+    #[test]
+    fn test_slack_mrkdwn() {
+        assert_eq!(
+            convert_markdown(SAMPLE, MarkdownDialect::Slack),
+            "*Deploy _v1.2_*\n*api* is ~down~ up, see <http://ci/1?a=1|logs>\n• run `make test`"
+        );
+        assert_eq!(
+            convert_markdown("a < b & c", MarkdownDialect::Slack),
+            "a &lt; b &amp; c"
+        );
+    }
+
+    #[test]
+    fn test_wxwork_and_plain_text() {
+        assert_eq!(
+            convert_markdown(SAMPLE, MarkdownDialect::WxWork),
+            "# Deploy v1.2\n**api** is down up, see [logs](http://ci/1?a=1)\n- run `make test`"
+        );
+        assert_eq!(
+            convert_markdown(SAMPLE, MarkdownDialect::PlainText),
+            "Deploy v1.2\napi is down up, see logs (http://ci/1?a=1)\n- run make test"
+        );
+        assert_eq!(
+            convert_markdown("```rust\nlet a_b = 1;\n```", MarkdownDialect::PlainText),
+            "let a_b = 1;"
+        );
+    }
+}
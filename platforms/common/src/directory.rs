@@ -0,0 +1,142 @@
+use crate::{Mention, Message, PushError, SendHook};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 一个逻辑用户在各平台上的标识
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectoryEntry {
+    /// 手机号，平台没有单独配置时使用
+    #[serde(default)]
+    pub phone: Option<String>,
+    /// 邮箱，平台没有单独配置且没有手机号时使用
+    #[serde(default)]
+    pub email: Option<String>,
+    /// 平台名称到平台用户 ID 的映射，如 `wxwork: zhangsan`
+    #[serde(flatten)]
+    pub platforms: HashMap<String, String>,
+}
+
+/// 用户目录，将逻辑用户 ID（如 LDAP 用户名）映射为各平台的提醒对象
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Directory {
+    users: HashMap<String, DirectoryEntry>,
+}
+
+impl Directory {
+    /// 从用户表创建目录
+    pub fn new(users: HashMap<String, DirectoryEntry>) -> Self {
+        Self { users }
+    }
+
+    /// 目录是否为空
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// 解析在指定平台上的提醒对象，按逻辑用户 ID、手机号或邮箱查找用户，目录中没有的用户原样返回
+    pub fn resolve(&self, mention: &Mention, platform: &str) -> Mention {
+        let Some(entry) = self.find(mention) else {
+            return mention.clone();
+        };
+        if let Some(id) = entry.platforms.get(platform) {
+            Mention::UserId(id.clone())
+        } else if let Some(phone) = &entry.phone {
+            Mention::Phone(phone.clone())
+        } else if let Some(email) = &entry.email {
+            Mention::Email(email.clone())
+        } else {
+            mention.clone()
+        }
+    }
+
+    /// 提醒对象对应的目录用户，邮箱不区分大小写
+    fn find(&self, mention: &Mention) -> Option<&DirectoryEntry> {
+        match mention {
+            Mention::All => None,
+            Mention::UserId(user) => self.users.get(user),
+            Mention::Phone(phone) => self
+                .users
+                .values()
+                .find(|entry| entry.phone.as_deref() == Some(phone.as_str())),
+            Mention::Email(email) => self.users.values().find(|entry| {
+                entry
+                    .email
+                    .as_deref()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(email))
+            }),
+        }
+    }
+}
+
+/// 发送前将消息中的逻辑用户替换为目标平台的标识
+#[async_trait]
+impl SendHook for Directory {
+    async fn before_send(&self, platform: &str, message: &mut Message) -> Result<(), PushError> {
+        for mention in &mut message.mentions {
+            *mention = self.resolve(mention, platform);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_per_platform() {
+        let directory: Directory = serde_json::from_str(
+            r#"{
+                "alice": {"wxwork": "zhangsan", "phone": "13800000000"},
+                "bob": {"email": "bob@example.com"}
+            }"#,
+        )
+        .unwrap();
+        let alice = Mention::from("alice");
+        assert_eq!(
+            directory.resolve(&alice, "wxwork"),
+            Mention::UserId("zhangsan".to_string())
+        );
+        assert_eq!(
+            directory.resolve(&alice, "dingtalk"),
+            Mention::Phone("13800000000".to_string())
+        );
+        assert_eq!(
+            directory.resolve(&Mention::from("bob"), "wxwork"),
+            Mention::Email("bob@example.com".to_string())
+        );
+        assert_eq!(
+            directory.resolve(&Mention::from("carol"), "wxwork"),
+            Mention::from("carol")
+        );
+        assert_eq!(directory.resolve(&Mention::All, "wxwork"), Mention::All);
+    }
+
+    #[test]
+    fn test_resolve_email_and_phone() {
+        let directory: Directory = serde_json::from_str(
+            r#"{
+                "alice": {"wxwork": "zhangsan", "phone": "13800000000"},
+                "bob": {"slack": "U123", "email": "bob@example.com"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            directory.resolve(&Mention::Phone("13800000000".to_string()), "wxwork"),
+            Mention::UserId("zhangsan".to_string())
+        );
+        assert_eq!(
+            directory.resolve(&Mention::Email("Bob@Example.com".to_string()), "slack"),
+            Mention::UserId("U123".to_string())
+        );
+        // 平台上没有对应用户时退回目录中的手机号或邮箱
+        assert_eq!(
+            directory.resolve(&Mention::Email("bob@example.com".to_string()), "wxwork"),
+            Mention::Email("bob@example.com".to_string())
+        );
+        let unknown = Mention::Phone("13900000000".to_string());
+        assert_eq!(directory.resolve(&unknown, "wxwork"), unknown);
+    }
+}
@@ -0,0 +1,80 @@
+//! 演练模式：路由、模板和限流照常执行，只跳过最终的平台请求
+//!
+//! 支持演练的平台声明 [`DRY_RUN_FEATURE`](crate::DRY_RUN_FEATURE)，在发请求前调用
+//! [`intercept`]，处于演练作用域时返回渲染好的请求载荷而不发出请求
+
+use crate::PushResult;
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    /// 作用域内拦截的请求载荷
+    static DRY_RUN: Arc<Mutex<Vec<Value>>>;
+}
+
+/// 在演练作用域内执行 `future`，已在作用域内时沿用外层作用域
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    if active() {
+        return future.await;
+    }
+    DRY_RUN.scope(Arc::default(), future).await
+}
+
+/// 在新的演练作用域内执行 `future`，同时返回作用域内拦截的全部请求载荷，
+/// 如超长消息拆分后的每一条
+pub async fn capture<F: Future>(future: F) -> (F::Output, Vec<Value>) {
+    let payloads: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let output = DRY_RUN.scope(payloads.clone(), future).await;
+    let payloads = std::mem::take(&mut *payloads.lock().unwrap());
+    (output, payloads)
+}
+
+/// 当前是否处于演练作用域
+pub fn active() -> bool {
+    DRY_RUN.try_with(|_| ()).is_ok()
+}
+
+/// 处于演练作用域时返回以 `payload` 为响应的成功结果，平台应直接返回它而不发出请求
+pub fn intercept(payload: &impl Serialize) -> Option<PushResult> {
+    DRY_RUN
+        .try_with(|payloads| {
+            let value = serde_json::to_value(payload).unwrap_or_default();
+            payloads.lock().unwrap().push(value)
+        })
+        .ok()?;
+    let response = serde_json::to_string(payload).unwrap_or_default();
+    Some(PushResult {
+        success: true,
+        response: Some(response),
+        dry_run: true,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_intercept_only_in_scope() {
+        let payload = json!({"msgtype": "text"});
+        assert!(intercept(&payload).is_none());
+        let result = scope(async { intercept(&payload) }).await.unwrap();
+        assert!(result.success && result.dry_run);
+        assert_eq!(result.response.as_deref(), Some(r#"{"msgtype":"text"}"#));
+        assert!(!active());
+    }
+
+    #[tokio::test]
+    async fn test_capture_collects_nested_payloads() {
+        let ((), payloads) = capture(async {
+            intercept(&json!({"part": 1}));
+            scope(async { intercept(&json!({"part": 2})) }).await;
+        })
+        .await;
+        assert_eq!(payloads, vec![json!({"part": 1}), json!({"part": 2})]);
+    }
+}
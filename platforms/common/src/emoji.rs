@@ -0,0 +1,225 @@
+use crate::{EMOJI_SHORTCODE_FEATURE, Message, MessageTransformer, PlatformInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 常用 shortcode，名称与 GitHub、Slack 一致，按名称排序以便二分查找
+const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("alarm_clock", "⏰"),
+    ("arrow_down", "⬇️"),
+    ("arrow_up", "⬆️"),
+    ("bangbang", "‼️"),
+    ("bell", "🔔"),
+    ("bomb", "💣"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("calendar", "📆"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("check", "✔️"),
+    ("clap", "👏"),
+    ("clipboard", "📋"),
+    ("clock3", "🕒"),
+    ("closed_lock_with_key", "🔐"),
+    ("cloud", "☁️"),
+    ("construction", "🚧"),
+    ("credit_card", "💳"),
+    ("cry", "😢"),
+    ("dart", "🎯"),
+    ("disappointed", "😞"),
+    ("dollar", "💵"),
+    ("email", "📧"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("gear", "⚙️"),
+    ("green_circle", "🟢"),
+    ("hammer", "🔨"),
+    ("heart", "❤️"),
+    ("heavy_check_mark", "✔️"),
+    ("hourglass", "⌛"),
+    ("house", "🏠"),
+    ("information_source", "ℹ️"),
+    ("key", "🔑"),
+    ("label", "🏷️"),
+    ("link", "🔗"),
+    ("lock", "🔒"),
+    ("mag", "🔍"),
+    ("memo", "📝"),
+    ("money_with_wings", "💸"),
+    ("no_entry", "⛔"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("package", "📦"),
+    ("pencil", "📝"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rainbow", "🌈"),
+    ("recycle", "♻️"),
+    ("red_circle", "🔴"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("scream", "😱"),
+    ("shield", "🛡️"),
+    ("skull", "💀"),
+    ("smile", "😄"),
+    ("snail", "🐌"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("stopwatch", "⏱️"),
+    ("sunny", "☀️"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("tools", "🛠️"),
+    ("trophy", "🏆"),
+    ("turtle", "🐢"),
+    ("unlock", "🔓"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("yellow_circle", "🟡"),
+    ("zap", "⚡"),
+];
+
+/// 将 `:rocket:` 形式的 shortcode 展开为 Unicode emoji，未知的 shortcode 原样保留
+///
+/// 目标平台声明了 [`EMOJI_SHORTCODE_FEATURE`]（如 Slack、Discord）时不展开，由平台渲染；
+/// 行内代码和代码块中的内容不处理
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmojiShortcodes {
+    /// 自定义 shortcode，名称不含冒号，优先于内置表
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+}
+
+impl EmojiShortcodes {
+    /// shortcode 对应的 emoji
+    pub fn lookup(&self, name: &str) -> Option<&str> {
+        self.custom.get(name).map(String::as_str).or_else(|| {
+            SHORTCODES
+                .binary_search_by(|(code, _)| code.cmp(&name))
+                .ok()
+                .map(|i| SHORTCODES[i].1)
+        })
+    }
+
+    /// 展开文本中的 shortcode
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut in_code = false;
+        for (i, segment) in text.split('`').enumerate() {
+            if i > 0 {
+                out.push('`');
+            }
+            // 反引号之间的内容是行内代码或代码块，原样保留
+            if in_code {
+                out.push_str(segment);
+            } else {
+                self.expand_segment(segment, &mut out);
+            }
+            in_code = !in_code;
+        }
+        out
+    }
+
+    fn expand_segment(&self, mut rest: &str, out: &mut String) {
+        while let Some(start) = rest.find(':') {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let emoji = after
+                .find(':')
+                .map(|end| &after[..end])
+                .filter(|name| is_shortcode_name(name))
+                .and_then(|name| Some((name, self.lookup(name)?)));
+            match emoji {
+                Some((name, emoji)) => {
+                    out.push_str(emoji);
+                    rest = &after[name.len() + 1..];
+                }
+                // 结尾的冒号可能是下一个 shortcode 的开头
+                None => {
+                    out.push(':');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+    }
+}
+
+fn is_shortcode_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_+-".contains(c))
+}
+
+impl MessageTransformer for EmojiShortcodes {
+    fn transform(&self, mut message: Message, target: &PlatformInfo) -> Message {
+        if target.has_feature(EMOJI_SHORTCODE_FEATURE) {
+            return message;
+        }
+        for text in message.content.texts_mut() {
+            if text.contains(':') {
+                *text = self.expand(text);
+            }
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+
+    #[test]
+    fn test_sorted() {
+        assert!(SHORTCODES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_expand() {
+        let emoji = EmojiShortcodes {
+            custom: BTreeMap::from([("shipit".to_string(), "🐿️".to_string())]),
+        };
+        assert_eq!(
+            emoji.expand(":rocket: deployed :shipit: at 10:30:00 :nope::tada:"),
+            "🚀 deployed 🐿️ at 10:30:00 :nope:🎉"
+        );
+        assert_eq!(
+            emoji.expand("run `echo :fire:` then :fire:"),
+            "run `echo :fire:` then 🔥"
+        );
+        assert_eq!(emoji.expand("http://host:8080/:x"), "http://host:8080/:x");
+    }
+
+    #[test]
+    fn test_native_platform() {
+        let mut info = PlatformInfo {
+            name: "slack".to_string(),
+            version: String::new(),
+            message_types: [crate::MessageKind::Text, crate::MessageKind::Markdown].into(),
+            mentions: Default::default(),
+            features: vec![],
+            limits: Default::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        };
+        let message = Message::new(MessageType::Markdown(":warning: disk full".to_string()));
+        let expanded = EmojiShortcodes::default().transform(message.clone(), &info);
+        assert!(matches!(expanded.content, MessageType::Markdown(t) if t == "⚠️ disk full"));
+
+        info.features.push(EMOJI_SHORTCODE_FEATURE.to_string());
+        let native = EmojiShortcodes::default().transform(message, &info);
+        assert!(matches!(native.content, MessageType::Markdown(t) if t == ":warning: disk full"));
+    }
+}
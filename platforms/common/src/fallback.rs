@@ -0,0 +1,355 @@
+use crate::{
+    AttachmentSource, CardButton, CardSection, DELETE_FEATURE, EDIT_FEATURE, Mention, Message,
+    MessageType, PlatformInfo, PushError, PushPlatformCapabilities, PushResult, RECEIPT_FEATURE,
+    Receipt,
+};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+
+/// 按顺序尝试多个平台，前一个出现可重试错误时改用下一个
+pub struct FallbackPlatform {
+    platforms: Vec<Box<dyn PushPlatformCapabilities>>,
+}
+
+impl FallbackPlatform {
+    /// 按优先级顺序创建
+    pub fn new(platforms: Vec<Box<dyn PushPlatformCapabilities>>) -> Self {
+        Self { platforms }
+    }
+
+    /// 依次调用 `op`，成功或遇到不可重试的错误时返回
+    async fn run<'a, F>(&'a self, op: F) -> Result<PushResult, PushError>
+    where
+        F: Fn(&'a dyn PushPlatformCapabilities) -> BoxFuture<'a, Result<PushResult, PushError>>
+            + Send,
+    {
+        let mut last_error = PushError::ConfigError("No fallback platforms configured".to_string());
+        for platform in &self.platforms {
+            match op(platform.as_ref()).await {
+                Ok(result) => return Ok(result),
+                // 其他平台可能支持该消息类型
+                Err(e) if matches!(e.inner(), PushError::Unsupported { .. }) => last_error = e,
+                // 消息本身或鉴权等问题换平台通常也无法解决
+                Err(e) if !e.is_retryable() => return Err(e),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// 依次查询支持回执的平台并汇总，全部查询失败时返回最后一个错误
+    async fn collect_receipts<'a, F>(&'a self, op: F) -> Result<Vec<Receipt>, PushError>
+    where
+        F: Fn(&'a dyn PushPlatformCapabilities) -> BoxFuture<'a, Result<Vec<Receipt>, PushError>>
+            + Send,
+    {
+        let mut receipts = Vec::new();
+        let mut last_error = None;
+        let mut polled = false;
+        for platform in &self.platforms {
+            if !platform.platform_info().has_feature(RECEIPT_FEATURE) {
+                continue;
+            }
+            match op(platform.as_ref()).await {
+                Ok(batch) => {
+                    polled = true;
+                    receipts.extend(batch);
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        match last_error {
+            Some(e) if !polled => Err(e),
+            _ => Ok(receipts),
+        }
+    }
+
+    /// 不知道消息由哪个平台发出，依次交给声明了 `feature` 的平台，返回第一个成功的结果
+    async fn run_any<'a, F>(&'a self, feature: &str, op: F) -> Result<PushResult, PushError>
+    where
+        F: Fn(&'a dyn PushPlatformCapabilities) -> BoxFuture<'a, Result<PushResult, PushError>>
+            + Send,
+    {
+        let mut last_error =
+            PushError::PlatformError(format!("No fallback platform supports '{}'", feature));
+        for platform in &self.platforms {
+            if !platform.platform_info().has_feature(feature) {
+                continue;
+            }
+            match op(platform.as_ref()).await {
+                Ok(result) => return Ok(result),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for FallbackPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        for platform in &mut self.platforms {
+            platform.init().await?;
+        }
+        Ok(())
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_text(content)).await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_text_with_mention(content, mentions.clone()))
+            .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_markdown(content)).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_rich(title, content, url)).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_image(image_url, caption)).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_link(title, description, url, image_url))
+            .await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_file(name, mime, source)).await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_card(title, sections, buttons)).await
+    }
+
+    async fn send_template(
+        &self,
+        name: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_template(name, variables)).await
+    }
+
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_audio(source, caption, duration_secs))
+            .await
+    }
+
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_video(source, caption, duration_secs))
+            .await
+    }
+
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_location(lat, lon, label)).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        self.run(|p| p.send(message.clone())).await
+    }
+
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        self.run(|p| p.send_message(message.clone())).await
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        self.run_any(EDIT_FEATURE, |p| {
+            p.update_message(message_id, message.clone())
+        })
+        .await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        self.run_any(DELETE_FEATURE, |p| p.delete_message(message_id))
+            .await
+    }
+
+    /// 汇总所有支持回执的平台，全部查询失败时返回最后一个错误
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        self.collect_receipts(|p| p.poll_receipts()).await
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        self.collect_receipts(|p| p.poll_receipts_for(message_ids))
+            .await
+    }
+
+    /// 任一平台健康即视为健康
+    async fn health_check(&self) -> Result<bool, PushError> {
+        for platform in &self.platforms {
+            if let Ok(true) = platform.health_check().await {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        let infos: Vec<PlatformInfo> = self.platforms.iter().map(|p| p.platform_info()).collect();
+        let names: Vec<&str> = infos.iter().map(|i| i.name.as_str()).collect();
+        PlatformInfo {
+            name: format!("fallback({})", names.join(",")),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            // 只声明所有平台都支持的能力
+            message_types: infos
+                .iter()
+                .map(|i| i.message_types.clone())
+                .reduce(|a, b| a.intersection(&b).copied().collect())
+                .unwrap_or_default(),
+            mentions: infos
+                .iter()
+                .map(|i| i.mentions)
+                .reduce(|a, b| a.intersect(&b))
+                .unwrap_or_default(),
+            features: infos
+                .first()
+                .map(|first| {
+                    first
+                        .features
+                        .iter()
+                        .filter(|f| infos.iter().all(|i| i.features.contains(f)))
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default(),
+            // 由各通道自己的 send_message 按自身限制拆分
+            limits: Default::default(),
+            // 各平台分别限流，任一平台都可能成为瓶颈
+            rate_limit: infos
+                .iter()
+                .filter_map(|i| i.rate_limit)
+                .min_by(|a, b| a.per_second().total_cmp(&b.per_second())),
+            markdown_dialect: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockPlatform;
+
+    fn down() -> MockPlatform {
+        MockPlatform::failing("down", u32::MAX, || {
+            PushError::NetworkError("connection refused".to_string())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_retryable_error() {
+        let fallback = FallbackPlatform::new(vec![
+            Box::new(down()),
+            Box::new(MockPlatform::new("backup")),
+        ]);
+        let result = fallback.send_text("hello").await.unwrap();
+        assert_eq!(result.response.as_deref(), Some("hello"));
+        assert_eq!(fallback.platform_info().name, "fallback(down,backup)");
+    }
+
+    #[tokio::test]
+    async fn test_stops_on_permanent_error_and_reports_last_error() {
+        let fallback = FallbackPlatform::new(vec![
+            Box::new(MockPlatform::failing("bad", 1, || {
+                PushError::MessageError("too long".to_string())
+            })),
+            Box::new(MockPlatform::new("backup")),
+        ]);
+        assert!(matches!(
+            fallback.send_text("hello").await,
+            Err(PushError::MessageError(_))
+        ));
+
+        let fallback = FallbackPlatform::new(vec![Box::new(down()), Box::new(down())]);
+        assert!(matches!(
+            fallback.send_text("hello").await,
+            Err(PushError::NetworkError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poll_receipts_from_all_members() {
+        let primary =
+            MockPlatform::failing("primary", 1, || PushError::NetworkError("down".to_string()))
+                .with_feature(RECEIPT_FEATURE);
+        let backup = MockPlatform::new("backup").with_feature(RECEIPT_FEATURE);
+        let fallback = FallbackPlatform::new(vec![Box::new(primary), Box::new(backup)]);
+        fallback.send_text("first").await.unwrap();
+        fallback.send_text("second").await.unwrap();
+        let receipts = fallback.poll_receipts().await.unwrap();
+        let ids: Vec<&str> = receipts.iter().map(|r| r.message_id.as_str()).collect();
+        assert_eq!(ids, vec!["primary-1", "backup-0"]);
+        assert!(fallback.poll_receipts().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_on_any_member() {
+        let primary = MockPlatform::new("primary").with_feature(EDIT_FEATURE);
+        let backup = MockPlatform::new("backup")
+            .with_feature(EDIT_FEATURE)
+            .with_feature(DELETE_FEATURE);
+        let fallback = FallbackPlatform::new(vec![Box::new(primary), Box::new(backup)]);
+        let edited = Message::new(MessageType::Text("RESOLVED".to_string()));
+        let result = fallback.update_message("backup-0", edited).await.unwrap();
+        assert_eq!(result.response.as_deref(), Some("edit backup-0: RESOLVED"));
+        assert!(fallback.delete_message("backup-1").await.is_ok());
+        assert!(matches!(
+            fallback.delete_message("primary-0").await,
+            Err(PushError::PlatformError(_))
+        ));
+    }
+}
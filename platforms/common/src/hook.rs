@@ -0,0 +1,364 @@
+use crate::{
+    AttachmentSource, CardButton, CardSection, Mention, Message, MessageType, PlatformInfo,
+    PushError, PushPlatformCapabilities, PushResult, Receipt,
+};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 发送拦截器，可用于日志、内容脱敏、指标统计和修改消息
+#[async_trait]
+pub trait SendHook: Send + Sync {
+    /// 发送前调用，可修改消息；返回错误时中止发送
+    async fn before_send(&self, _platform: &str, _message: &mut Message) -> Result<(), PushError> {
+        Ok(())
+    }
+
+    /// 发送成功后调用
+    async fn after_send(&self, _platform: &str, _message: &Message, _result: &PushResult) {}
+
+    /// 发送失败后调用
+    async fn on_error(&self, _platform: &str, _message: &Message, _error: &PushError) {}
+}
+
+/// 在平台外层按顺序执行拦截器链的装饰器
+pub struct HookedPlatform {
+    inner: Box<dyn PushPlatformCapabilities>,
+    hooks: Vec<Arc<dyn SendHook>>,
+}
+
+impl HookedPlatform {
+    /// 用拦截器链包装平台
+    pub fn new(inner: Box<dyn PushPlatformCapabilities>, hooks: Vec<Arc<dyn SendHook>>) -> Self {
+        Self { inner, hooks }
+    }
+
+    /// 依次执行 `before_send`，调用 `op` 后通知所有拦截器结果
+    async fn intercept<'a, F>(
+        &'a self,
+        mut message: Message,
+        op: F,
+    ) -> Result<PushResult, PushError>
+    where
+        F: FnOnce(Message) -> BoxFuture<'a, Result<PushResult, PushError>> + Send,
+    {
+        let platform = self.inner.platform_info().name;
+        for hook in &self.hooks {
+            hook.before_send(&platform, &mut message).await?;
+        }
+        let result = op(message.clone()).await;
+        for hook in &self.hooks {
+            match &result {
+                Ok(result) => hook.after_send(&platform, &message, result).await,
+                Err(e) => hook.on_error(&platform, &message, e).await,
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for HookedPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        self.inner.init().await
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.send_message(MessageType::Text(content.to_string()).into())
+            .await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        let mut message = Message::new(MessageType::Text(content.to_string()));
+        message.mentions = mentions;
+        self.send_message(message).await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.send_message(MessageType::Markdown(content.to_string()).into())
+            .await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Rich {
+                title: title.to_string(),
+                content: content.to_string(),
+                url: url.map(str::to_string),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Image {
+                url: image_url.to_string(),
+                caption: caption.map(str::to_string),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Link {
+                title: title.to_string(),
+                description: description.to_string(),
+                url: url.to_string(),
+                image_url: image_url.map(str::to_string),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::File {
+                name: name.to_string(),
+                mime: mime.map(str::to_string),
+                source: source.clone(),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Card {
+                title: title.to_string(),
+                sections: sections.to_vec(),
+                buttons: buttons.to_vec(),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_template(
+        &self,
+        name: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Template {
+                name: name.to_string(),
+                variables: variables.clone(),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Audio {
+                source: source.clone(),
+                caption: caption.map(str::to_string),
+                duration_secs,
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Video {
+                source: source.clone(),
+                caption: caption.map(str::to_string),
+                duration_secs,
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_message(
+            MessageType::Location {
+                lat,
+                lon,
+                label: label.map(str::to_string),
+            }
+            .into(),
+        )
+        .await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        self.send_message(message.into()).await
+    }
+
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        self.intercept(message, |message| self.inner.send_message(message))
+            .await
+    }
+
+    /// 编辑后的内容同样经过拦截器链
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        self.intercept(message, |message| {
+            self.inner.update_message(message_id, message)
+        })
+        .await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        self.inner.delete_message(message_id).await
+    }
+
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        self.inner.poll_receipts().await
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        self.inner.poll_receipts_for(message_ids).await
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        self.inner.health_check().await
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        self.inner.platform_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockPlatform;
+    use std::sync::Mutex;
+
+    /// 替换消息中的敏感词
+    struct Redact;
+
+    #[async_trait]
+    impl SendHook for Redact {
+        async fn before_send(
+            &self,
+            _platform: &str,
+            message: &mut Message,
+        ) -> Result<(), PushError> {
+            if let MessageType::Text(content) = &mut message.content {
+                *content = content.replace("hunter2", "***");
+            }
+            Ok(())
+        }
+    }
+
+    /// 记录回调顺序
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    #[async_trait]
+    impl SendHook for Recorder {
+        async fn after_send(&self, platform: &str, _message: &Message, _result: &PushResult) {
+            self.0.lock().unwrap().push(format!("ok:{}", platform));
+        }
+
+        async fn on_error(&self, platform: &str, _message: &Message, _error: &PushError) {
+            self.0.lock().unwrap().push(format!("err:{}", platform));
+        }
+    }
+
+    /// 拒绝所有消息
+    struct Deny;
+
+    #[async_trait]
+    impl SendHook for Deny {
+        async fn before_send(
+            &self,
+            _platform: &str,
+            _message: &mut Message,
+        ) -> Result<(), PushError> {
+            Err(PushError::MessageError("blocked".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_mutate_and_observe() {
+        let recorder = Arc::new(Recorder::default());
+        let platform = HookedPlatform::new(
+            Box::new(MockPlatform::new("mock")),
+            vec![Arc::new(Redact), recorder.clone()],
+        );
+        let result = platform.send_text("password is hunter2").await.unwrap();
+        assert_eq!(result.response.as_deref(), Some("password is ***"));
+        assert!(platform.send_text("fail").await.is_err());
+        assert_eq!(*recorder.0.lock().unwrap(), vec!["ok:mock", "err:mock"]);
+    }
+
+    #[tokio::test]
+    async fn test_hooks_apply_to_updates() {
+        let mock = MockPlatform::new("mock").with_feature(crate::EDIT_FEATURE);
+        let platform = HookedPlatform::new(Box::new(mock), vec![Arc::new(Redact)]);
+        let message = Message::new(MessageType::Text("hunter2 rotated".to_string()));
+        let result = platform.update_message("mock-0", message).await.unwrap();
+        assert_eq!(result.response.as_deref(), Some("edit mock-0: *** rotated"));
+        assert!(platform.delete_message("mock-0").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_before_send_error_aborts() {
+        let mock = MockPlatform::new("mock");
+        let platform = HookedPlatform::new(Box::new(mock), vec![Arc::new(Deny)]);
+        assert!(matches!(
+            platform.send_text("hi").await,
+            Err(PushError::MessageError(_))
+        ));
+    }
+}
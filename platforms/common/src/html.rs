@@ -0,0 +1,241 @@
+/// 将 HTML（如邮件正文）转换为通用 Markdown，未识别的标签只保留文字
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut pre = false;
+    let mut links: Vec<Option<String>> = Vec::new();
+    // 列表嵌套栈，有序列表记录当前序号
+    let mut lists: Vec<Option<usize>> = Vec::new();
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start], pre);
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "script" | "style" if !closing => {
+                let lower = rest.to_ascii_lowercase();
+                rest = lower
+                    .find(&format!("</{}", name))
+                    .and_then(|i| rest[i..].find('>').map(|end| &rest[i + end + 1..]))
+                    .unwrap_or("");
+            }
+            "br" => newline(&mut out),
+            "p" | "div" | "section" | "article" | "header" | "footer" | "table" | "tr" => {
+                block(&mut out)
+            }
+            "td" | "th" if closing => out.push(' '),
+            "hr" => {
+                block(&mut out);
+                out.push_str("---");
+                block(&mut out);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                block(&mut out);
+                if !closing {
+                    let level = name[1..].parse().unwrap_or(1);
+                    out.push_str(&"#".repeat(level));
+                    out.push(' ');
+                }
+            }
+            "b" | "strong" => out.push_str("**"),
+            "i" | "em" => out.push('*'),
+            "s" | "del" | "strike" => out.push_str("~~"),
+            "code" if !pre => out.push('`'),
+            "pre" if !closing => {
+                block(&mut out);
+                out.push_str("```\n");
+                pre = true;
+            }
+            "pre" => {
+                newline(&mut out);
+                out.push_str("```");
+                block(&mut out);
+                pre = false;
+            }
+            "blockquote" => {
+                block(&mut out);
+                if !closing {
+                    out.push_str("> ");
+                }
+            }
+            "a" if !closing => {
+                let href = attribute(tag, "href");
+                if href.is_some() {
+                    out.push('[');
+                }
+                links.push(href);
+            }
+            "a" => {
+                if let Some(Some(href)) = links.pop() {
+                    out.push_str(&format!("]({})", href));
+                }
+            }
+            "img" => {
+                if let Some(src) = attribute(tag, "src") {
+                    let alt = attribute(tag, "alt").unwrap_or_else(|| "image".to_string());
+                    out.push_str(&format!("[{}]({})", alt, src));
+                }
+            }
+            "ul" | "ol" if !closing => {
+                newline(&mut out);
+                lists.push((name == "ol").then_some(0));
+            }
+            "ul" | "ol" => {
+                lists.pop();
+                if lists.is_empty() {
+                    block(&mut out);
+                }
+            }
+            "li" if !closing => {
+                newline(&mut out);
+                out.push_str(&"  ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        out.push_str(&format!("{}. ", n));
+                    }
+                    _ => out.push_str("- "),
+                }
+            }
+            _ => {}
+        }
+    }
+    push_text(&mut out, rest, pre);
+    out.trim().to_string()
+}
+
+/// 追加文字，`<pre>` 之外的连续空白合并为一个空格
+fn push_text(out: &mut String, text: &str, pre: bool) {
+    let text = decode_entities(text);
+    if pre {
+        out.push_str(&text);
+        return;
+    }
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let separated = out.is_empty() || out.ends_with([' ', '\n']);
+    if text.starts_with(char::is_whitespace) && !separated {
+        out.push(' ');
+    }
+    if words.is_empty() {
+        return;
+    }
+    out.push_str(&words.join(" "));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// 换行，去掉行尾空格
+fn newline(out: &mut String) {
+    let trimmed = out.trim_end_matches(' ').len();
+    out.truncate(trimmed);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// 段落分隔，保证前面恰好有一个空行
+fn block(out: &mut String) {
+    newline(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// 读取标签属性值，支持单双引号
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let start = lower.find(&format!("{}=", name))? + name.len() + 1;
+    let value = &tag[start..];
+    let (quote, value) = match value.chars().next()? {
+        q @ ('"' | '\'') => (q, &value[1..]),
+        _ => (' ', value),
+    };
+    let end = value.find(quote).unwrap_or(value.len());
+    Some(decode_entities(&value[..end]))
+}
+
+/// 解码常用命名实体和数字实体
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = entity.strip_prefix('#')?;
+                let value = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(value)
+            }
+        });
+        match (entity, c) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_body_to_markdown() {
+        let html = r#"<html><head><style>p { color: red; }</style></head><body>
+            <h2>Build   failed</h2>
+            <p>Job <b>api</b> failed on <a href="http://ci/1?a=1&amp;b=2">#42</a>.<br>Owner: Tom &amp; Jerry</p>
+            <ul><li>step <code>test</code></li><li>step <i>lint</i></li></ul>
+            <pre>error: x
+  at y</pre>
+            <!-- footer --></body></html>"#;
+        assert_eq!(
+            html_to_markdown(html),
+            "## Build failed\n\nJob **api** failed on [#42](http://ci/1?a=1&b=2).\nOwner: Tom & Jerry\n\n- step `test`\n- step *lint*\n\n```\nerror: x\n  at y\n```"
+        );
+    }
+
+    #[test]
+    fn test_entities_and_ordered_lists() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &#20320;&#x597D; &bogus; &"),
+            "<a> 你好 &bogus; &"
+        );
+        assert_eq!(
+            html_to_markdown("<ol><li>one</li><li>two</li></ol><img src='x.png' alt='chart'>"),
+            "1. one\n2. two\n\n[chart](x.png)"
+        );
+    }
+}
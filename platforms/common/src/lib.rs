@@ -1,6 +1,105 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+// 让派生宏生成的 `::common::` 路径在本 crate 内也能解析
+extern crate self as common;
+
+mod attachment;
+mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod capability;
+mod card;
+mod chart;
+mod config;
+mod context;
+mod decoration;
+mod dialect;
+mod directory;
+pub mod dry_run;
+mod emoji;
+mod fallback;
+mod hook;
+mod html;
+mod locale;
+mod mention;
+mod multi;
+mod plugin;
+mod policy;
+mod qr;
+mod rasterize;
+mod receipt;
+mod redact;
+mod resilient;
+pub mod sign;
+mod sms;
+mod split;
+mod table;
+mod template;
+/// 单元测试共用的模拟平台
+#[cfg(test)]
+mod testing;
+mod thread;
+mod token;
+mod transform;
+pub mod vcr;
+
+pub use attachment::AttachmentSource;
+pub use batch::{send_concurrent, send_to_many};
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingPlatform;
+pub use capability::{MentionSupport, MessageKind, RateLimit};
+pub use card::{CardAction, CardButton, CardSection, card_to_markdown};
+pub use chart::{MAX_CHART_POINTS, MetricSeries, chart_image};
+pub use common_derive::PushConfig;
+pub use config::{
+    Budget, ChannelConfig, ConfigFormat, MultiPushConfig, QuietHours, Route, interpolate_env,
+    load_config, parse_config, serialize_config,
+};
+pub use context::PlatformContext;
+pub use decoration::Decorations;
+pub use dialect::{MarkdownDialect, convert_markdown};
+pub use directory::{Directory, DirectoryEntry};
+pub use emoji::EmojiShortcodes;
+pub use fallback::FallbackPlatform;
+pub use hook::{HookedPlatform, SendHook};
+pub use html::html_to_markdown;
+pub use locale::{BuiltinText, match_locale};
+pub use mention::Mention;
+pub use multi::{MultiPush, MultiPushReport, Strategy};
+pub use plugin::{
+    COMMON_VERSION, PLUGIN_ABI_VERSION, PLUGIN_SYMBOL, PluginDeclaration, RUSTC_VERSION,
+};
+pub use policy::{ContentPolicy, PolicyAction};
+pub use qr::{qr_message, qr_png};
+pub use rasterize::MarkdownImageConfig;
+pub use receipt::{DeliveryStatus, Receipt};
+pub use redact::{RedactionRule, Redactor};
+pub use resilient::{ResilientPlatform, RetryClass, RetryPolicy};
+pub use sms::{SmsConfig, SmsEncoding, UrlShortener, sms_segments};
+pub use split::{
+    LengthUnit, MessageLimits, MessagePart, message_parts, send_parts, split_content, split_message,
+};
+pub use table::{TableStyle, degrade_tables, has_table};
+pub use template::{
+    LocalizedTemplate, TemplateDefinition, TemplateRenderer, format_timestamp, parse_timezone,
+    render_template, render_template_in,
+};
+pub use thread::ThreadMap;
+#[cfg(feature = "redis")]
+pub use token::RedisTokenCache;
+pub use token::{
+    AccessToken, CachedTokenProvider, MemoryTokenCache, TokenCache, TokenFetcher, TokenProvider,
+};
+pub use transform::{
+    CapabilityDegrader, CapabilityMode, MessageTransformer, TransformPipeline, degrade, map_url,
+    negotiate, strip_markdown,
+};
+pub use vcr::endpoint;
 
 /// 推送平台错误类型
 #[derive(Debug, thiserror::Error)]
@@ -19,6 +118,93 @@ pub enum PushError {
 
     #[error("Platform error: {0}")]
     PlatformError(String),
+
+    #[error("Rate limited: {message}")]
+    RateLimited {
+        message: String,
+        /// 平台要求的等待时间
+        retry_after: Option<Duration>,
+    },
+
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error(
+        "Unsupported: {platform} does not support {message_type} messages{}",
+        .suggestion.map(|kind| format!(", send it as {} instead", kind)).unwrap_or_default()
+    )]
+    Unsupported {
+        platform: String,
+        message_type: MessageKind,
+        /// 按平台能力降级后的消息类型，无法降级时为 `None`
+        suggestion: Option<MessageKind>,
+    },
+
+    /// 附带诊断信息的错误，`result` 记录尝试次数、耗时、HTTP 状态码和平台错误码
+    #[error("{error}")]
+    Failure {
+        error: Box<PushError>,
+        result: Box<PushResult>,
+    },
+}
+
+impl PushError {
+    /// 平台不支持该消息，建议类型取按平台能力降级后的结果
+    pub fn unsupported(info: &PlatformInfo, message: &MessageType) -> Self {
+        let message_type = message.kind();
+        let suggestion = Some(degrade(message.clone(), info).kind())
+            .filter(|kind| *kind != message_type && info.supports(*kind));
+        Self::Unsupported {
+            platform: info.name.clone(),
+            message_type,
+            suggestion,
+        }
+    }
+
+    /// 去掉诊断信息后的错误，按错误类别匹配时使用
+    pub fn inner(&self) -> &PushError {
+        match self {
+            Self::Failure { error, .. } => error.inner(),
+            _ => self,
+        }
+    }
+
+    /// 失败时的诊断信息
+    pub fn diagnostics(&self) -> Option<&PushResult> {
+        match self {
+            Self::Failure { result, .. } => Some(result),
+            _ => None,
+        }
+    }
+
+    /// 补充诊断信息，如尝试次数、耗时、HTTP 状态码和平台错误码
+    pub fn with_diagnostics(self, update: impl FnOnce(&mut PushResult)) -> Self {
+        let (error, mut result) = match self {
+            Self::Failure { error, result } => (error, result),
+            error => (Box::new(error), Box::default()),
+        };
+        update(&mut result);
+        Self::Failure { error, result }
+    }
+
+    /// 是否为可重试的临时性错误
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.inner(),
+            Self::NetworkError(_) | Self::RateLimited { .. } | Self::Timeout(_)
+        )
+    }
+
+    /// 平台要求的重试等待时间
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.inner() {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
 }
 
 /// 消息类型枚举
@@ -31,6 +217,8 @@ pub enum MessageType {
     Text(String),
     /// Markdown格式消息
     Markdown(String),
+    /// HTML 内容，不支持 HTML 的平台发送前转换为 Markdown
+    Html(String),
     /// 富文本消息
     Rich {
         title: String,
@@ -49,18 +237,279 @@ pub enum MessageType {
         url: String,
         image_url: Option<String>,
     },
+    /// 文件消息
+    File {
+        name: String,
+        mime: Option<String>,
+        source: AttachmentSource,
+    },
+    /// 交互卡片消息
+    Card {
+        title: String,
+        sections: Vec<CardSection>,
+        buttons: Vec<CardButton>,
+    },
+    /// 模板消息，由平台原生模板或注册表中的模板渲染
+    Template {
+        name: String,
+        variables: HashMap<String, String>,
+    },
+    /// 音频消息
+    Audio {
+        source: AttachmentSource,
+        caption: Option<String>,
+        duration_secs: Option<u32>,
+    },
+    /// 视频消息
+    Video {
+        source: AttachmentSource,
+        caption: Option<String>,
+        duration_secs: Option<u32>,
+    },
+    /// 位置消息
+    Location {
+        lat: f64,
+        lon: f64,
+        label: Option<String>,
+    },
 }
 
 /// 消息优先级
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     Low,
+    #[default]
     Normal,
     High,
     Urgent,
 }
 
+/// 请求 ID 在消息元数据中的键名
+pub const REQUEST_ID_KEY: &str = "request_id";
+
+/// 时区在消息元数据中的键名，模板中的 `tz` 过滤器未指定时区时使用
+pub const TIMEZONE_KEY: &str = "timezone";
+
+/// 语言在消息元数据中的键名，如 `zh-CN`、`en`、`ja`，用于选择模板的语言版本
+pub const LOCALE_KEY: &str = "locale";
+
+/// 已有会话的平台原生 ID 在消息元数据中的键名，支持会话的平台据此回复到该会话，
+/// 如 Slack 的 `thread_ts`、Telegram 的 `reply_to_message_id`
+pub const THREAD_ID_KEY: &str = "thread_id";
+
+/// 支持会话回复的平台在 [`PlatformInfo::features`] 中声明的特性
+pub const THREAD_FEATURE: &str = "threads";
+
+/// 支持编辑已发送消息的平台声明的特性
+pub const EDIT_FEATURE: &str = "edit";
+
+/// 支持撤回已发送消息的平台声明的特性
+pub const DELETE_FEATURE: &str = "delete";
+
+/// 可以查询投递回执的平台声明的特性
+pub const RECEIPT_FEATURE: &str = "receipts";
+
+/// `health_check` 会向平台确认凭据有效的平台声明的特性；未声明的平台无法在不发消息的情况下校验凭据
+pub const HEALTH_CHECK_FEATURE: &str = "health_check";
+
+/// 支持演练模式的平台声明的特性，见 [`dry_run`]
+pub const DRY_RUN_FEATURE: &str = "dry_run";
+
+/// 能直接渲染 `:rocket:` 形式 shortcode 的平台声明的特性
+pub const EMOJI_SHORTCODE_FEATURE: &str = "emoji_shortcodes";
+
+/// 能渲染 Markdown 表格的平台声明的特性，未声明时表格转为代码块或键值列表
+pub const TABLE_FEATURE: &str = "tables";
+
+/// 只有文本消息能@人的平台声明的特性，正文不是文本时在最后以文本消息补发@提醒
+pub const MENTION_FOLLOW_UP_FEATURE: &str = "mention_follow_up";
+
+/// 消息信封，携带消息内容及优先级、@提及等元信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    /// 消息内容
+    pub content: MessageType,
+    /// 优先级
+    #[serde(default)]
+    pub priority: Priority,
+    /// @提及列表
+    #[serde(default)]
+    pub mentions: Vec<Mention>,
+    /// 附加元数据
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// 标签，如 `service`、`env`、`team`，用于路由匹配和按维度统计
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// 需要确认，服务端在确认前按间隔重复发送到原通道
+    #[serde(default)]
+    pub require_ack: bool,
+    /// 会话键，相同键的后续消息发到第一条消息所在的会话，如告警的分组键
+    ///
+    /// 原生支持会话键的平台（如 Google Chat 的 `threadKey`）直接使用，其余支持会话的平台
+    /// 由服务端记录会话 ID 并通过 [`THREAD_ID_KEY`] 元数据传入
+    #[serde(default)]
+    pub thread_key: Option<String>,
+    /// 指标趋势，发送正文后附带一张趋势图；平台不支持图片或未启用 `charts` 特性时改为文字摘要
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chart: Option<MetricSeries>,
+    /// 本条消息单次请求的超时（毫秒），覆盖平台配置，如心跳检查只等 2 秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// 本条消息的重试次数，覆盖平台配置，如心跳检查不重试
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u32>,
+}
+
+impl Message {
+    /// 创建默认优先级、无@提及的消息
+    pub fn new(content: MessageType) -> Self {
+        Self {
+            content,
+            priority: Priority::default(),
+            mentions: Vec::new(),
+            metadata: HashMap::new(),
+            labels: HashMap::new(),
+            require_ack: false,
+            thread_key: None,
+            chart: None,
+            timeout_ms: None,
+            retry_count: None,
+        }
+    }
+
+    /// 设置标签
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// 设置会话键
+    pub fn with_thread_key(mut self, thread_key: impl Into<String>) -> Self {
+        self.thread_key = Some(thread_key.into());
+        self
+    }
+
+    /// 覆盖本条消息单次请求的超时
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = Some(timeout.as_millis() as u64);
+        self
+    }
+
+    /// 覆盖本条消息的重试次数
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = Some(retry_count);
+        self
+    }
+
+    /// 本条消息单次请求的超时，未覆盖时为 `None`
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    /// 附带指标趋势图
+    pub fn with_chart(mut self, chart: MetricSeries) -> Self {
+        self.chart = Some(chart);
+        self
+    }
+
+    /// 设置元数据
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// 请求 ID，未设置时为 `None`
+    pub fn request_id(&self) -> Option<&str> {
+        self.metadata.get(REQUEST_ID_KEY).map(String::as_str)
+    }
+
+    /// 设置消息语言
+    pub fn with_locale(self, locale: impl Into<String>) -> Self {
+        self.with_metadata(LOCALE_KEY, locale)
+    }
+
+    /// 消息语言，未设置时为 `None`
+    pub fn locale(&self) -> Option<&str> {
+        self.metadata.get(LOCALE_KEY).map(String::as_str)
+    }
+}
+
+impl MessageType {
+    /// 消息类型
+    pub fn kind(&self) -> MessageKind {
+        MessageKind::from(self)
+    }
+
+    /// 消息中所有会展示给接收方的文字，包括链接地址，不包括附件内容和回调 ID
+    pub fn texts_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            MessageType::Text(text) | MessageType::Markdown(text) | MessageType::Html(text) => {
+                vec![text]
+            }
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => [title, content].into_iter().chain(url).collect(),
+            MessageType::Image { url, caption } => [url].into_iter().chain(caption).collect(),
+            MessageType::Link {
+                title,
+                description,
+                url,
+                image_url,
+            } => [title, description, url]
+                .into_iter()
+                .chain(image_url)
+                .collect(),
+            MessageType::File { name, source, .. } => match source {
+                AttachmentSource::Url(url) => vec![name, url],
+                _ => vec![name],
+            },
+            MessageType::Card {
+                title,
+                sections,
+                buttons,
+            } => {
+                let mut texts = vec![title];
+                for section in sections {
+                    texts.extend(&mut section.title);
+                    texts.push(&mut section.content);
+                    texts.extend(&mut section.image_url);
+                }
+                for button in buttons {
+                    texts.push(&mut button.label);
+                    if let CardAction::Url(url) = &mut button.action {
+                        texts.push(url);
+                    }
+                }
+                texts
+            }
+            MessageType::Template { variables, .. } => variables.values_mut().collect(),
+            MessageType::Audio {
+                source, caption, ..
+            }
+            | MessageType::Video {
+                source, caption, ..
+            } => {
+                let mut texts: Vec<&mut String> = caption.iter_mut().collect();
+                if let AttachmentSource::Url(url) = source {
+                    texts.push(url);
+                }
+                texts
+            }
+            MessageType::Location { label, .. } => label.iter_mut().collect(),
+        }
+    }
+}
+
+impl From<MessageType> for Message {
+    fn from(content: MessageType) -> Self {
+        Self::new(content)
+    }
+}
+
 /// 推送结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PushResult {
@@ -72,6 +521,27 @@ pub struct PushResult {
     pub response: Option<String>,
     /// 时间戳
     pub timestamp: DateTime<Utc>,
+    /// 发送尝试次数
+    #[serde(default)]
+    pub attempts: u32,
+    /// 耗时（毫秒），包含重试
+    #[serde(default)]
+    pub elapsed_ms: Option<u64>,
+    /// 平台返回的 HTTP 状态码
+    #[serde(default)]
+    pub http_status: Option<u16>,
+    /// 平台原生错误码
+    #[serde(default)]
+    pub error_code: Option<String>,
+    /// 实际投递的通道名称
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// 请求 ID，用于跨重试和降级追踪同一条通知
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// 演练模式下未实际发送，`response` 为渲染后的请求载荷
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 impl Default for PushResult {
@@ -81,6 +551,13 @@ impl Default for PushResult {
             success: false,
             response: None,
             timestamp: Utc::now(),
+            attempts: 0,
+            elapsed_ms: None,
+            http_status: None,
+            error_code: None,
+            channel: None,
+            request_id: None,
+            dry_run: false,
         }
     }
 }
@@ -101,6 +578,26 @@ pub trait PushInitConfig: Send + Sync {
 
     /// 获取重试次数
     fn retry_count(&self) -> u32;
+
+    /// 出站代理地址，支持 `http://`、`https://` 和 `socks5://`，默认不使用代理
+    fn proxy(&self) -> Option<&str> {
+        None
+    }
+
+    /// 重试策略，默认按 [`PushInitConfig::retry_count`] 重试网络错误、超时和限流
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::default()
+    }
+}
+
+/// 配置结构的 JSON Schema，通常由 `#[derive(PushConfig)]` 生成
+pub trait ConfigSchema {
+    fn config_schema() -> Value;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_json;
 }
 
 /// 推送平台能力trait（用于dyn兼容）
@@ -111,7 +608,11 @@ pub trait PushPlatformCapabilities: Send + Sync {
 
     /// 发送纯文本消息
     async fn send_text(&self, content: &str) -> Result<PushResult, PushError>;
-    async fn send_text_with_mention(&self, content: &str,mention_list:Vec<String>) -> Result<PushResult, PushError>;
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError>;
 
     /// 发送Markdown消息
     async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError>;
@@ -140,9 +641,161 @@ pub trait PushPlatformCapabilities: Send + Sync {
         image_url: Option<&str>,
     ) -> Result<PushResult, PushError>;
 
+    /// 发送文件消息，需要上传素材的平台自行处理上传流程；默认不支持
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        let message = MessageType::File {
+            name: name.to_string(),
+            mime: mime.map(str::to_string),
+            source: source.clone(),
+        };
+        Err(PushError::unsupported(&self.platform_info(), &message))
+    }
+
+    /// 发送卡片消息，默认渲染为 Markdown 后按平台能力发送
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        let markdown = MessageType::Markdown(card_to_markdown(title, sections, buttons));
+        self.send(degrade(markdown, &self.platform_info())).await
+    }
+
+    /// 发送平台原生模板消息，默认不支持
+    async fn send_template(
+        &self,
+        name: &str,
+        _variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        Err(PushError::MessageError(format!(
+            "{} has no native template '{}'",
+            self.platform_info().name,
+            name
+        )))
+    }
+
+    /// 发送音频消息，默认不支持
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        let message = MessageType::Audio {
+            source: source.clone(),
+            caption: caption.map(str::to_string),
+            duration_secs,
+        };
+        Err(PushError::unsupported(&self.platform_info(), &message))
+    }
+
+    /// 发送视频消息，默认不支持
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        let message = MessageType::Video {
+            source: source.clone(),
+            caption: caption.map(str::to_string),
+            duration_secs,
+        };
+        Err(PushError::unsupported(&self.platform_info(), &message))
+    }
+
+    /// 发送位置消息，默认不支持
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let message = MessageType::Location {
+            lat,
+            lon,
+            label: label.map(str::to_string),
+        };
+        Err(PushError::unsupported(&self.platform_info(), &message))
+    }
+
     /// 通用发送方法
     async fn send(&self, message: MessageType) -> Result<PushResult, PushError>;
 
+    /// 发送带元信息的消息，默认按平台能力降级和拆分后，逐部分交给 `send_part`，
+    /// 见 [`send_parts`]
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        send_parts(self, &message).await
+    }
+
+    /// 发送 `send_message` 拆出的一部分，`message` 为原消息，可以从中读取会话、优先级等元信息；
+    /// 默认将带@提醒的文本交给 `send_text_with_mention`，Markdown 转换为平台方言，其余交给 `send`
+    async fn send_part(
+        &self,
+        _message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        match part.content {
+            MessageType::Text(content) if !part.mentions.is_empty() => {
+                self.send_text_with_mention(&content, part.mentions).await
+            }
+            MessageType::Markdown(content) => {
+                let dialect = self.platform_info().markdown_dialect;
+                self.send(MessageType::Markdown(convert_markdown(&content, dialect)))
+                    .await
+            }
+            content => self.send(content).await,
+        }
+    }
+
+    /// 编辑已发送的消息，`message_id` 为发送结果中的 `message_id`；
+    /// 支持的平台需在特性中声明 [`EDIT_FEATURE`]，默认不支持
+    async fn update_message(
+        &self,
+        _message_id: &str,
+        _message: Message,
+    ) -> Result<PushResult, PushError> {
+        Err(PushError::PlatformError(format!(
+            "{} does not support editing messages",
+            self.platform_info().name
+        )))
+    }
+
+    /// 撤回已发送的消息；支持的平台需在特性中声明 [`DELETE_FEATURE`]，默认不支持
+    async fn delete_message(&self, _message_id: &str) -> Result<PushResult, PushError> {
+        Err(PushError::PlatformError(format!(
+            "{} does not support deleting messages",
+            self.platform_info().name
+        )))
+    }
+
+    /// 取出自上次查询以来的投递回执，如 APNs 反馈、Telegram 已读状态；
+    /// 支持的平台需在特性中声明 [`RECEIPT_FEATURE`]，默认没有回执
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        Ok(Vec::new())
+    }
+
+    /// 查询指定消息的回执，等待回执的消息由调用方记录，平台实例重建后仍能继续查询；
+    /// 需要逐条查询消息状态的平台（如 Discord 表情回应）覆盖，默认取出 [`poll_receipts`](Self::poll_receipts) 的回执
+    async fn poll_receipts_for(&self, _message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        self.poll_receipts().await
+    }
+
+    /// 批量发送消息，默认逐条发送；支持合并发送的平台可以覆盖
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        let mut results = Vec::with_capacity(messages.len());
+        for message in messages {
+            results.push(self.send_message(message).await);
+        }
+        results
+    }
+
     /// 检查平台健康状态
     async fn health_check(&self) -> Result<bool, PushError>;
 
@@ -150,6 +803,314 @@ pub trait PushPlatformCapabilities: Send + Sync {
     fn platform_info(&self) -> PlatformInfo;
 }
 
+#[async_trait]
+impl<T: PushPlatformCapabilities + ?Sized> PushPlatformCapabilities for Box<T> {
+    async fn init(&mut self) -> Result<(), PushError> {
+        (**self).init().await
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        (**self).send_text(content).await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_text_with_mention(content, mentions).await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        (**self).send_markdown(content).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_rich(title, content, url).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_image(image_url, caption).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_link(title, description, url, image_url).await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_file(name, mime, source).await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        (**self).send_card(title, sections, buttons).await
+    }
+
+    async fn send_template(
+        &self,
+        name: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_template(name, variables).await
+    }
+
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_audio(source, caption, duration_secs).await
+    }
+
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_video(source, caption, duration_secs).await
+    }
+
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_location(lat, lon, label).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        (**self).send(message).await
+    }
+
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        (**self).send_message(message).await
+    }
+
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_part(message, part).await
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        (**self).update_message(message_id, message).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        (**self).delete_message(message_id).await
+    }
+
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        (**self).poll_receipts().await
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        (**self).poll_receipts_for(message_ids).await
+    }
+
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        (**self).send_batch(messages).await
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        (**self).health_check().await
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        (**self).platform_info()
+    }
+}
+
+/// 共享的平台实例，只有唯一持有者才能调用 `init`
+#[async_trait]
+impl<T: PushPlatformCapabilities + ?Sized> PushPlatformCapabilities for Arc<T> {
+    async fn init(&mut self) -> Result<(), PushError> {
+        match Arc::get_mut(self) {
+            Some(platform) => platform.init().await,
+            None => Err(PushError::ConfigError(
+                "Cannot init a shared platform instance".to_string(),
+            )),
+        }
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        (**self).send_text(content).await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_text_with_mention(content, mentions).await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        (**self).send_markdown(content).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_rich(title, content, url).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_image(image_url, caption).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_link(title, description, url, image_url).await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_file(name, mime, source).await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        (**self).send_card(title, sections, buttons).await
+    }
+
+    async fn send_template(
+        &self,
+        name: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_template(name, variables).await
+    }
+
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_audio(source, caption, duration_secs).await
+    }
+
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_video(source, caption, duration_secs).await
+    }
+
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_location(lat, lon, label).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        (**self).send(message).await
+    }
+
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        (**self).send_message(message).await
+    }
+
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        (**self).send_part(message, part).await
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        (**self).update_message(message_id, message).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        (**self).delete_message(message_id).await
+    }
+
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        (**self).poll_receipts().await
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        (**self).poll_receipts_for(message_ids).await
+    }
+
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        (**self).send_batch(messages).await
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        (**self).health_check().await
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        (**self).platform_info()
+    }
+}
+
 /// 推送平台trait（用于具体实现）
 pub trait PushPlatform<C: PushInitConfig>: PushPlatformCapabilities {
     /// 创建一个新的推送平台实例
@@ -165,109 +1126,243 @@ pub struct PlatformInfo {
     pub name: String,
     /// 版本
     pub version: String,
-    /// 支持的特性
+    /// 原生支持的消息类型，其余类型发送前按能力降级
+    #[serde(default)]
+    pub message_types: BTreeSet<MessageKind>,
+    /// 能原生送达的@提醒方式
+    #[serde(default)]
+    pub mentions: MentionSupport,
+    /// 消息类型之外的扩展特性，如会话回复、编辑撤回、投递回执
+    #[serde(default)]
     pub features: Vec<String>,
-    /// 是否支持Markdown
-    pub supports_markdown: bool,
-    /// 是否支持富文本
-    pub supports_rich_text: bool,
-    /// 是否支持图片
-    pub supports_images: bool,
+    /// 单条消息长度与附件大小限制
+    #[serde(default)]
+    pub limits: MessageLimits,
+    /// 平台公布的频率限制，未知时为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Markdown 方言，发送前将通用 Markdown 转换为该方言
+    #[serde(default)]
+    pub markdown_dialect: MarkdownDialect,
+}
+
+impl PlatformInfo {
+    /// 是否原生支持该消息类型
+    pub fn supports(&self, kind: MessageKind) -> bool {
+        self.message_types.contains(&kind)
+    }
+
+    /// 是否声明了扩展特性
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
 }
 
 /// 消息构建器
 pub struct MessageBuilder {
-    message_type: MessageType,
-    priority: Priority,
-    mentions: Vec<String>,
+    message: Message,
 }
 
 impl MessageBuilder {
     /// 创建文本消息构建器
     pub fn text(content: impl Into<String>) -> Self {
-        Self {
-            message_type: MessageType::Text(content.into()),
-            priority: Priority::Normal,
-            mentions: Vec::new(),
-        }
+        Self::new(MessageType::Text(content.into()))
     }
 
     /// 创建Markdown消息构建器
     pub fn markdown(content: impl Into<String>) -> Self {
-        Self {
-            message_type: MessageType::Markdown(content.into()),
-            priority: Priority::Normal,
-            mentions: Vec::new(),
-        }
+        Self::new(MessageType::Markdown(content.into()))
+    }
+
+    /// 渲染 Jinja 模板创建 Markdown 消息构建器，上下文可以是任意可序列化的值
+    pub fn from_template(template: &str, context: &impl Serialize) -> Result<Self, PushError> {
+        render_template(template, context).map(Self::markdown)
+    }
+
+    /// 创建 HTML 消息构建器，例如复用邮件正文
+    pub fn html(content: impl Into<String>) -> Self {
+        Self::new(MessageType::Html(content.into()))
     }
 
     /// 创建富文本消息构建器
     pub fn rich(title: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::new(MessageType::Rich {
+            title: title.into(),
+            content: content.into(),
+            url: None,
+        })
+    }
+
+    fn new(content: MessageType) -> Self {
         Self {
-            message_type: MessageType::Rich {
-                title: title.into(),
-                content: content.into(),
-                url: None,
-            },
-            priority: Priority::Normal,
-            mentions: Vec::new(),
+            message: Message::new(content),
         }
     }
 
     /// 设置优先级
     pub fn priority(mut self, priority: Priority) -> Self {
-        self.priority = priority;
+        self.message.priority = priority;
         self
     }
 
     /// 添加@提及
-    pub fn mention(mut self, user: impl Into<String>) -> Self {
-        self.mentions.push(user.into());
+    pub fn mention(mut self, user: impl Into<Mention>) -> Self {
+        self.message.mentions.push(user.into());
         self
     }
 
     /// 添加多个@提及
-    pub fn mentions(mut self, users: Vec<String>) -> Self {
-        self.mentions.extend(users);
+    pub fn mentions(mut self, users: impl IntoIterator<Item = impl Into<Mention>>) -> Self {
+        self.message
+            .mentions
+            .extend(users.into_iter().map(Into::into));
+        self
+    }
+
+    /// 添加元数据
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.message.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// 添加标签
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.message.labels.insert(key.into(), value.into());
+        self
+    }
+
+    /// 设置会话键
+    pub fn thread_key(mut self, thread_key: impl Into<String>) -> Self {
+        self.message.thread_key = Some(thread_key.into());
+        self
+    }
+
+    /// 附带指标趋势图
+    pub fn chart(mut self, chart: MetricSeries) -> Self {
+        self.message.chart = Some(chart);
         self
     }
 
     /// 构建消息
-    pub fn build(self) -> MessageType {
-        self.message_type
+    pub fn build(self) -> Message {
+        self.message
     }
 }
 
 /// 平台工厂trait
 pub trait PlatformFactory: Send + Sync {
-    /// 根据JSON Value创建平台实例
-    fn create(&self, config: Value) -> Result<Box<dyn PushPlatformCapabilities>, PushError>;
+    /// 根据JSON Value创建平台实例，HTTP 客户端等共享资源从上下文获取
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError>;
 
     /// 获取平台名称
     fn name(&self) -> &'static str;
+
+    /// 平台配置的 JSON Schema，默认只约束为对象
+    fn config_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    /// 不依赖配置的平台信息，用于平台发现
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        None
+    }
 }
 
 /// 平台注册表
+///
+/// 工厂和拦截器都以 `Arc` 共享，克隆开销很小，可以直接在多个服务线程间复制
+#[derive(Default, Clone)]
 pub struct PlatformRegistry {
-    factories: std::collections::HashMap<String, Box<dyn PlatformFactory>>,
+    factories: HashMap<String, Arc<dyn PlatformFactory>>,
+    hooks: Vec<Arc<dyn SendHook>>,
+    templates: Option<Arc<TemplateRenderer>>,
+    context: PlatformContext,
 }
 
 impl PlatformRegistry {
     /// 创建新的注册表
     pub fn new() -> Self {
-        Self {
-            factories: std::collections::HashMap::new(),
-        }
+        Self::default()
     }
 
-    /// 注册平台工厂
+    /// 注册平台工厂，同名工厂会被覆盖
     pub fn register(&mut self, factory: Box<dyn PlatformFactory>) {
-        self.factories.insert(factory.name().to_string(), factory);
+        self.replace(factory);
+    }
+
+    /// 注册平台工厂，返回被替换的同名工厂
+    pub fn replace(
+        &mut self,
+        factory: Box<dyn PlatformFactory>,
+    ) -> Option<Arc<dyn PlatformFactory>> {
+        self.factories
+            .insert(factory.name().to_string(), Arc::from(factory))
+    }
+
+    /// 移除平台工厂
+    pub fn unregister(&mut self, name: &str) -> Option<Arc<dyn PlatformFactory>> {
+        self.factories.remove(name)
+    }
+
+    /// 是否注册了指定平台
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// 遍历所有平台名称和工厂，顺序不固定
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn PlatformFactory)> {
+        self.factories
+            .iter()
+            .map(|(name, factory)| (name.as_str(), factory.as_ref()))
+    }
+
+    /// 添加发送拦截器，对之后通过 `create` 创建的所有平台生效
+    pub fn add_hook(&mut self, hook: Arc<dyn SendHook>) {
+        self.hooks.push(hook);
+    }
+
+    /// 设置模板，不支持原生模板的平台发送模板消息时使用这些模板渲染
+    pub fn set_templates(&mut self, templates: HashMap<String, TemplateDefinition>) {
+        self.templates = Some(Arc::new(TemplateRenderer::new(templates)));
+    }
+
+    /// 设置创建平台时使用的共享上下文
+    pub fn set_context(&mut self, context: PlatformContext) {
+        self.context = context;
     }
 
     /// 获取平台工厂
-    pub fn get_factory(&self, name: &str) -> Option<&Box<dyn PlatformFactory>> {
-        self.factories.get(name)
+    pub fn get_factory(&self, name: &str) -> Option<&dyn PlatformFactory> {
+        self.factories.get(name).map(|f| f.as_ref())
+    }
+
+    /// 创建平台实例，并按注册顺序套上拦截器链
+    pub fn create(
+        &self,
+        name: &str,
+        config: Value,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        let factory = self
+            .get_factory(name)
+            .ok_or_else(|| PushError::ConfigError(format!("Platform '{}' not found", name)))?;
+        let platform = factory.create(config, &self.context)?;
+
+        let mut hooks = Vec::with_capacity(self.hooks.len() + 1);
+        if let Some(templates) = &self.templates {
+            let native = platform.platform_info().supports(MessageKind::Template);
+            if !native {
+                hooks.push(templates.clone() as Arc<dyn SendHook>);
+            }
+        }
+        hooks.extend(self.hooks.iter().cloned());
+        if hooks.is_empty() {
+            return Ok(platform);
+        }
+        Ok(Box::new(HookedPlatform::new(platform, hooks)))
     }
 
     /// 获取所有支持的平名名称
@@ -283,53 +1378,278 @@ mod tests {
     // 测试用的mock配置
     struct MockConfig;
 
-    // impl PushInitConfig for MockConfig {
-    //     fn platform_name(&self) -> &'static str {
-    //         "mock"
-    //     }
-    //
-    //     fn webhook_url(&self) -> &str {
-    //         "https://mock.example.com/webhook"
-    //     }
-    //
-    //     fn secret(&self) -> Option<&str> {
-    //         Some("mock-secret")
-    //     }
-    //
-    //     fn timeout(&self) -> u64 {
-    //         30
-    //     }
-    //
-    //     fn retry_count(&self) -> u32 {
-    //         3
-    //     }
-    // }
+    impl PushInitConfig for MockConfig {
+        fn platform_name(&self) -> &str {
+            "mock"
+        }
+
+        fn webhook_url(&self) -> String {
+            "https://mock.example.com/webhook".to_string()
+        }
+
+        fn secret(&self) -> Option<&str> {
+            Some("mock-secret")
+        }
+
+        fn timeout(&self) -> u64 {
+            30
+        }
+
+        fn retry_count(&self) -> u32 {
+            3
+        }
+    }
+
+    #[test]
+    fn test_mock_config() {
+        let config = MockConfig;
+        assert_eq!(config.platform_name(), "mock");
+        assert_eq!(config.secret(), Some("mock-secret"));
+        assert_eq!(config.timeout(), 30);
+    }
 
     #[test]
     fn test_message_builder() {
         let text_msg = MessageBuilder::text("Hello World").build();
-        match text_msg {
+        match text_msg.content {
             MessageType::Text(content) => assert_eq!(content, "Hello World"),
             _ => panic!("Expected text message"),
         }
 
         let md_msg = MessageBuilder::markdown("# Hello").build();
-        match md_msg {
+        match md_msg.content {
             MessageType::Markdown(content) => assert_eq!(content, "# Hello"),
             _ => panic!("Expected markdown message"),
         }
     }
 
+    /// 通过派生宏实现的配置
+    #[derive(Deserialize, PushConfig)]
+    #[push(
+        platform = "derived",
+        webhook_url = |c: &DerivedConfig| format!("https://hook/{}", c.key),
+        default_timeout = 10
+    )]
+    struct DerivedConfig {
+        /// 机器人 key
+        key: String,
+        #[push(secret)]
+        secret: Option<String>,
+        #[push(retry_count)]
+        #[serde(default)]
+        retries: u32,
+        #[push(timeout)]
+        timeout: Option<u64>,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
     #[test]
+    fn test_derive_push_config() {
+        let config: DerivedConfig =
+            serde_json::from_value(serde_json::json!({"key": "k1", "retries": 2})).unwrap();
+        assert_eq!(config.platform_name(), "derived");
+        assert_eq!(config.webhook_url(), "https://hook/k1");
+        assert_eq!(config.secret(), None);
+        assert_eq!(config.timeout(), 10);
+        assert_eq!(config.retry_count(), 2);
+        assert!(config.tags.is_empty());
+
+        let schema = DerivedConfig::config_schema();
+        assert_eq!(schema["title"], "derived");
+        assert_eq!(schema["properties"]["key"]["type"], "string");
+        assert_eq!(schema["properties"]["key"]["description"], "机器人 key");
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(schema["properties"]["timeout"]["type"], "integer");
+        assert_eq!(schema["required"], serde_json::json!(["key"]));
+    }
+
+    #[test]
+    fn test_message_builder_from_template() {
+        #[derive(Serialize)]
+        struct Context {
+            service: &'static str,
+            failures: Vec<&'static str>,
+        }
+        let context = Context {
+            service: "api",
+            failures: vec!["lint", "test"],
+        };
+        let message = MessageBuilder::from_template(
+            "**{{ service }}** failed:{% for step in failures %}\n- {{ step }}{% endfor %}",
+            &context,
+        )
+        .unwrap()
+        .build();
+        assert!(matches!(
+            message.content,
+            MessageType::Markdown(text) if text == "**api** failed:\n- lint\n- test"
+        ));
+    }
+
+    #[test]
+    fn test_message_builder_keeps_envelope() {
+        let message = MessageBuilder::text("Deploy failed")
+            .priority(Priority::Urgent)
+            .mention("alice")
+            .mentions(vec!["bob".to_string()])
+            .metadata("service", "api")
+            .build();
+        assert_eq!(message.priority, Priority::Urgent);
+        assert_eq!(
+            message.mentions,
+            vec![Mention::from("alice"), Mention::from("bob")]
+        );
+        assert_eq!(
+            message.metadata.get("service").map(String::as_str),
+            Some("api")
+        );
+
+        let plain = Message::from(MessageType::Text("hi".to_string()));
+        assert_eq!(plain.priority, Priority::Normal);
+        assert!(plain.mentions.is_empty());
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let limited = PushError::RateLimited {
+            message: "too many requests".to_string(),
+            retry_after: Some(Duration::from_secs(5)),
+        };
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(5)));
+        assert!(PushError::Timeout("30s".to_string()).is_retryable());
+        assert!(!PushError::AuthError("bad token".to_string()).is_retryable());
+        assert!(!PushError::PayloadTooLarge("4096 bytes".to_string()).is_retryable());
+        assert_eq!(
+            PushError::NetworkError("reset".to_string()).retry_after(),
+            None
+        );
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_push_result_default() {
         let result = PushResult::default();
         assert_eq!(result.success, false);
         assert!(result.message_id.is_none());
     }
 
+    #[test]
+    fn test_push_result_backward_compatible() {
+        let result: PushResult = serde_json::from_str(
+            r#"{"message_id": null, "success": true, "response": "ok",
+                "timestamp": "2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(result.success);
+        assert_eq!(result.attempts, 0);
+        assert!(result.elapsed_ms.is_none());
+        assert!(result.channel.is_none());
+    }
+
     #[test]
     fn test_platform_registry() {
         let registry = PlatformRegistry::new();
         assert!(registry.list_platforms().is_empty());
     }
+
+    struct MockFactory;
+
+    impl PlatformFactory for MockFactory {
+        fn create(
+            &self,
+            _config: Value,
+            _context: &PlatformContext,
+        ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+            Ok(Box::new(testing::MockPlatform::new("mock")))
+        }
+
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+    }
+
+    #[test]
+    fn test_registry_management() {
+        let mut registry = PlatformRegistry::new();
+        assert!(registry.replace(Box::new(MockFactory)).is_none());
+        assert!(registry.contains("mock"));
+        assert!(registry.replace(Box::new(MockFactory)).is_some());
+
+        let shared = registry.clone();
+        assert!(registry.unregister("mock").is_some());
+        assert!(!registry.contains("mock"));
+        assert!(registry.create("mock", Value::Null).is_err());
+
+        let names: Vec<&str> = shared.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["mock"]);
+        assert!(shared.create("mock", Value::Null).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_registry_renders_templates_for_non_template_platforms() {
+        let mut registry = PlatformRegistry::new();
+        registry.register(Box::new(MockFactory));
+        registry.set_templates(HashMap::from([(
+            "greet".to_string(),
+            TemplateDefinition {
+                title: None,
+                body: "hello {{name}}".to_string(),
+                markdown: false,
+                locales: HashMap::new(),
+            },
+        )]));
+        let platform = registry.create("mock", Value::Null).unwrap();
+        let result = platform
+            .send(MessageType::Template {
+                name: "greet".to_string(),
+                variables: HashMap::from([("name".to_string(), "bob".to_string())]),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.response.as_deref(), Some("hello bob"));
+    }
+
+    #[tokio::test]
+    async fn test_default_unsupported_error() {
+        let platform = testing::MockPlatform::new("mock");
+        assert!(matches!(
+            platform.send_location(1.0, 2.0, None).await,
+            Err(PushError::Unsupported {
+                message_type: MessageKind::Location,
+                suggestion: Some(MessageKind::Markdown),
+                ..
+            })
+        ));
+        // 本地文件无法降级，没有建议类型
+        let source = AttachmentSource::Path("/tmp/clip.mp4".into());
+        let error = platform.send_video(&source, None, None).await.unwrap_err();
+        assert!(matches!(
+            error,
+            PushError::Unsupported {
+                message_type: MessageKind::Video,
+                suggestion: None,
+                ..
+            }
+        ));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_send_message_splits_long_text() {
+        let platform = testing::MockPlatform::new("sms").with_limits(MessageLimits {
+            text: Some(160),
+            markdown: None,
+            unit: LengthUnit::Chars,
+            attachment_bytes: None,
+        });
+        let mut message = Message::new(MessageType::Text("word ".repeat(50)));
+        message.mentions = vec![Mention::from("alice")];
+        platform.send_message(message).await.unwrap();
+        let sent = platform.sent();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].starts_with("(1/2) ") && sent[0].ends_with("@alice"));
+        assert!(sent[1].starts_with("(2/2) ") && !sent[1].contains("@alice"));
+    }
 }
@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+/// 内置文字，如静默时段摘要的标题、确认提醒的前缀
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinText {
+    /// 摘要标题，参数 `count`
+    DigestHeader,
+    /// 摘要标题后的起始时间，参数 `since`
+    DigestSince,
+    /// 摘要中被省略的消息数，参数 `count`
+    DigestOmitted,
+    /// 确认提醒前缀，参数 `attempt`、`max`
+    Reminder,
+    /// 通道测试消息，参数 `channel`、`time`
+    TestMessage,
+}
+
+/// 内置文字支持的语言，第一个为默认语言
+const LANGUAGES: [&str; 3] = ["en", "zh", "ja"];
+
+impl BuiltinText {
+    fn templates(self) -> [&'static str; LANGUAGES.len()] {
+        match self {
+            Self::DigestHeader => [
+                "**Quiet hours digest**: {count} message(s) held",
+                "**静默时段摘要**：共暂存 {count} 条消息",
+                "**サイレント時間のまとめ**: {count} 件のメッセージを保留",
+            ],
+            Self::DigestSince => [" since {since}", "（自 {since} 起）", "（{since} 以降）"],
+            Self::DigestOmitted => [
+                "- ...and {count} earlier message(s) omitted",
+                "- ……另有 {count} 条较早的消息已省略",
+                "- ...ほか古いメッセージ {count} 件を省略",
+            ],
+            Self::Reminder => [
+                "[Reminder {attempt}/{max}] ",
+                "[提醒 {attempt}/{max}] ",
+                "[リマインダー {attempt}/{max}] ",
+            ],
+            Self::TestMessage => [
+                "[multi_push] Test message for channel '{channel}' sent at {time}",
+                "[multi_push] 通道 {channel} 的测试消息，发送于 {time}",
+                "[multi_push] チャンネル {channel} のテストメッセージ（{time} 送信）",
+            ],
+        }
+    }
+
+    /// 按语言输出内置文字，替换其中的 `{参数}`；不支持的语言使用英文
+    pub fn localize(self, locale: Option<&str>, args: &[(&str, &dyn Display)]) -> String {
+        let index = locale
+            .and_then(|locale| match_locale(locale, LANGUAGES))
+            .and_then(|language| LANGUAGES.iter().position(|l| *l == language))
+            .unwrap_or(0);
+        let mut text = self.templates()[index].to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+}
+
+/// 语言标签的主语言部分，如 `zh-CN` 和 `zh_Hans` 的 `zh`
+fn primary(locale: &str) -> &str {
+    locale.split(['-', '_']).next().unwrap_or(locale)
+}
+
+fn same_tag(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.chars()
+            .zip(b.chars())
+            .all(|(a, b)| a.eq_ignore_ascii_case(&b) || matches!((a, b), ('-', '_') | ('_', '-')))
+}
+
+/// 从 `available` 中选出与 `requested` 最匹配的语言
+///
+/// 优先完全匹配（不区分大小写，`_` 等同 `-`），其次主语言相同，如 `zh-TW` 匹配 `zh` 或 `zh-CN`
+pub fn match_locale<'a>(
+    requested: &str,
+    available: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut fallback = None;
+    for candidate in available {
+        if same_tag(candidate, requested) {
+            return Some(candidate);
+        }
+        if fallback.is_none() && primary(candidate).eq_ignore_ascii_case(primary(requested)) {
+            fallback = Some(candidate);
+        }
+    }
+    fallback
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_locale() {
+        let available = ["en", "zh-CN", "ja"];
+        assert_eq!(match_locale("zh_cn", available), Some("zh-CN"));
+        assert_eq!(match_locale("zh-TW", available), Some("zh-CN"));
+        assert_eq!(match_locale("en-US", available), Some("en"));
+        assert_eq!(match_locale("fr", available), None);
+    }
+
+    #[test]
+    fn test_localize() {
+        let args: &[(&str, &dyn Display)] = &[("attempt", &2), ("max", &5)];
+        assert_eq!(
+            BuiltinText::Reminder.localize(Some("zh-CN"), args),
+            "[提醒 2/5] "
+        );
+        assert_eq!(
+            BuiltinText::Reminder.localize(Some("ja-JP"), args),
+            "[リマインダー 2/5] "
+        );
+        assert_eq!(
+            BuiltinText::Reminder.localize(Some("fr"), args),
+            "[Reminder 2/5] "
+        );
+        assert_eq!(
+            BuiltinText::DigestHeader.localize(None, &[("count", &3)]),
+            "**Quiet hours digest**: 3 message(s) held"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// @提醒对象，由各平台映射为自己的提醒方式
+///
+/// 序列化为字符串：`@all`、`phone:13800000000`、`email:a@example.com`、`user:alice`；
+/// 未带前缀时按内容推断，E.164 格式（`+` 加 8 到 15 位数字）视为手机号，含 `@` 视为邮箱，
+/// 其余视为用户 ID；不带 `+` 的纯数字可能是 Telegram、Discord 等平台的数字用户 ID，手机号需加 `phone:` 前缀
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Mention {
+    /// 提醒所有人
+    All,
+    /// 平台用户 ID
+    UserId(String),
+    /// 手机号
+    Phone(String),
+    /// 邮箱
+    Email(String),
+}
+
+impl Mention {
+    /// 从字符串解析提醒对象
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        if let Some(id) = value.strip_prefix("user:") {
+            return Self::UserId(id.to_string());
+        }
+        if let Some(phone) = value.strip_prefix("phone:") {
+            return Self::Phone(phone.to_string());
+        }
+        if let Some(email) = value.strip_prefix("email:") {
+            return Self::Email(email.to_string());
+        }
+        if value.eq_ignore_ascii_case("all") || value.eq_ignore_ascii_case("@all") {
+            return Self::All;
+        }
+        if is_e164(value) {
+            Self::Phone(value.to_string())
+        } else if value.contains('@') {
+            Self::Email(value.to_string())
+        } else {
+            Self::UserId(value.to_string())
+        }
+    }
+}
+
+/// 是否为 E.164 格式的手机号
+fn is_e164(value: &str) -> bool {
+    value.strip_prefix('+').is_some_and(|digits| {
+        (8..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+impl From<&str> for Mention {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+impl From<String> for Mention {
+    fn from(value: String) -> Self {
+        Self::parse(&value)
+    }
+}
+
+impl From<Mention> for String {
+    fn from(mention: Mention) -> Self {
+        match mention {
+            Mention::All => "@all".to_string(),
+            Mention::UserId(id) => format!("user:{}", id),
+            Mention::Phone(phone) => format!("phone:{}", phone),
+            Mention::Email(email) => format!("email:{}", email),
+        }
+    }
+}
+
+/// 不支持结构化提醒的平台直接在文本中使用 `@xxx`
+impl fmt::Display for Mention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "@all"),
+            Self::UserId(value) | Self::Phone(value) | Self::Email(value) => {
+                write!(f, "@{}", value)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_roundtrip() {
+        assert_eq!(Mention::from("@all"), Mention::All);
+        assert_eq!(Mention::from("alice"), Mention::UserId("alice".to_string()));
+        assert_eq!(
+            Mention::from("+8613800000000"),
+            Mention::Phone("+8613800000000".to_string())
+        );
+        assert_eq!(
+            Mention::from("bob@example.com"),
+            Mention::Email("bob@example.com".to_string())
+        );
+        assert_eq!(Mention::from("user:42"), Mention::UserId("42".to_string()));
+        // 不带 `+` 的纯数字是平台的数字用户 ID
+        assert_eq!(
+            Mention::from("123456789012"),
+            Mention::UserId("123456789012".to_string())
+        );
+        assert_eq!(Mention::from("+123"), Mention::UserId("+123".to_string()));
+
+        let mentions: Vec<Mention> =
+            serde_json::from_str(r#"["alice", "phone:13800000000", "@all"]"#).unwrap();
+        let json = serde_json::to_string(&mentions).unwrap();
+        assert_eq!(json, r#"["user:alice","phone:13800000000","@all"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<Mention>>(&json).unwrap(),
+            mentions
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{Message, PushError, PushPlatformCapabilities, PushResult};
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// 多平台推送的完成策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    /// 等待所有平台完成，全部成功才算成功
+    #[default]
+    All,
+    /// 任一平台成功即返回，其余发送被取消
+    FirstSuccess,
+    /// 指定数量的平台成功即返回
+    Quorum(usize),
+}
+
+/// 多平台推送结果
+#[derive(Debug)]
+pub struct MultiPushReport {
+    /// 各平台结果，按完成顺序排列；提前返回时未完成的平台不在其中
+    pub results: Vec<(String, Result<PushResult, PushError>)>,
+    /// 是否满足策略
+    pub success: bool,
+}
+
+impl MultiPushReport {
+    /// 成功的平台数量
+    pub fn success_count(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_ok()).count()
+    }
+}
+
+/// 并发向多个平台推送同一条消息
+#[derive(Default)]
+pub struct MultiPush {
+    platforms: Vec<(String, Box<dyn PushPlatformCapabilities>)>,
+    strategy: Strategy,
+}
+
+impl MultiPush {
+    /// 使用指定策略创建
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            platforms: Vec::new(),
+            strategy,
+        }
+    }
+
+    /// 添加平台
+    pub fn add(&mut self, name: impl Into<String>, platform: Box<dyn PushPlatformCapabilities>) {
+        self.platforms.push((name.into(), platform));
+    }
+
+    /// 添加平台（构建器形式）
+    pub fn with_platform(
+        mut self,
+        name: impl Into<String>,
+        platform: Box<dyn PushPlatformCapabilities>,
+    ) -> Self {
+        self.add(name, platform);
+        self
+    }
+
+    /// 平台数量
+    pub fn len(&self) -> usize {
+        self.platforms.len()
+    }
+
+    /// 是否没有平台
+    pub fn is_empty(&self) -> bool {
+        self.platforms.is_empty()
+    }
+
+    /// 满足策略所需的成功数量
+    fn required(&self) -> usize {
+        match self.strategy {
+            Strategy::All => self.platforms.len(),
+            Strategy::FirstSuccess => 1,
+            Strategy::Quorum(n) => n,
+        }
+    }
+
+    /// 并发发送，按策略决定何时返回
+    pub async fn send(&self, message: impl Into<Message>) -> MultiPushReport {
+        let message = message.into();
+        let required = self.required();
+        let mut pending: FuturesUnordered<_> = self
+            .platforms
+            .iter()
+            .map(|(name, platform)| {
+                let message = message.clone();
+                async move {
+                    let started = Instant::now();
+                    let mut result = platform.send_message(message).await;
+                    if let Ok(result) = &mut result {
+                        result
+                            .elapsed_ms
+                            .get_or_insert(started.elapsed().as_millis() as u64);
+                    }
+                    (name.clone(), result)
+                }
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(self.platforms.len());
+        let mut successes = 0;
+        while let Some((name, result)) = pending.next().await {
+            if result.is_ok() {
+                successes += 1;
+            }
+            results.push((name, result));
+            if self.strategy != Strategy::All && successes >= required {
+                break;
+            }
+        }
+
+        MultiPushReport {
+            results,
+            success: successes >= required,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+    use crate::testing::MockPlatform;
+    use std::time::Duration;
+
+    fn multi(strategy: Strategy) -> MultiPush {
+        MultiPush::new(strategy)
+            .with_platform(
+                "slow",
+                Box::new(MockPlatform::new("slow").with_delay(Duration::from_millis(200))),
+            )
+            .with_platform("fast", Box::new(MockPlatform::new("fast")))
+            .with_platform(
+                "broken",
+                Box::new(MockPlatform::failing("broken", u32::MAX, || {
+                    PushError::AuthError("bad token".to_string())
+                })),
+            )
+    }
+
+    fn text() -> MessageType {
+        MessageType::Text("hello".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_all_waits_for_every_platform() {
+        let report = multi(Strategy::All).send(text()).await;
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.success_count(), 2);
+        assert!(!report.success);
+    }
+
+    #[tokio::test]
+    async fn test_first_success_returns_early() {
+        let report = multi(Strategy::FirstSuccess).send(text()).await;
+        assert!(report.success);
+        assert_eq!(report.success_count(), 1);
+        assert!(report.results.iter().all(|(name, _)| name != "slow"));
+    }
+
+    #[tokio::test]
+    async fn test_quorum() {
+        assert!(multi(Strategy::Quorum(2)).send(text()).await.success);
+        let report = multi(Strategy::Quorum(3)).send(text()).await;
+        assert!(!report.success);
+        assert_eq!(report.results.len(), 3);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::PlatformRegistry;
+#[cfg(feature = "plugins")]
+use crate::PushError;
+#[cfg(feature = "plugins")]
+use std::ffi::CStr;
+use std::ffi::c_char;
+#[cfg(feature = "plugins")]
+use std::path::Path;
+
+/// 插件 ABI 版本，插件声明中的版本与宿主不一致时拒绝加载
+pub const PLUGIN_ABI_VERSION: u32 = 4;
+/// 构建插件所用的 `common` 版本，以 NUL 结尾
+pub const COMMON_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+/// 构建插件所用的编译器版本，以 NUL 结尾
+pub const RUSTC_VERSION: &str = concat!(env!("MULTI_PUSH_RUSTC_VERSION"), "\0");
+/// 插件导出的声明符号名
+pub const PLUGIN_SYMBOL: &[u8] = b"multi_push_plugin\0";
+
+/// 插件声明，由 [`declare_plugin!`](crate::declare_plugin) 生成并导出
+///
+/// 声明本身只含 C 兼容的字段，宿主先核对 ABI、编译器和 `common` 版本再调用注册函数；
+/// 注册时传递的平台 trait 对象没有稳定的 ABI，版本不一致的插件一律拒绝加载
+#[repr(C)]
+pub struct PluginDeclaration {
+    /// 插件 ABI 版本
+    pub abi_version: u32,
+    /// 构建插件所用的编译器版本，指向以 NUL 结尾的静态字符串
+    pub rustc_version: *const c_char,
+    /// 构建插件所用的 `common` 版本，指向以 NUL 结尾的静态字符串
+    pub common_version: *const c_char,
+    /// 向注册表注册插件提供的平台工厂
+    pub register: unsafe extern "C" fn(*mut PlatformRegistry),
+}
+
+// 声明只包含指向静态字符串的指针和函数指针，可以在线程间共享
+unsafe impl Sync for PluginDeclaration {}
+
+/// 在 `cdylib` 插件中导出插件声明
+///
+/// ```ignore
+/// fn register(registry: &mut common::PlatformRegistry) {
+///     registry.register(Box::new(MyPlatformFactory));
+/// }
+///
+/// common::declare_plugin!(register);
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($register:expr) => {
+        #[unsafe(no_mangle)]
+        #[allow(non_upper_case_globals)]
+        pub static multi_push_plugin: $crate::PluginDeclaration = $crate::PluginDeclaration {
+            abi_version: $crate::PLUGIN_ABI_VERSION,
+            rustc_version: $crate::RUSTC_VERSION.as_ptr().cast(),
+            common_version: $crate::COMMON_VERSION.as_ptr().cast(),
+            register: {
+                // 以 C 调用约定导出注册函数
+                unsafe extern "C" fn multi_push_plugin_register(
+                    registry: *mut $crate::PlatformRegistry,
+                ) {
+                    let register: fn(&mut $crate::PlatformRegistry) = $register;
+                    register(unsafe { &mut *registry });
+                }
+                multi_push_plugin_register
+            },
+        };
+    };
+}
+
+#[cfg(feature = "plugins")]
+impl PlatformRegistry {
+    /// 加载插件动态库并注册其中的平台，返回新注册的平台名称
+    ///
+    /// 插件加载后不会卸载，以保证由其创建的平台实例始终有效
+    ///
+    /// # Safety
+    ///
+    /// 插件代码在宿主进程内执行，调用方必须保证动态库可信，
+    /// 并且由 [`declare_plugin!`](crate::declare_plugin) 导出声明；编译器或 `common` 版本不一致时拒绝加载
+    pub unsafe fn load_plugin(&mut self, path: impl AsRef<Path>) -> Result<Vec<String>, PushError> {
+        let path = path.as_ref();
+        let error = |e: String| {
+            PushError::ConfigError(format!("Failed to load plugin {}: {}", path.display(), e))
+        };
+        let library =
+            unsafe { libloading::Library::new(path) }.map_err(|e| error(e.to_string()))?;
+        let declaration = unsafe {
+            let symbol = library
+                .get::<*const PluginDeclaration>(PLUGIN_SYMBOL)
+                .map_err(|e| error(e.to_string()))?;
+            &**symbol
+        };
+        if declaration.abi_version != PLUGIN_ABI_VERSION {
+            return Err(error(format!(
+                "ABI version {} does not match {}",
+                declaration.abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
+        let rustc_version = unsafe { CStr::from_ptr(declaration.rustc_version) };
+        if rustc_version.to_bytes_with_nul() != RUSTC_VERSION.as_bytes() {
+            return Err(error(format!(
+                "built with {} but host was built with {}",
+                rustc_version.to_string_lossy(),
+                version(RUSTC_VERSION)
+            )));
+        }
+        let common_version = unsafe { CStr::from_ptr(declaration.common_version) };
+        if common_version.to_bytes_with_nul() != COMMON_VERSION.as_bytes() {
+            return Err(error(format!(
+                "built against common {} but host uses {}",
+                common_version.to_string_lossy(),
+                version(COMMON_VERSION)
+            )));
+        }
+
+        let before = self.list_platforms();
+        unsafe { (declaration.register)(self) };
+        std::mem::forget(library);
+        let mut added: Vec<String> = self
+            .list_platforms()
+            .into_iter()
+            .filter(|name| !before.contains(name))
+            .collect();
+        added.sort();
+        Ok(added)
+    }
+}
+
+/// 去掉结尾 NUL 的版本字符串，用于错误信息
+#[cfg(feature = "plugins")]
+fn version(value: &str) -> &str {
+    value.trim_end_matches('\0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn register(registry: &mut PlatformRegistry) {
+        registry.set_templates(Default::default());
+    }
+
+    crate::declare_plugin!(register);
+
+    #[test]
+    fn test_declare_plugin() {
+        assert_eq!(multi_push_plugin.abi_version, PLUGIN_ABI_VERSION);
+        let common_version = unsafe { CStr::from_ptr(multi_push_plugin.common_version) };
+        assert_eq!(common_version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+        let rustc_version = unsafe { CStr::from_ptr(multi_push_plugin.rustc_version) };
+        assert!(rustc_version.to_str().unwrap().starts_with("rustc "));
+
+        let mut registry = PlatformRegistry::new();
+        unsafe { (multi_push_plugin.register)(&mut registry) };
+    }
+
+    #[cfg(feature = "plugins")]
+    #[test]
+    fn test_load_missing_plugin() {
+        let mut registry = PlatformRegistry::new();
+        let result = unsafe { registry.load_plugin("/nonexistent/libplugin.so") };
+        assert!(matches!(result, Err(PushError::ConfigError(_))));
+    }
+}
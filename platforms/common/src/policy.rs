@@ -0,0 +1,151 @@
+use crate::{Message, PushError, SendHook};
+use async_trait::async_trait;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// 消息违反内容策略时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    /// 拒绝发送
+    #[default]
+    Reject,
+    /// 屏蔽违禁词、截断多余的 @提及后发送，缺少必需关键词时仍然拒绝
+    Sanitize,
+}
+
+/// 通道内容策略，用于面向客户等不能直接转发原始告警文本的通道
+///
+/// 关键词匹配不区分大小写
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentPolicy {
+    /// 不允许出现的关键词
+    pub blocked_keywords: Vec<String>,
+    /// 至少要出现其中一个的关键词
+    pub required_keywords: Vec<String>,
+    /// 最多 @提及的人数
+    pub max_mentions: Option<usize>,
+    /// 违反策略时的处理方式
+    pub action: PolicyAction,
+}
+
+impl ContentPolicy {
+    /// 检查消息，按 `action` 拒绝或就地修正
+    pub fn enforce(&self, message: &mut Message) -> Result<(), PushError> {
+        if !self.required_keywords.is_empty() {
+            let texts = message.content.texts_mut();
+            let found = self.required_keywords.iter().any(|keyword| {
+                let regex = keyword_regex(keyword);
+                texts.iter().any(|text| regex.is_match(text))
+            });
+            if !found {
+                return Err(violation(format!(
+                    "missing required keyword, expected one of {:?}",
+                    self.required_keywords
+                )));
+            }
+        }
+
+        for keyword in &self.blocked_keywords {
+            let regex = keyword_regex(keyword);
+            for text in message.content.texts_mut() {
+                if !regex.is_match(text) {
+                    continue;
+                }
+                if self.action == PolicyAction::Reject {
+                    return Err(violation(format!("contains blocked keyword '{}'", keyword)));
+                }
+                *text = regex
+                    .replace_all(text, |caps: &regex::Captures| {
+                        "*".repeat(caps[0].chars().count())
+                    })
+                    .into_owned();
+            }
+        }
+
+        if let Some(max) = self.max_mentions
+            && message.mentions.len() > max
+        {
+            if self.action == PolicyAction::Reject {
+                return Err(violation(format!(
+                    "{} mentions exceed the limit of {}",
+                    message.mentions.len(),
+                    max
+                )));
+            }
+            message.mentions.truncate(max);
+        }
+        Ok(())
+    }
+}
+
+fn keyword_regex(keyword: &str) -> Regex {
+    RegexBuilder::new(&regex::escape(keyword))
+        .case_insensitive(true)
+        .build()
+        .expect("escaped keyword is a valid regex")
+}
+
+fn violation(reason: String) -> PushError {
+    PushError::MessageError(format!("Message violates content policy: {}", reason))
+}
+
+#[async_trait]
+impl SendHook for ContentPolicy {
+    async fn before_send(&self, _platform: &str, message: &mut Message) -> Result<(), PushError> {
+        self.enforce(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mention, MessageType};
+
+    fn message(text: &str, mentions: usize) -> Message {
+        let mut message = Message::new(MessageType::Text(text.to_string()));
+        message.mentions = (0..mentions)
+            .map(|i| Mention::UserId(format!("u{}", i)))
+            .collect();
+        message
+    }
+
+    #[test]
+    fn test_reject() {
+        let policy = ContentPolicy {
+            blocked_keywords: vec!["internal".to_string()],
+            required_keywords: vec!["[status]".to_string()],
+            max_mentions: Some(2),
+            action: PolicyAction::Reject,
+        };
+        assert!(policy.enforce(&mut message("[STATUS] all good", 2)).is_ok());
+        for (text, mentions) in [
+            ("all good", 0),
+            ("[status] see Internal wiki", 0),
+            ("[status] all good", 3),
+        ] {
+            assert!(matches!(
+                policy.enforce(&mut message(text, mentions)),
+                Err(PushError::MessageError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let policy: ContentPolicy = serde_json::from_value(serde_json::json!({
+            "blocked_keywords": ["db-prod-01", "damn"],
+            "max_mentions": 1,
+            "action": "sanitize"
+        }))
+        .unwrap();
+        let mut message = message("Damn, db-prod-01 is down", 3);
+        policy.enforce(&mut message).unwrap();
+        assert!(matches!(
+            &message.content,
+            MessageType::Text(text) if text == "****, ********** is down"
+        ));
+        assert_eq!(message.mentions.len(), 1);
+    }
+}
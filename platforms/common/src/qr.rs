@@ -0,0 +1,89 @@
+use crate::{MessageType, PushError};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use qrcode::{Color, QrCode};
+
+/// 默认每个模块的像素数
+const DEFAULT_SCALE: u32 = 8;
+/// 规范要求的四周留白模块数
+const QUIET_ZONE: u32 = 4;
+/// 模块数超过该值时按比例缩小，避免生成过大的图片
+const MAX_IMAGE_SIZE: u32 = 1024;
+
+/// 将文字或链接编码为二维码 PNG，`scale` 为每个模块的像素数
+pub fn qr_png(text: &str, scale: Option<u32>) -> Result<Vec<u8>, PushError> {
+    let code = QrCode::new(text.as_bytes())
+        .map_err(|e| PushError::MessageError(format!("Failed to encode QR code: {}", e)))?;
+    let modules = code.width() as u32;
+    let total = modules + QUIET_ZONE * 2;
+    let scale = scale
+        .unwrap_or(DEFAULT_SCALE)
+        .clamp(1, (MAX_IMAGE_SIZE / total).max(1));
+    let size = total * scale;
+    let colors = code.to_colors();
+
+    let mut pixels = vec![u8::MAX; (size * size) as usize];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x = (i as u32 % modules + QUIET_ZONE) * scale;
+        let y = (i as u32 / modules + QUIET_ZONE) * scale;
+        for row in y..y + scale {
+            let start = (row * size + x) as usize;
+            pixels[start..start + scale as usize].fill(0);
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size, size);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| PushError::MessageError(format!("Failed to encode QR image: {}", e)))?;
+    Ok(png)
+}
+
+/// 二维码图片消息，图片以 `data:` URL 内联，平台发送时按本地内容处理
+pub fn qr_message(text: &str, caption: Option<String>) -> Result<MessageType, PushError> {
+    let png = qr_png(text, None)?;
+    Ok(MessageType::Image {
+        url: format!("data:image/png;base64,{}", STANDARD.encode(png)),
+        caption,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttachmentSource;
+
+    #[tokio::test]
+    async fn test_qr_message() {
+        let message =
+            qr_message("https://example.com/pair?code=42", Some("Scan".to_string())).unwrap();
+        let MessageType::Image { url, caption } = message else {
+            unreachable!()
+        };
+        assert_eq!(caption.as_deref(), Some("Scan"));
+        let source = AttachmentSource::Url(url);
+        assert!(source.url().is_none());
+        let png = source.read_local().await.unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let info = decoder.read_info().unwrap().info().clone();
+        // 版本 3 为 29 个模块，加上留白共 37 个
+        assert_eq!(info.width, 37 * DEFAULT_SCALE);
+    }
+
+    #[test]
+    fn test_large_payload_is_scaled_down() {
+        let png = qr_png(&"x".repeat(2000), Some(50)).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let info = decoder.read_info().unwrap().info().clone();
+        assert!(info.width <= MAX_IMAGE_SIZE);
+    }
+}
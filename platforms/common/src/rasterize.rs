@@ -0,0 +1,312 @@
+use crate::{Message, MessageKind, MessageTransformer, MessageType, PlatformInfo, PushError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Markdown 渲染为图片的设置，用于支持图片但不支持 Markdown 的平台
+///
+/// 需要启用 `rasterize` 特性，否则消息原样发送
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkdownImageConfig {
+    /// TrueType/OpenType 字体文件，需要覆盖消息使用的文字，如中文字体
+    pub font_path: PathBuf,
+    /// 正文字号（像素）
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// 图片宽度（像素），超出的行自动换行
+    #[serde(default = "default_width")]
+    pub width: u32,
+}
+
+fn default_font_size() -> f32 {
+    18.0
+}
+
+fn default_width() -> u32 {
+    720
+}
+
+impl MarkdownImageConfig {
+    /// 将 Markdown 渲染为 PNG
+    pub fn render(&self, markdown: &str) -> Result<Vec<u8>, PushError> {
+        render::render_markdown(self, markdown)
+    }
+}
+
+/// 目标平台不支持 Markdown 但支持图片时，将 Markdown 消息渲染为图片，渲染失败时原样发送
+impl MessageTransformer for MarkdownImageConfig {
+    fn transform(&self, mut message: Message, target: &PlatformInfo) -> Message {
+        if target.supports(MessageKind::Markdown) || !target.supports(MessageKind::Image) {
+            return message;
+        }
+        let MessageType::Markdown(markdown) = &message.content else {
+            return message;
+        };
+        if let Ok(png) = self.render(markdown) {
+            use base64::Engine;
+            message.content = MessageType::Image {
+                url: format!(
+                    "data:image/png;base64,{}",
+                    base64::engine::general_purpose::STANDARD.encode(png)
+                ),
+                caption: None,
+            };
+        }
+        message
+    }
+}
+
+#[cfg(feature = "rasterize")]
+mod render {
+    use super::MarkdownImageConfig;
+    use crate::{PushError, TableStyle, degrade_tables, strip_markdown};
+    use fontdue::{Font, FontSettings};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    /// 图片最大高度，超出的内容被截断
+    const MAX_HEIGHT: u32 = 4000;
+    const PADDING: u32 = 24;
+    /// 代码块背景灰度
+    const CODE_BACKGROUND: u8 = 0xEE;
+
+    /// 已加载的字体，按路径缓存
+    fn load_font(path: &Path) -> Result<Arc<Font>, PushError> {
+        static FONTS: OnceLock<Mutex<HashMap<PathBuf, Arc<Font>>>> = OnceLock::new();
+        let mut fonts = FONTS.get_or_init(Mutex::default).lock().unwrap();
+        if let Some(font) = fonts.get(path) {
+            return Ok(font.clone());
+        }
+        let bytes = std::fs::read(path).map_err(|e| {
+            PushError::ConfigError(format!("Failed to read font {}: {}", path.display(), e))
+        })?;
+        let font = Font::from_bytes(bytes, FontSettings::default()).map_err(|e| {
+            PushError::ConfigError(format!("Invalid font {}: {}", path.display(), e))
+        })?;
+        let font = Arc::new(font);
+        fonts.insert(path.to_path_buf(), font.clone());
+        Ok(font)
+    }
+
+    /// 排版后的一行
+    struct Line {
+        text: String,
+        size: f32,
+        code: bool,
+    }
+
+    /// 按宽度换行，空格处优先断行，没有空格的长文字（如中文）按字符断行
+    fn wrap(font: &Font, text: &str, size: f32, max_width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        let mut width = 0.0;
+        let mut last_space = None;
+        for c in text.chars() {
+            let advance = font.metrics(c, size).advance_width;
+            if width + advance > max_width && !line.is_empty() {
+                match last_space.take().filter(|_| c != ' ') {
+                    Some(at) => {
+                        let rest = line.split_off(at);
+                        lines.push(line.trim_end().to_string());
+                        line = rest.trim_start().to_string();
+                    }
+                    None => lines.push(std::mem::take(&mut line)),
+                }
+                width = line
+                    .chars()
+                    .map(|c| font.metrics(c, size).advance_width)
+                    .sum();
+            }
+            if c == ' ' {
+                last_space = Some(line.len());
+            }
+            line.push(c);
+            width += advance;
+        }
+        lines.push(line);
+        lines
+    }
+
+    fn layout(config: &MarkdownImageConfig, font: &Font, markdown: &str) -> Vec<Line> {
+        let max_width = (config.width - PADDING * 2) as f32;
+        let markdown = degrade_tables(markdown, TableStyle::CodeBlock);
+        let mut lines = Vec::new();
+        let mut in_fence = false;
+        for raw in markdown.lines() {
+            let trimmed = raw.trim_start();
+            if trimmed.starts_with("```") {
+                in_fence = !in_fence;
+                continue;
+            }
+            let (text, size, code) = if in_fence {
+                (raw.to_string(), config.font_size * 0.9, true)
+            } else {
+                let level = trimmed.chars().take_while(|c| *c == '#').count();
+                let size = match level {
+                    1 => config.font_size * 1.5,
+                    2 => config.font_size * 1.3,
+                    3..=6 => config.font_size * 1.15,
+                    _ => config.font_size,
+                };
+                let text = match trimmed.strip_prefix("- ").or(trimmed.strip_prefix("* ")) {
+                    Some(item) => format!("• {}", strip_markdown(item)),
+                    None => strip_markdown(raw),
+                };
+                (text, size, false)
+            };
+            for text in wrap(font, &text, size, max_width) {
+                lines.push(Line { text, size, code });
+            }
+        }
+        lines
+    }
+
+    pub fn render_markdown(
+        config: &MarkdownImageConfig,
+        markdown: &str,
+    ) -> Result<Vec<u8>, PushError> {
+        let font = load_font(&config.font_path)?;
+        let lines = layout(config, &font, markdown);
+        let line_height = |size: f32| (size * 1.5).ceil() as u32;
+        let content_height: u32 = lines.iter().map(|line| line_height(line.size)).sum();
+        let width = config.width;
+        let height = (content_height + PADDING * 2).min(MAX_HEIGHT);
+
+        let mut pixels = vec![u8::MAX; (width * height) as usize];
+        let mut top = PADDING;
+        for line in &lines {
+            let line_h = line_height(line.size);
+            if top + line_h > height - PADDING {
+                break;
+            }
+            if line.code {
+                for y in top..top + line_h {
+                    let row = (y * width) as usize;
+                    pixels[row + (PADDING / 2) as usize..row + (width - PADDING / 2) as usize]
+                        .fill(CODE_BACKGROUND);
+                }
+            }
+            let ascent = font
+                .horizontal_line_metrics(line.size)
+                .map(|metrics| metrics.ascent)
+                .unwrap_or(line.size);
+            let baseline = top as f32 + (line_h as f32 - line.size) / 2.0 + ascent;
+            let mut x = PADDING as f32;
+            for c in line.text.chars() {
+                let (metrics, coverage) = font.rasterize(c, line.size);
+                let left = x.round() as i32 + metrics.xmin;
+                let glyph_top = baseline.round() as i32 - metrics.height as i32 - metrics.ymin;
+                for gy in 0..metrics.height {
+                    for gx in 0..metrics.width {
+                        let (px, py) = (left + gx as i32, glyph_top + gy as i32);
+                        if px < 0 || py < 0 || px >= width as i32 || py >= height as i32 {
+                            continue;
+                        }
+                        let alpha = coverage[gy * metrics.width + gx] as u32;
+                        let pixel = &mut pixels[(py as u32 * width + px as u32) as usize];
+                        *pixel = (*pixel as u32 * (255 - alpha) / 255) as u8;
+                    }
+                }
+                x += metrics.advance_width;
+            }
+            top += line_h;
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|e| PushError::MessageError(format!("Failed to encode image: {}", e)))?;
+        Ok(png)
+    }
+}
+
+#[cfg(not(feature = "rasterize"))]
+mod render {
+    use super::MarkdownImageConfig;
+    use crate::PushError;
+
+    pub fn render_markdown(
+        _config: &MarkdownImageConfig,
+        _markdown: &str,
+    ) -> Result<Vec<u8>, PushError> {
+        Err(PushError::ConfigError(
+            "Markdown rendering requires the `rasterize` feature".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> MarkdownImageConfig {
+        serde_json::from_value(serde_json::json!({
+            "font_path": "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf"
+        }))
+        .unwrap()
+    }
+
+    fn target(markdown: bool, images: bool) -> PlatformInfo {
+        PlatformInfo {
+            name: "sms".to_string(),
+            version: String::new(),
+            message_types: [
+                (true, MessageKind::Text),
+                (markdown, MessageKind::Markdown),
+                (images, MessageKind::Image),
+            ]
+            .into_iter()
+            .filter_map(|(supported, kind)| supported.then_some(kind))
+            .collect(),
+            mentions: Default::default(),
+            features: vec![],
+            limits: Default::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_only_applies_without_markdown_support() {
+        let config = config();
+        assert_eq!(config.font_size, 18.0);
+        let message = Message::new(MessageType::Markdown("# Report".to_string()));
+        for target in [target(true, true), target(false, false)] {
+            let message = config.transform(message.clone(), &target);
+            assert!(matches!(message.content, MessageType::Markdown(_)));
+        }
+        let missing = MarkdownImageConfig {
+            font_path: "/nonexistent.ttf".into(),
+            ..config
+        };
+        let message = missing.transform(message, &target(false, true));
+        assert!(matches!(message.content, MessageType::Markdown(_)));
+    }
+
+    #[cfg(feature = "rasterize")]
+    #[test]
+    fn test_render() {
+        let config = config();
+        if !config.font_path.exists() {
+            return;
+        }
+        let markdown = "# Weekly report\n\n- **api**: 99.9% uptime\n\n| host | load |\n|---|---|\n| db1 | 0.93 |\n\n```\ncargo test\n```";
+        let message = config.transform(
+            Message::new(MessageType::Markdown(markdown.to_string())),
+            &target(false, true),
+        );
+        let MessageType::Image { url, .. } = message.content else {
+            panic!("markdown was not rendered");
+        };
+        assert!(url.starts_with("data:image/png;base64,"));
+        let png = config.render(markdown).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let info = decoder.read_info().unwrap().info().clone();
+        assert_eq!(info.width, 720);
+        assert!(info.height > 200);
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 消息的投递状态，由平台异步回报
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// 已提交给平台，尚无回执
+    Sent,
+    /// 已送达接收方
+    Delivered,
+    /// 接收方已读
+    Read,
+    /// 接收方已确认，如在消息上回应了确认表情；需要确认的消息随之停止提醒
+    Acknowledged,
+    /// 被接收方拒收，如邮件退信、APNs 设备令牌失效
+    Bounced,
+    /// 平台投递失败
+    Failed,
+}
+
+impl DeliveryStatus {
+    /// 是否为最终状态，之后的回执不再覆盖
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Self::Read | Self::Acknowledged | Self::Bounced | Self::Failed
+        )
+    }
+}
+
+/// 平台回报的投递回执
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Receipt {
+    /// 发送结果中的 `message_id`
+    pub message_id: String,
+    pub status: DeliveryStatus,
+    /// 退信或失败原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// 平台记录的时间，缺省为收到回执的时间
+    #[serde(default = "Utc::now")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Receipt {
+    pub fn new(message_id: impl Into<String>, status: DeliveryStatus) -> Self {
+        Self {
+            message_id: message_id.into(),
+            status,
+            reason: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// 设置退信或失败原因
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let receipt: Receipt = serde_json::from_str(
+            r#"{"message_id": "<a1@mail>", "status": "bounced", "reason": "mailbox full"}"#,
+        )
+        .unwrap();
+        assert_eq!(receipt.status, DeliveryStatus::Bounced);
+        assert!(receipt.status.is_final());
+        assert_eq!(
+            receipt,
+            Receipt {
+                timestamp: receipt.timestamp,
+                ..Receipt::new("<a1@mail>", DeliveryStatus::Bounced).with_reason("mailbox full")
+            }
+        );
+        assert!(!DeliveryStatus::Delivered.is_final());
+    }
+}
@@ -0,0 +1,792 @@
+use crate::transform::negotiate;
+use crate::{
+    AttachmentSource, CapabilityMode, CardButton, CardSection, Mention, Message, MessagePart,
+    MessageType, PlatformInfo, PushError, PushInitConfig, PushPlatformCapabilities, PushResult,
+    Receipt, send_parts,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 默认首次重试间隔
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 重试间隔上限
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// 被限流时默认最长排队等待时间
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(120);
+
+/// 可重试的错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryClass {
+    Network,
+    Timeout,
+    /// 限流，按平台要求的时间排队后重试，不计入重试次数
+    RateLimited,
+    /// 平台返回的其他错误，默认不重试
+    Platform,
+}
+
+impl RetryClass {
+    fn matches(self, error: &PushError) -> bool {
+        matches!(
+            (self, error.inner()),
+            (Self::Network, PushError::NetworkError(_))
+                | (Self::Timeout, PushError::Timeout(_))
+                | (Self::RateLimited, PushError::RateLimited { .. })
+                | (Self::Platform, PushError::PlatformError(_))
+        )
+    }
+}
+
+/// 重试策略，如按条计费的短信通道减少重试次数，免费的聊天 Webhook 可以多重试
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// 最多发送次数，包括第一次；未设置时为平台配置的重试次数加一
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    /// 首次重试间隔（毫秒），之后每次翻倍
+    pub initial_backoff_ms: u64,
+    /// 重试间隔上限（毫秒）
+    pub max_backoff_ms: u64,
+    /// 抖动比例，0 到 1，每次间隔随机增减该比例，避免多个实例同时重试
+    pub jitter: f64,
+    /// 重试的错误类别
+    pub retry_on: Vec<RetryClass>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: None,
+            initial_backoff_ms: DEFAULT_BACKOFF.as_millis() as u64,
+            max_backoff_ms: MAX_BACKOFF.as_millis() as u64,
+            jitter: 0.0,
+            retry_on: vec![
+                RetryClass::Network,
+                RetryClass::Timeout,
+                RetryClass::RateLimited,
+            ],
+        }
+    }
+}
+
+/// 为任意平台增加单次超时与指数退避重试的装饰器
+///
+/// 被限流时，同一实例上的所有发送都会排队到限流窗口结束后再发出，
+/// 限流重试不计入重试次数，只受最长排队时间约束
+pub struct ResilientPlatform<T> {
+    inner: T,
+    timeout: Duration,
+    retry_count: u32,
+    /// 重试策略限定的最多发送次数，消息自带的重试次数也不能超过
+    max_attempts: Option<u32>,
+    backoff: Duration,
+    max_backoff: Duration,
+    jitter: f64,
+    retry_on: Vec<RetryClass>,
+    rate_limit_wait: Duration,
+    capability_mode: CapabilityMode,
+    /// 限流窗口的结束时间
+    blocked_until: Mutex<Option<Instant>>,
+}
+
+impl<T: PushPlatformCapabilities> ResilientPlatform<T> {
+    /// 按 `PushInitConfig` 中的超时与重试策略包装平台
+    pub fn new(inner: T, config: &dyn PushInitConfig) -> Self {
+        Self::with_policy(
+            inner,
+            Duration::from_secs(config.timeout()),
+            config.retry_count(),
+        )
+        .with_retry_policy(&config.retry_policy())
+    }
+
+    /// 指定单次超时与重试次数包装平台
+    pub fn with_policy(inner: T, timeout: Duration, retry_count: u32) -> Self {
+        Self {
+            inner,
+            timeout,
+            retry_count,
+            max_attempts: None,
+            backoff: DEFAULT_BACKOFF,
+            max_backoff: MAX_BACKOFF,
+            jitter: 0.0,
+            retry_on: RetryPolicy::default().retry_on,
+            rate_limit_wait: DEFAULT_RATE_LIMIT_WAIT,
+            capability_mode: CapabilityMode::Lenient,
+            blocked_until: Mutex::new(None),
+        }
+    }
+
+    /// 设置首次重试间隔，之后每次翻倍
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// 应用重试策略，策略未设置最多发送次数时保留原有的重试次数
+    pub fn with_retry_policy(mut self, policy: &RetryPolicy) -> Self {
+        if let Some(max_attempts) = policy.max_attempts {
+            self.retry_count = max_attempts.saturating_sub(1);
+        }
+        self.max_attempts = policy.max_attempts;
+        self.backoff = Duration::from_millis(policy.initial_backoff_ms);
+        self.max_backoff = Duration::from_millis(policy.max_backoff_ms);
+        self.jitter = policy.jitter.clamp(0.0, 1.0);
+        self.retry_on = policy.retry_on.clone();
+        self
+    }
+
+    /// 设置被限流时的最长排队等待时间，超过后返回限流错误
+    pub fn with_rate_limit_wait(mut self, wait: Duration) -> Self {
+        self.rate_limit_wait = wait;
+        self
+    }
+
+    /// 设置 `send` 遇到平台不支持的消息时自动改写还是直接报错
+    pub fn with_capability_mode(mut self, mode: CapabilityMode) -> Self {
+        self.capability_mode = mode;
+        self
+    }
+
+    /// 获取被包装的平台
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    async fn run<F, Fut>(&self, op: F) -> Result<PushResult, PushError>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: Future<Output = Result<PushResult, PushError>> + Send,
+    {
+        self.run_with(self.timeout, self.retry_count, op).await
+    }
+
+    /// 按指定的单次超时与重试次数执行，用于消息自带的覆盖值
+    async fn run_with<F, Fut>(
+        &self,
+        timeout: Duration,
+        retry_count: u32,
+        op: F,
+    ) -> Result<PushResult, PushError>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: Future<Output = Result<PushResult, PushError>> + Send,
+    {
+        let started = Instant::now();
+        let mut attempt = 0;
+        let mut retries = 0;
+        let mut backoff = self.backoff;
+        loop {
+            self.wait_for_rate_limit().await;
+            attempt += 1;
+            let result = match tokio::time::timeout(timeout, op()).await {
+                Ok(result) => result,
+                Err(_) => Err(PushError::Timeout(format!(
+                    "Request timed out after {:?}",
+                    timeout
+                ))),
+            };
+            match result {
+                Ok(mut result) => {
+                    result.attempts = attempt;
+                    result.elapsed_ms = Some(started.elapsed().as_millis() as u64);
+                    return Ok(result);
+                }
+                Err(e) if is_rate_limited(&e) && self.retries_on(&e) => {
+                    let delay = e.retry_after().unwrap_or(backoff);
+                    if started.elapsed() + delay > self.rate_limit_wait {
+                        return Err(failed(e, attempt, started));
+                    }
+                    self.block_for(delay);
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+                // 配置、鉴权等永久性错误重试无意义
+                Err(e) if self.retries_on(&e) && retries < retry_count => {
+                    retries += 1;
+                    tokio::time::sleep(self.jittered(backoff)).await;
+                    backoff = (backoff * 2).min(self.max_backoff);
+                }
+                Err(e) => return Err(failed(e, attempt, started)),
+            }
+        }
+    }
+
+    /// 消息自带的重试次数，不超过重试策略的最多发送次数
+    fn retry_count_for(&self, message: &Message) -> u32 {
+        let retry_count = message.retry_count.unwrap_or(self.retry_count);
+        match self.max_attempts {
+            Some(max_attempts) => retry_count.min(max_attempts.saturating_sub(1)),
+            None => retry_count,
+        }
+    }
+
+    /// 错误是否属于策略中可重试的类别
+    fn retries_on(&self, error: &PushError) -> bool {
+        self.retry_on.iter().any(|class| class.matches(error))
+    }
+
+    /// 按抖动比例随机增减重试间隔
+    fn jittered(&self, backoff: Duration) -> Duration {
+        if self.jitter == 0.0 {
+            return backoff;
+        }
+        // [-1, 1) 的随机数，不为此引入随机数依赖
+        let bits = RandomState::new().hash_one(Instant::now()) >> 11;
+        let unit = bits as f64 / (1u64 << 53) as f64 * 2.0 - 1.0;
+        backoff.mul_f64(1.0 + self.jitter * unit)
+    }
+
+    /// 在限流窗口内排队等待
+    async fn wait_for_rate_limit(&self) {
+        let blocked_until = *self.blocked_until.lock().unwrap();
+        if let Some(until) = blocked_until {
+            tokio::time::sleep_until(until.into()).await;
+        }
+    }
+
+    /// 延长限流窗口
+    fn block_for(&self, delay: Duration) {
+        let until = Instant::now() + delay;
+        let mut blocked_until = self.blocked_until.lock().unwrap();
+        if blocked_until.is_none_or(|current| current < until) {
+            *blocked_until = Some(until);
+        }
+    }
+}
+
+/// 是否为限流错误
+fn is_rate_limited(error: &PushError) -> bool {
+    matches!(error.inner(), PushError::RateLimited { .. })
+}
+
+/// 失败时同样记录尝试次数和耗时
+fn failed(error: PushError, attempts: u32, started: Instant) -> PushError {
+    error.with_diagnostics(|result| {
+        result.attempts = attempts;
+        result.elapsed_ms = Some(started.elapsed().as_millis() as u64);
+    })
+}
+
+#[async_trait]
+impl<T: PushPlatformCapabilities> PushPlatformCapabilities for ResilientPlatform<T> {
+    async fn init(&mut self) -> Result<(), PushError> {
+        self.inner.init().await
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_text(content)).await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_text_with_mention(content, mentions.clone()))
+            .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_markdown(content)).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_rich(title, content, url)).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_image(image_url, caption)).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_link(title, description, url, image_url))
+            .await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_file(name, mime, source)).await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_card(title, sections, buttons))
+            .await
+    }
+
+    async fn send_template(
+        &self,
+        name: &str,
+        variables: &HashMap<String, String>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_template(name, variables)).await
+    }
+
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_audio(source, caption, duration_secs))
+            .await
+    }
+
+    async fn send_video(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_video(source, caption, duration_secs))
+            .await
+    }
+
+    async fn send_location(
+        &self,
+        lat: f64,
+        lon: f64,
+        label: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.send_location(lat, lon, label)).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        let parts = negotiate(message, &self.inner.platform_info(), self.capability_mode)?;
+        let mut result = None;
+        for part in parts {
+            let sent = self.run(|| self.inner.send(part.clone())).await?;
+            result.get_or_insert(sent);
+        }
+        result.ok_or_else(|| PushError::MessageError("Message is empty".to_string()))
+    }
+
+    /// 严格模式下先检查平台能否原样发送，再由 `send_part` 逐部分发送
+    async fn send_message(&self, message: Message) -> Result<PushResult, PushError> {
+        if self.capability_mode == CapabilityMode::Strict {
+            negotiate(
+                message.content.clone(),
+                &self.inner.platform_info(),
+                CapabilityMode::Strict,
+            )?;
+        }
+        send_parts(self, &message).await
+    }
+
+    /// 消息拆分后的每部分分别超时和重试，后续部分失败时不会重发已送达的部分
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        let timeout = message.timeout().unwrap_or(self.timeout);
+        let retry_count = self.retry_count_for(message);
+        self.run_with(timeout, retry_count, || {
+            self.inner.send_part(message, part.clone())
+        })
+        .await
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.update_message(message_id, message.clone()))
+            .await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        self.run(|| self.inner.delete_message(message_id)).await
+    }
+
+    /// 查询失败时由调用方下次再查，不重试
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        self.inner.poll_receipts().await
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        self.inner.poll_receipts_for(message_ids).await
+    }
+
+    /// 交给被包装的平台批量发送（平台可能合并消息），可重试的失败消息按原顺序
+    /// 再次整批交给被包装的平台，合并发送的消息作为一个整体重试
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        let started = Instant::now();
+        let mut results: Vec<Option<Result<PushResult, PushError>>> =
+            messages.iter().map(|_| None).collect();
+        let mut pending: Vec<usize> = (0..messages.len()).collect();
+        let mut attempt = 0;
+        let mut retries = 0;
+        let mut backoff = self.backoff;
+        while !pending.is_empty() {
+            self.wait_for_rate_limit().await;
+            attempt += 1;
+            let batch: Vec<Message> = pending.iter().map(|&i| messages[i].clone()).collect();
+            // 整批的超时为各条消息单次超时之和
+            let timeout: Duration = batch
+                .iter()
+                .map(|message| message.timeout().unwrap_or(self.timeout))
+                .sum();
+            let count = batch.len();
+            let mut batch_results =
+                match tokio::time::timeout(timeout, self.inner.send_batch(batch)).await {
+                    Ok(results) => results,
+                    Err(_) => (0..count)
+                        .map(|_| {
+                            Err(PushError::Timeout(format!(
+                                "Batch timed out after {:?}",
+                                timeout
+                            )))
+                        })
+                        .collect(),
+                };
+            // 结果数与消息数不一致时，缺少结果的消息按失败处理而不是被丢弃
+            let returned = batch_results.len();
+            if returned != count {
+                batch_results.truncate(count);
+                batch_results.resize_with(count, || {
+                    Err(PushError::PlatformError(format!(
+                        "Batch send returned {} results for {} messages",
+                        returned, count
+                    )))
+                });
+            }
+            let mut retrying = Vec::new();
+            let mut rate_limit_delay = None;
+            let mut backing_off = false;
+            for (i, result) in pending.into_iter().zip(batch_results) {
+                match result {
+                    Ok(mut result) => {
+                        result.attempts = attempt;
+                        result.elapsed_ms = Some(started.elapsed().as_millis() as u64);
+                        results[i] = Some(Ok(result));
+                    }
+                    // 限流重试不受重试次数约束
+                    Err(e) if is_rate_limited(&e) && self.retries_on(&e) => {
+                        let delay = e.retry_after().unwrap_or(backoff);
+                        if started.elapsed() + delay > self.rate_limit_wait {
+                            results[i] = Some(Err(failed(e, attempt, started)));
+                        } else {
+                            rate_limit_delay = rate_limit_delay.max(Some(delay));
+                            retrying.push(i);
+                        }
+                    }
+                    Err(e)
+                        if self.retries_on(&e) && retries < self.retry_count_for(&messages[i]) =>
+                    {
+                        backing_off = true;
+                        retrying.push(i);
+                    }
+                    Err(e) => results[i] = Some(Err(failed(e, attempt, started))),
+                }
+            }
+            if backing_off {
+                retries += 1;
+            }
+            match rate_limit_delay {
+                Some(delay) => self.block_for(delay),
+                None if backing_off => tokio::time::sleep(self.jittered(backoff)).await,
+                None => {}
+            }
+            backoff = (backoff * 2).min(self.max_backoff);
+            pending = retrying;
+        }
+        results.into_iter().flatten().collect()
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        tokio::time::timeout(self.timeout, self.inner.health_check())
+            .await
+            .map_err(|_| {
+                PushError::Timeout(format!("Health check timed out after {:?}", self.timeout))
+            })?
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        self.inner.platform_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageLimits;
+    use crate::testing::MockPlatform;
+
+    fn network_error() -> PushError {
+        PushError::NetworkError("connection reset".to_string())
+    }
+
+    fn resilient(platform: MockPlatform, retry_count: u32) -> ResilientPlatform<MockPlatform> {
+        ResilientPlatform::with_policy(platform, Duration::from_millis(50), retry_count)
+            .with_backoff(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_retries_network_errors() {
+        let platform = resilient(MockPlatform::failing("flaky", 2, network_error), 3);
+        let result = platform.send_text("hi").await.unwrap();
+        assert_eq!(result.attempts, 3);
+        assert!(result.elapsed_ms.is_some());
+
+        let platform = resilient(MockPlatform::failing("flaky", 5, network_error), 2);
+        let err = platform.send_text("hi").await.unwrap_err();
+        assert_eq!(platform.inner().calls(), 3);
+        // 失败时同样带上尝试次数和耗时
+        assert!(matches!(err.inner(), PushError::NetworkError(_)));
+        let diagnostics = err.diagnostics().unwrap();
+        assert_eq!(diagnostics.attempts, 3);
+        assert!(diagnostics.elapsed_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_other_errors() {
+        let platform = resilient(
+            MockPlatform::failing("flaky", 1, || PushError::AuthError("bad token".to_string())),
+            3,
+        );
+        assert!(matches!(
+            platform.send_text("hi").await.unwrap_err().inner(),
+            PushError::AuthError(_)
+        ));
+        assert_eq!(platform.inner().calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_honors_retry_after() {
+        let platform = resilient(
+            MockPlatform::failing("flaky", 1, || PushError::RateLimited {
+                message: "slow down".to_string(),
+                retry_after: Some(Duration::from_millis(30)),
+            }),
+            1,
+        );
+        let started = std::time::Instant::now();
+        assert_eq!(platform.send_text("hi").await.unwrap().attempts, 2);
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn test_batch_retries_failed_messages() {
+        let platform = resilient(MockPlatform::failing("flaky", 1, network_error), 2);
+        let text = |s: &str| Message::from(MessageType::Text(s.to_string()));
+        let messages = vec![text("a"), text("b")];
+        let results = platform.send_batch(messages).await;
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(platform.inner().calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_split_message_retries_failed_part_only() {
+        let limits = MessageLimits {
+            text: Some(50),
+            ..Default::default()
+        };
+        let platform = resilient(
+            MockPlatform::new("flaky").with_limits(limits).failing_at(1),
+            2,
+        );
+        let content = ["a".repeat(25), "b".repeat(25), "c".repeat(25)].join("\n");
+        platform
+            .send_message(MessageType::Text(content).into())
+            .await
+            .unwrap();
+        let sent = platform.inner().sent();
+        assert_eq!(sent.len(), 3);
+        let first = "a".repeat(25);
+        assert_eq!(sent.iter().filter(|s| s.contains(&first)).count(), 1);
+        assert_eq!(platform.inner().calls(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_times_out_and_reports_missing_results() {
+        let text = |s: &str| Message::from(MessageType::Text(s.to_string()));
+        let platform = resilient(
+            MockPlatform::new("slow").with_delay(Duration::from_millis(500)),
+            0,
+        );
+        let results = platform.send_batch(vec![text("a")]).await;
+        assert!(matches!(
+            results[0].as_ref().unwrap_err().inner(),
+            PushError::Timeout(_)
+        ));
+
+        let platform = resilient(MockPlatform::new("short").with_batch_limit(1), 0);
+        let results = platform.send_batch(vec![text("a"), text("b")]).await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1].as_ref().unwrap_err().inner(),
+            PushError::PlatformError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_queues_rate_limited_sends() {
+        let rate_limited = || PushError::RateLimited {
+            message: "api freq out of limit".to_string(),
+            retry_after: Some(Duration::from_millis(20)),
+        };
+        let platform = resilient(MockPlatform::failing("flaky", 2, rate_limited), 0);
+        assert_eq!(platform.send_text("hi").await.unwrap().attempts, 3);
+
+        let platform = resilient(MockPlatform::failing("flaky", 5, rate_limited), 0)
+            .with_rate_limit_wait(Duration::from_millis(30));
+        assert!(matches!(
+            platform.send_text("hi").await.unwrap_err().inner(),
+            PushError::RateLimited { .. }
+        ));
+        assert_eq!(platform.inner().calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy() {
+        // 按条计费的通道只发一次
+        let policy: RetryPolicy = serde_json::from_value(serde_json::json!({
+            "max_attempts": 1
+        }))
+        .unwrap();
+        let platform =
+            resilient(MockPlatform::failing("sms", 1, network_error), 3).with_retry_policy(&policy);
+        assert!(platform.send_text("hi").await.is_err());
+        assert_eq!(platform.inner().calls(), 1);
+
+        // 未设置次数时保留平台的重试次数，只重试指定的错误类别
+        let policy = RetryPolicy {
+            initial_backoff_ms: 1,
+            jitter: 0.5,
+            retry_on: vec![RetryClass::Platform],
+            ..Default::default()
+        };
+        let platform = resilient(
+            MockPlatform::failing("flaky", 2, || PushError::PlatformError("busy".to_string())),
+            3,
+        )
+        .with_retry_policy(&policy);
+        assert_eq!(platform.send_text("hi").await.unwrap().attempts, 3);
+        let platform = resilient(MockPlatform::failing("flaky", 1, network_error), 3)
+            .with_retry_policy(&policy);
+        assert!(platform.send_text("hi").await.is_err());
+        assert_eq!(platform.inner().calls(), 1);
+    }
+
+    #[test]
+    fn test_jitter_stays_in_range() {
+        let policy = RetryPolicy {
+            jitter: 0.2,
+            ..Default::default()
+        };
+        let platform = resilient(MockPlatform::new("flaky"), 0).with_retry_policy(&policy);
+        for _ in 0..100 {
+            let delay = platform.jittered(Duration::from_millis(1000));
+            assert!(delay >= Duration::from_millis(800) && delay <= Duration::from_millis(1200));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_counts_as_attempt() {
+        let slow = MockPlatform::new("slow").with_delay(Duration::from_millis(200));
+        let platform = resilient(slow, 1);
+        let err = platform.send_text("hi").await.unwrap_err();
+        assert!(matches!(err.inner(), PushError::Timeout(_)));
+        assert_eq!(platform.inner().calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_negotiates_capabilities() {
+        let limits = crate::MessageLimits {
+            text: Some(160),
+            markdown: None,
+            unit: crate::LengthUnit::Chars,
+            attachment_bytes: None,
+        };
+        let long = || MessageType::Text("word ".repeat(50));
+
+        let platform = resilient(MockPlatform::new("sms").with_limits(limits.clone()), 0);
+        platform.send(long()).await.unwrap();
+        assert_eq!(platform.inner().sent().len(), 2);
+
+        let platform = resilient(MockPlatform::new("sms").with_limits(limits), 0)
+            .with_capability_mode(CapabilityMode::Strict);
+        assert!(matches!(
+            platform.send(long()).await,
+            Err(PushError::PayloadTooLarge(_))
+        ));
+        assert!(matches!(
+            platform.send_message(Message::new(long())).await,
+            Err(PushError::PayloadTooLarge(_))
+        ));
+        assert_eq!(platform.inner().calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_message_overrides_timeout_and_retries() {
+        let platform = resilient(MockPlatform::failing("flaky", 2, network_error), 3);
+        let message = Message::new(MessageType::Text("heartbeat".to_string())).with_retry_count(0);
+        assert!(platform.send_message(message).await.is_err());
+        assert_eq!(platform.inner().calls(), 1);
+
+        let platform = resilient(MockPlatform::failing("flaky", 2, network_error), 0);
+        let message = Message::new(MessageType::Text("incident".to_string())).with_retry_count(2);
+        assert_eq!(platform.send_message(message).await.unwrap().attempts, 3);
+
+        // 消息自带的重试次数不超过通道重试策略的最多发送次数
+        let policy = RetryPolicy {
+            max_attempts: Some(2),
+            initial_backoff_ms: 1,
+            ..Default::default()
+        };
+        let platform =
+            resilient(MockPlatform::failing("sms", 5, network_error), 0).with_retry_policy(&policy);
+        let message = Message::new(MessageType::Text("incident".to_string())).with_retry_count(10);
+        let err = platform.send_message(message).await.unwrap_err();
+        assert_eq!(err.diagnostics().unwrap().attempts, 2);
+        assert_eq!(platform.inner().calls(), 2);
+
+        let platform = ResilientPlatform::with_policy(
+            MockPlatform::new("slow").with_delay(Duration::from_millis(50)),
+            Duration::from_millis(10),
+            0,
+        );
+        let message = Message::new(MessageType::Text("report".to_string()))
+            .with_timeout(Duration::from_secs(1));
+        assert!(platform.send_message(message).await.is_ok());
+        assert!(matches!(
+            platform.send_text("report").await.unwrap_err().inner(),
+            PushError::Timeout(_)
+        ));
+    }
+}
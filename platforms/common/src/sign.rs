@@ -0,0 +1,165 @@
+//! 平台签名常用的摘要、编码和时间戳工具
+//!
+//! 钉钉、飞书等机器人和 GitHub 风格的 Webhook 都基于 HMAC 签名，只是拼接方式和编码不同，
+//! 新平台直接组合这里的函数即可
+
+use crate::PushError;
+use base64::Engine;
+use chrono::Utc;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+
+fn hmac(digest: MessageDigest, key: &[u8], data: &[u8]) -> Result<Vec<u8>, PushError> {
+    PKey::hmac(key)
+        .and_then(|key| {
+            let mut signer = Signer::new(digest, &key)?;
+            signer.update(data)?;
+            signer.sign_to_vec()
+        })
+        .map_err(|e| PushError::AuthError(format!("Failed to compute signature: {}", e)))
+}
+
+/// HMAC-SHA256
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>, PushError> {
+    hmac(MessageDigest::sha256(), key, data)
+}
+
+/// HMAC-SHA1
+pub fn hmac_sha1(key: &[u8], data: &[u8]) -> Result<Vec<u8>, PushError> {
+    hmac(MessageDigest::sha1(), key, data)
+}
+
+/// SHA256 摘要
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    openssl::sha::sha256(data)
+}
+
+/// SHA1 摘要
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    openssl::sha::sha1(data)
+}
+
+/// 十六进制小写编码
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 标准 base64 编码（带填充）
+pub fn base64(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// 百分号编码，只保留 RFC 3986 的非保留字符，空格编码为 `%20`
+pub fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// 当前 Unix 时间戳（秒）
+pub fn timestamp_secs() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// 当前 Unix 时间戳（毫秒）
+pub fn timestamp_millis() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+/// 常量时间比较，用于校验签名，避免计时攻击
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && openssl::memcmp::eq(a, b)
+}
+
+/// 钉钉机器人加签：`urlencode(base64(HMAC-SHA256(secret, "{timestamp}\n{secret}")))`，
+/// `timestamp` 为毫秒
+pub fn dingtalk_sign(secret: &str, timestamp: i64) -> Result<String, PushError> {
+    let data = format!("{}\n{}", timestamp, secret);
+    Ok(url_encode(&base64(&hmac_sha256(
+        secret.as_bytes(),
+        data.as_bytes(),
+    )?)))
+}
+
+/// 飞书机器人签名：以 `"{timestamp}\n{secret}"` 为密钥对空内容做 HMAC-SHA256 后 base64，
+/// `timestamp` 为秒
+pub fn feishu_sign(secret: &str, timestamp: i64) -> Result<String, PushError> {
+    let key = format!("{}\n{}", timestamp, secret);
+    Ok(base64(&hmac_sha256(key.as_bytes(), b"")?))
+}
+
+/// GitHub 风格的 Webhook 签名 `sha256=hex(HMAC-SHA256(secret, body))`
+pub fn webhook_signature(secret: &str, body: &[u8]) -> Result<String, PushError> {
+    Ok(format!(
+        "sha256={}",
+        hex(&hmac_sha256(secret.as_bytes(), body)?)
+    ))
+}
+
+/// 校验 GitHub 风格的 Webhook 签名
+pub fn verify_webhook_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    webhook_signature(secret, body)
+        .is_ok_and(|expected| constant_time_eq(expected.as_bytes(), signature.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac() {
+        // RFC 4231 / RFC 2202 测试用例 2
+        let data = b"what do ya want for nothing?";
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", data).unwrap()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(&hmac_sha1(b"Jefe", data).unwrap()),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            hex(&sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(base64(b"hello"), "aGVsbG8=");
+        assert_eq!(url_encode("a+b/c=d e~"), "a%2Bb%2Fc%3Dd%20e~");
+        assert_eq!(url_encode("中"), "%E4%B8%AD");
+        assert!(timestamp_millis() / 1000 - timestamp_secs() <= 1);
+    }
+
+    #[test]
+    fn test_platform_signatures() {
+        // GitHub 文档中的示例
+        let body = b"Hello, World!";
+        let signature = webhook_signature("It's a Secret to Everybody", body).unwrap();
+        assert_eq!(
+            signature,
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+        assert!(verify_webhook_signature(
+            "It's a Secret to Everybody",
+            body,
+            &signature
+        ));
+        assert!(!verify_webhook_signature("wrong", body, &signature));
+
+        let dingtalk = dingtalk_sign("SEC000", 1700000000000).unwrap();
+        assert!(dingtalk.ends_with("%3D"));
+        assert!(!dingtalk.contains(['+', '/', '=']));
+        let expected = base64(&hmac_sha256(b"1700000000\nsecret", b"").unwrap());
+        assert_eq!(feishu_sign("secret", 1700000000).unwrap(), expected);
+    }
+}
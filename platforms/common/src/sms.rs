@@ -0,0 +1,400 @@
+use crate::{Message, PlatformInfo, PushError, SendHook, degrade};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+/// GSM 03.38 基本字符集
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+/// GSM 03.38 扩展字符，每个占两个单位
+const GSM7_EXTENDED: &str = "^{}\\[~]|€\u{c}";
+/// 短链接服务的请求超时
+const SHORTENER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 短信编码，决定每条短信能容纳的字符数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsEncoding {
+    /// 单条 160 个字符，长短信每条 153 个
+    Gsm7,
+    /// 出现 GSM-7 以外的字符（如中文、emoji）时使用，单条 70 个，长短信每条 67 个
+    Ucs2,
+}
+
+impl SmsEncoding {
+    /// 文本需要的编码
+    pub fn detect(text: &str) -> Self {
+        if text
+            .chars()
+            .all(|c| GSM7_BASIC.contains(c) || GSM7_EXTENDED.contains(c))
+        {
+            Self::Gsm7
+        } else {
+            Self::Ucs2
+        }
+    }
+
+    /// 文本按该编码占用的单位数：GSM-7 扩展字符占 2，UCS-2 按 UTF-16 码元计算
+    pub fn units(self, text: &str) -> usize {
+        match self {
+            Self::Gsm7 => text
+                .chars()
+                .map(|c| if GSM7_EXTENDED.contains(c) { 2 } else { 1 })
+                .sum(),
+            Self::Ucs2 => text.encode_utf16().count(),
+        }
+    }
+
+    /// 分为 `segments` 条时最多容纳的单位数
+    pub fn capacity(self, segments: usize) -> usize {
+        match (self, segments) {
+            (_, 0) => 0,
+            (Self::Gsm7, 1) => 160,
+            (Self::Ucs2, 1) => 70,
+            (Self::Gsm7, n) => 153 * n,
+            (Self::Ucs2, n) => 67 * n,
+        }
+    }
+}
+
+/// 文本作为短信发送时的编码和条数
+pub fn sms_segments(text: &str) -> (SmsEncoding, usize) {
+    let encoding = SmsEncoding::detect(text);
+    let units = encoding.units(text);
+    let segments = (1..).find(|&n| encoding.capacity(n) >= units).unwrap_or(1);
+    (encoding, segments)
+}
+
+/// 短信内容预算：控制条数，超出时截断，可选缩短链接
+///
+/// 配置在通道上，发送前按目标平台能力将消息降级为文本后处理
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmsConfig {
+    /// 最多拆成几条短信，超出时截断
+    pub max_segments: usize,
+    /// 截断时追加的标记，应只含 GSM-7 字符
+    pub ellipsis: String,
+    /// 将弯引号、破折号、省略号等替换为 GSM-7 字符，避免整条消息按 UCS-2 计费
+    pub normalize: bool,
+    /// 缩短链接的服务，未设置时不缩短
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortener: Option<UrlShortener>,
+}
+
+impl Default for SmsConfig {
+    fn default() -> Self {
+        Self {
+            max_segments: 1,
+            ellipsis: "...".to_string(),
+            normalize: true,
+            shortener: None,
+        }
+    }
+}
+
+/// 短链接服务，请求为 `POST {"url": "<长链接>"}`，从响应 JSON 中读取短链接
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UrlShortener {
+    /// 服务地址
+    pub endpoint: String,
+    /// 以 Bearer 方式携带的令牌
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// 只缩短不短于该长度的链接
+    #[serde(default = "default_min_length")]
+    pub min_length: usize,
+    /// 响应中短链接所在的字段
+    #[serde(default = "default_response_field")]
+    pub response_field: String,
+}
+
+fn default_min_length() -> usize {
+    30
+}
+
+fn default_response_field() -> String {
+    "short_url".to_string()
+}
+
+impl UrlShortener {
+    /// 请求短链接服务缩短一个链接
+    pub async fn shorten(&self, client: &reqwest::Client, url: &str) -> Result<String, PushError> {
+        let mut request = client
+            .post(crate::endpoint(&self.endpoint))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::json!({ "url": url }).to_string());
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+        let body: serde_json::Value = serde_json::from_slice(
+            &response
+                .bytes()
+                .await
+                .map_err(|e| PushError::NetworkError(e.to_string()))?,
+        )
+        .map_err(|e| PushError::PlatformError(e.to_string()))?;
+        body.get(&self.response_field)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                PushError::PlatformError(format!(
+                    "Shortener response has no '{}' field",
+                    self.response_field
+                ))
+            })
+    }
+}
+
+impl SmsConfig {
+    /// 规整字符并截断到预算内，不缩短链接
+    pub fn fit(&self, text: &str) -> String {
+        let text = if self.normalize {
+            normalize(text)
+        } else {
+            text.to_string()
+        };
+        truncate(&text, self.max_segments.max(1), &self.ellipsis)
+    }
+}
+
+/// 替换常见的非 GSM-7 标点
+fn normalize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '‘' | '’' | '‚' | '′' => out.push('\''),
+            '“' | '”' | '„' | '″' => out.push('"'),
+            '–' | '—' | '−' => out.push('-'),
+            '…' => out.push_str("..."),
+            '\u{a0}' | '\u{2009}' | '\u{202f}' => out.push(' '),
+            '•' => out.push('-'),
+            '\t' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 链接的字节范围
+fn urls(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = ["https://", "http://"]
+        .iter()
+        .filter_map(|scheme| text[offset..].find(scheme))
+        .min()
+    {
+        let start = offset + start;
+        let end = text[start..]
+            .find(char::is_whitespace)
+            .map_or(text.len(), |len| start + len);
+        ranges.push((start, end));
+        offset = end;
+    }
+    ranges
+}
+
+/// 截断到 `segments` 条以内：在词边界截断并追加省略标记，最后一个链接尽量完整保留
+fn truncate(text: &str, segments: usize, ellipsis: &str) -> String {
+    let encoding = SmsEncoding::detect(text);
+    let capacity = encoding.capacity(segments);
+    if encoding.units(text) <= capacity {
+        return text.to_string();
+    }
+    // 保留的链接放在省略标记之后，占用不超过一半的预算
+    let link = urls(text)
+        .last()
+        .map(|&(start, end)| (start, &text[start..end]))
+        .filter(|(_, url)| encoding.units(url) < capacity / 2);
+    let suffix = match link {
+        Some((_, url)) => format!("{} {}", ellipsis, url),
+        None => ellipsis.to_string(),
+    };
+    let body = match link {
+        Some((start, _)) => &text[..start],
+        None => text,
+    };
+    let budget = capacity.saturating_sub(encoding.units(&suffix));
+    let mut end = 0;
+    let mut used = 0;
+    for (i, c) in body.char_indices() {
+        let width = encoding.units(c.encode_utf8(&mut [0; 4]));
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        end = i + c.len_utf8();
+    }
+    let mut cut = &body[..end];
+    // 不截断到链接中间
+    if let Some(&(start, _)) = urls(body).iter().find(|&&(s, e)| s < end && end < e) {
+        cut = &body[..start];
+    }
+    // 在最后一个空白处截断，避免截断单词；中文等没有空白的文字按字符截断
+    if end < body.len()
+        && let Some(space) = cut.rfind(char::is_whitespace)
+        && space >= cut.len() * 2 / 3
+    {
+        cut = &cut[..space];
+    }
+    format!("{}{}", cut.trim_end(), suffix)
+}
+
+/// 将文本中足够长的链接替换为 `shorten` 返回的短链接，失败时保留原链接
+async fn shorten_urls<F, Fut>(text: &str, min_length: usize, shorten: F) -> String
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<String, PushError>>,
+{
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, end) in urls(text) {
+        let url = &text[start..end];
+        out.push_str(&text[last..start]);
+        if url.len() >= min_length
+            && let Ok(short) = shorten(url.to_string()).await
+        {
+            out.push_str(&short);
+        } else {
+            out.push_str(url);
+        }
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// 在发送前将消息按短信预算处理的拦截器
+pub(crate) struct SmsHook {
+    pub config: SmsConfig,
+    pub target: PlatformInfo,
+    pub client: reqwest::Client,
+}
+
+impl SmsHook {
+    pub fn new(config: SmsConfig, target: PlatformInfo) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(SHORTENER_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            target,
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl SendHook for SmsHook {
+    async fn before_send(&self, _platform: &str, message: &mut Message) -> Result<(), PushError> {
+        // 先按平台能力降级，按最终发出的文字计算长度
+        message.content = degrade(message.content.clone(), &self.target);
+        for text in message.content.texts_mut() {
+            if let Some(shortener) = &self.config.shortener {
+                let shorten =
+                    |url: String| async move { shortener.shorten(&self.client, &url).await };
+                *text = shorten_urls(text, shortener.min_length, shorten).await;
+            }
+            *text = self.config.fit(text);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(sms_segments(&"a".repeat(160)), (SmsEncoding::Gsm7, 1));
+        assert_eq!(sms_segments(&"a".repeat(161)), (SmsEncoding::Gsm7, 2));
+        // 扩展字符占两个单位
+        assert_eq!(sms_segments(&"€".repeat(80)), (SmsEncoding::Gsm7, 1));
+        assert_eq!(sms_segments(&"€".repeat(81)), (SmsEncoding::Gsm7, 2));
+        assert_eq!(sms_segments(&"告".repeat(70)), (SmsEncoding::Ucs2, 1));
+        assert_eq!(sms_segments(&"告".repeat(71)), (SmsEncoding::Ucs2, 2));
+        // emoji 占两个 UTF-16 码元
+        assert_eq!(sms_segments(&"🔥".repeat(34)), (SmsEncoding::Ucs2, 1));
+        assert_eq!(sms_segments(&"a".repeat(307)), (SmsEncoding::Gsm7, 3));
+    }
+
+    #[test]
+    fn test_fit() {
+        let config = SmsConfig::default();
+        // 弯引号替换后按 GSM-7 计算，不再截断到 70 个字符
+        let quoted = format!("“{}”", "a".repeat(100));
+        let fitted = config.fit(&quoted);
+        assert_eq!(fitted, format!("\"{}\"", "a".repeat(100)));
+
+        let text = format!(
+            "{} see https://status.example.com/incidents/42",
+            "disk usage is above the threshold on db-1 ".repeat(5)
+        );
+        let fitted = config.fit(&text);
+        assert_eq!(sms_segments(&fitted), (SmsEncoding::Gsm7, 1));
+        assert!(fitted.ends_with("... https://status.example.com/incidents/42"));
+        assert!(fitted.starts_with("disk usage is above the threshold on db-1 disk"));
+        // 在词边界截断
+        let body = fitted.split("...").next().unwrap();
+        assert!(text.contains(&format!("{} ", body)));
+
+        let chinese = "数据库主库不可用".repeat(20);
+        let fitted = config.fit(&chinese);
+        assert_eq!(SmsEncoding::Ucs2.units(&fitted), 70);
+        assert!(fitted.ends_with("..."));
+
+        let two = SmsConfig {
+            max_segments: 2,
+            ..Default::default()
+        };
+        assert_eq!(two.fit(&"a".repeat(300)).len(), 300);
+        assert_eq!(two.fit(&"a".repeat(400)).len(), 306);
+    }
+
+    #[tokio::test]
+    async fn test_channel_hook() {
+        use crate::testing::MockPlatform;
+        use crate::{ChannelConfig, MessageType, PushPlatformCapabilities};
+        use std::sync::Arc;
+
+        let channel: ChannelConfig = serde_json::from_value(
+            serde_json::json!({"platform": "mock", "config": {}, "sms": {}}),
+        )
+        .unwrap();
+        let mock = Arc::new(MockPlatform::new("sms"));
+        let platform = channel.decorate("pager", Box::new(mock.clone()));
+        let text = format!("DB down “primary” {}", "x ".repeat(100));
+        platform
+            .send_message(Message::new(MessageType::Text(text)))
+            .await
+            .unwrap();
+        let sent = mock.sent().remove(0);
+        assert!(sent.starts_with("DB down \"primary\" x x"));
+        assert!(sent.ends_with("x..."));
+        assert_eq!(sms_segments(&sent), (SmsEncoding::Gsm7, 1));
+    }
+
+    #[tokio::test]
+    async fn test_shorten_urls() {
+        let text = "see https://example.com/a/very/long/path/to/the/runbook and http://x.io";
+        let shortened = shorten_urls(text, 30, |url: String| async move {
+            Ok(format!("https://s.io/{}", url.len()))
+        })
+        .await;
+        assert_eq!(shortened, "see https://s.io/51 and http://x.io");
+        let failed = shorten_urls(text, 30, |_| async {
+            Err(PushError::NetworkError("down".to_string()))
+        })
+        .await;
+        assert_eq!(failed, text);
+    }
+}
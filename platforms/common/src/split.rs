@@ -0,0 +1,305 @@
+use crate::{
+    MENTION_FOLLOW_UP_FEATURE, Mention, Message, MessageKind, MessageType, PlatformInfo, PushError,
+    PushPlatformCapabilities, PushResult, chart_image, degrade,
+};
+use serde::{Deserialize, Serialize};
+
+/// 为分片编号和补全代码块预留的长度
+const PART_RESERVE: usize = 20;
+
+/// 长度计算单位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    /// 按 Unicode 字符计数
+    #[default]
+    Chars,
+    /// 按 UTF-8 字节计数
+    Bytes,
+}
+
+impl LengthUnit {
+    /// 按当前单位计算长度
+    pub fn measure(self, text: &str) -> usize {
+        match self {
+            Self::Chars => text.chars().count(),
+            Self::Bytes => text.len(),
+        }
+    }
+
+    fn width(self, c: char) -> usize {
+        match self {
+            Self::Chars => 1,
+            Self::Bytes => c.len_utf8(),
+        }
+    }
+}
+
+/// 平台单条消息的长度限制，`None` 表示不限制
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageLimits {
+    /// 纯文本最大长度
+    #[serde(default)]
+    pub text: Option<usize>,
+    /// Markdown 最大长度
+    #[serde(default)]
+    pub markdown: Option<usize>,
+    /// 计数单位
+    #[serde(default)]
+    pub unit: LengthUnit,
+    /// 单个附件最大字节数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment_bytes: Option<usize>,
+}
+
+/// 将超长的文本/Markdown 消息拆分为带编号的多条，其他类型原样返回
+pub fn split_message(message: MessageType, limits: &MessageLimits) -> Vec<MessageType> {
+    match message {
+        MessageType::Text(content) => match limits.text {
+            Some(max) => split_content(&content, max, limits.unit, false)
+                .into_iter()
+                .map(MessageType::Text)
+                .collect(),
+            None => vec![MessageType::Text(content)],
+        },
+        MessageType::Markdown(content) => match limits.markdown {
+            Some(max) => split_content(&content, max, limits.unit, true)
+                .into_iter()
+                .map(MessageType::Markdown)
+                .collect(),
+            None => vec![MessageType::Markdown(content)],
+        },
+        message => vec![message],
+    }
+}
+
+/// `send_message` 依次发送的一部分
+#[derive(Debug, Clone)]
+pub struct MessagePart {
+    /// 降级和拆分后的内容
+    pub content: MessageType,
+    /// 随这一部分发送的@提醒，只附在第一条正文上
+    pub mentions: Vec<Mention>,
+    /// 是否为正文之后补发的内容，如图表和@提醒
+    pub follow_up: bool,
+}
+
+/// 按平台能力把消息拆成依次发送的部分：降级消息类型，按长度限制拆分正文，
+/// 正文之后补发图表，平台不支持图片时改为文字摘要；
+/// 声明 [`MENTION_FOLLOW_UP_FEATURE`] 的平台@提醒只附在文本上，没有文本时最后补发
+pub fn message_parts(message: &Message, info: &PlatformInfo) -> Vec<MessagePart> {
+    let text_only = info.has_feature(MENTION_FOLLOW_UP_FEATURE);
+    let mut mentions = message.mentions.clone();
+    let mut parts: Vec<MessagePart> =
+        split_message(degrade(message.content.clone(), info), &info.limits)
+            .into_iter()
+            .map(|content| {
+                let mentionable = !text_only || matches!(content, MessageType::Text(_));
+                MessagePart {
+                    mentions: if mentionable {
+                        std::mem::take(&mut mentions)
+                    } else {
+                        vec![]
+                    },
+                    content,
+                    follow_up: false,
+                }
+            })
+            .collect();
+    if let Some(series) = message.chart.as_ref().filter(|_| !parts.is_empty()) {
+        let chart = chart_image(series)
+            .ok()
+            .filter(|_| info.supports(MessageKind::Image))
+            .unwrap_or_else(|| MessageType::Text(series.summary()));
+        parts.push(MessagePart {
+            content: chart,
+            mentions: vec![],
+            follow_up: true,
+        });
+    }
+    // 只剩平台无法提醒的对象（如邮箱）时不再补发
+    if !parts.is_empty() && mentions.iter().any(|m| info.mentions.supports(m)) {
+        parts.push(MessagePart {
+            content: MessageType::Text(String::new()),
+            mentions,
+            follow_up: true,
+        });
+    }
+    parts
+}
+
+/// 依次把 [`message_parts`] 拆出的各部分交给 `send_part`，返回第一条正文的结果
+pub async fn send_parts<P: PushPlatformCapabilities + ?Sized>(
+    platform: &P,
+    message: &Message,
+) -> Result<PushResult, PushError> {
+    let mut result = None;
+    for part in message_parts(message, &platform.platform_info()) {
+        let follow_up = part.follow_up;
+        let sent = platform.send_part(message, part).await?;
+        if !follow_up {
+            result.get_or_insert(sent);
+        }
+    }
+    result.ok_or_else(|| PushError::MessageError("Message is empty".to_string()))
+}
+
+/// 按行拆分内容，超长的行再按空白或字符边界拆分；
+/// Markdown 模式下被截断的代码块会在分片末尾闭合并在下一片重新打开
+pub fn split_content(content: &str, max: usize, unit: LengthUnit, markdown: bool) -> Vec<String> {
+    if unit.measure(content) <= max {
+        return vec![content.to_string()];
+    }
+    let budget = max.saturating_sub(PART_RESERVE).max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        for piece in split_long(line, budget, unit) {
+            if !current.trim().is_empty() && unit.measure(&current) + unit.measure(piece) > budget {
+                let mut chunk = current.trim_end().to_string();
+                current.clear();
+                if in_fence {
+                    chunk.push_str("\n```");
+                    current.push_str("```\n");
+                }
+                chunks.push(chunk);
+            }
+            current.push_str(piece);
+        }
+        if markdown && line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current.trim_end().to_string());
+    }
+
+    let total = chunks.len();
+    let separator = if markdown { "\n" } else { " " };
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| format!("({}/{}){}{}", i + 1, total, separator, chunk))
+        .collect()
+}
+
+/// 将超过预算的单行拆开，优先在空白处断开，且不会切断 UTF-8 字符
+fn split_long(line: &str, budget: usize, unit: LengthUnit) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = line;
+    while unit.measure(rest) > budget {
+        let mut size = 0;
+        let mut end = 0;
+        let mut last_space = None;
+        for (i, c) in rest.char_indices() {
+            size += unit.width(c);
+            if size > budget {
+                break;
+            }
+            end = i + c.len_utf8();
+            if c.is_whitespace() {
+                last_space = Some(end);
+            }
+        }
+        let mut cut = last_space.unwrap_or(end);
+        if cut == 0 {
+            cut = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        parts.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    if !rest.is_empty() {
+        parts.push(rest);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions_follow_up_non_text_body() {
+        let mut info = PlatformInfo {
+            name: "mock".to_string(),
+            version: "0".to_string(),
+            message_types: [MessageKind::Text, MessageKind::Markdown].into(),
+            mentions: crate::MentionSupport {
+                all: true,
+                ..Default::default()
+            },
+            features: vec![],
+            limits: MessageLimits::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        };
+        let mut message = Message::new(MessageType::Markdown("**down**".to_string()));
+        message.mentions = vec![Mention::All];
+        let parts = message_parts(&message, &info);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].mentions.len(), 1);
+
+        info.features = vec![MENTION_FOLLOW_UP_FEATURE.to_string()];
+        let parts = message_parts(&message, &info);
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].mentions.is_empty());
+        assert!(parts[1].follow_up);
+        assert!(matches!(&parts[1].content, MessageType::Text(text) if text.is_empty()));
+        assert_eq!(parts[1].mentions.len(), 1);
+
+        // 平台无法提醒的对象不补发
+        message.mentions = vec!["bob@example.com".into()];
+        assert_eq!(message_parts(&message, &info).len(), 1);
+    }
+
+    #[test]
+    fn test_short_content_is_untouched() {
+        assert_eq!(
+            split_content("hello", 160, LengthUnit::Chars, false),
+            vec!["hello"]
+        );
+        let limits = MessageLimits::default();
+        assert_eq!(
+            split_message(MessageType::Text("x".repeat(10_000)), &limits).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_split_respects_limit_and_utf8() {
+        let content = "告警".repeat(100);
+        let parts = split_content(&content, 64, LengthUnit::Bytes, false);
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|p| p.len() <= 64));
+        assert!(parts[0].starts_with(&format!("(1/{}) ", parts.len())));
+        let joined: String = parts.iter().map(|p| p.split_once(' ').unwrap().1).collect();
+        assert_eq!(joined, content);
+    }
+
+    #[test]
+    fn test_split_prefers_word_boundaries() {
+        let content = "alpha beta gamma delta ".repeat(10);
+        let parts = split_content(content.trim_end(), 40, LengthUnit::Chars, false);
+        for part in &parts {
+            assert!(part.chars().count() <= 40);
+            let body = part.split_once(' ').unwrap().1;
+            assert!(!body.ends_with("alph") && !body.starts_with("pha"));
+        }
+    }
+
+    #[test]
+    fn test_markdown_code_fence_is_reopened() {
+        let mut content = String::from("**logs**\n```\n");
+        for i in 0..20 {
+            content.push_str(&format!("line {}\n", i));
+        }
+        content.push_str("```");
+        let parts = split_content(&content, 80, LengthUnit::Bytes, true);
+        assert!(parts.len() > 1);
+        for part in &parts {
+            assert!(part.len() <= 80);
+            assert_eq!(part.matches("```").count() % 2, 0, "{}", part);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{MarkdownDialect, MessageKind, PlatformInfo, TABLE_FEATURE};
+
+/// 表格的降级方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+    /// 对齐后放进代码块，保留行列结构
+    CodeBlock,
+    /// 每行转为一段“列名: 值”，第一列作为段落标题
+    KeyValue,
+}
+
+impl TableStyle {
+    /// 目标平台适用的降级方式，平台声明了 [`TABLE_FEATURE`] 时不需要降级
+    pub fn for_platform(target: &PlatformInfo) -> Option<Self> {
+        if target.has_feature(TABLE_FEATURE) {
+            return None;
+        }
+        let code_blocks = target.supports(MessageKind::Markdown)
+            && !matches!(
+                target.markdown_dialect,
+                MarkdownDialect::WxWork | MarkdownDialect::PlainText
+            );
+        Some(if code_blocks {
+            Self::CodeBlock
+        } else {
+            Self::KeyValue
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+struct Table {
+    header: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+/// 将 Markdown 中的表格按 `style` 转换，代码块中的内容不处理
+pub fn degrade_tables(markdown: &str, style: TableStyle) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut in_fence = false;
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let table = (!in_fence).then(|| parse_table(&lines[i..])).flatten();
+        match table {
+            Some((table, consumed)) => {
+                out.push(match style {
+                    TableStyle::CodeBlock => render_code_block(&table),
+                    TableStyle::KeyValue => render_key_value(&table),
+                });
+                i += consumed;
+            }
+            None => {
+                out.push(lines[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join("\n")
+}
+
+/// 内容中是否包含表格
+pub fn has_table(markdown: &str) -> bool {
+    let lines: Vec<&str> = markdown.lines().collect();
+    (0..lines.len()).any(|i| parse_table(&lines[i..]).is_some())
+}
+
+/// 从第一行开始解析表格，返回表格和占用的行数
+fn parse_table(lines: &[&str]) -> Option<(Table, usize)> {
+    let header = split_row(lines.first()?)?;
+    let aligns = split_row(lines.get(1)?)?
+        .iter()
+        .map(|cell| parse_align(cell))
+        .collect::<Option<Vec<_>>>()?;
+    if aligns.len() != header.len() {
+        return None;
+    }
+    let mut rows = Vec::new();
+    for line in &lines[2..] {
+        match split_row(line) {
+            Some(mut cells) => {
+                cells.resize(header.len(), String::new());
+                rows.push(cells);
+            }
+            None => break,
+        }
+    }
+    let consumed = rows.len() + 2;
+    Some((
+        Table {
+            header,
+            aligns,
+            rows,
+        },
+        consumed,
+    ))
+}
+
+/// 拆分表格行，`\|` 视为单元格内的竖线
+fn split_row(line: &str) -> Option<Vec<String>> {
+    let line = line.trim();
+    if !line.contains('|') {
+        return None;
+    }
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => line,
+    };
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+    Some(
+        cells
+            .into_iter()
+            .map(|cell| cell.trim().to_string())
+            .collect(),
+    )
+}
+
+fn parse_align(cell: &str) -> Option<Align> {
+    let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+    if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+        return None;
+    }
+    Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => Align::Center,
+        (false, true) => Align::Right,
+        _ => Align::Left,
+    })
+}
+
+/// 等宽字体下的显示宽度，中日韩文字和全角符号占两列
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF => 2,
+            0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    let space = width.saturating_sub(display_width(text));
+    let (left, right) = match align {
+        Align::Left => (0, space),
+        Align::Right => (space, 0),
+        Align::Center => (space / 2, space - space / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+fn render_code_block(table: &Table) -> String {
+    let widths: Vec<usize> = (0..table.header.len())
+        .map(|col| {
+            std::iter::once(&table.header)
+                .chain(&table.rows)
+                .map(|row| display_width(&row[col]))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let render_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .zip(&table.aligns)
+            .map(|((cell, width), align)| pad(cell, *width, *align))
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut lines = vec!["```".to_string(), render_row(&table.header)];
+    lines.push(separator.join("  "));
+    lines.extend(table.rows.iter().map(|row| render_row(row)));
+    lines.push("```".to_string());
+    lines.join("\n")
+}
+
+fn render_key_value(table: &Table) -> String {
+    table
+        .rows
+        .iter()
+        .map(|row| {
+            let mut lines = vec![format!("**{}**", row[0])];
+            for (name, value) in table.header.iter().zip(row).skip(1) {
+                if !value.is_empty() {
+                    lines.push(format!("{}: {}", name, value));
+                }
+            }
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "Daily report\n\n| 服务 | Requests | p99 |\n|:---|---:|:-:|\n| api | 1200 | 35ms |\n| 网关 | 98 | 1s |\n\ndone";
+
+    #[test]
+    fn test_code_block() {
+        assert!(has_table(REPORT));
+        assert_eq!(
+            degrade_tables(REPORT, TableStyle::CodeBlock),
+            "Daily report\n\n```\n服务  Requests  p99\n----  --------  ----\napi       1200  35ms\n网关        98   1s\n```\n\ndone"
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            degrade_tables(REPORT, TableStyle::KeyValue),
+            "Daily report\n\n**api**\nRequests: 1200\np99: 35ms\n\n**网关**\nRequests: 98\np99: 1s\n\ndone"
+        );
+    }
+
+    #[test]
+    fn test_not_a_table() {
+        for text in [
+            "a | b",
+            "| a | b |\n| c | d |",
+            "```\n| a | b |\n|---|---|\n```",
+        ] {
+            assert_eq!(degrade_tables(text, TableStyle::KeyValue), text);
+        }
+        let escaped = "| cmd | note |\n|---|---|\n| `a \\| b` | pipe |";
+        assert_eq!(
+            degrade_tables(escaped, TableStyle::KeyValue),
+            "**`a | b`**\nnote: pipe"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{Message, MessageType, PushError, SendHook, TIMEZONE_KEY, match_locale};
+use async_trait::async_trait;
+use chrono::{FixedOffset, SecondsFormat, Utc};
+use jiff::Timestamp;
+use jiff::tz::{Offset, TimeZone};
+use minijinja::{Environment, ErrorKind, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// `tz` 过滤器未指定格式时使用的时间格式
+const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %Z";
+
+/// 使用 Jinja 语法渲染模板，支持 `{{ var }}`、`{% for %}` 循环和 `{% if %}` 条件
+///
+/// 模板中可以使用当前 UTC 时间 `now` 和 `tz` 过滤器，如 `{{ now | tz("Asia/Shanghai") }}`、
+/// `{{ ts | tz("+08:00", "%H:%M") }}`
+pub fn render_template(template: &str, context: &impl Serialize) -> Result<String, PushError> {
+    render_template_in(template, context, None)
+}
+
+/// 渲染模板，`tz` 过滤器未指定时区时使用 `timezone`，都未指定时使用 UTC
+pub fn render_template_in(
+    template: &str,
+    context: &impl Serialize,
+    timezone: Option<&str>,
+) -> Result<String, PushError> {
+    let default_timezone = timezone.unwrap_or("UTC").to_string();
+    let mut env = Environment::new();
+    env.add_global("now", Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true));
+    env.add_filter(
+        "tz",
+        move |value: Value, timezone: Option<String>, format: Option<String>| {
+            let timezone = timezone.as_deref().unwrap_or(&default_timezone);
+            let format = format.as_deref().unwrap_or(DEFAULT_TIME_FORMAT);
+            let timestamp = match value.as_i64() {
+                Some(secs) => Timestamp::from_second(secs).map_err(|e| e.to_string()),
+                None => value
+                    .as_str()
+                    .unwrap_or_default()
+                    .parse::<Timestamp>()
+                    .map_err(|e| e.to_string()),
+            }
+            .map_err(|e| {
+                minijinja::Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("invalid timestamp '{}': {}", value, e),
+                )
+            })?;
+            format_timestamp(timestamp, timezone, format)
+                .map_err(|e| minijinja::Error::new(ErrorKind::InvalidOperation, e.to_string()))
+        },
+    );
+    env.render_str(template, context)
+        .map_err(|e| PushError::MessageError(format!("Failed to render template: {}", e)))
+}
+
+/// 解析时区，支持 IANA 名称（如 `Asia/Shanghai`）和 `+08:00` 形式的固定偏移
+pub fn parse_timezone(name: &str) -> Result<TimeZone, PushError> {
+    if let Ok(offset) = name.parse::<FixedOffset>() {
+        let offset = Offset::from_seconds(offset.local_minus_utc())
+            .map_err(|e| PushError::ConfigError(e.to_string()))?;
+        return Ok(TimeZone::fixed(offset));
+    }
+    TimeZone::get(name).map_err(|_| PushError::ConfigError(format!("Unknown timezone '{}'", name)))
+}
+
+/// 按 strftime 格式输出时间戳在指定时区的本地时间
+pub fn format_timestamp(
+    timestamp: Timestamp,
+    timezone: &str,
+    format: &str,
+) -> Result<String, PushError> {
+    let zoned = timestamp.to_zoned(parse_timezone(timezone)?);
+    Ok(zoned.strftime(format).to_string())
+}
+
+/// 注册表级模板定义，用于不支持原生模板的平台
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateDefinition {
+    /// 标题模板，设置后渲染为富文本消息
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 内容模板
+    pub body: String,
+    /// 渲染结果是否为 Markdown
+    #[serde(default)]
+    pub markdown: bool,
+    /// 按语言区分的版本，键为语言标签，如 `zh-CN`、`ja`；消息语言匹配不到时使用默认版本
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub locales: HashMap<String, LocalizedTemplate>,
+}
+
+/// 模板的一个语言版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedTemplate {
+    /// 标题模板，未设置时使用默认版本的标题
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 内容模板
+    pub body: String,
+}
+
+impl TemplateDefinition {
+    /// 渲染模板，未提供的变量渲染为空
+    pub fn render(&self, variables: &HashMap<String, String>) -> Result<MessageType, PushError> {
+        self.render_in(variables, None)
+    }
+
+    /// 渲染模板，`tz` 过滤器未指定时区时使用 `timezone`
+    pub fn render_in(
+        &self,
+        variables: &HashMap<String, String>,
+        timezone: Option<&str>,
+    ) -> Result<MessageType, PushError> {
+        self.render_localized(variables, timezone, None)
+    }
+
+    /// 按 `locale` 选择语言版本渲染模板，匹配规则见 [`match_locale`]
+    pub fn render_localized(
+        &self,
+        variables: &HashMap<String, String>,
+        timezone: Option<&str>,
+        locale: Option<&str>,
+    ) -> Result<MessageType, PushError> {
+        let localized = locale
+            .and_then(|locale| match_locale(locale, self.locales.keys().map(String::as_str)))
+            .map(|locale| &self.locales[locale]);
+        let body = localized.map_or(&self.body, |localized| &localized.body);
+        let title = localized
+            .and_then(|localized| localized.title.as_ref())
+            .or(self.title.as_ref());
+        let content = render_template_in(body, variables, timezone)?;
+        Ok(match title {
+            Some(title) => MessageType::Rich {
+                title: render_template_in(title, variables, timezone)?,
+                content,
+                url: None,
+            },
+            None if self.markdown => MessageType::Markdown(content),
+            None => MessageType::Text(content),
+        })
+    }
+}
+
+/// 发送前将模板消息渲染为文本的拦截器
+pub struct TemplateRenderer {
+    templates: HashMap<String, TemplateDefinition>,
+}
+
+impl TemplateRenderer {
+    pub fn new(templates: HashMap<String, TemplateDefinition>) -> Self {
+        Self { templates }
+    }
+}
+
+#[async_trait]
+impl SendHook for TemplateRenderer {
+    async fn before_send(&self, platform: &str, message: &mut Message) -> Result<(), PushError> {
+        let MessageType::Template { name, variables } = &message.content else {
+            return Ok(());
+        };
+        let template = self.templates.get(name).ok_or_else(|| {
+            PushError::MessageError(format!(
+                "Template '{}' is not registered and {} has no native templates",
+                name, platform
+            ))
+        })?;
+        let timezone = message.metadata.get(TIMEZONE_KEY).map(String::as_str);
+        message.content = template.render_localized(variables, timezone, message.locale())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_renderer() {
+        let renderer = TemplateRenderer::new(HashMap::from([(
+            "deploy".to_string(),
+            TemplateDefinition {
+                title: None,
+                body: "**{{service}}** deployed {{version}}".to_string(),
+                markdown: true,
+                locales: HashMap::from([(
+                    "zh-CN".to_string(),
+                    LocalizedTemplate {
+                        title: None,
+                        body: "**{{service}}** 已发布 {{version}}".to_string(),
+                    },
+                )]),
+            },
+        )]));
+        let mut message = Message::new(MessageType::Template {
+            name: "deploy".to_string(),
+            variables: HashMap::from([
+                ("service".to_string(), "api".to_string()),
+                ("version".to_string(), "v2".to_string()),
+            ]),
+        });
+        renderer.before_send("mock", &mut message).await.unwrap();
+        assert!(matches!(
+            &message.content,
+            MessageType::Markdown(text) if text == "**api** deployed v2"
+        ));
+
+        let mut localized = Message::new(MessageType::Template {
+            name: "deploy".to_string(),
+            variables: HashMap::from([
+                ("service".to_string(), "api".to_string()),
+                ("version".to_string(), "v2".to_string()),
+            ]),
+        })
+        .with_locale("zh");
+        renderer.before_send("mock", &mut localized).await.unwrap();
+        assert!(matches!(
+            &localized.content,
+            MessageType::Markdown(text) if text == "**api** 已发布 v2"
+        ));
+
+        let mut unknown = Message::new(MessageType::Template {
+            name: "missing".to_string(),
+            variables: HashMap::new(),
+        });
+        assert!(renderer.before_send("mock", &mut unknown).await.is_err());
+    }
+
+    #[test]
+    fn test_title_and_conditionals() {
+        let definition = TemplateDefinition {
+            title: Some("{{ service }} alert".to_string()),
+            body: "{% if env == 'prod' %}[PROD] {% endif %}p99 is {{ latency }}".to_string(),
+            markdown: false,
+            locales: HashMap::new(),
+        };
+        let variables = HashMap::from([
+            ("service".to_string(), "api".to_string()),
+            ("env".to_string(), "prod".to_string()),
+            ("latency".to_string(), "1.2s".to_string()),
+        ]);
+        match definition.render(&variables).unwrap() {
+            MessageType::Rich { title, content, .. } => {
+                assert_eq!(title, "api alert");
+                assert_eq!(content, "[PROD] p99 is 1.2s");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(render_template("{% if %}", &variables).is_err());
+    }
+
+    #[test]
+    fn test_tz_filter() {
+        let context = HashMap::from([("ts", "2024-01-01T16:30:00Z")]);
+        assert_eq!(
+            render_template("{{ ts | tz('Asia/Shanghai') }}", &context).unwrap(),
+            "2024-01-02 00:30:00 CST"
+        );
+        assert_eq!(
+            render_template("{{ ts | tz('-05:00', '%H:%M') }}", &context).unwrap(),
+            "11:30"
+        );
+        assert_eq!(
+            render_template_in(
+                "{{ 1704126600 | tz(none, '%d %H:%M') }}",
+                &context,
+                Some("+08:00")
+            )
+            .unwrap(),
+            "02 00:30"
+        );
+        assert!(
+            render_template("{{ now | tz }}", &context)
+                .unwrap()
+                .ends_with(" UTC")
+        );
+        assert!(render_template("{{ ts | tz('Mars/Base') }}", &context).is_err());
+        assert!(render_template("{{ 'soon' | tz }}", &context).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{
+    AttachmentSource, DELETE_FEATURE, DeliveryStatus, EDIT_FEATURE, Mention, MentionSupport,
+    Message, MessageKind, MessageLimits, MessageType, PlatformInfo, PushError,
+    PushPlatformCapabilities, PushResult, RECEIPT_FEATURE, Receipt,
+};
+use async_trait::async_trait;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// 可配置失败次数与延迟的模拟平台，记录调用次数、最大并发与发送内容
+pub struct MockPlatform {
+    name: String,
+    calls: AtomicU32,
+    failures: u32,
+    fail_at: Option<u32>,
+    batch_limit: Option<usize>,
+    error: fn() -> PushError,
+    delay: Duration,
+    sent: Mutex<Vec<String>>,
+    active: AtomicUsize,
+    peak: AtomicUsize,
+    limits: MessageLimits,
+    features: Vec<String>,
+    receipts: Mutex<Vec<Receipt>>,
+}
+
+impl MockPlatform {
+    /// 总是成功的平台
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            calls: AtomicU32::new(0),
+            failures: 0,
+            fail_at: None,
+            batch_limit: None,
+            error: || PushError::NetworkError("mock failure".to_string()),
+            delay: Duration::ZERO,
+            sent: Mutex::new(Vec::new()),
+            active: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            limits: MessageLimits::default(),
+            features: vec![],
+            receipts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// 前 `failures` 次发送返回 `error`
+    pub fn failing(name: &str, failures: u32, error: fn() -> PushError) -> Self {
+        Self {
+            failures,
+            error,
+            ..Self::new(name)
+        }
+    }
+
+    /// 第 `call` 次（从 0 开始计数）发送返回错误
+    pub fn failing_at(mut self, call: u32) -> Self {
+        self.fail_at = Some(call);
+        self
+    }
+
+    /// 批量发送只返回前 `limit` 条消息的结果，模拟结果数与消息数不一致的平台
+    pub fn with_batch_limit(mut self, limit: usize) -> Self {
+        self.batch_limit = Some(limit);
+        self
+    }
+
+    /// 每次发送前等待 `delay`
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// 声明单条消息长度限制
+    pub fn with_limits(mut self, limits: MessageLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 声明额外支持的特性
+    pub fn with_feature(mut self, feature: &str) -> Self {
+        self.features.push(feature.to_string());
+        self
+    }
+
+    pub fn calls(&self) -> u32 {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    pub fn sent(&self) -> Vec<String> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// 声明了特性且消息由本平台发出时记录操作，否则返回错误
+    async fn modify(
+        &self,
+        feature: &str,
+        message_id: &str,
+        action: String,
+    ) -> Result<PushResult, PushError> {
+        if !self.features.iter().any(|f| f == feature) {
+            return Err(PushError::PlatformError(format!(
+                "{} is not supported",
+                feature
+            )));
+        }
+        if !message_id.starts_with(&format!("{}-", self.name)) {
+            return Err(PushError::PlatformError(format!(
+                "Message {} not found",
+                message_id
+            )));
+        }
+        let mut result = self.send_text(&action).await?;
+        result.message_id = Some(message_id.to_string());
+        Ok(result)
+    }
+
+    /// 观察到的最大并发发送数
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for MockPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        Ok(())
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        if call < self.failures || self.fail_at == Some(call) || content == "fail" {
+            return Err((self.error)());
+        }
+        self.sent.lock().unwrap().push(content.to_string());
+        let message_id = format!("{}-{}", self.name, call);
+        if self.features.iter().any(|f| f == RECEIPT_FEATURE) {
+            self.receipts
+                .lock()
+                .unwrap()
+                .push(Receipt::new(&message_id, DeliveryStatus::Delivered));
+        }
+        Ok(PushResult {
+            message_id: Some(message_id),
+            success: true,
+            response: Some(content.to_string()),
+            ..Default::default()
+        })
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        let mentions: Vec<String> = mentions.iter().map(Mention::to_string).collect();
+        self.send_text(&format!("{} {}", content, mentions.join(" ")))
+            .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.send_text(content).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        _url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(&format!("{}\n{}", title, content)).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        _caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(image_url).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        _description: &str,
+        url: &str,
+        _image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(&format!("{} {}", title, url)).await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        _mime: Option<&str>,
+        _source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(name).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        match message {
+            MessageType::Text(content) | MessageType::Html(content) => {
+                self.send_text(&content).await
+            }
+            MessageType::Markdown(content) => self.send_markdown(&content).await,
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => self.send_rich(&title, &content, url.as_deref()).await,
+            MessageType::Image { url, caption } => self.send_image(&url, caption.as_deref()).await,
+            MessageType::Link {
+                title,
+                description,
+                url,
+                image_url,
+            } => {
+                self.send_link(&title, &description, &url, image_url.as_deref())
+                    .await
+            }
+            MessageType::File { name, mime, source } => {
+                self.send_file(&name, mime.as_deref(), &source).await
+            }
+            MessageType::Card {
+                title,
+                sections,
+                buttons,
+            } => self.send_card(&title, &sections, &buttons).await,
+            MessageType::Template { name, variables } => {
+                self.send_template(&name, &variables).await
+            }
+            MessageType::Audio {
+                source,
+                caption,
+                duration_secs,
+            } => {
+                self.send_audio(&source, caption.as_deref(), duration_secs)
+                    .await
+            }
+            MessageType::Video {
+                source,
+                caption,
+                duration_secs,
+            } => {
+                self.send_video(&source, caption.as_deref(), duration_secs)
+                    .await
+            }
+            MessageType::Location { lat, lon, label } => {
+                self.send_location(lat, lon, label.as_deref()).await
+            }
+        }
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        let content = match message.content {
+            MessageType::Text(content) | MessageType::Markdown(content) => content,
+            _ => String::new(),
+        };
+        self.modify(
+            EDIT_FEATURE,
+            message_id,
+            format!("edit {}: {}", message_id, content),
+        )
+        .await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        self.modify(DELETE_FEATURE, message_id, format!("delete {}", message_id))
+            .await
+    }
+
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        let limit = self.batch_limit.unwrap_or(messages.len());
+        let mut results = Vec::new();
+        for message in messages.into_iter().take(limit) {
+            results.push(self.send_message(message).await);
+        }
+        results
+    }
+
+    /// 声明了回执特性时，每条发送成功的消息回报一次已送达
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        Ok(std::mem::take(&mut *self.receipts.lock().unwrap()))
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        Ok(true)
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        PlatformInfo {
+            name: self.name.clone(),
+            version: "0".to_string(),
+            message_types: [MessageKind::Text, MessageKind::Markdown].into(),
+            mentions: MentionSupport {
+                all: true,
+                user_id: true,
+                phone: true,
+                email: true,
+            },
+            features: self.features.clone(),
+            limits: self.limits.clone(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        }
+    }
+}
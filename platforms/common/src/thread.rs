@@ -0,0 +1,162 @@
+use crate::{
+    HookedPlatform, Message, PushError, PushPlatformCapabilities, PushResult, SendHook,
+    THREAD_FEATURE, THREAD_ID_KEY,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 会话键到平台原生会话 ID 的映射，同一 `thread_key` 的后续消息发到第一条消息所在的会话
+///
+/// 会话 ID 取第一条消息的 `message_id`，如 Slack 的 `ts`、Telegram 的 `message_id`；
+/// 按 `scope` 隔离，同一平台的不同群或频道互不影响
+pub struct ThreadMap {
+    ttl: Duration,
+    threads: Mutex<HashMap<(String, String), Thread>>,
+}
+
+struct Thread {
+    id: String,
+    last_used: Instant,
+}
+
+impl ThreadMap {
+    /// 创建映射，超过 `ttl` 没有新消息的会话被遗忘，之后的消息开启新会话
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            threads: Mutex::default(),
+        }
+    }
+
+    /// 查找会话 ID，并刷新其过期时间
+    pub fn get(&self, scope: &str, key: &str) -> Option<String> {
+        let mut threads = self.threads.lock().unwrap();
+        let now = Instant::now();
+        let entry = (scope.to_string(), key.to_string());
+        match threads.get_mut(&entry) {
+            Some(thread) if now.duration_since(thread.last_used) < self.ttl => {
+                thread.last_used = now;
+                Some(thread.id.clone())
+            }
+            Some(_) => {
+                threads.remove(&entry);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 记录会话 ID，同时清理已过期的会话
+    pub fn insert(&self, scope: &str, key: &str, id: impl Into<String>) {
+        let mut threads = self.threads.lock().unwrap();
+        let now = Instant::now();
+        threads.retain(|_, thread| now.duration_since(thread.last_used) < self.ttl);
+        threads.insert(
+            (scope.to_string(), key.to_string()),
+            Thread {
+                id: id.into(),
+                last_used: now,
+            },
+        );
+    }
+
+    /// 为支持会话的平台套上会话跟踪拦截器，平台不支持时原样返回
+    pub fn wrap(
+        self: &Arc<Self>,
+        scope: &str,
+        platform: Box<dyn PushPlatformCapabilities>,
+    ) -> Box<dyn PushPlatformCapabilities> {
+        let info = platform.platform_info();
+        if !info.has_feature(THREAD_FEATURE) {
+            return platform;
+        }
+        let tracker = ThreadTracker {
+            scope: scope.to_string(),
+            threads: self.clone(),
+        };
+        Box::new(HookedPlatform::new(platform, vec![Arc::new(tracker)]))
+    }
+}
+
+/// 发送前填入已有的会话 ID，会话的第一条消息发送成功后记录其 ID
+struct ThreadTracker {
+    scope: String,
+    threads: Arc<ThreadMap>,
+}
+
+#[async_trait]
+impl SendHook for ThreadTracker {
+    async fn before_send(&self, _platform: &str, message: &mut Message) -> Result<(), PushError> {
+        if let Some(key) = &message.thread_key
+            && !message.metadata.contains_key(THREAD_ID_KEY)
+            && let Some(id) = self.threads.get(&self.scope, key)
+        {
+            message.metadata.insert(THREAD_ID_KEY.to_string(), id);
+        }
+        Ok(())
+    }
+
+    async fn after_send(&self, _platform: &str, message: &Message, result: &PushResult) {
+        if let Some(key) = &message.thread_key
+            && !message.metadata.contains_key(THREAD_ID_KEY)
+            && let Some(id) = &result.message_id
+        {
+            self.threads.insert(&self.scope, key, id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MessageType;
+    use crate::testing::MockPlatform;
+
+    /// 记录实际发给平台的消息
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Message>>);
+
+    #[async_trait]
+    impl SendHook for Recorder {
+        async fn after_send(&self, _platform: &str, message: &Message, _result: &PushResult) {
+            self.0.lock().unwrap().push(message.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_up_in_thread() {
+        let threads = Arc::new(ThreadMap::new(Duration::from_secs(60)));
+        let recorder = Arc::new(Recorder::default());
+        let mock = MockPlatform::new("slack").with_feature(THREAD_FEATURE);
+        let platform = threads.wrap(
+            "ops",
+            Box::new(HookedPlatform::new(Box::new(mock), vec![recorder.clone()])),
+        );
+
+        let alert = |text: &str| {
+            Message::new(MessageType::Text(text.to_string())).with_thread_key("alert-42")
+        };
+        platform.send_message(alert("firing")).await.unwrap();
+        platform.send_message(alert("resolved")).await.unwrap();
+        platform
+            .send_message(Message::new(MessageType::Text("unrelated".to_string())))
+            .await
+            .unwrap();
+
+        let sent = recorder.0.lock().unwrap();
+        assert!(!sent[0].metadata.contains_key(THREAD_ID_KEY));
+        assert_eq!(sent[1].metadata[THREAD_ID_KEY], "slack-0");
+        assert!(!sent[2].metadata.contains_key(THREAD_ID_KEY));
+        assert_eq!(threads.get("ops", "alert-42").as_deref(), Some("slack-0"));
+        assert!(threads.get("dev", "alert-42").is_none());
+    }
+
+    #[test]
+    fn test_expiry() {
+        let threads = ThreadMap::new(Duration::ZERO);
+        threads.insert("ops", "k", "1");
+        assert!(threads.get("ops", "k").is_none());
+    }
+}
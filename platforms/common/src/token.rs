@@ -0,0 +1,313 @@
+use crate::PushError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 默认提前刷新的时间，令牌剩余有效期不足该值时刷新
+const DEFAULT_REFRESH_AHEAD: Duration = Duration::from_secs(300);
+
+/// 平台颁发的访问令牌，如企业微信应用、公众号的 `access_token`、飞书的 `tenant_access_token`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub value: String,
+    /// 过期时间
+    pub expires_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    /// 从现在起 `expires_in` 后过期的令牌，平台接口通常返回 `expires_in` 秒数
+    pub fn new(value: impl Into<String>, expires_in: Duration) -> Self {
+        Self {
+            value: value.into(),
+            expires_at: Utc::now()
+                + chrono::Duration::from_std(expires_in).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    /// 在 `now` 之后 `margin` 内是否仍然有效
+    pub fn valid_for(&self, now: DateTime<Utc>, margin: Duration) -> bool {
+        chrono::Duration::from_std(margin).is_ok_and(|margin| self.expires_at - margin > now)
+    }
+
+    /// 剩余有效期，已过期时为零
+    pub fn remaining(&self) -> Duration {
+        (self.expires_at - Utc::now()).to_std().unwrap_or_default()
+    }
+}
+
+/// 向平台申请新令牌，如调用 `gettoken` 接口
+///
+/// 返回 `Result<AccessToken, PushError>` 的异步闭包自动实现该 trait
+#[async_trait]
+pub trait TokenFetcher: Send + Sync {
+    async fn fetch(&self) -> Result<AccessToken, PushError>;
+}
+
+#[async_trait]
+impl<F, Fut> TokenFetcher for F
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<AccessToken, PushError>> + Send,
+{
+    async fn fetch(&self) -> Result<AccessToken, PushError> {
+        self().await
+    }
+}
+
+/// 令牌缓存，键通常包含平台和应用标识，如 `wxwork:{corp_id}:{agent_id}`
+#[async_trait]
+pub trait TokenCache: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<AccessToken>, PushError>;
+    async fn set(&self, key: &str, token: &AccessToken) -> Result<(), PushError>;
+    async fn remove(&self, key: &str) -> Result<(), PushError>;
+}
+
+/// 进程内令牌缓存
+#[derive(Default)]
+pub struct MemoryTokenCache {
+    tokens: Mutex<HashMap<String, AccessToken>>,
+}
+
+impl MemoryTokenCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenCache for MemoryTokenCache {
+    async fn get(&self, key: &str) -> Result<Option<AccessToken>, PushError> {
+        Ok(self.tokens.lock().unwrap().get(key).cloned())
+    }
+
+    async fn set(&self, key: &str, token: &AccessToken) -> Result<(), PushError> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), token.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), PushError> {
+        self.tokens.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// Redis 令牌缓存，多个副本共享同一个令牌，避免互相刷新导致旧令牌失效
+///
+/// 需要启用 `redis` 特性
+#[cfg(feature = "redis")]
+pub struct RedisTokenCache {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisTokenCache {
+    /// 连接 Redis，如 `redis://127.0.0.1:6379/0`，键名加上 `prefix` 前缀
+    pub async fn connect(url: &str, prefix: impl Into<String>) -> Result<Self, PushError> {
+        let connection = redis::Client::open(url)
+            .map_err(|e| PushError::ConfigError(format!("Invalid Redis URL: {}", e)))?
+            .get_connection_manager()
+            .await
+            .map_err(redis_error)?;
+        Ok(Self {
+            connection,
+            prefix: prefix.into(),
+        })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[cfg(feature = "redis")]
+fn redis_error(e: redis::RedisError) -> PushError {
+    PushError::NetworkError(format!("Redis error: {}", e))
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl TokenCache for RedisTokenCache {
+    async fn get(&self, key: &str) -> Result<Option<AccessToken>, PushError> {
+        let value: Option<String> =
+            redis::AsyncCommands::get(&mut self.connection.clone(), self.key(key))
+                .await
+                .map_err(redis_error)?;
+        // 格式不对的值视为没有缓存，刷新后覆盖
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()))
+    }
+
+    async fn set(&self, key: &str, token: &AccessToken) -> Result<(), PushError> {
+        let value = serde_json::to_string(token)
+            .map_err(|e| PushError::MessageError(format!("Failed to serialize token: {}", e)))?;
+        let ttl = token.remaining().as_secs().max(1);
+        redis::AsyncCommands::set_ex::<_, _, ()>(
+            &mut self.connection.clone(),
+            self.key(key),
+            value,
+            ttl,
+        )
+        .await
+        .map_err(redis_error)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), PushError> {
+        redis::AsyncCommands::del::<_, ()>(&mut self.connection.clone(), self.key(key))
+            .await
+            .map_err(redis_error)
+    }
+}
+
+/// 提供可用的访问令牌
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// 当前有效的令牌，必要时刷新
+    async fn token(&self) -> Result<String, PushError>;
+
+    /// 平台判定令牌失效时调用（如企业微信返回 42001），下次获取时重新申请
+    async fn invalidate(&self) -> Result<(), PushError>;
+}
+
+/// 带缓存的令牌提供者
+///
+/// 剩余有效期不足 `refresh_ahead` 时提前刷新，刷新失败但旧令牌未过期时继续使用旧令牌；
+/// 同一实例的并发请求只触发一次刷新，多副本之间的刷新需要平台允许新旧令牌并存
+pub struct CachedTokenProvider<F, C = MemoryTokenCache> {
+    key: String,
+    fetcher: F,
+    cache: C,
+    refresh_ahead: Duration,
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl<F: TokenFetcher> CachedTokenProvider<F> {
+    /// 使用进程内缓存
+    pub fn new(key: impl Into<String>, fetcher: F) -> Self {
+        Self::with_cache(key, fetcher, MemoryTokenCache::new())
+    }
+}
+
+impl<F: TokenFetcher, C: TokenCache> CachedTokenProvider<F, C> {
+    pub fn with_cache(key: impl Into<String>, fetcher: F, cache: C) -> Self {
+        Self {
+            key: key.into(),
+            fetcher,
+            cache,
+            refresh_ahead: DEFAULT_REFRESH_AHEAD,
+            refreshing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// 设置提前刷新的时间，默认 5 分钟
+    pub fn with_refresh_ahead(mut self, refresh_ahead: Duration) -> Self {
+        self.refresh_ahead = refresh_ahead;
+        self
+    }
+
+    async fn fresh(&self) -> Result<Option<AccessToken>, PushError> {
+        Ok(self
+            .cache
+            .get(&self.key)
+            .await?
+            .filter(|token| token.valid_for(Utc::now(), self.refresh_ahead)))
+    }
+}
+
+#[async_trait]
+impl<F: TokenFetcher, C: TokenCache> TokenProvider for CachedTokenProvider<F, C> {
+    async fn token(&self) -> Result<String, PushError> {
+        if let Some(token) = self.fresh().await? {
+            return Ok(token.value);
+        }
+        let _refreshing = self.refreshing.lock().await;
+        // 等锁期间其他请求可能已经刷新
+        let cached = self.cache.get(&self.key).await?;
+        if let Some(token) = cached
+            .as_ref()
+            .filter(|token| token.valid_for(Utc::now(), self.refresh_ahead))
+        {
+            return Ok(token.value.clone());
+        }
+        match self.fetcher.fetch().await {
+            Ok(token) => {
+                self.cache.set(&self.key, &token).await?;
+                Ok(token.value)
+            }
+            Err(e) => cached
+                .filter(|token| token.valid_for(Utc::now(), Duration::ZERO))
+                .map(|token| token.value)
+                .ok_or(e),
+        }
+    }
+
+    async fn invalidate(&self) -> Result<(), PushError> {
+        self.cache.remove(&self.key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting(
+        calls: Arc<AtomicUsize>,
+        expires_in: Duration,
+    ) -> impl Fn() -> futures::future::BoxFuture<'static, Result<AccessToken, PushError>> {
+        move || {
+            let calls = calls.clone();
+            Box::pin(async move {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                if n > 2 {
+                    return Err(PushError::NetworkError("gettoken failed".to_string()));
+                }
+                Ok(AccessToken::new(format!("token-{}", n), expires_in))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_and_invalidate() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = CachedTokenProvider::new(
+            "wxwork:corp",
+            counting(calls.clone(), Duration::from_secs(7200)),
+        );
+        let (a, b) = tokio::join!(provider.token(), provider.token());
+        assert_eq!(a.unwrap(), "token-1");
+        assert_eq!(b.unwrap(), "token-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        provider.invalidate().await.unwrap();
+        assert_eq!(provider.token().await.unwrap(), "token-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_ahead() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        // 有效期短于提前刷新时间，每次获取都会尝试刷新
+        let provider = CachedTokenProvider::new(
+            "feishu:app",
+            counting(calls.clone(), Duration::from_secs(60)),
+        )
+        .with_refresh_ahead(Duration::from_secs(120));
+        assert_eq!(provider.token().await.unwrap(), "token-1");
+        assert_eq!(provider.token().await.unwrap(), "token-2");
+        // 刷新失败时继续使用未过期的旧令牌
+        assert_eq!(provider.token().await.unwrap(), "token-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        provider.invalidate().await.unwrap();
+        assert!(provider.token().await.is_err());
+    }
+}
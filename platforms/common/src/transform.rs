@@ -0,0 +1,536 @@
+use crate::{
+    AttachmentSource, Message, MessageKind, MessageType, PlatformInfo, PushError, SendHook,
+    TableStyle, card_to_markdown, degrade_tables, has_table, html_to_markdown, split_message,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// 消息超出平台能力时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityMode {
+    /// 自动改用平台支持的表示，如链接转为带链接的 Markdown，超长内容拆分为多条
+    #[default]
+    Lenient,
+    /// 平台不原生支持消息类型或内容超出长度限制时返回错误，不做任何改写
+    Strict,
+}
+
+/// 按平台能力和长度限制确定实际发送的消息，宽松模式下可能拆分为多条
+pub fn negotiate(
+    message: MessageType,
+    target: &PlatformInfo,
+    mode: CapabilityMode,
+) -> Result<Vec<MessageType>, PushError> {
+    match mode {
+        CapabilityMode::Lenient => Ok(split_message(degrade(message, target), &target.limits)),
+        CapabilityMode::Strict => {
+            let degraded = degrade(message.clone(), target);
+            if degraded.kind() != message.kind() {
+                return Err(PushError::Unsupported {
+                    platform: target.name.clone(),
+                    message_type: message.kind(),
+                    suggestion: Some(degraded.kind()),
+                });
+            }
+            let parts = split_message(message.clone(), &target.limits).len();
+            if parts > 1 {
+                return Err(PushError::PayloadTooLarge(format!(
+                    "{} message exceeds the {} limit (lenient mode would split it into {} parts)",
+                    message.kind(),
+                    target.name,
+                    parts
+                )));
+            }
+            if let Some((len, max)) = attachment_len(&message)
+                .zip(target.limits.attachment_bytes)
+                .filter(|(len, max)| len > max)
+            {
+                return Err(PushError::PayloadTooLarge(format!(
+                    "{} attachment is {} bytes, {} accepts at most {}",
+                    message.kind(),
+                    len,
+                    target.name,
+                    max
+                )));
+            }
+            Ok(vec![message])
+        }
+    }
+}
+
+/// 附件消息中无需下载即可得知的内容大小
+fn attachment_len(message: &MessageType) -> Option<usize> {
+    match message {
+        MessageType::File { source, .. }
+        | MessageType::Audio { source, .. }
+        | MessageType::Video { source, .. } => source.known_len(),
+        _ => None,
+    }
+}
+
+/// 消息转换阶段，发送前根据目标平台调整消息
+pub trait MessageTransformer: Send + Sync {
+    fn transform(&self, message: Message, target: &PlatformInfo) -> Message;
+}
+
+/// 按 `PlatformInfo` 声明的能力逐级降级消息：图片/链接→富文本→Markdown→纯文本
+pub struct CapabilityDegrader;
+
+impl MessageTransformer for CapabilityDegrader {
+    fn transform(&self, mut message: Message, target: &PlatformInfo) -> Message {
+        message.content = degrade(message.content, target);
+        message
+    }
+}
+
+/// 按顺序执行的转换流水线
+#[derive(Default)]
+pub struct TransformPipeline {
+    stages: Vec<Box<dyn MessageTransformer>>,
+}
+
+impl TransformPipeline {
+    /// 创建空流水线
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加转换阶段
+    pub fn with_stage(mut self, stage: impl MessageTransformer + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// 依次执行所有阶段
+    pub fn apply(&self, message: Message, target: &PlatformInfo) -> Message {
+        self.stages
+            .iter()
+            .fold(message, |message, stage| stage.transform(message, target))
+    }
+}
+
+/// 在发送前对消息执行转换的拦截器，目标平台能力在套上时确定
+pub(crate) struct TransformHook<T> {
+    pub stage: T,
+    pub target: PlatformInfo,
+}
+
+#[async_trait]
+impl<T: MessageTransformer> SendHook for TransformHook<T> {
+    async fn before_send(&self, _platform: &str, message: &mut Message) -> Result<(), PushError> {
+        *message = self.stage.transform(message.clone(), &self.target);
+        Ok(())
+    }
+}
+
+/// 严格模式下在发送前检查消息是否无需改写即可发送的拦截器
+pub(crate) struct CapabilityCheck {
+    pub target: PlatformInfo,
+}
+
+#[async_trait]
+impl SendHook for CapabilityCheck {
+    async fn before_send(&self, _platform: &str, message: &mut Message) -> Result<(), PushError> {
+        if let Some(mention) = message
+            .mentions
+            .iter()
+            .find(|m| !self.target.mentions.supports(m))
+        {
+            return Err(PushError::MessageError(format!(
+                "{} cannot deliver mention {}",
+                self.target.name, mention
+            )));
+        }
+        negotiate(
+            message.content.clone(),
+            &self.target,
+            CapabilityMode::Strict,
+        )
+        .map(|_| ())
+    }
+}
+
+/// 将不支持的音视频降级为文件（本地内容）或链接（远程地址）
+fn degrade_media(
+    kind: &str,
+    source: AttachmentSource,
+    caption: Option<String>,
+    target: &PlatformInfo,
+) -> MessageType {
+    let title = caption.unwrap_or_else(|| kind.to_string());
+    let message = match source {
+        AttachmentSource::Url(url) => MessageType::Link {
+            title,
+            description: String::new(),
+            url,
+            image_url: None,
+        },
+        source => MessageType::File {
+            name: match &source {
+                AttachmentSource::Path(path) => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or(title),
+                _ => title,
+            },
+            mime: None,
+            source,
+        },
+    };
+    degrade(message, target)
+}
+
+/// 位置对应的 OpenStreetMap 地图链接
+pub fn map_url(lat: f64, lon: f64) -> String {
+    format!("https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map=16/{lat}/{lon}")
+}
+
+/// 将消息降级为目标平台支持的类型，Markdown 表格按平台能力转为代码块或键值列表
+pub fn degrade(message: MessageType, target: &PlatformInfo) -> MessageType {
+    match message {
+        MessageType::Html(html) if !target.supports(MessageKind::Html) => {
+            degrade(MessageType::Markdown(html_to_markdown(&html)), target)
+        }
+        MessageType::Location { lat, lon, label } if !target.supports(MessageKind::Location) => {
+            degrade(
+                MessageType::Rich {
+                    title: label.unwrap_or_else(|| "Location".to_string()),
+                    content: format!("{}, {}", lat, lon),
+                    url: Some(map_url(lat, lon)),
+                },
+                target,
+            )
+        }
+        MessageType::Audio {
+            source, caption, ..
+        } if !target.supports(MessageKind::Audio) => {
+            degrade_media("Audio", source, caption, target)
+        }
+        MessageType::Video {
+            source, caption, ..
+        } if !target.supports(MessageKind::Video) => {
+            degrade_media("Video", source, caption, target)
+        }
+        // 只有远程文件可以降级为链接
+        MessageType::File {
+            name,
+            source: AttachmentSource::Url(url),
+            ..
+        } if !target.supports(MessageKind::File) => degrade(
+            MessageType::Link {
+                title: name,
+                description: String::new(),
+                url,
+                image_url: None,
+            },
+            target,
+        ),
+        MessageType::Image { url, caption } if !target.supports(MessageKind::Image) => {
+            let title = caption.unwrap_or_else(|| "Image".to_string());
+            degrade(
+                MessageType::Link {
+                    title,
+                    description: String::new(),
+                    url,
+                    image_url: None,
+                },
+                target,
+            )
+        }
+        MessageType::Link {
+            title,
+            description,
+            url,
+            ..
+        } if !target.supports(MessageKind::Link) => degrade(
+            MessageType::Rich {
+                title,
+                content: description,
+                url: Some(url),
+            },
+            target,
+        ),
+        MessageType::Card {
+            title,
+            sections,
+            buttons,
+        } if !target.supports(MessageKind::Card) => degrade(
+            MessageType::Markdown(card_to_markdown(&title, &sections, &buttons)),
+            target,
+        ),
+        MessageType::Rich {
+            title,
+            content,
+            url,
+        } if !target.supports(MessageKind::Rich) => {
+            let mut lines = vec![format!("**{}**", title)];
+            if !content.is_empty() {
+                lines.push(content);
+            }
+            if let Some(url) = url {
+                lines.push(format!("[{}]({})", url, url));
+            }
+            degrade(MessageType::Markdown(lines.join("\n")), target)
+        }
+        MessageType::Markdown(content) if has_table(&content) => {
+            match TableStyle::for_platform(target) {
+                Some(style) => degrade(
+                    MessageType::Markdown(degrade_tables(&content, style)),
+                    target,
+                ),
+                None => MessageType::Markdown(content),
+            }
+        }
+        MessageType::Markdown(content) if !target.supports(MessageKind::Markdown) => {
+            MessageType::Text(strip_markdown(&content))
+        }
+        message => message,
+    }
+}
+
+/// 去除常见 Markdown 标记，链接转为 `文字 (地址)`
+pub fn strip_markdown(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            let line = line.trim_start_matches(['#', '>']).trim_start();
+            strip_inline(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn strip_inline(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let link = after.find("](").and_then(|mid| {
+            let end = after[mid + 2..].find(')')? + mid + 2;
+            Some((&after[..mid], &after[mid + 2..end], end))
+        });
+        match link {
+            Some((label, url, end)) => {
+                if label == url {
+                    text.push_str(url);
+                } else {
+                    text.push_str(&format!("{} ({})", label, url));
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('[');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text.replace("**", "").replace("__", "").replace('`', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(markdown: bool, rich: bool, images: bool) -> PlatformInfo {
+        PlatformInfo {
+            name: "target".to_string(),
+            version: "0".to_string(),
+            message_types: [
+                (true, MessageKind::Text),
+                (markdown, MessageKind::Markdown),
+                (rich, MessageKind::Rich),
+                (images, MessageKind::Image),
+            ]
+            .into_iter()
+            .filter_map(|(supported, kind)| supported.then_some(kind))
+            .collect(),
+            mentions: Default::default(),
+            features: vec![],
+            limits: Default::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_strip_markdown() {
+        assert_eq!(
+            strip_markdown("# Deploy **done**\n> see [logs](http://ci/1) and `main`"),
+            "Deploy done\nsee logs (http://ci/1) and main"
+        );
+        assert_eq!(strip_markdown("a [b] c"), "a [b] c");
+    }
+
+    #[test]
+    fn test_rich_degrades_to_markdown_then_text() {
+        let rich = MessageType::Rich {
+            title: "Build".to_string(),
+            content: "passed".to_string(),
+            url: Some("http://ci/1".to_string()),
+        };
+        match degrade(rich.clone(), &info(true, false, false)) {
+            MessageType::Markdown(content) => {
+                assert_eq!(content, "**Build**\npassed\n[http://ci/1](http://ci/1)")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match degrade(rich, &info(false, false, false)) {
+            MessageType::Text(content) => assert_eq!(content, "Build\npassed\nhttp://ci/1"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tables_degrade_by_capability() {
+        let table = "| host | load |\n|---|---|\n| db1 | 0.9 |".to_string();
+        let degraded =
+            |target: &PlatformInfo| match degrade(MessageType::Markdown(table.clone()), target) {
+                MessageType::Markdown(text) | MessageType::Text(text) => text,
+                other => panic!("unexpected {:?}", other),
+            };
+        let mut target = info(true, false, false);
+        assert!(degraded(&target).starts_with("```\nhost  load\n"));
+        target.markdown_dialect = crate::MarkdownDialect::WxWork;
+        assert_eq!(degraded(&target), "**db1**\nload: 0.9");
+        assert_eq!(degraded(&info(false, false, false)), "db1\nload: 0.9");
+        target.features.push(crate::TABLE_FEATURE.to_string());
+        assert_eq!(degraded(&target), table);
+    }
+
+    #[test]
+    fn test_media_degrades_to_file_or_link() {
+        let mut target = info(true, false, false);
+        target.message_types.insert(MessageKind::File);
+        let audio = MessageType::Audio {
+            source: AttachmentSource::Path("/tmp/alert.mp3".into()),
+            caption: None,
+            duration_secs: Some(3),
+        };
+        assert!(matches!(
+            degrade(audio, &target),
+            MessageType::File { name, .. } if name == "alert.mp3"
+        ));
+        let video = MessageType::Video {
+            source: AttachmentSource::Url("http://cdn/clip.mp4".to_string()),
+            caption: Some("clip".to_string()),
+            duration_secs: None,
+        };
+        assert!(matches!(
+            degrade(video, &target),
+            MessageType::Markdown(text) if text == "**clip**\n[http://cdn/clip.mp4](http://cdn/clip.mp4)"
+        ));
+    }
+
+    #[test]
+    fn test_location_degrades_to_map_link() {
+        let location = MessageType::Location {
+            lat: 31.23,
+            lon: 121.47,
+            label: Some("Truck 7".to_string()),
+        };
+        match degrade(location, &info(false, true, false)) {
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => {
+                assert_eq!(title, "Truck 7");
+                assert_eq!(content, "31.23, 121.47");
+                assert_eq!(
+                    url.as_deref(),
+                    Some(
+                        "https://www.openstreetmap.org/?mlat=31.23&mlon=121.47#map=16/31.23/121.47"
+                    )
+                );
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_image_and_supported_types_pass_through() {
+        let image = MessageType::Image {
+            url: "http://img/1.png".to_string(),
+            caption: Some("chart".to_string()),
+        };
+        match degrade(image.clone(), &info(false, false, false)) {
+            MessageType::Text(content) => assert_eq!(content, "chart\nhttp://img/1.png"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            degrade(image, &info(false, false, true)),
+            MessageType::Image { .. }
+        ));
+
+        let html = MessageType::Html("<p>Job <b>api</b> failed</p>".to_string());
+        assert!(matches!(
+            degrade(html.clone(), &info(true, false, false)),
+            MessageType::Markdown(text) if text == "Job **api** failed"
+        ));
+        assert!(matches!(
+            degrade(html, &info(false, false, false)),
+            MessageType::Text(text) if text == "Job api failed"
+        ));
+
+        let pipeline = TransformPipeline::new().with_stage(CapabilityDegrader);
+        let message = pipeline.apply(
+            MessageType::Markdown("**x**".to_string()).into(),
+            &info(true, false, false),
+        );
+        assert!(matches!(message.content, MessageType::Markdown(_)));
+    }
+
+    #[test]
+    fn test_negotiate_modes() {
+        let target = info(true, false, false);
+        let link = MessageType::Link {
+            title: "Release".to_string(),
+            description: "v1.2".to_string(),
+            url: "http://ci/1".to_string(),
+            image_url: None,
+        };
+        let parts = negotiate(link.clone(), &target, CapabilityMode::Lenient).unwrap();
+        assert!(matches!(parts.as_slice(), [MessageType::Markdown(_)]));
+
+        let err = negotiate(link, &target, CapabilityMode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported: target does not support Link messages, send it as Markdown instead"
+        );
+
+        let text = MessageType::Text("ok".to_string());
+        let parts = negotiate(text, &target, CapabilityMode::Strict).unwrap();
+        assert!(matches!(parts.as_slice(), [MessageType::Text(t)] if t == "ok"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_checks_attachments_and_mentions() {
+        let mut target = info(true, false, false);
+        target.message_types.insert(MessageKind::File);
+        target.limits.attachment_bytes = Some(4);
+        let file = MessageType::File {
+            name: "dump.bin".to_string(),
+            mime: None,
+            source: AttachmentSource::Bytes(vec![0; 8]),
+        };
+        assert!(matches!(
+            negotiate(file.clone(), &target, CapabilityMode::Strict),
+            Err(PushError::PayloadTooLarge(_))
+        ));
+        assert!(negotiate(file, &target, CapabilityMode::Lenient).is_ok());
+
+        target.mentions.user_id = true;
+        let check = CapabilityCheck { target };
+        let mut message = Message::new(MessageType::Text("disk full".to_string()));
+        message.mentions = vec![crate::Mention::from("alice")];
+        assert!(check.before_send("target", &mut message).await.is_ok());
+        message.mentions.push(crate::Mention::All);
+        assert!(matches!(
+            check.before_send("target", &mut message).await,
+            Err(PushError::MessageError(e)) if e == "target cannot deliver mention @all"
+        ));
+    }
+}
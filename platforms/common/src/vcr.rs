@@ -0,0 +1,464 @@
+//! 平台 HTTP 请求的录制与回放，让平台适配器的集成测试不依赖真实凭据
+//!
+//! 平台发请求前用 [`endpoint`] 包装地址；测试中在 [`Recorder::scope`] 内调用平台时，
+//! 请求改写到本地的录制服务：回放模式按方法和地址返回夹具中的响应，录制模式
+//! （环境变量 `MULTI_PUSH_VCR=record`）转发到真实地址并保存到夹具。
+//! 录制和回放需要 `vcr` 特性，未启用时 [`endpoint`] 原样返回地址
+
+/// 平台请求的地址，在录制/回放作用域内改写到本地的录制服务
+pub fn endpoint(url: &str) -> String {
+    #[cfg(feature = "vcr")]
+    if let Ok(Some(base)) = recorder::BASE.try_with(Clone::clone)
+        && let Some((scheme, rest)) = url.split_once("://")
+    {
+        return format!("{}/{}/{}", base, scheme, rest);
+    }
+    url.to_string()
+}
+
+/// 在作用域内把平台请求改写到 `base`，如本地的模拟上游
+#[cfg(feature = "vcr")]
+pub async fn redirect<F: std::future::Future>(base: impl Into<String>, future: F) -> F::Output {
+    recorder::BASE
+        .scope(Some(base.into().trim_end_matches('/').to_string()), future)
+        .await
+}
+
+#[cfg(feature = "vcr")]
+pub use recorder::{Cassette, Interaction, Mode, RecordedRequest, RecordedResponse, Recorder};
+
+#[cfg(feature = "vcr")]
+mod recorder {
+    use crate::PushError;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use std::future::Future;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+
+    /// 选择模式的环境变量，值为 `record` 时录制，否则回放
+    const MODE_ENV: &str = "MULTI_PUSH_VCR";
+
+    tokio::task_local! {
+        pub(super) static BASE: Option<String>;
+    }
+
+    /// 录制或回放
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Mode {
+        Record,
+        Replay,
+    }
+
+    impl Mode {
+        /// 按环境变量 `MULTI_PUSH_VCR` 选择
+        pub fn from_env() -> Self {
+            match std::env::var(MODE_ENV).as_deref() {
+                Ok("record") => Self::Record,
+                _ => Self::Replay,
+            }
+        }
+    }
+
+    /// 录制的请求，回放时按方法和地址匹配
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct RecordedRequest {
+        pub method: String,
+        pub url: String,
+        /// 请求体，只用于查看，不参与匹配
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pub body: String,
+    }
+
+    /// 录制的响应
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct RecordedResponse {
+        pub status: u16,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub headers: BTreeMap<String, String>,
+        pub body: String,
+        /// 响应体不是 UTF-8 时以 base64 保存
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        pub base64: bool,
+    }
+
+    impl RecordedResponse {
+        fn bytes(&self) -> Result<Vec<u8>, PushError> {
+            if self.base64 {
+                STANDARD
+                    .decode(&self.body)
+                    .map_err(|e| PushError::ConfigError(format!("Invalid cassette body: {}", e)))
+            } else {
+                Ok(self.body.clone().into_bytes())
+            }
+        }
+    }
+
+    /// 一次请求和响应
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Interaction {
+        pub request: RecordedRequest,
+        pub response: RecordedResponse,
+    }
+
+    /// 夹具文件，JSON 格式
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct Cassette {
+        pub interactions: Vec<Interaction>,
+    }
+
+    impl Cassette {
+        pub fn load(path: &Path) -> Result<Self, PushError> {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                PushError::ConfigError(format!("Failed to read cassette {}: {}", path.display(), e))
+            })?;
+            serde_json::from_str(&content).map_err(|e| {
+                PushError::ConfigError(format!("Invalid cassette {}: {}", path.display(), e))
+            })
+        }
+
+        pub fn save(&self, path: &Path) -> Result<(), PushError> {
+            let content = serde_json::to_string_pretty(self)
+                .map_err(|e| PushError::ConfigError(e.to_string()))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    PushError::ConfigError(format!("Failed to create {}: {}", parent.display(), e))
+                })?;
+            }
+            std::fs::write(path, content + "\n").map_err(|e| {
+                PushError::ConfigError(format!(
+                    "Failed to write cassette {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+        }
+    }
+
+    struct State {
+        mode: Mode,
+        cassette: Cassette,
+        /// 回放时已使用的记录
+        used: Vec<bool>,
+        /// 录制前替换的敏感值和占位符，回放时对收到的请求做同样的替换
+        redactions: Vec<(String, String)>,
+        http_client: reqwest::Client,
+    }
+
+    impl State {
+        fn redact(&self, value: &str) -> String {
+            self.redactions
+                .iter()
+                .fold(value.to_string(), |value, (secret, placeholder)| {
+                    value.replace(secret, placeholder)
+                })
+        }
+    }
+
+    /// 本地录制服务，在 [`scope`](Self::scope) 内通过 [`endpoint`](super::endpoint)
+    /// 发出的请求都经过它
+    pub struct Recorder {
+        path: PathBuf,
+        base: String,
+        state: Arc<Mutex<State>>,
+        server: JoinHandle<()>,
+    }
+
+    impl Recorder {
+        /// 按环境变量选择模式启动，回放模式下夹具必须存在
+        pub async fn start(path: impl Into<PathBuf>) -> Result<Self, PushError> {
+            Self::with_mode(path, Mode::from_env()).await
+        }
+
+        pub async fn with_mode(path: impl Into<PathBuf>, mode: Mode) -> Result<Self, PushError> {
+            let path = path.into();
+            let cassette = match mode {
+                Mode::Replay => Cassette::load(&path)?,
+                Mode::Record => Cassette::default(),
+            };
+            let listener = TcpListener::bind("127.0.0.1:0")
+                .await
+                .map_err(|e| PushError::NetworkError(e.to_string()))?;
+            let addr = listener
+                .local_addr()
+                .map_err(|e| PushError::NetworkError(e.to_string()))?;
+            let state = Arc::new(Mutex::new(State {
+                mode,
+                used: vec![false; cassette.interactions.len()],
+                cassette,
+                redactions: Vec::new(),
+                http_client: reqwest::Client::new(),
+            }));
+            let server = tokio::spawn(serve(listener, state.clone()));
+            Ok(Self {
+                path,
+                base: format!("http://{}", addr),
+                state,
+                server,
+            })
+        }
+
+        /// 录制时把 `secret` 替换为 `placeholder`，如 webhook 地址中的 key
+        pub fn redact(self, secret: impl Into<String>, placeholder: impl Into<String>) -> Self {
+            let secret = secret.into();
+            if !secret.is_empty() {
+                self.state
+                    .lock()
+                    .unwrap()
+                    .redactions
+                    .push((secret, placeholder.into()));
+            }
+            self
+        }
+
+        pub fn mode(&self) -> Mode {
+            self.state.lock().unwrap().mode
+        }
+
+        /// 在作用域内执行 `future`，其中的平台请求经过录制服务
+        pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+            super::redirect(self.base.clone(), future).await
+        }
+
+        /// 停止服务，录制模式下保存夹具
+        pub fn finish(self) -> Result<Cassette, PushError> {
+            self.server.abort();
+            let state = self.state.lock().unwrap();
+            if state.mode == Mode::Record {
+                state.cassette.save(&self.path)?;
+            }
+            Ok(state.cassette.clone())
+        }
+    }
+
+    async fn serve(listener: TcpListener, state: Arc<Mutex<State>>) {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle(stream, state.clone()));
+        }
+    }
+
+    /// 处理一个连接上的一个请求，响应后关闭连接
+    async fn handle(stream: TcpStream, state: Arc<Mutex<State>>) {
+        let mut stream = BufReader::new(stream);
+        let (status, headers, body) = match read_request(&mut stream).await {
+            Ok((method, url, content_type, body)) => {
+                match respond(&state, method, url, content_type, body).await {
+                    Ok(response) => match response.bytes() {
+                        Ok(body) => (response.status, response.headers, body),
+                        Err(e) => error_response(e),
+                    },
+                    Err(e) => error_response(e),
+                }
+            }
+            Err(e) => error_response(e),
+        };
+        let reason = reqwest::StatusCode::from_u16(status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("");
+        let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason);
+        for (name, value) in &headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "content-length: {}\r\nconnection: close\r\n\r\n",
+            body.len()
+        ));
+        let stream = stream.get_mut();
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(&body).await;
+        let _ = stream.shutdown().await;
+    }
+
+    /// 录制服务自身的错误以 599 返回，平台会按网络错误处理
+    fn error_response(e: PushError) -> (u16, BTreeMap<String, String>, Vec<u8>) {
+        (599, BTreeMap::new(), e.to_string().into_bytes())
+    }
+
+    /// 读取请求，返回方法、原始地址、Content-Type 和请求体
+    async fn read_request(
+        stream: &mut BufReader<TcpStream>,
+    ) -> Result<(String, String, Option<String>, Vec<u8>), PushError> {
+        let io_error = |e: std::io::Error| PushError::NetworkError(e.to_string());
+        let mut line = String::new();
+        stream.read_line(&mut line).await.map_err(io_error)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(PushError::NetworkError(
+                "Malformed request line".to_string(),
+            ));
+        };
+        let method = method.to_string();
+        // `/https/host/path?query` 还原为 `https://host/path?query`
+        let url = target
+            .trim_start_matches('/')
+            .split_once('/')
+            .map(|(scheme, rest)| format!("{}://{}", scheme, rest))
+            .ok_or_else(|| PushError::NetworkError(format!("Unexpected target {}", target)))?;
+
+        let mut content_length = 0;
+        let mut content_type = None;
+        loop {
+            line.clear();
+            stream.read_line(&mut line).await.map_err(io_error)?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                let value = value.trim();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => content_length = value.parse().unwrap_or(0),
+                    "content-type" => content_type = Some(value.to_string()),
+                    "transfer-encoding" => {
+                        return Err(PushError::NetworkError(
+                            "Chunked requests are not supported".to_string(),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        stream.read_exact(&mut body).await.map_err(io_error)?;
+        Ok((method, url, content_type, body))
+    }
+
+    async fn respond(
+        state: &Mutex<State>,
+        method: String,
+        url: String,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    ) -> Result<RecordedResponse, PushError> {
+        let (mode, http_client) = {
+            let state = state.lock().unwrap();
+            (state.mode, state.http_client.clone())
+        };
+        match mode {
+            Mode::Replay => {
+                let mut state = state.lock().unwrap();
+                let url = state.redact(&url);
+                let State { cassette, used, .. } = &mut *state;
+                let index = cassette
+                    .interactions
+                    .iter()
+                    .enumerate()
+                    .position(|(i, interaction)| {
+                        !used[i]
+                            && interaction.request.method == method
+                            && interaction.request.url == url
+                    })
+                    .ok_or_else(|| {
+                        PushError::NetworkError(format!(
+                            "No recorded response for {} {}",
+                            method, url
+                        ))
+                    })?;
+                used[index] = true;
+                Ok(cassette.interactions[index].response.clone())
+            }
+            Mode::Record => {
+                let method_value = reqwest::Method::from_bytes(method.as_bytes())
+                    .map_err(|e| PushError::NetworkError(e.to_string()))?;
+                let mut request = http_client.request(method_value, &url).body(body.clone());
+                if let Some(content_type) = content_type {
+                    request = request.header(reqwest::header::CONTENT_TYPE, content_type);
+                }
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|e| PushError::NetworkError(e.to_string()))?;
+                let status = response.status().as_u16();
+                let headers: BTreeMap<String, String> = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| ("content-type".to_string(), v.to_string()))
+                    .into_iter()
+                    .collect();
+                let bytes = response
+                    .bytes()
+                    .await
+                    .map_err(|e| PushError::NetworkError(e.to_string()))?;
+                let mut state = state.lock().unwrap();
+                let (response_body, base64) = match String::from_utf8(bytes.to_vec()) {
+                    Ok(text) => (state.redact(&text), false),
+                    Err(_) => (STANDARD.encode(&bytes), true),
+                };
+                let response = RecordedResponse {
+                    status,
+                    headers,
+                    body: response_body,
+                    base64,
+                };
+                let interaction = Interaction {
+                    request: RecordedRequest {
+                        url: state.redact(&url),
+                        body: state.redact(&String::from_utf8_lossy(&body)),
+                        method,
+                    },
+                    response: response.clone(),
+                };
+                state.cassette.interactions.push(interaction);
+                state.used.push(true);
+                Ok(response)
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "vcr"))]
+mod tests {
+    use super::*;
+
+    fn cassette() -> Cassette {
+        serde_json::from_value(serde_json::json!({
+            "interactions": [{
+                "request": {"method": "POST", "url": "https://example.com/hook?key=TOKEN"},
+                "response": {
+                    "status": 200,
+                    "headers": {"content-type": "application/json"},
+                    "body": "{\"errcode\":0}"
+                }
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_endpoint_outside_scope() {
+        assert_eq!(endpoint("https://example.com/a"), "https://example.com/a");
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let path = std::env::temp_dir().join(format!("vcr-{}.json", std::process::id()));
+        cassette().save(&path).unwrap();
+        let recorder = Recorder::with_mode(&path, Mode::Replay)
+            .await
+            .unwrap()
+            .redact("secret", "TOKEN");
+        let client = reqwest::Client::new();
+        let (first, second) = recorder
+            .scope(async {
+                let url = endpoint("https://example.com/hook?key=secret");
+                assert!(url.starts_with("http://127.0.0.1:"));
+                let first = client.post(&url).body("{}").send().await.unwrap();
+                // 每条记录只回放一次
+                let second = client.post(&url).send().await.unwrap();
+                (first, second)
+            })
+            .await;
+        assert_eq!(first.status(), 200);
+        assert_eq!(first.text().await.unwrap(), "{\"errcode\":0}");
+        assert_eq!(second.status().as_u16(), 599);
+        recorder.finish().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
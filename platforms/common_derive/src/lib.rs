@@ -0,0 +1,318 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, Field, Fields, GenericArgument, Ident, LitStr,
+    PathArguments, Type, parse_macro_input,
+};
+
+/// 为平台配置结构体实现 `PushInitConfig` 和 `ConfigSchema`
+///
+/// 结构体属性 `#[push(...)]`：
+/// - `platform = "name"`：平台名称（必填，可以是常量）
+/// - `webhook_url = path`：生成 webhook 地址的函数，签名为 `fn(&Self) -> String`
+/// - `default_timeout = 30`、`default_retry_count = 3`：未配置时的默认值
+///
+/// 字段属性 `#[push(webhook)]`、`#[push(secret)]`、`#[push(timeout)]`、`#[push(retry_count)]`、
+/// `#[push(proxy)]`、`#[push(retry)]` 指定对应的取值字段，字段可以是 `Option`
+#[proc_macro_derive(PushConfig, attributes(push))]
+pub fn derive_push_config(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// 通过字段属性指定的取值字段
+struct Slot {
+    ident: Ident,
+    optional: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let mut platform = None;
+    let mut webhook_fn = None;
+    let mut default_timeout: Expr = syn::parse_quote!(30);
+    let mut default_retry_count: Expr = syn::parse_quote!(3);
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("push")) {
+        attr.parse_nested_meta(|meta| {
+            let value: Expr = meta.value()?.parse()?;
+            if meta.path.is_ident("platform") {
+                platform = Some(value);
+            } else if meta.path.is_ident("webhook_url") {
+                webhook_fn = Some(value);
+            } else if meta.path.is_ident("default_timeout") {
+                default_timeout = value;
+            } else if meta.path.is_ident("default_retry_count") {
+                default_retry_count = value;
+            } else {
+                return Err(meta.error("unsupported push attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    let platform =
+        platform.ok_or_else(|| Error::new(name.span(), "missing #[push(platform = \"...\")]"))?;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(name.span(), "PushConfig only supports structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(name.span(), "PushConfig requires named fields"));
+    };
+
+    let mut webhook = None;
+    let mut secret = None;
+    let mut timeout = None;
+    let mut retry_count = None;
+    let mut proxy = None;
+    let mut retry = None;
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.clone().expect("named field");
+        let optional = option_inner(&field.ty).is_some();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("push")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("webhook") {
+                    &mut webhook
+                } else if meta.path.is_ident("secret") {
+                    &mut secret
+                } else if meta.path.is_ident("timeout") {
+                    &mut timeout
+                } else if meta.path.is_ident("retry_count") {
+                    &mut retry_count
+                } else if meta.path.is_ident("proxy") {
+                    &mut proxy
+                } else if meta.path.is_ident("retry") {
+                    &mut retry
+                } else {
+                    return Err(meta.error("unsupported push field attribute"));
+                };
+                *slot = Some(Slot {
+                    ident: ident.clone(),
+                    optional,
+                });
+                Ok(())
+            })?;
+        }
+
+        let (rename, has_default) = serde_field(field);
+        let key = rename.unwrap_or_else(|| ident.to_string());
+        let schema = type_schema(&field.ty);
+        let description = match doc(&field.attrs) {
+            Some(doc) => quote! { schema["description"] = #doc.into(); },
+            None => quote! {},
+        };
+        properties.push(quote! {
+            let mut schema = #schema;
+            #description
+            properties.insert(#key.to_string(), schema);
+        });
+        if !optional && !has_default {
+            required.push(key);
+        }
+    }
+
+    let webhook_body = match (webhook, webhook_fn) {
+        (Some(Slot { ident, optional }), _) if optional => {
+            quote! { self.#ident.clone().unwrap_or_default() }
+        }
+        (Some(Slot { ident, .. }), _) => quote! { self.#ident.clone() },
+        (None, Some(path)) => quote! { (#path)(self) },
+        (None, None) => {
+            return Err(Error::new(
+                name.span(),
+                "PushConfig needs a #[push(webhook)] field or #[push(webhook_url = ...)]",
+            ));
+        }
+    };
+    let secret_body = match secret {
+        Some(Slot { ident, optional }) if optional => quote! { self.#ident.as_deref() },
+        Some(Slot { ident, .. }) => quote! { Some(self.#ident.as_str()) },
+        None => quote! { None },
+    };
+    let timeout_body = match timeout {
+        Some(Slot { ident, optional }) if optional => {
+            quote! { self.#ident.unwrap_or(#default_timeout) }
+        }
+        Some(Slot { ident, .. }) => quote! { self.#ident },
+        None => quote! { #default_timeout },
+    };
+    let retry_body = match retry_count {
+        Some(Slot { ident, optional }) if optional => {
+            quote! { self.#ident.unwrap_or(#default_retry_count) }
+        }
+        Some(Slot { ident, .. }) => quote! { self.#ident },
+        None => quote! { #default_retry_count },
+    };
+
+    // 未指定代理字段时沿用 trait 的默认实现
+    let proxy_fn = match proxy {
+        Some(Slot { ident, optional }) if optional => quote! {
+            fn proxy(&self) -> Option<&str> {
+                self.#ident.as_deref()
+            }
+        },
+        Some(Slot { ident, .. }) => quote! {
+            fn proxy(&self) -> Option<&str> {
+                Some(self.#ident.as_str())
+            }
+        },
+        None => quote! {},
+    };
+    let retry_fn = match retry {
+        Some(Slot { ident, optional }) if optional => quote! {
+            fn retry_policy(&self) -> ::common::RetryPolicy {
+                self.#ident.clone().unwrap_or_default()
+            }
+        },
+        Some(Slot { ident, .. }) => quote! {
+            fn retry_policy(&self) -> ::common::RetryPolicy {
+                self.#ident.clone()
+            }
+        },
+        None => quote! {},
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::common::PushInitConfig for #name #ty_generics #where_clause {
+            fn platform_name(&self) -> &str {
+                #platform
+            }
+
+            fn webhook_url(&self) -> String {
+                #webhook_body
+            }
+
+            fn secret(&self) -> Option<&str> {
+                #secret_body
+            }
+
+            fn timeout(&self) -> u64 {
+                #timeout_body
+            }
+
+            fn retry_count(&self) -> u32 {
+                #retry_body
+            }
+
+            #proxy_fn
+
+            #retry_fn
+        }
+
+        impl #impl_generics ::common::ConfigSchema for #name #ty_generics #where_clause {
+            fn config_schema() -> ::common::__private::serde_json::Value {
+                let mut properties = ::common::__private::serde_json::Map::new();
+                #(#properties)*
+                ::common::__private::serde_json::json!({
+                    "type": "object",
+                    "title": #platform,
+                    "properties": properties,
+                    "required": [#(#required),*],
+                })
+            }
+        }
+    })
+}
+
+/// `Option<T>` 的内部类型
+fn option_inner(ty: &Type) -> Option<&Type> {
+    generic_args(ty, "Option").and_then(|args| args.first().copied())
+}
+
+/// 类型最后一段路径名为 `name` 时返回其泛型参数
+fn generic_args<'a>(ty: &'a Type, name: &str) -> Option<Vec<&'a Type>> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Some(Vec::new());
+    };
+    Some(
+        args.args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// 根据 Rust 类型生成 JSON Schema 表达式，无法识别的类型不做约束
+fn type_schema(ty: &Type) -> TokenStream2 {
+    let json = quote! { ::common::__private::serde_json::json! };
+    if let Some(inner) = option_inner(ty) {
+        return type_schema(inner);
+    }
+    if let Some(items) = generic_args(ty, "Vec").and_then(|args| args.first().copied()) {
+        let items = type_schema(items);
+        return quote! { #json({ "type": "array", "items": #items }) };
+    }
+    let Type::Path(path) = ty else {
+        return quote! { #json({}) };
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return quote! { #json({}) };
+    };
+    let kind = match segment.ident.to_string().as_str() {
+        "String" | "str" | "PathBuf" => "string",
+        "bool" => "boolean",
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" => {
+            "integer"
+        }
+        "f32" | "f64" => "number",
+        "HashMap" | "BTreeMap" => "object",
+        _ => return quote! { #json({}) },
+    };
+    quote! { #json({ "type": #kind }) }
+}
+
+/// 读取字段的 `#[serde(rename = "...")]` 和 `#[serde(default)]`
+fn serde_field(field: &Field) -> (Option<String>, bool) {
+    let mut rename = None;
+    let mut has_default = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        // 其他 serde 属性与 schema 无关，解析失败时忽略
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+                has_default = true;
+                if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    (rename, has_default)
+}
+
+/// 合并字段的文档注释
+fn doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Some(s.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
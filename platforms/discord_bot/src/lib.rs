@@ -0,0 +1,913 @@
+//! Discord Bot 平台：用 Bot Token 调用 REST API 发到频道或私信给用户
+//!
+//! 相比 Webhook，Bot 可以私信用户、编辑和删除消息、上传更多文件，
+//! 并能查询消息上的表情回应，把接收方的回应当作已读确认
+
+use async_trait::async_trait;
+use common::{
+    AttachmentSource, ConfigSchema, DELETE_FEATURE, DRY_RUN_FEATURE, DeliveryStatus, EDIT_FEATURE,
+    HEALTH_CHECK_FEATURE, LengthUnit, MarkdownDialect, Mention, MentionSupport, Message,
+    MessageKind, MessageLimits, MessagePart, MessageType, PlatformContext, PlatformFactory,
+    PlatformInfo, PushConfig, PushError, PushPlatform, PushPlatformCapabilities, PushResult,
+    RECEIPT_FEATURE, RateLimit, Receipt, ResilientPlatform, RetryPolicy, THREAD_FEATURE,
+    THREAD_ID_KEY, degrade,
+};
+use log::*;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const PLATFORM_NAME: &str = "discord_bot";
+const API_URL: &str = "https://discord.com/api/v10";
+/// 单条消息文字上限（字符）
+const MAX_CONTENT_CHARS: usize = 2000;
+/// 嵌入内容描述上限（字符）
+const MAX_EMBED_CHARS: usize = 4096;
+/// 未加成服务器中 Bot 单次上传的文件大小上限（25MB）
+const MAX_FILE_BYTES: usize = 25 * 1024 * 1024;
+
+/// Discord Bot 配置，`channel_id` 和 `user_id` 二选一
+#[derive(Debug, Clone, Serialize, Deserialize, PushConfig)]
+#[push(
+    platform = PLATFORM_NAME,
+    webhook_url = api_url,
+    default_timeout = 30,
+    default_retry_count = 3
+)]
+pub struct DiscordBotConfig {
+    /// Bot Token
+    #[push(secret)]
+    pub token: String,
+    /// 发送到的频道 ID
+    #[serde(default)]
+    pub channel_id: Option<String>,
+    /// 私信的用户 ID，需与 Bot 至少有一个共同服务器
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// 视为已读确认的表情，为空时任意表情都算
+    #[serde(default)]
+    pub ack_emojis: Vec<String>,
+    /// 发送后多久内查询表情回应（秒），0 表示不查询
+    #[serde(default = "default_ack_window_secs")]
+    pub ack_window_secs: u64,
+    /// 出站代理
+    #[push(proxy)]
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// 重试策略
+    #[push(retry)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+fn default_ack_window_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn api_url(_config: &DiscordBotConfig) -> String {
+    API_URL.to_string()
+}
+
+impl DiscordBotConfig {
+    /// 检查发送目标
+    fn validate(&self) -> Result<(), PushError> {
+        match (&self.channel_id, &self.user_id) {
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            _ => Err(PushError::ConfigError(
+                "Discord bot needs exactly one of channel_id or user_id".to_string(),
+            )),
+        }
+    }
+}
+
+/// Discord Bot 推送平台
+pub struct DiscordBotPlatform {
+    config: DiscordBotConfig,
+    http_client: Client,
+    /// 私信频道 ID，首次发送时创建
+    dm_channel: Mutex<Option<String>>,
+    /// 等待表情回应的消息及其发送时间
+    awaiting_ack: Mutex<HashMap<String, Instant>>,
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for DiscordBotPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        self.config.validate()
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(MessageType::Text(content.to_string()), vec![], None)
+            .await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.post(MessageType::Text(content.to_string()), mentions, None)
+            .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(MessageType::Markdown(content.to_string()), vec![], None)
+            .await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let rich = MessageType::Rich {
+            title: title.to_string(),
+            content: content.to_string(),
+            url: url.map(str::to_string),
+        };
+        self.send(rich).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let image = MessageType::Image {
+            url: image_url.to_string(),
+            caption: caption.map(str::to_string),
+        };
+        self.post(image, vec![], None).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let link = MessageType::Link {
+            title: title.to_string(),
+            description: description.to_string(),
+            url: url.to_string(),
+            image_url: image_url.map(str::to_string),
+        };
+        self.post(link, vec![], None).await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.upload(name, mime, source, None, None).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        self.post(degrade(message, &platform_info()), vec![], None)
+            .await
+    }
+
+    /// 元数据中有 [`THREAD_ID_KEY`] 时作为对该消息的回复发送
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        let reply_to = message.metadata.get(THREAD_ID_KEY).map(String::as_str);
+        self.post(part.content, part.mentions, reply_to).await
+    }
+
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        let payload = match degrade(message.content, &platform_info()) {
+            MessageType::Text(content) | MessageType::Markdown(content) => {
+                json!({ "content": content, "embeds": [] })
+            }
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => {
+                json!({ "content": "", "embeds": [embed(&title, &content, url.as_deref(), None)] })
+            }
+            _ => {
+                return Err(PushError::MessageError(
+                    "Only text messages can be edited on Discord".to_string(),
+                ));
+            }
+        };
+        let channel = self.channel().await?;
+        let path = format!("/channels/{}/messages/{}", channel, message_id);
+        self.api(Method::PATCH, &path, &payload).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        let channel = self.channel().await?;
+        let path = format!("/channels/{}/messages/{}", channel, message_id);
+        let payload = json!({ "channel_id": channel, "message_id": message_id });
+        if let Some(result) = common::dry_run::intercept(&payload) {
+            return Ok(result);
+        }
+        let (status, retry_after, text) = execute(self.request(Method::DELETE, &path)).await?;
+        if !status.is_success() {
+            return Err(http_error(status, retry_after, &text));
+        }
+        self.awaiting_ack.lock().unwrap().remove(message_id);
+        Ok(PushResult {
+            success: true,
+            message_id: Some(message_id.to_string()),
+            http_status: Some(status.as_u16()),
+            ..Default::default()
+        })
+    }
+
+    /// 查询本实例发出、等待确认的消息上的表情回应
+    async fn poll_receipts(&self) -> Result<Vec<Receipt>, PushError> {
+        let pending: Vec<String> = {
+            let mut awaiting = self.awaiting_ack.lock().unwrap();
+            self.prune_awaiting(&mut awaiting);
+            awaiting.keys().cloned().collect()
+        };
+        self.reaction_receipts(&pending).await
+    }
+
+    /// 查询调用方记录的消息上的表情回应，实例重建后发出的消息同样能确认
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        self.reaction_receipts(message_ids).await
+    }
+
+    /// 查询 Bot 自身的用户信息检查 Token 是否有效
+    async fn health_check(&self) -> Result<bool, PushError> {
+        let (status, retry_after, text) = execute(self.request(Method::GET, "/users/@me")).await?;
+        parse_response::<Value>(status, retry_after, &text).map(|_| true)
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        platform_info()
+    }
+}
+
+/// Discord Bot 平台信息，与配置无关
+fn platform_info() -> PlatformInfo {
+    PlatformInfo {
+        name: PLATFORM_NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        message_types: BTreeSet::from([
+            MessageKind::Text,
+            MessageKind::Markdown,
+            MessageKind::Rich,
+            MessageKind::Image,
+            MessageKind::Link,
+            MessageKind::File,
+        ]),
+        mentions: MentionSupport {
+            all: true,
+            user_id: true,
+            ..Default::default()
+        },
+        features: vec![
+            THREAD_FEATURE.to_string(),
+            EDIT_FEATURE.to_string(),
+            DELETE_FEATURE.to_string(),
+            RECEIPT_FEATURE.to_string(),
+            DRY_RUN_FEATURE.to_string(),
+            HEALTH_CHECK_FEATURE.to_string(),
+        ],
+        limits: MessageLimits {
+            text: Some(MAX_CONTENT_CHARS),
+            markdown: Some(MAX_CONTENT_CHARS),
+            unit: LengthUnit::Chars,
+            attachment_bytes: Some(MAX_FILE_BYTES),
+        },
+        rate_limit: Some(RateLimit {
+            requests: 5,
+            per_secs: 5,
+        }),
+        markdown_dialect: MarkdownDialect::Standard,
+    }
+}
+
+impl PushPlatform<DiscordBotConfig> for DiscordBotPlatform {
+    fn new(config: DiscordBotConfig) -> Self
+    where
+        Self: Sized,
+    {
+        Self::with_client(config, Client::new())
+    }
+}
+
+impl DiscordBotPlatform {
+    /// 使用共享的 HTTP 客户端创建平台
+    pub fn with_client(config: DiscordBotConfig, http_client: Client) -> Self {
+        Self {
+            config,
+            http_client,
+            dm_channel: Mutex::new(None),
+            awaiting_ack: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 发送一条已降级的消息，`reply_to` 不为空时作为对该消息的回复
+    async fn post(
+        &self,
+        content: MessageType,
+        mentions: Vec<Mention>,
+        reply_to: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let (content, embeds) = match content {
+            MessageType::Text(text) | MessageType::Markdown(text) => (text, vec![]),
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => (
+                String::new(),
+                vec![embed(&title, &content, url.as_deref(), None)],
+            ),
+            // 内联图片没有可引用的地址，作为附件上传
+            MessageType::Image { url, caption } if url.starts_with("data:") => {
+                let source = AttachmentSource::Url(url);
+                return self
+                    .upload("image.png", None, &source, caption.as_deref(), reply_to)
+                    .await;
+            }
+            MessageType::Image { url, caption } => {
+                let title = caption.unwrap_or_default();
+                (String::new(), vec![embed(&title, "", None, Some(&url))])
+            }
+            MessageType::Link {
+                title,
+                description,
+                url,
+                image_url,
+            } => (
+                String::new(),
+                vec![embed(
+                    &title,
+                    &description,
+                    Some(&url),
+                    image_url.as_deref(),
+                )],
+            ),
+            MessageType::File { name, mime, source } => {
+                return self
+                    .upload(&name, mime.as_deref(), &source, None, reply_to)
+                    .await;
+            }
+            other => return Err(PushError::unsupported(&platform_info(), &other)),
+        };
+        let mut payload = message_payload(&content, &mentions, reply_to);
+        payload["embeds"] = json!(embeds);
+        let channel = self.channel().await?;
+        let path = format!("/channels/{}/messages", channel);
+        let result = self.api(Method::POST, &path, &payload).await?;
+        self.await_ack(&result);
+        Ok(result)
+    }
+
+    /// 以附件形式发送文件，消息内容为 `caption`
+    async fn upload(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        reply_to: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let bytes = source.read(&self.http_client, name, MAX_FILE_BYTES).await?;
+        let mut payload = message_payload(caption.unwrap_or_default(), &[], reply_to);
+        payload["attachments"] = json!([{ "id": 0, "filename": name }]);
+        if let Some(result) = common::dry_run::intercept(&payload) {
+            return Ok(result);
+        }
+        let mut part = Part::bytes(bytes).file_name(name.to_string());
+        if let Some(mime) = mime {
+            part = part
+                .mime_str(mime)
+                .map_err(|e| PushError::MessageError(e.to_string()))?;
+        }
+        let form = Form::new()
+            .text("payload_json", payload.to_string())
+            .part("files[0]", part);
+        let channel = self.channel().await?;
+        let path = format!("/channels/{}/messages", channel);
+        let request = self.request(Method::POST, &path).multipart(form);
+        let result = sent(execute(request).await?)?;
+        self.await_ack(&result);
+        Ok(result)
+    }
+
+    /// 发送目标的频道 ID，私信时首次调用创建私信频道
+    async fn channel(&self) -> Result<String, PushError> {
+        if let Some(channel) = &self.config.channel_id {
+            return Ok(channel.clone());
+        }
+        if let Some(channel) = self.dm_channel.lock().unwrap().clone() {
+            return Ok(channel);
+        }
+        let user_id = self.config.user_id.as_deref().ok_or_else(|| {
+            PushError::ConfigError("Discord bot needs a channel_id or user_id".to_string())
+        })?;
+        // 演练时不创建私信频道
+        if common::dry_run::active() {
+            return Ok(format!("@{}", user_id));
+        }
+        let request = self
+            .request(Method::POST, "/users/@me/channels")
+            .json(&json!({ "recipient_id": user_id }));
+        let (status, retry_after, text) = execute(request).await?;
+        let channel: DiscordChannel = parse_response(status, retry_after, &text)?;
+        *self.dm_channel.lock().unwrap() = Some(channel.id.clone());
+        Ok(channel.id)
+    }
+
+    /// 带 Bot Token 的 REST API 请求
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http_client
+            .request(method, common::endpoint(&format!("{API_URL}{path}")))
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bot {}", self.config.token),
+            )
+    }
+
+    /// 以 JSON 调用 REST API，结果的 `message_id` 为消息 ID
+    async fn api(
+        &self,
+        method: Method,
+        path: &str,
+        payload: &Value,
+    ) -> Result<PushResult, PushError> {
+        if let Some(result) = common::dry_run::intercept(payload) {
+            return Ok(result);
+        }
+        sent(execute(self.request(method, path).json(payload)).await?)
+    }
+
+    /// 记录需要查询表情回应的消息，同时清理超出确认窗口的记录，避免从不查询时无限增长
+    fn await_ack(&self, result: &PushResult) {
+        if self.config.ack_window_secs == 0 || result.dry_run {
+            return;
+        }
+        if let Some(message_id) = &result.message_id {
+            let mut awaiting = self.awaiting_ack.lock().unwrap();
+            self.prune_awaiting(&mut awaiting);
+            awaiting.insert(message_id.clone(), Instant::now());
+        }
+    }
+
+    /// 丢弃超出确认窗口的等待记录
+    fn prune_awaiting(&self, awaiting: &mut HashMap<String, Instant>) {
+        let window = Duration::from_secs(self.config.ack_window_secs);
+        awaiting.retain(|_, sent_at| sent_at.elapsed() < window);
+    }
+
+    /// 逐条查询消息上的表情回应，接收方用确认表情回应过的消息视为已确认；已删除的消息跳过
+    async fn reaction_receipts(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let channel = self.channel().await?;
+        let mut receipts = Vec::new();
+        for message_id in message_ids {
+            let path = format!("/channels/{}/messages/{}", channel, message_id);
+            let (status, retry_after, text) = execute(self.request(Method::GET, &path)).await?;
+            if status == StatusCode::NOT_FOUND {
+                // 消息已被删除，不再等待
+                self.awaiting_ack.lock().unwrap().remove(message_id);
+                continue;
+            }
+            let message: DiscordMessage = parse_response(status, retry_after, &text)?;
+            if let Some(emoji) = message.acknowledged_with(&self.config.ack_emojis) {
+                debug!("Discord message {} acknowledged with {}", message_id, emoji);
+                self.awaiting_ack.lock().unwrap().remove(message_id);
+                receipts.push(Receipt::new(
+                    message_id.clone(),
+                    DeliveryStatus::Acknowledged,
+                ));
+            }
+        }
+        Ok(receipts)
+    }
+}
+
+/// 消息内容、提醒和回复引用；只允许提醒 `mentions` 中的对象，避免内容中的 `@everyone` 误提醒
+fn message_payload(content: &str, mentions: &[Mention], reply_to: Option<&str>) -> Value {
+    let mut users = Vec::new();
+    let mut parse = Vec::new();
+    let mut tags = Vec::new();
+    for mention in mentions {
+        match mention {
+            Mention::All => {
+                parse.push("everyone");
+                tags.push("@everyone".to_string());
+            }
+            Mention::UserId(id) => {
+                users.push(id.clone());
+                tags.push(format!("<@{}>", id));
+            }
+            Mention::Phone(value) | Mention::Email(value) => tags.push(value.clone()),
+        }
+    }
+    let content = match (tags.is_empty(), content.is_empty()) {
+        (true, _) => content.to_string(),
+        (false, true) => tags.join(" "),
+        (false, false) => format!("{} {}", tags.join(" "), content),
+    };
+    let mut payload = json!({
+        "content": content,
+        "allowed_mentions": { "parse": parse, "users": users },
+    });
+    if let Some(message_id) = reply_to {
+        payload["message_reference"] = json!({
+            "message_id": message_id,
+            "fail_if_not_exists": false,
+        });
+    }
+    payload
+}
+
+/// 嵌入内容，描述超长时截断
+fn embed(title: &str, description: &str, url: Option<&str>, image_url: Option<&str>) -> Value {
+    let mut embed = json!({});
+    if !title.is_empty() {
+        embed["title"] = json!(title);
+    }
+    if !description.is_empty() {
+        embed["description"] = json!(
+            description
+                .chars()
+                .take(MAX_EMBED_CHARS)
+                .collect::<String>()
+        );
+    }
+    if let Some(url) = url {
+        embed["url"] = json!(url);
+    }
+    if let Some(image_url) = image_url {
+        embed["image"] = json!({ "url": image_url });
+    }
+    embed
+}
+
+/// 发送请求，返回状态码、Retry-After 和响应体
+async fn execute(
+    request: RequestBuilder,
+) -> Result<(StatusCode, Option<Duration>, String), PushError> {
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            PushError::Timeout(e.to_string())
+        } else {
+            PushError::NetworkError(e.to_string())
+        }
+    })?;
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(Duration::from_secs_f64);
+    let text = response
+        .text()
+        .await
+        .map_err(|e| PushError::NetworkError(e.to_string()))?;
+    Ok((status, retry_after, text))
+}
+
+/// 将创建或编辑消息的响应转为推送结果
+fn sent(
+    (status, retry_after, text): (StatusCode, Option<Duration>, String),
+) -> Result<PushResult, PushError> {
+    let message: DiscordMessage = parse_response(status, retry_after, &text)?;
+    Ok(PushResult {
+        success: true,
+        message_id: Some(message.id),
+        response: Some(text),
+        http_status: Some(status.as_u16()),
+        ..Default::default()
+    })
+}
+
+fn parse_response<T: serde::de::DeserializeOwned>(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    text: &str,
+) -> Result<T, PushError> {
+    if !status.is_success() {
+        return Err(http_error(status, retry_after, text));
+    }
+    serde_json::from_str(text).map_err(|e| PushError::PlatformError(e.to_string()))
+}
+
+/// 按 HTTP 状态码映射错误，限流时优先使用响应体中的 `retry_after`
+fn http_error(status: StatusCode, retry_after: Option<Duration>, text: &str) -> PushError {
+    let error: DiscordError = serde_json::from_str(text).unwrap_or_default();
+    let message = match error.code {
+        Some(code) => format!("Discord API Error {}: {}", code, error.message),
+        None => format!("Request failed with status: {}, body: {}", status, text),
+    };
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => PushError::AuthError(message),
+        StatusCode::NOT_FOUND => PushError::ConfigError(message),
+        StatusCode::TOO_MANY_REQUESTS => PushError::RateLimited {
+            message,
+            retry_after: error
+                .retry_after
+                .map(Duration::from_secs_f64)
+                .or(retry_after),
+        },
+        StatusCode::PAYLOAD_TOO_LARGE => PushError::PayloadTooLarge(message),
+        s if s.is_server_error() => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+// --- Discord API Structs ---
+
+#[derive(Deserialize)]
+struct DiscordChannel {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct DiscordMessage {
+    id: String,
+    #[serde(default)]
+    reactions: Vec<DiscordReaction>,
+}
+
+impl DiscordMessage {
+    /// Bot 以外的用户回应过的确认表情
+    fn acknowledged_with(&self, ack_emojis: &[String]) -> Option<&str> {
+        self.reactions
+            .iter()
+            .filter(|r| r.count > u64::from(r.me))
+            .map(|r| r.emoji.name.as_deref().unwrap_or_default())
+            .find(|name| ack_emojis.is_empty() || ack_emojis.iter().any(|e| e == name))
+    }
+}
+
+#[derive(Deserialize)]
+struct DiscordReaction {
+    count: u64,
+    #[serde(default)]
+    me: bool,
+    emoji: DiscordEmoji,
+}
+
+#[derive(Deserialize)]
+struct DiscordEmoji {
+    /// 标准表情为字符本身，自定义表情为名称
+    name: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct DiscordError {
+    #[serde(default)]
+    code: Option<u64>,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    retry_after: Option<f64>,
+}
+
+// --- Platform Factory ---
+
+pub struct DiscordBotPlatformFactory;
+
+impl PlatformFactory for DiscordBotPlatformFactory {
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        let config: DiscordBotConfig =
+            serde_json::from_value(config).map_err(|e| PushError::ConfigError(e.to_string()))?;
+        config.validate()?;
+        let http_client = context.client_for(&config)?;
+        let platform = DiscordBotPlatform::with_client(config.clone(), http_client);
+        Ok(Box::new(ResilientPlatform::new(platform, &config)))
+    }
+
+    fn name(&self) -> &'static str {
+        PLATFORM_NAME
+    }
+
+    fn config_schema(&self) -> Value {
+        DiscordBotConfig::config_schema()
+    }
+
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        Some(platform_info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::wiremock::matchers::{method, path};
+    use test_support::wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config() -> DiscordBotConfig {
+        DiscordBotConfig {
+            token: "TOKEN".to_string(),
+            channel_id: Some("100".to_string()),
+            user_id: None,
+            ack_emojis: vec![],
+            ack_window_secs: default_ack_window_secs(),
+            proxy: None,
+            retry: None,
+        }
+    }
+
+    fn body(request: &test_support::wiremock::Request) -> Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(config().validate().is_ok());
+        let both = DiscordBotConfig {
+            user_id: Some("42".to_string()),
+            ..config()
+        };
+        assert!(both.validate().is_err());
+        let neither = DiscordBotConfig {
+            channel_id: None,
+            ..config()
+        };
+        assert!(neither.validate().is_err());
+    }
+
+    #[test]
+    fn test_message_payload() {
+        let payload = message_payload("disk full", &[Mention::All, "42".into()], Some("7"));
+        assert_eq!(payload["content"], "@everyone <@42> disk full");
+        assert_eq!(
+            payload["allowed_mentions"],
+            json!({"parse": ["everyone"], "users": ["42"]})
+        );
+        assert_eq!(payload["message_reference"]["message_id"], "7");
+        // 内容中的 @everyone 不会提醒
+        let payload = message_payload("@everyone", &[], None);
+        assert_eq!(payload["allowed_mentions"]["parse"], json!([]));
+    }
+
+    #[test]
+    fn test_rate_limit_uses_body_retry_after() {
+        let error = http_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(5)),
+            r#"{"message": "You are being rate limited.", "retry_after": 1.5, "global": false}"#,
+        );
+        assert_eq!(error.retry_after(), Some(Duration::from_millis(1500)));
+        let error = http_error(
+            StatusCode::FORBIDDEN,
+            None,
+            r#"{"message": "Cannot send messages to this user", "code": 50007}"#,
+        );
+        assert!(matches!(error, PushError::AuthError(m) if m.contains("50007")));
+    }
+
+    #[tokio::test]
+    async fn test_dm_edit_and_reaction_ack() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/https/discord.com/api/v10/users/@me/channels"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "900"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/https/discord.com/api/v10/channels/900/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "1"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/https/discord.com/api/v10/channels/900/messages/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "1"})))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/https/discord.com/api/v10/channels/900/messages/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "1",
+                "reactions": [
+                    {"count": 1, "me": true, "emoji": {"id": null, "name": "👀"}},
+                    {"count": 1, "me": false, "emoji": {"id": null, "name": "✅"}}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let platform = DiscordBotPlatform::new(DiscordBotConfig {
+            channel_id: None,
+            user_id: Some("42".to_string()),
+            ack_emojis: vec!["👀".to_string(), "✅".to_string()],
+            ..config()
+        });
+        let scenario = async {
+            let result = platform.send_text("db down").await.unwrap();
+            assert_eq!(result.message_id.as_deref(), Some("1"));
+            platform.send_text("still down").await.unwrap();
+            let resolved = Message::new(MessageType::Text("RESOLVED".to_string()));
+            platform.update_message("1", resolved).await.unwrap();
+            let receipts = platform.poll_receipts().await.unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].status, DeliveryStatus::Acknowledged);
+            // 已确认的消息不再查询
+            assert!(platform.poll_receipts().await.unwrap().is_empty());
+            // 重建的实例没有等待记录，按调用方记录的消息 ID 仍能查到确认
+            let rebuilt = DiscordBotPlatform::new(DiscordBotConfig {
+                channel_id: None,
+                user_id: Some("42".to_string()),
+                ack_emojis: vec!["✅".to_string()],
+                ..config()
+            });
+            let receipts = rebuilt.poll_receipts_for(&["1".to_string()]).await.unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].status, DeliveryStatus::Acknowledged);
+        };
+        common::vcr::redirect(server.uri(), scenario).await;
+
+        let requests = server.received_requests().await.unwrap();
+        let paths: Vec<_> = requests
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.url.path()))
+            .collect();
+        // 私信频道只创建一次
+        assert_eq!(
+            paths,
+            [
+                "POST /https/discord.com/api/v10/users/@me/channels",
+                "POST /https/discord.com/api/v10/channels/900/messages",
+                "POST /https/discord.com/api/v10/channels/900/messages",
+                "PATCH /https/discord.com/api/v10/channels/900/messages/1",
+                "GET /https/discord.com/api/v10/channels/900/messages/1",
+                "POST /https/discord.com/api/v10/users/@me/channels",
+                "GET /https/discord.com/api/v10/channels/900/messages/1",
+            ]
+        );
+        assert_eq!(body(&requests[0]), json!({"recipient_id": "42"}));
+        assert_eq!(body(&requests[3])["content"], "RESOLVED");
+        assert_eq!(
+            requests[1].headers.get("authorization").unwrap(),
+            "Bot TOKEN"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_upload() {
+        let upstream = test_support::Upstream::start(
+            ResponseTemplate::new(200).set_body_json(json!({"id": "1"})),
+        )
+        .await;
+        let platform = DiscordBotPlatform::new(config());
+        let source = AttachmentSource::Bytes(b"report".to_vec());
+        let result = upstream
+            .scope(platform.send_file("report.txt", Some("text/plain"), &source))
+            .await
+            .unwrap();
+        assert_eq!(result.message_id.as_deref(), Some("1"));
+
+        let requests = upstream.requests().await;
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("name=\"payload_json\""));
+        assert!(body.contains(r#""filename":"report.txt""#));
+        assert!(body.contains("name=\"files[0]\"; filename=\"report.txt\""));
+        assert!(body.contains("report"));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_does_not_create_dm_channel() {
+        let upstream = test_support::Upstream::start(ResponseTemplate::new(500)).await;
+        let platform = DiscordBotPlatform::new(DiscordBotConfig {
+            channel_id: None,
+            user_id: Some("42".to_string()),
+            ..config()
+        });
+        let result = upstream
+            .scope(common::dry_run::scope(platform.send_text("hello")))
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        assert!(upstream.requests().await.is_empty());
+        assert!(platform.awaiting_ack.lock().unwrap().is_empty());
+    }
+
+    test_support::conformance_suite!(
+        DiscordBotPlatform::new(config()),
+        test_support::CannedResponses::new(json!({"id": "1"}))
+    );
+}
@@ -0,0 +1,60 @@
+//! 多平台推送门面：重新导出公共类型，并按 cargo feature 注册启用的平台
+
+pub use common::*;
+#[cfg(feature = "discord_bot")]
+pub use discord_bot;
+#[cfg(feature = "slack_bot")]
+pub use slack_bot;
+#[cfg(feature = "telegram")]
+pub use telegram_bot;
+#[cfg(feature = "voice")]
+pub use voice_call;
+#[cfg(feature = "wxwork")]
+pub use wxwork_group_bot;
+
+/// 创建注册了所有已启用平台的注册表
+#[allow(unused_mut)]
+pub fn default_registry() -> PlatformRegistry {
+    let mut registry = PlatformRegistry::new();
+    #[cfg(feature = "wxwork")]
+    registry.register(Box::new(wxwork_group_bot::WxWorkPlatformFactory));
+    #[cfg(feature = "voice")]
+    registry.register(Box::new(voice_call::VoicePlatformFactory));
+    #[cfg(feature = "slack_bot")]
+    registry.register(Box::new(slack_bot::SlackBotPlatformFactory));
+    #[cfg(feature = "discord_bot")]
+    registry.register(Box::new(discord_bot::DiscordBotPlatformFactory));
+    #[cfg(feature = "telegram")]
+    registry.register(Box::new(telegram_bot::TelegramBotPlatformFactory));
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_registry() {
+        let registry = default_registry();
+        assert_eq!(
+            registry.get_factory("wxwork").is_some(),
+            cfg!(feature = "wxwork")
+        );
+        assert_eq!(
+            registry.get_factory("voice").is_some(),
+            cfg!(feature = "voice")
+        );
+        assert_eq!(
+            registry.get_factory("slack_bot").is_some(),
+            cfg!(feature = "slack_bot")
+        );
+        assert_eq!(
+            registry.get_factory("discord_bot").is_some(),
+            cfg!(feature = "discord_bot")
+        );
+        assert_eq!(
+            registry.get_factory("telegram").is_some(),
+            cfg!(feature = "telegram")
+        );
+    }
+}
@@ -0,0 +1,697 @@
+//! Slack Bot API 平台：用 Bot Token 调用 `chat.postMessage` 发到指定频道
+//!
+//! 与 Incoming Webhook 不同，Bot API 返回消息的 `ts`，因此支持会话回复、编辑、撤回和上传文件，
+//! 适合需要跟踪告警生命周期的场景
+
+use async_trait::async_trait;
+use common::{
+    AttachmentSource, ConfigSchema, DELETE_FEATURE, DRY_RUN_FEATURE, EDIT_FEATURE,
+    HEALTH_CHECK_FEATURE, LengthUnit, MarkdownDialect, Mention, MentionSupport, Message,
+    MessageKind, MessageLimits, MessagePart, MessageType, PlatformContext, PlatformFactory,
+    PlatformInfo, PushConfig, PushError, PushPlatform, PushPlatformCapabilities, PushResult,
+    RateLimit, ResilientPlatform, RetryPolicy, THREAD_FEATURE, THREAD_ID_KEY, convert_markdown,
+    degrade,
+};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+const PLATFORM_NAME: &str = "slack_bot";
+const API_URL: &str = "https://slack.com/api";
+/// 演练时代替上传文件返回的文件 ID
+const DRY_RUN_FILE_ID: &str = "dry-run";
+/// 单条消息文字上限（字符），Slack 建议不超过 4000
+const MAX_TEXT_CHARS: usize = 4000;
+/// 文件大小上限（1GB）
+const MAX_FILE_BYTES: usize = 1024 * 1024 * 1024;
+
+/// Slack Bot 配置
+#[derive(Debug, Clone, Serialize, Deserialize, PushConfig)]
+#[push(
+    platform = PLATFORM_NAME,
+    webhook_url = post_message_url,
+    default_timeout = 30,
+    default_retry_count = 3
+)]
+pub struct SlackBotConfig {
+    /// Bot User OAuth Token，以 `xoxb-` 开头
+    #[push(secret)]
+    pub token: String,
+    /// 频道 ID，如 `C0123456789`，Bot 需已加入该频道
+    pub channel: String,
+    /// 会话回复同时发到频道
+    #[serde(default)]
+    pub reply_broadcast: bool,
+    /// 出站代理
+    #[push(proxy)]
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// 重试策略
+    #[push(retry)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+fn post_message_url(_config: &SlackBotConfig) -> String {
+    format!("{API_URL}/chat.postMessage")
+}
+
+/// Slack Bot 推送平台
+pub struct SlackBotPlatform {
+    config: SlackBotConfig,
+    http_client: Client,
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for SlackBotPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        Ok(())
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(MessageType::Text(content.to_string()), vec![], None)
+            .await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.post(MessageType::Text(content.to_string()), mentions, None)
+            .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(MessageType::Markdown(content.to_string()), vec![], None)
+            .await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let rich = MessageType::Rich {
+            title: title.to_string(),
+            content: content.to_string(),
+            url: url.map(str::to_string),
+        };
+        self.send(rich).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let image = MessageType::Image {
+            url: image_url.to_string(),
+            caption: caption.map(str::to_string),
+        };
+        self.post(image, vec![], None).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let link = MessageType::Link {
+            title: title.to_string(),
+            description: description.to_string(),
+            url: url.to_string(),
+            image_url: image_url.map(str::to_string),
+        };
+        self.post(link, vec![], None).await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        _mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        self.upload(name, source, None, None).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        self.post(degrade(message, &platform_info()), vec![], None)
+            .await
+    }
+
+    /// 元数据中有 [`THREAD_ID_KEY`] 时回复到该会话；拆分发送的消息以第一条的 `ts`
+    /// 作为之后回复的会话 ID
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        let thread_ts = message.metadata.get(THREAD_ID_KEY).map(String::as_str);
+        self.post(part.content, part.mentions, thread_ts).await
+    }
+
+    /// 用新内容替换已发送消息的文字，`message_id` 为发送结果中的 `ts`
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        let text = match degrade(message.content, &platform_info()) {
+            MessageType::Text(text) => text,
+            MessageType::Markdown(content) => convert_markdown(&content, MarkdownDialect::Slack),
+            _ => {
+                return Err(PushError::MessageError(
+                    "Only text messages can be edited on Slack".to_string(),
+                ));
+            }
+        };
+        let payload = json!({
+            "channel": self.config.channel,
+            "ts": message_id,
+            "text": text,
+        });
+        self.api("chat.update", &payload).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        let payload = json!({
+            "channel": self.config.channel,
+            "ts": message_id,
+        });
+        self.api("chat.delete", &payload).await
+    }
+
+    /// 调用 `auth.test` 检查 Token 是否有效
+    async fn health_check(&self) -> Result<bool, PushError> {
+        let request = self.request("auth.test");
+        let (status, retry_after, text) = execute(request).await?;
+        parse_response(status, retry_after, &text).map(|_| true)
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        platform_info()
+    }
+}
+
+/// Slack Bot 平台信息，与配置无关
+fn platform_info() -> PlatformInfo {
+    PlatformInfo {
+        name: PLATFORM_NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        message_types: BTreeSet::from([
+            MessageKind::Text,
+            MessageKind::Markdown,
+            MessageKind::Image,
+            MessageKind::Link,
+            MessageKind::File,
+        ]),
+        mentions: MentionSupport {
+            all: true,
+            user_id: true,
+            ..Default::default()
+        },
+        features: vec![
+            THREAD_FEATURE.to_string(),
+            EDIT_FEATURE.to_string(),
+            DELETE_FEATURE.to_string(),
+            DRY_RUN_FEATURE.to_string(),
+            HEALTH_CHECK_FEATURE.to_string(),
+        ],
+        limits: MessageLimits {
+            text: Some(MAX_TEXT_CHARS),
+            markdown: Some(MAX_TEXT_CHARS),
+            unit: LengthUnit::Chars,
+            attachment_bytes: Some(MAX_FILE_BYTES),
+        },
+        rate_limit: Some(RateLimit {
+            requests: 1,
+            per_secs: 1,
+        }),
+        markdown_dialect: MarkdownDialect::Slack,
+    }
+}
+
+impl PushPlatform<SlackBotConfig> for SlackBotPlatform {
+    fn new(config: SlackBotConfig) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            config,
+            http_client: Client::new(),
+        }
+    }
+}
+
+impl SlackBotPlatform {
+    /// 使用共享的 HTTP 客户端创建平台
+    pub fn with_client(config: SlackBotConfig, http_client: Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    /// 发送一条已降级的消息，`thread_ts` 不为空时回复到该会话
+    async fn post(
+        &self,
+        content: MessageType,
+        mentions: Vec<Mention>,
+        thread_ts: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let mut payload = match content {
+            MessageType::Text(text) => json!({ "text": with_mentions(&text, &mentions) }),
+            MessageType::Markdown(content) => {
+                let content = convert_markdown(&content, MarkdownDialect::Slack);
+                json!({ "text": with_mentions(&content, &mentions), "mrkdwn": true })
+            }
+            // 内联图片没有可引用的地址，作为文件上传
+            MessageType::Image { url, caption } if url.starts_with("data:") => {
+                let name = "image.png";
+                let source = AttachmentSource::Url(url);
+                return self
+                    .upload(name, &source, caption.as_deref(), thread_ts)
+                    .await;
+            }
+            MessageType::Image { url, caption } => {
+                let alt_text = caption.clone().unwrap_or_else(|| "image".to_string());
+                let mut image = json!({ "type": "image", "image_url": url, "alt_text": alt_text });
+                if let Some(caption) = &caption {
+                    image["title"] = json!({ "type": "plain_text", "text": caption });
+                }
+                json!({ "text": with_mentions(&alt_text, &mentions), "blocks": [image] })
+            }
+            MessageType::Link {
+                title,
+                description,
+                url,
+                ..
+            } => {
+                let mut text = format!("*<{}|{}>*", url, escape(&title));
+                if !description.is_empty() {
+                    text.push('\n');
+                    text.push_str(&escape(&description));
+                }
+                json!({ "text": with_mentions(&text, &mentions), "mrkdwn": true })
+            }
+            MessageType::File { name, source, .. } => {
+                return self.upload(&name, &source, None, thread_ts).await;
+            }
+            other => return Err(PushError::unsupported(&platform_info(), &other)),
+        };
+        payload["channel"] = json!(self.config.channel);
+        if let Some(thread_ts) = thread_ts {
+            payload["thread_ts"] = json!(thread_ts);
+            if self.config.reply_broadcast {
+                payload["reply_broadcast"] = json!(true);
+            }
+        }
+        self.api("chat.postMessage", &payload).await
+    }
+
+    /// 按 Slack 的外部上传流程上传文件并分享到频道：申请上传地址、上传内容、完成上传
+    async fn upload(
+        &self,
+        name: &str,
+        source: &AttachmentSource,
+        comment: Option<&str>,
+        thread_ts: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let bytes = source.read(&self.http_client, name, MAX_FILE_BYTES).await?;
+        // 演练时不上传，完成上传的载荷中的文件 ID 为占位值
+        let file_id = if common::dry_run::active() {
+            DRY_RUN_FILE_ID.to_string()
+        } else {
+            let request = self.request("files.getUploadURLExternal").form(&[
+                ("filename", name.to_string()),
+                ("length", bytes.len().to_string()),
+            ]);
+            let (status, retry_after, text) = execute(request).await?;
+            let target: UploadTarget =
+                serde_json::from_value(parse_response(status, retry_after, &text)?)
+                    .map_err(|e| PushError::PlatformError(e.to_string()))?;
+            let request = self
+                .http_client
+                .post(common::endpoint(&target.upload_url))
+                .body(bytes);
+            let (status, retry_after, text) = execute(request).await?;
+            if !status.is_success() {
+                return Err(http_error(
+                    status,
+                    retry_after,
+                    format!("Upload failed with status: {}, body: {}", status, text),
+                ));
+            }
+            target.file_id
+        };
+
+        let mut payload = json!({
+            "files": [{ "id": file_id, "title": name }],
+            "channel_id": self.config.channel,
+        });
+        if let Some(comment) = comment {
+            payload["initial_comment"] = json!(comment);
+        }
+        if let Some(thread_ts) = thread_ts {
+            payload["thread_ts"] = json!(thread_ts);
+        }
+        self.api("files.completeUploadExternal", &payload).await
+    }
+
+    /// 带 Bot Token 的 Web API 请求
+    fn request(&self, method: &str) -> RequestBuilder {
+        self.http_client
+            .post(common::endpoint(&format!("{API_URL}/{method}")))
+            .bearer_auth(&self.config.token)
+    }
+
+    /// 以 JSON 调用 Web API，结果的 `message_id` 为消息的 `ts`
+    async fn api(&self, method: &str, payload: &Value) -> Result<PushResult, PushError> {
+        if let Some(result) = common::dry_run::intercept(payload) {
+            return Ok(result);
+        }
+        let request = self.request(method).json(payload);
+        let (status, retry_after, text) = execute(request).await?;
+        let response = parse_response(status, retry_after, &text)?;
+        Ok(PushResult {
+            success: true,
+            message_id: response["ts"].as_str().map(str::to_string),
+            response: Some(text),
+            http_status: Some(status.as_u16()),
+            ..Default::default()
+        })
+    }
+}
+
+/// 发送请求，返回状态码、Retry-After 和响应体
+async fn execute(
+    request: RequestBuilder,
+) -> Result<(StatusCode, Option<Duration>, String), PushError> {
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            PushError::Timeout(e.to_string())
+        } else {
+            PushError::NetworkError(e.to_string())
+        }
+    })?;
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs);
+    let text = response
+        .text()
+        .await
+        .map_err(|e| PushError::NetworkError(e.to_string()))?;
+    Ok((status, retry_after, text))
+}
+
+/// 解析 Web API 响应，`ok` 为 `false` 时按错误码返回错误
+fn parse_response(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    text: &str,
+) -> Result<Value, PushError> {
+    if !status.is_success() {
+        let message = format!("Request failed with status: {}, body: {}", status, text);
+        return Err(http_error(status, retry_after, message));
+    }
+    let response: SlackResponse =
+        serde_json::from_str(text).map_err(|e| PushError::PlatformError(e.to_string()))?;
+    if response.ok {
+        Ok(response.rest)
+    } else {
+        Err(api_error(
+            response.error.as_deref().unwrap_or("unknown_error"),
+            retry_after,
+        ))
+    }
+}
+
+/// 按 HTTP 状态码映射错误
+fn http_error(status: StatusCode, retry_after: Option<Duration>, message: String) -> PushError {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => PushError::AuthError(message),
+        StatusCode::TOO_MANY_REQUESTS => PushError::RateLimited {
+            message,
+            retry_after,
+        },
+        StatusCode::PAYLOAD_TOO_LARGE => PushError::PayloadTooLarge(message),
+        s if s.is_server_error() => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+/// 将 Slack 错误码映射为结构化错误
+fn api_error(error: &str, retry_after: Option<Duration>) -> PushError {
+    let message = format!("Slack API Error: {}", error);
+    match error {
+        "not_authed" | "invalid_auth" | "account_inactive" | "token_revoked" | "token_expired"
+        | "missing_scope" => PushError::AuthError(message),
+        "ratelimited" | "rate_limited" => PushError::RateLimited {
+            message,
+            retry_after: retry_after.or(Some(Duration::from_secs(1))),
+        },
+        "msg_too_long" | "msg_blocks_too_long" => PushError::PayloadTooLarge(message),
+        "channel_not_found" | "not_in_channel" | "is_archived" => PushError::ConfigError(message),
+        "internal_error" | "fatal_error" | "service_unavailable" | "request_timeout" => {
+            PushError::NetworkError(message)
+        }
+        _ => PushError::PlatformError(message),
+    }
+}
+
+/// 在内容前加上提醒：用户 ID 为 `<@U123>`，所有人为 `<!channel>`，
+/// 手机号和邮箱无法直接提醒，以文字形式附上
+fn with_mentions(content: &str, mentions: &[Mention]) -> String {
+    let tags: Vec<String> = mentions
+        .iter()
+        .map(|mention| match mention {
+            Mention::All => "<!channel>".to_string(),
+            Mention::UserId(id) => format!("<@{}>", id),
+            Mention::Phone(value) | Mention::Email(value) => value.clone(),
+        })
+        .collect();
+    match (tags.is_empty(), content.is_empty()) {
+        (true, _) => content.to_string(),
+        (false, true) => tags.join(" "),
+        (false, false) => format!("{} {}", tags.join(" "), content),
+    }
+}
+
+/// 转义 mrkdwn 中的控制字符
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// --- Slack API Structs ---
+
+#[derive(Deserialize)]
+struct SlackResponse {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(flatten)]
+    rest: Value,
+}
+
+#[derive(Deserialize)]
+struct UploadTarget {
+    upload_url: String,
+    file_id: String,
+}
+
+// --- Platform Factory ---
+
+pub struct SlackBotPlatformFactory;
+
+impl PlatformFactory for SlackBotPlatformFactory {
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        let config: SlackBotConfig =
+            serde_json::from_value(config).map_err(|e| PushError::ConfigError(e.to_string()))?;
+        let http_client = context.client_for(&config)?;
+        let platform = SlackBotPlatform::with_client(config.clone(), http_client);
+        Ok(Box::new(ResilientPlatform::new(platform, &config)))
+    }
+
+    fn name(&self) -> &'static str {
+        PLATFORM_NAME
+    }
+
+    fn config_schema(&self) -> Value {
+        SlackBotConfig::config_schema()
+    }
+
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        Some(platform_info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::wiremock::matchers::any;
+    use test_support::wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config() -> SlackBotConfig {
+        SlackBotConfig {
+            token: "xoxb-TOKEN".to_string(),
+            channel: "C123".to_string(),
+            reply_broadcast: false,
+            proxy: None,
+            retry: None,
+        }
+    }
+
+    fn success() -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .set_body_json(json!({"ok": true, "channel": "C123", "ts": "1700000000.000100"}))
+    }
+
+    fn body(request: &test_support::wiremock::Request) -> Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    #[test]
+    fn test_api_error_classification() {
+        assert!(matches!(
+            api_error("invalid_auth", None),
+            PushError::AuthError(_)
+        ));
+        assert!(matches!(
+            api_error("not_in_channel", None),
+            PushError::ConfigError(_)
+        ));
+        let limited = api_error("ratelimited", Some(Duration::from_secs(30)));
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(30)));
+        assert!(!api_error("invalid_blocks", None).is_retryable());
+    }
+
+    #[test]
+    fn test_with_mentions() {
+        let mentions = vec![Mention::All, "U123".into(), "bob@example.com".into()];
+        assert_eq!(
+            with_mentions("Deploy failed", &mentions),
+            "<!channel> <@U123> bob@example.com Deploy failed"
+        );
+        assert_eq!(with_mentions("", &[Mention::All]), "<!channel>");
+        assert_eq!(with_mentions("hi", &[]), "hi");
+    }
+
+    #[tokio::test]
+    async fn test_thread_reply_update_and_delete() {
+        let upstream = test_support::Upstream::start(success()).await;
+        let platform = SlackBotPlatform::new(SlackBotConfig {
+            reply_broadcast: true,
+            ..config()
+        });
+        let root = Message::new(MessageType::Markdown("**db down**".to_string()));
+        let result = upstream.scope(platform.send_message(root)).await.unwrap();
+        let ts = result.message_id.unwrap();
+        assert_eq!(ts, "1700000000.000100");
+
+        let reply = Message::new(MessageType::Text("still down".to_string()))
+            .with_metadata(THREAD_ID_KEY, ts.clone());
+        upstream.scope(platform.send_message(reply)).await.unwrap();
+        let resolved = Message::new(MessageType::Text("RESOLVED".to_string()));
+        upstream
+            .scope(platform.update_message(&ts, resolved))
+            .await
+            .unwrap();
+        upstream.scope(platform.delete_message(&ts)).await.unwrap();
+
+        let requests = upstream.requests().await;
+        assert!(requests[0].url.path().ends_with("/chat.postMessage"));
+        assert_eq!(body(&requests[0])["text"], "*db down*");
+        assert!(body(&requests[0]).get("thread_ts").is_none());
+        assert_eq!(body(&requests[1])["thread_ts"], ts);
+        assert_eq!(body(&requests[1])["reply_broadcast"], true);
+        assert!(requests[2].url.path().ends_with("/chat.update"));
+        assert_eq!(body(&requests[2])["text"], "RESOLVED");
+        assert!(requests[3].url.path().ends_with("/chat.delete"));
+        assert_eq!(body(&requests[3]), json!({"channel": "C123", "ts": ts}));
+        assert_eq!(
+            requests[0].headers.get("authorization").unwrap(),
+            "Bearer xoxb-TOKEN"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_upload() {
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "upload_url": format!("{}/upload", server.uri()),
+                "file_id": "F123",
+            })))
+            .mount(&server)
+            .await;
+        let platform = SlackBotPlatform::new(config());
+        let source = AttachmentSource::Bytes(b"report".to_vec());
+        common::vcr::redirect(
+            server.uri(),
+            platform.send_file("report.txt", None, &source),
+        )
+        .await
+        .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(
+            requests[0]
+                .url
+                .path()
+                .ends_with("/files.getUploadURLExternal")
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&requests[0].body),
+            "filename=report.txt&length=6"
+        );
+        assert_eq!(requests[1].body, b"report");
+        assert_eq!(
+            body(&requests[2]),
+            json!({"files": [{"id": "F123", "title": "report.txt"}], "channel_id": "C123"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_skips_upload() {
+        let upstream = test_support::Upstream::start(success()).await;
+        let platform = SlackBotPlatform::new(config());
+        let source = AttachmentSource::Bytes(b"report".to_vec());
+        let result = upstream
+            .scope(common::dry_run::scope(platform.send_file(
+                "report.txt",
+                None,
+                &source,
+            )))
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        let payload: Value = serde_json::from_str(result.response.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["files"][0]["id"], DRY_RUN_FILE_ID);
+        assert!(upstream.requests().await.is_empty());
+    }
+
+    test_support::conformance_suite!(
+        SlackBotPlatform::new(config()),
+        test_support::CannedResponses::new(json!({"ok": true, "ts": "1.0"})).with_auth_error(
+            ResponseTemplate::new(200).set_body_json(json!({"ok": false, "error": "invalid_auth"}))
+        )
+    );
+}
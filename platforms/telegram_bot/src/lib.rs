@@ -0,0 +1,690 @@
+//! Telegram Bot 平台：调用 Bot API 发到私聊、群组或频道
+//!
+//! 支持论坛群组的话题（`message_thread_id`），低优先级消息静默发送，
+//! 卡片消息的按钮转为内联键盘
+
+use async_trait::async_trait;
+use common::{
+    CardAction, CardButton, CardSection, ConfigSchema, DELETE_FEATURE, DRY_RUN_FEATURE,
+    EDIT_FEATURE, HEALTH_CHECK_FEATURE, LengthUnit, MarkdownDialect, Mention, MentionSupport,
+    Message, MessageKind, MessageLimits, MessagePart, MessageType, PlatformContext,
+    PlatformFactory, PlatformInfo, Priority, PushConfig, PushError, PushPlatform,
+    PushPlatformCapabilities, PushResult, RateLimit, ResilientPlatform, RetryPolicy,
+    THREAD_FEATURE, THREAD_ID_KEY, card_to_markdown, convert_markdown, degrade,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+const PLATFORM_NAME: &str = "telegram";
+const API_URL: &str = "https://api.telegram.org";
+/// 单条消息文字上限（字符）
+const MAX_TEXT_CHARS: usize = 4096;
+/// 按钮回调数据上限（字节）
+const MAX_CALLBACK_BYTES: usize = 64;
+
+/// Telegram Bot 配置
+#[derive(Debug, Clone, Serialize, Deserialize, PushConfig)]
+#[push(
+    platform = PLATFORM_NAME,
+    webhook_url = api_url,
+    default_timeout = 30,
+    default_retry_count = 3
+)]
+pub struct TelegramBotConfig {
+    /// BotFather 颁发的 Token
+    #[push(secret)]
+    pub bot_token: String,
+    /// 会话 ID，群组为负数，频道也可以写 `@channel_name`
+    pub chat_id: String,
+    /// 论坛群组的话题 ID，为空时发到 General 话题
+    #[serde(default)]
+    pub message_thread_id: Option<i64>,
+    /// 出站代理
+    #[push(proxy)]
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// 重试策略
+    #[push(retry)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+fn api_url(config: &TelegramBotConfig) -> String {
+    format!("{}/bot{}/sendMessage", API_URL, config.bot_token)
+}
+
+/// 单次发送的附加参数
+#[derive(Debug, Clone, Copy, Default)]
+struct SendOptions {
+    /// 回复的消息 ID
+    reply_to: Option<i64>,
+    /// 不发出通知提示音
+    silent: bool,
+}
+
+/// Telegram Bot 推送平台
+pub struct TelegramBotPlatform {
+    config: TelegramBotConfig,
+    http_client: Client,
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for TelegramBotPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        Ok(())
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(
+            MessageType::Text(content.to_string()),
+            &[],
+            SendOptions::default(),
+        )
+        .await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.post(
+            MessageType::Text(content.to_string()),
+            &mentions,
+            SendOptions::default(),
+        )
+        .await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.post(
+            MessageType::Markdown(content.to_string()),
+            &[],
+            SendOptions::default(),
+        )
+        .await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let rich = MessageType::Rich {
+            title: title.to_string(),
+            content: content.to_string(),
+            url: url.map(str::to_string),
+        };
+        self.send(rich).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let image = MessageType::Image {
+            url: image_url.to_string(),
+            caption: caption.map(str::to_string),
+        };
+        self.post(image, &[], SendOptions::default()).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        let link = MessageType::Link {
+            title: title.to_string(),
+            description: description.to_string(),
+            url: url.to_string(),
+            image_url: image_url.map(str::to_string),
+        };
+        self.post(link, &[], SendOptions::default()).await
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        let card = MessageType::Card {
+            title: title.to_string(),
+            sections: sections.to_vec(),
+            buttons: buttons.to_vec(),
+        };
+        self.post(card, &[], SendOptions::default()).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        let message = degrade(message, &platform_info());
+        self.post(message, &[], SendOptions::default()).await
+    }
+
+    /// 低优先级消息静默发送；元数据中有 [`THREAD_ID_KEY`] 时回复该消息
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        let options = SendOptions {
+            reply_to: message
+                .metadata
+                .get(THREAD_ID_KEY)
+                .and_then(|id| id.parse().ok()),
+            silent: message.priority == Priority::Low,
+        };
+        self.post(part.content, &part.mentions, options).await
+    }
+
+    /// 编辑已发送消息的文字，卡片消息同时替换内联键盘
+    async fn update_message(
+        &self,
+        message_id: &str,
+        message: Message,
+    ) -> Result<PushResult, PushError> {
+        let message_id = parse_message_id(message_id)?;
+        let mut payload = match render(degrade(message.content, &platform_info()), &[])? {
+            Rendered::Text(payload) => payload,
+            Rendered::Photo(_) => {
+                return Err(PushError::MessageError(
+                    "Only text messages can be edited on Telegram".to_string(),
+                ));
+            }
+        };
+        payload["chat_id"] = json!(self.config.chat_id);
+        payload["message_id"] = json!(message_id);
+        self.call("editMessageText", &payload).await
+    }
+
+    async fn delete_message(&self, message_id: &str) -> Result<PushResult, PushError> {
+        let payload = json!({
+            "chat_id": self.config.chat_id,
+            "message_id": parse_message_id(message_id)?,
+        });
+        self.call("deleteMessage", &payload).await
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        let response = self
+            .http_client
+            .get(common::endpoint(&self.method_url("getMe")))
+            .send()
+            .await
+            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+        Ok(response.status().is_success())
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        platform_info()
+    }
+}
+
+/// Telegram Bot 平台信息，与配置无关
+fn platform_info() -> PlatformInfo {
+    PlatformInfo {
+        name: PLATFORM_NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        message_types: BTreeSet::from([
+            MessageKind::Text,
+            MessageKind::Markdown,
+            MessageKind::Image,
+            MessageKind::Link,
+            MessageKind::Card,
+        ]),
+        mentions: MentionSupport {
+            user_id: true,
+            ..Default::default()
+        },
+        features: vec![
+            THREAD_FEATURE.to_string(),
+            EDIT_FEATURE.to_string(),
+            DELETE_FEATURE.to_string(),
+            DRY_RUN_FEATURE.to_string(),
+            HEALTH_CHECK_FEATURE.to_string(),
+        ],
+        limits: MessageLimits {
+            text: Some(MAX_TEXT_CHARS),
+            markdown: Some(MAX_TEXT_CHARS),
+            unit: LengthUnit::Chars,
+            attachment_bytes: None,
+        },
+        rate_limit: Some(RateLimit {
+            requests: 20,
+            per_secs: 60,
+        }),
+        markdown_dialect: MarkdownDialect::TelegramV2,
+    }
+}
+
+impl PushPlatform<TelegramBotConfig> for TelegramBotPlatform {
+    fn new(config: TelegramBotConfig) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            config,
+            http_client: Client::new(),
+        }
+    }
+}
+
+impl TelegramBotPlatform {
+    /// 使用共享的 HTTP 客户端创建平台
+    pub fn with_client(config: TelegramBotConfig, http_client: Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    fn method_url(&self, method: &str) -> String {
+        format!("{}/bot{}/{}", API_URL, self.config.bot_token, method)
+    }
+
+    /// 发送一条已降级的消息，带上会话、话题和通知参数
+    async fn post(
+        &self,
+        content: MessageType,
+        mentions: &[Mention],
+        options: SendOptions,
+    ) -> Result<PushResult, PushError> {
+        let (method, mut payload) = match render(content, mentions)? {
+            Rendered::Text(payload) => ("sendMessage", payload),
+            Rendered::Photo(payload) => ("sendPhoto", payload),
+        };
+        payload["chat_id"] = json!(self.config.chat_id);
+        if let Some(thread_id) = self.config.message_thread_id {
+            payload["message_thread_id"] = json!(thread_id);
+        }
+        if let Some(message_id) = options.reply_to {
+            payload["reply_parameters"] = json!({
+                "message_id": message_id,
+                "allow_sending_without_reply": true,
+            });
+        }
+        if options.silent {
+            payload["disable_notification"] = json!(true);
+        }
+        self.call(method, &payload).await
+    }
+
+    /// 调用 Bot API，结果的 `message_id` 为消息 ID
+    async fn call(&self, method: &str, payload: &Value) -> Result<PushResult, PushError> {
+        if let Some(result) = common::dry_run::intercept(payload) {
+            return Ok(result);
+        }
+        let response = self
+            .http_client
+            .post(common::endpoint(&self.method_url(method)))
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    PushError::Timeout(e.to_string())
+                } else {
+                    PushError::NetworkError(e.to_string())
+                }
+            })?;
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
+        let text = response
+            .text()
+            .await
+            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+        let telegram_response: TelegramResponse = match serde_json::from_str(&text) {
+            Ok(response) => response,
+            Err(_) if !status.is_success() => {
+                let message = format!("Request failed with status: {}, body: {}", status, text);
+                return Err(api_error(status.as_u16(), message, retry_after));
+            }
+            Err(e) => return Err(PushError::PlatformError(e.to_string())),
+        };
+        if !telegram_response.ok {
+            let message = format!(
+                "Telegram API Error: {}",
+                telegram_response.description.unwrap_or_default()
+            );
+            let retry_after = telegram_response
+                .parameters
+                .and_then(|p| p.retry_after)
+                .map(Duration::from_secs)
+                .or(retry_after);
+            let code = telegram_response.error_code.unwrap_or(status.as_u16());
+            return Err(api_error(code, message, retry_after));
+        }
+        // deleteMessage 等方法的结果为 true，没有消息 ID
+        let message_id = telegram_response
+            .result
+            .as_ref()
+            .and_then(|r| r.get("message_id"))
+            .map(|id| id.to_string());
+        Ok(PushResult {
+            success: true,
+            message_id,
+            response: Some(text),
+            http_status: Some(status.as_u16()),
+            ..Default::default()
+        })
+    }
+}
+
+/// 渲染后的请求体
+enum Rendered {
+    /// `sendMessage` / `editMessageText`
+    Text(Value),
+    /// `sendPhoto`
+    Photo(Value),
+}
+
+/// 将消息渲染为 Bot API 请求体，不含会话参数
+fn render(content: MessageType, mentions: &[Mention]) -> Result<Rendered, PushError> {
+    let payload = match content {
+        MessageType::Text(text) => json!({ "text": with_mentions(&text, mentions) }),
+        MessageType::Markdown(content) => json!({
+            "text": with_mentions(&convert_markdown(&content, MarkdownDialect::TelegramV2), mentions),
+            "parse_mode": "MarkdownV2",
+        }),
+        MessageType::Image { url, caption } => {
+            let mut payload = json!({ "photo": url });
+            if let Some(caption) = caption {
+                payload["caption"] = json!(caption);
+            }
+            return Ok(Rendered::Photo(payload));
+        }
+        MessageType::Link {
+            title,
+            description,
+            url,
+            ..
+        } => {
+            let mut markdown = format!("[{}]({})", title, url);
+            if !description.is_empty() {
+                markdown.push('\n');
+                markdown.push_str(&description);
+            }
+            json!({
+                "text": convert_markdown(&markdown, MarkdownDialect::TelegramV2),
+                "parse_mode": "MarkdownV2",
+            })
+        }
+        MessageType::Card {
+            title,
+            sections,
+            buttons,
+        } => {
+            // 按钮放在内联键盘中，正文不再重复链接
+            let markdown = card_to_markdown(&title, &sections, &[]);
+            let mut payload = json!({
+                "text": convert_markdown(&markdown, MarkdownDialect::TelegramV2),
+                "parse_mode": "MarkdownV2",
+            });
+            if !buttons.is_empty() {
+                payload["reply_markup"] = inline_keyboard(&buttons)?;
+            }
+            payload
+        }
+        other => return Err(PushError::unsupported(&platform_info(), &other)),
+    };
+    Ok(Rendered::Text(payload))
+}
+
+/// 卡片按钮转为内联键盘，每个按钮一行
+fn inline_keyboard(buttons: &[CardButton]) -> Result<Value, PushError> {
+    let rows = buttons
+        .iter()
+        .map(|button| match &button.action {
+            CardAction::Url(url) => Ok(json!([{ "text": button.label, "url": url }])),
+            CardAction::Callback(data) if data.len() > MAX_CALLBACK_BYTES => {
+                Err(PushError::MessageError(format!(
+                    "Callback data of button '{}' exceeds {} bytes",
+                    button.label, MAX_CALLBACK_BYTES
+                )))
+            }
+            CardAction::Callback(data) => {
+                Ok(json!([{ "text": button.label, "callback_data": data }]))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({ "inline_keyboard": rows }))
+}
+
+/// 在内容前加上提醒，Telegram 只能按用户名提醒，其他对象以文字形式附上
+fn with_mentions(content: &str, mentions: &[Mention]) -> String {
+    let tags: Vec<String> = mentions
+        .iter()
+        .filter_map(|mention| match mention {
+            Mention::All => None,
+            Mention::UserId(id) if id.starts_with('@') => Some(id.clone()),
+            Mention::UserId(id) => Some(format!("@{}", id)),
+            Mention::Phone(value) | Mention::Email(value) => Some(value.clone()),
+        })
+        .collect();
+    match (tags.is_empty(), content.is_empty()) {
+        (true, _) => content.to_string(),
+        (false, true) => tags.join(" "),
+        (false, false) => format!("{} {}", tags.join(" "), content),
+    }
+}
+
+fn parse_message_id(message_id: &str) -> Result<i64, PushError> {
+    message_id
+        .parse()
+        .map_err(|_| PushError::MessageError(format!("Invalid message id: {}", message_id)))
+}
+
+/// 按错误码映射错误，Telegram 的错误码与 HTTP 状态码一致
+fn api_error(code: u16, message: String, retry_after: Option<Duration>) -> PushError {
+    match code {
+        401 | 403 => PushError::AuthError(message),
+        429 => PushError::RateLimited {
+            message,
+            retry_after,
+        },
+        413 => PushError::PayloadTooLarge(message),
+        400 if message.contains("chat not found") => PushError::ConfigError(message),
+        c if c >= 500 => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+// --- Telegram API Structs ---
+
+#[derive(Deserialize)]
+struct TelegramResponse {
+    ok: bool,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error_code: Option<u16>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    parameters: Option<TelegramResponseParameters>,
+}
+
+#[derive(Deserialize)]
+struct TelegramResponseParameters {
+    #[serde(default)]
+    retry_after: Option<u64>,
+}
+
+// --- Platform Factory ---
+
+pub struct TelegramBotPlatformFactory;
+
+impl PlatformFactory for TelegramBotPlatformFactory {
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        let config: TelegramBotConfig =
+            serde_json::from_value(config).map_err(|e| PushError::ConfigError(e.to_string()))?;
+        let http_client = context.client_for(&config)?;
+        let platform = TelegramBotPlatform::with_client(config.clone(), http_client);
+        Ok(Box::new(ResilientPlatform::new(platform, &config)))
+    }
+
+    fn name(&self) -> &'static str {
+        PLATFORM_NAME
+    }
+
+    fn config_schema(&self) -> Value {
+        TelegramBotConfig::config_schema()
+    }
+
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        Some(platform_info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_support::wiremock::ResponseTemplate;
+
+    fn config() -> TelegramBotConfig {
+        TelegramBotConfig {
+            bot_token: "123:ABC".to_string(),
+            chat_id: "-1001".to_string(),
+            message_thread_id: None,
+            proxy: None,
+            retry: None,
+        }
+    }
+
+    fn success() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({"ok": true, "result": {"message_id": 42}}))
+    }
+
+    fn body(request: &test_support::wiremock::Request) -> Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    #[test]
+    fn test_inline_keyboard() {
+        let buttons = vec![
+            CardButton::url("Runbook", "https://wiki/runbook"),
+            CardButton::callback("Ack", "ack:42"),
+        ];
+        assert_eq!(
+            inline_keyboard(&buttons).unwrap(),
+            json!({"inline_keyboard": [
+                [{"text": "Runbook", "url": "https://wiki/runbook"}],
+                [{"text": "Ack", "callback_data": "ack:42"}]
+            ]})
+        );
+        let too_long = vec![CardButton::callback("Ack", "x".repeat(65))];
+        assert!(matches!(
+            inline_keyboard(&too_long),
+            Err(PushError::MessageError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_topic_silent_and_reply() {
+        let upstream = test_support::Upstream::start(success()).await;
+        let platform = TelegramBotPlatform::new(TelegramBotConfig {
+            message_thread_id: Some(7),
+            ..config()
+        });
+        let message = Message::new(MessageType::Text("disk at 80%".to_string()))
+            .with_metadata(THREAD_ID_KEY, "41");
+        let message = Message {
+            priority: Priority::Low,
+            ..message
+        };
+        let result = upstream
+            .scope(platform.send_message(message))
+            .await
+            .unwrap();
+        assert_eq!(result.message_id.as_deref(), Some("42"));
+        let urgent = Message {
+            priority: Priority::Urgent,
+            ..Message::new(MessageType::Text("disk full".to_string()))
+        };
+        upstream.scope(platform.send_message(urgent)).await.unwrap();
+
+        let requests = upstream.requests().await;
+        assert!(requests[0].url.path().ends_with("/bot123:ABC/sendMessage"));
+        let low = body(&requests[0]);
+        assert_eq!(low["message_thread_id"], 7);
+        assert_eq!(low["disable_notification"], true);
+        assert_eq!(low["reply_parameters"]["message_id"], 41);
+        let urgent = body(&requests[1]);
+        assert_eq!(urgent["message_thread_id"], 7);
+        assert!(urgent.get("disable_notification").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_card_edit_and_delete() {
+        let upstream = test_support::Upstream::start(success()).await;
+        let platform = TelegramBotPlatform::new(config());
+        let card = MessageType::Card {
+            title: "DB down".to_string(),
+            sections: vec![CardSection {
+                content: "p99 1.2s".to_string(),
+                ..Default::default()
+            }],
+            buttons: vec![CardButton::callback("Ack", "ack:1")],
+        };
+        upstream.scope(platform.send(card.clone())).await.unwrap();
+        upstream
+            .scope(platform.update_message("42", Message::new(card)))
+            .await
+            .unwrap();
+        upstream.scope(platform.delete_message("42")).await.unwrap();
+
+        let requests = upstream.requests().await;
+        let sent = body(&requests[0]);
+        assert_eq!(sent["parse_mode"], "MarkdownV2");
+        assert_eq!(sent["text"], "*DB down*\n\np99 1\\.2s");
+        assert_eq!(
+            sent["reply_markup"]["inline_keyboard"][0][0]["callback_data"],
+            "ack:1"
+        );
+        assert!(requests[1].url.path().ends_with("/editMessageText"));
+        assert_eq!(body(&requests[1])["message_id"], 42);
+        assert_eq!(body(&requests[1])["reply_markup"], sent["reply_markup"]);
+        assert!(requests[2].url.path().ends_with("/deleteMessage"));
+        assert_eq!(
+            body(&requests[2]),
+            json!({"chat_id": "-1001", "message_id": 42})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_parameters() {
+        let upstream =
+            test_support::Upstream::start(ResponseTemplate::new(429).set_body_json(json!({
+                "ok": false,
+                "error_code": 429,
+                "description": "Too Many Requests: retry after 12",
+                "parameters": {"retry_after": 12}
+            })))
+            .await;
+        let platform = TelegramBotPlatform::new(config());
+        let error = upstream.scope(platform.send_text("hi")).await.unwrap_err();
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(12)));
+    }
+
+    test_support::conformance_suite!(
+        TelegramBotPlatform::new(config()),
+        test_support::CannedResponses::new(json!({"ok": true, "result": {"message_id": 1}}))
+    );
+}
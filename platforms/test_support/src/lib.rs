@@ -0,0 +1,183 @@
+//! 平台 crate 的契约测试
+//!
+//! 用 wiremock 模拟上游返回成功、鉴权失败、限流和无法解析的响应，检查平台把它们
+//! 映射为一致的结果。平台发请求时需用 [`common::endpoint`] 包装地址，
+//! 然后在测试模块中调用 [`conformance_suite!`]：
+//!
+//! ```ignore
+//! test_support::conformance_suite!(
+//!     WxWorkGroupBotPlatform::new(config()),
+//!     test_support::CannedResponses::new(json!({"errcode": 0}))
+//! );
+//! ```
+
+use common::{PushError, PushPlatformCapabilities};
+use serde_json::Value;
+use std::future::Future;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+pub use wiremock;
+
+/// 限流响应默认携带的 Retry-After 秒数
+pub const RETRY_AFTER_SECS: u64 = 30;
+
+/// 模拟的平台上游，所有请求返回同一个响应
+pub struct Upstream {
+    server: MockServer,
+}
+
+impl Upstream {
+    pub async fn start(response: ResponseTemplate) -> Self {
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(response)
+            .mount(&server)
+            .await;
+        Self { server }
+    }
+
+    /// 在作用域内执行 `future`，其中的平台请求发往模拟上游
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        common::vcr::redirect(self.server.uri(), future).await
+    }
+
+    /// 上游收到的请求
+    pub async fn requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+}
+
+/// 上游的典型响应，默认按 HTTP 状态码表示错误，平台以响应体错误码表示时替换对应项
+#[derive(Clone)]
+pub struct CannedResponses {
+    pub success: ResponseTemplate,
+    pub auth_error: ResponseTemplate,
+    pub rate_limit: ResponseTemplate,
+    pub malformed: ResponseTemplate,
+}
+
+impl CannedResponses {
+    /// `success` 为平台发送成功时的响应体
+    pub fn new(success: Value) -> Self {
+        Self {
+            success: ResponseTemplate::new(200).set_body_json(success),
+            auth_error: ResponseTemplate::new(401)
+                .set_body_json(serde_json::json!({"error": "unauthorized"})),
+            rate_limit: ResponseTemplate::new(429)
+                .insert_header("Retry-After", RETRY_AFTER_SECS.to_string().as_str())
+                .set_body_json(serde_json::json!({"error": "too many requests"})),
+            malformed: ResponseTemplate::new(200)
+                .insert_header("Content-Type", "application/json")
+                .set_body_string("{\"errcode\": 0"),
+        }
+    }
+
+    pub fn with_auth_error(mut self, response: ResponseTemplate) -> Self {
+        self.auth_error = response;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, response: ResponseTemplate) -> Self {
+        self.rate_limit = response;
+        self
+    }
+
+    pub fn with_malformed(mut self, response: ResponseTemplate) -> Self {
+        self.malformed = response;
+        self
+    }
+}
+
+/// 发送文本和 Markdown 都成功，且各只发出一个请求
+pub async fn check_success(platform: &dyn PushPlatformCapabilities, responses: &CannedResponses) {
+    let upstream = Upstream::start(responses.success.clone()).await;
+    let text = upstream.scope(platform.send_text("conformance")).await;
+    let text = text.unwrap_or_else(|e| panic!("send_text failed: {}", e));
+    assert!(text.success, "send_text returned an unsuccessful result");
+    let markdown = upstream
+        .scope(platform.send_markdown("**conformance**"))
+        .await;
+    let markdown = markdown.unwrap_or_else(|e| panic!("send_markdown failed: {}", e));
+    assert!(
+        markdown.success,
+        "send_markdown returned an unsuccessful result"
+    );
+    assert_eq!(
+        upstream.requests().await.len(),
+        2,
+        "each send should issue exactly one request"
+    );
+}
+
+/// 鉴权失败映射为不可重试的 [`PushError::AuthError`]
+pub async fn check_auth_error(
+    platform: &dyn PushPlatformCapabilities,
+    responses: &CannedResponses,
+) {
+    let upstream = Upstream::start(responses.auth_error.clone()).await;
+    match upstream.scope(platform.send_text("conformance")).await {
+        Err(e) if matches!(e.inner(), PushError::AuthError(_)) => assert!(!e.is_retryable()),
+        other => panic!("expected an authentication error, got {:?}", other),
+    }
+}
+
+/// 限流映射为 [`PushError::RateLimited`] 并带上等待时间
+pub async fn check_rate_limit(
+    platform: &dyn PushPlatformCapabilities,
+    responses: &CannedResponses,
+) {
+    let upstream = Upstream::start(responses.rate_limit.clone()).await;
+    match upstream.scope(platform.send_text("conformance")).await {
+        Err(e) if matches!(e.inner(), PushError::RateLimited { .. }) => {
+            assert!(e.is_retryable());
+            assert!(e.retry_after().is_some(), "rate limit without retry_after");
+        }
+        other => panic!("expected a rate limit error, got {:?}", other),
+    }
+}
+
+/// 无法解析的响应返回错误而不是 panic 或当作成功
+pub async fn check_malformed(platform: &dyn PushPlatformCapabilities, responses: &CannedResponses) {
+    let upstream = Upstream::start(responses.malformed.clone()).await;
+    let result = upstream.scope(platform.send_text("conformance")).await;
+    assert!(
+        result.is_err(),
+        "malformed response treated as success: {:?}",
+        result
+    );
+}
+
+/// 对平台运行全部契约测试，生成 `conformance` 测试模块
+///
+/// `$platform` 和 `$responses` 在每个测试中重新求值，可以引用调用处模块中的项；
+/// 调用的 crate 需依赖带 `macros` 特性的 tokio
+#[macro_export]
+macro_rules! conformance_suite {
+    ($platform:expr, $responses:expr $(,)?) => {
+        mod conformance {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[tokio::test]
+            async fn success() {
+                $crate::check_success(&$platform, &$responses).await;
+            }
+
+            #[tokio::test]
+            async fn auth_error() {
+                $crate::check_auth_error(&$platform, &$responses).await;
+            }
+
+            #[tokio::test]
+            async fn rate_limit() {
+                $crate::check_rate_limit(&$platform, &$responses).await;
+            }
+
+            #[tokio::test]
+            async fn malformed() {
+                $crate::check_malformed(&$platform, &$responses).await;
+            }
+        }
+    };
+}
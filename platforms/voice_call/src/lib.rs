@@ -0,0 +1,713 @@
+//! 电话告警：通过 Twilio Voice 或阿里云语音服务拨打电话，以文本转语音朗读消息内容
+//!
+//! 电话按次计费且会吵醒值班人员，默认只为 `urgent` 优先级的消息拨打，
+//! 适合作为升级链的最后一级
+
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use common::{
+    ConfigSchema, DRY_RUN_FEATURE, Mention, Message, MessageKind, MessageLimits, MessagePart,
+    MessageType, PlatformContext, PlatformFactory, PlatformInfo, Priority, PushConfig, PushError,
+    PushInitConfig, PushPlatform, PushPlatformCapabilities, PushResult, ResilientPlatform,
+    RetryPolicy, degrade, sign, strip_markdown,
+};
+use log::*;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+const PLATFORM_NAME: &str = "voice";
+const TWILIO_BASE_URL: &str = "https://api.twilio.com/2010-04-01/Accounts";
+const ALIYUN_URL: &str = "https://dyvmsapi.aliyuncs.com/";
+const ALIYUN_API_VERSION: &str = "2017-05-25";
+/// 阿里云语音模板中引用消息内容的变量名
+const ALIYUN_TTS_VARIABLE: &str = "content";
+/// 朗读内容上限（字符），过长的内容在电话中难以听清
+const MAX_SPEECH_CHARS: usize = 300;
+
+/// 语音服务商
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoiceProvider {
+    Twilio,
+    /// 阿里云语音服务（VMS）
+    Aliyun,
+}
+
+/// 电话告警配置
+#[derive(Debug, Clone, Serialize, Deserialize, PushConfig)]
+#[push(
+    platform = PLATFORM_NAME,
+    webhook_url = api_url,
+    default_timeout = 30,
+    default_retry_count = 0
+)]
+pub struct VoiceConfig {
+    /// 语音服务商，`twilio` 或 `aliyun`
+    pub provider: VoiceProvider,
+    /// Twilio 的 Account SID 或阿里云的 AccessKey ID
+    pub account: String,
+    /// Twilio 的 Auth Token 或阿里云的 AccessKey Secret
+    #[push(secret)]
+    pub secret: String,
+    /// 主叫号码，阿里云为已购买的显示号码
+    pub caller: String,
+    /// 被叫号码，依次拨打
+    pub callees: Vec<String>,
+    /// 阿里云文本转语音模板 ID，模板中用 `${content}` 引用消息内容
+    #[serde(default)]
+    pub tts_code: Option<String>,
+    /// 朗读语言，如 `zh-CN`、`en-US`，仅 Twilio 使用
+    #[serde(default)]
+    pub language: Option<String>,
+    /// 朗读次数
+    #[serde(default = "default_play_times")]
+    pub play_times: u32,
+    /// 只为不低于该优先级的消息拨打电话
+    #[serde(default = "default_min_priority")]
+    pub min_priority: Priority,
+    /// 出站代理
+    #[push(proxy)]
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// 重试策略，默认不重试，避免请求超时但电话已拨出时重复拨打
+    #[push(retry)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+fn default_play_times() -> u32 {
+    2
+}
+
+fn default_min_priority() -> Priority {
+    Priority::Urgent
+}
+
+fn api_url(config: &VoiceConfig) -> String {
+    match config.provider {
+        VoiceProvider::Twilio => format!("{TWILIO_BASE_URL}/{}/Calls.json", config.account),
+        VoiceProvider::Aliyun => ALIYUN_URL.to_string(),
+    }
+}
+
+impl VoiceConfig {
+    /// 检查服务商所需的字段
+    fn validate(&self) -> Result<(), PushError> {
+        if self.callees.is_empty() {
+            return Err(PushError::ConfigError(
+                "Voice channel needs at least one callee".to_string(),
+            ));
+        }
+        if self.provider == VoiceProvider::Aliyun && self.tts_code.is_none() {
+            return Err(PushError::ConfigError(
+                "Aliyun voice calls need a tts_code".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 电话告警平台
+pub struct VoicePlatform {
+    config: VoiceConfig,
+    http_client: Client,
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for VoicePlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        self.config.validate()
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.call_all(content).await
+    }
+
+    /// 电话无法提醒指定的人，始终拨打配置的被叫号码
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        _mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.call_all(content).await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.call_all(&strip_markdown(content)).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        _url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.call_all(&format!("{}\n{}", title, content)).await
+    }
+
+    async fn send_image(
+        &self,
+        _image_url: &str,
+        caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        match caption {
+            Some(caption) => self.call_all(caption).await,
+            None => Err(PushError::MessageError(
+                "Voice calls cannot read an image without a caption".to_string(),
+            )),
+        }
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        description: &str,
+        _url: &str,
+        _image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.call_all(&format!("{}\n{}", title, description)).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        match degrade(message, &platform_info()) {
+            MessageType::Text(content) => self.send_text(&content).await,
+            MessageType::Markdown(content) => self.send_markdown(&content).await,
+            other => Err(PushError::unsupported(&platform_info(), &other)),
+        }
+    }
+
+    /// 低于 `min_priority` 的消息跳过不拨打，不算失败；超长内容截断而不是拆分，一条消息只打一通电话
+    /// 图表等补发内容不拨打电话
+    async fn send_part(
+        &self,
+        message: &Message,
+        part: MessagePart,
+    ) -> Result<PushResult, PushError> {
+        if part.follow_up {
+            return Ok(PushResult {
+                success: true,
+                response: Some("Skipped: voice calls only read out the message body".to_string()),
+                ..Default::default()
+            });
+        }
+        if message.priority < self.config.min_priority {
+            debug!(
+                "Skipping voice call for {:?} priority message",
+                message.priority
+            );
+            return Ok(PushResult {
+                success: true,
+                response: Some(format!(
+                    "Skipped: voice calls are only placed for {:?} priority and above",
+                    self.config.min_priority
+                )),
+                ..Default::default()
+            });
+        }
+        self.send(part.content).await
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        Ok(self.config.validate().is_ok())
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        platform_info()
+    }
+}
+
+/// 电话告警平台信息，与配置无关
+fn platform_info() -> PlatformInfo {
+    PlatformInfo {
+        name: PLATFORM_NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        message_types: BTreeSet::from([MessageKind::Text]),
+        mentions: Default::default(),
+        features: vec![DRY_RUN_FEATURE.to_string()],
+        limits: MessageLimits::default(),
+        rate_limit: None,
+        markdown_dialect: Default::default(),
+    }
+}
+
+impl PushPlatform<VoiceConfig> for VoicePlatform {
+    fn new(config: VoiceConfig) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            config,
+            http_client: Client::new(),
+        }
+    }
+}
+
+impl VoicePlatform {
+    /// 使用共享的 HTTP 客户端创建平台
+    pub fn with_client(config: VoiceConfig, http_client: Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    /// 依次拨打所有被叫号码，至少一通电话拨出即视为成功
+    async fn call_all(&self, content: &str) -> Result<PushResult, PushError> {
+        let speech = speech(content);
+        if speech.is_empty() {
+            return Err(PushError::MessageError("Message is empty".to_string()));
+        }
+        let mut placed: Vec<PushResult> = Vec::new();
+        let mut last_error = None;
+        for callee in &self.config.callees {
+            match self.call(callee, &speech).await {
+                Ok(result) => placed.push(result),
+                Err(e) => {
+                    warn!("Failed to place voice call to {}: {}", callee, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if placed.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                PushError::ConfigError("Voice channel needs at least one callee".to_string())
+            }));
+        }
+        let call_ids: Vec<&str> = placed
+            .iter()
+            .filter_map(|result| result.message_id.as_deref())
+            .collect();
+        let mut result = placed[0].clone();
+        if placed.len() > 1 && !result.dry_run {
+            result.response = Some(format!(
+                "Placed {} of {} call(s): {}",
+                placed.len(),
+                self.config.callees.len(),
+                call_ids.join(", ")
+            ));
+        }
+        Ok(result)
+    }
+
+    async fn call(&self, callee: &str, speech: &str) -> Result<PushResult, PushError> {
+        match self.config.provider {
+            VoiceProvider::Twilio => self.call_twilio(callee, speech).await,
+            VoiceProvider::Aliyun => self.call_aliyun(callee, speech).await,
+        }
+    }
+
+    async fn call_twilio(&self, callee: &str, speech: &str) -> Result<PushResult, PushError> {
+        let payload = TwilioCall {
+            to: callee.to_string(),
+            from: self.config.caller.clone(),
+            twiml: twiml(
+                speech,
+                self.config.language.as_deref(),
+                self.config.play_times,
+            ),
+        };
+        if let Some(result) = common::dry_run::intercept(&payload) {
+            return Ok(result);
+        }
+        let request = self
+            .http_client
+            .post(common::endpoint(&self.config.webhook_url()))
+            .basic_auth(&self.config.account, Some(&self.config.secret))
+            .form(&payload);
+        let (status, retry_after, text) = execute(request).await?;
+        if !status.is_success() {
+            let message = match serde_json::from_str::<TwilioError>(&text) {
+                Ok(error) => format!(
+                    "Twilio API Error: code={}, message={}",
+                    error.code.unwrap_or_default(),
+                    error.message
+                ),
+                Err(_) => format!("Request failed with status: {}, body: {}", status, text),
+            };
+            return Err(http_error(status, retry_after, message));
+        }
+        let call: TwilioCallResponse =
+            serde_json::from_str(&text).map_err(|e| PushError::PlatformError(e.to_string()))?;
+        Ok(PushResult {
+            success: true,
+            message_id: Some(call.sid),
+            response: Some(text),
+            http_status: Some(status.as_u16()),
+            ..Default::default()
+        })
+    }
+
+    async fn call_aliyun(&self, callee: &str, speech: &str) -> Result<PushResult, PushError> {
+        let tts_code = self.config.tts_code.clone().unwrap_or_default();
+        let tts_param = serde_json::to_string(&BTreeMap::from([(ALIYUN_TTS_VARIABLE, speech)]))
+            .unwrap_or_default();
+        let mut params: BTreeMap<&str, String> = BTreeMap::from([
+            ("Action", "SingleCallByTts".to_string()),
+            ("Version", ALIYUN_API_VERSION.to_string()),
+            ("Format", "JSON".to_string()),
+            ("AccessKeyId", self.config.account.clone()),
+            ("SignatureMethod", "HMAC-SHA1".to_string()),
+            ("SignatureVersion", "1.0".to_string()),
+            ("SignatureNonce", uuid::Uuid::new_v4().to_string()),
+            (
+                "Timestamp",
+                Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            ),
+            ("CalledShowNumber", self.config.caller.clone()),
+            ("CalledNumber", callee.to_string()),
+            ("TtsCode", tts_code),
+            ("TtsParam", tts_param),
+            ("PlayTimes", self.config.play_times.to_string()),
+        ]);
+        if let Some(result) = common::dry_run::intercept(&params) {
+            return Ok(result);
+        }
+        let signature = aliyun_signature("POST", &params, &self.config.secret)?;
+        params.insert("Signature", signature);
+        let request = self
+            .http_client
+            .post(common::endpoint(&self.config.webhook_url()))
+            .form(&params);
+        let (status, retry_after, text) = execute(request).await?;
+        match serde_json::from_str::<AliyunResponse>(&text) {
+            Ok(response) if status.is_success() && response.code == "OK" => Ok(PushResult {
+                success: true,
+                message_id: response.call_id,
+                response: Some(text),
+                http_status: Some(status.as_u16()),
+                error_code: Some(response.code),
+                ..Default::default()
+            }),
+            Ok(response) => Err(aliyun_error(&response.code, &response.message)),
+            Err(_) if !status.is_success() => Err(http_error(
+                status,
+                retry_after,
+                format!("Request failed with status: {}, body: {}", status, text),
+            )),
+            Err(e) => Err(PushError::PlatformError(e.to_string())),
+        }
+    }
+}
+
+/// 发送请求，返回状态码、Retry-After 和响应体
+async fn execute(
+    request: reqwest::RequestBuilder,
+) -> Result<(StatusCode, Option<Duration>, String), PushError> {
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            PushError::Timeout(e.to_string())
+        } else {
+            PushError::NetworkError(e.to_string())
+        }
+    })?;
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs);
+    let text = response
+        .text()
+        .await
+        .map_err(|e| PushError::NetworkError(e.to_string()))?;
+    Ok((status, retry_after, text))
+}
+
+/// 按 HTTP 状态码映射错误
+fn http_error(status: StatusCode, retry_after: Option<Duration>, message: String) -> PushError {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => PushError::AuthError(message),
+        StatusCode::TOO_MANY_REQUESTS => PushError::RateLimited {
+            message,
+            retry_after,
+        },
+        s if s.is_server_error() => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+/// 将阿里云错误码映射为结构化错误
+fn aliyun_error(code: &str, message: &str) -> PushError {
+    let message = format!("Aliyun VMS Error: code={}, message={}", code, message);
+    match code {
+        // 同一号码的呼叫频率受流控限制
+        "isv.BUSINESS_LIMIT_CONTROL" | "Throttling.User" => PushError::RateLimited {
+            message,
+            retry_after: Some(Duration::from_secs(60)),
+        },
+        "SignatureDoesNotMatch" | "Forbidden.RAM" => PushError::AuthError(message),
+        c if c.starts_with("InvalidAccessKeyId") => PushError::AuthError(message),
+        "InternalError" | "ServiceUnavailable" => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    }
+}
+
+/// 朗读的内容：合并空白，超长时截断
+fn speech(content: &str) -> String {
+    let words: Vec<&str> = content.split_whitespace().collect();
+    words.join(" ").chars().take(MAX_SPEECH_CHARS).collect()
+}
+
+/// Twilio 通话的 TwiML 指令
+fn twiml(speech: &str, language: Option<&str>, play_times: u32) -> String {
+    let language = language
+        .map(|language| format!(" language=\"{}\"", escape_xml(language)))
+        .unwrap_or_default();
+    format!(
+        "<Response><Say{} loop=\"{}\">{}</Say></Response>",
+        language,
+        play_times.max(1),
+        escape_xml(speech)
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// 阿里云 RPC 风格接口的 HMAC-SHA1 签名
+fn aliyun_signature(
+    method: &str,
+    params: &BTreeMap<&str, String>,
+    secret: &str,
+) -> Result<String, PushError> {
+    let query = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", sign::url_encode(key), sign::url_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let string_to_sign = format!("{}&%2F&{}", method, sign::url_encode(&query));
+    let mac = sign::hmac_sha1(format!("{}&", secret).as_bytes(), string_to_sign.as_bytes())?;
+    Ok(sign::base64(&mac))
+}
+
+// --- Provider API Structs ---
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct TwilioCall {
+    to: String,
+    from: String,
+    twiml: String,
+}
+
+#[derive(Deserialize)]
+struct TwilioCallResponse {
+    sid: String,
+}
+
+#[derive(Deserialize)]
+struct TwilioError {
+    #[serde(default)]
+    code: Option<i64>,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AliyunResponse {
+    code: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    call_id: Option<String>,
+}
+
+// --- Platform Factory ---
+
+pub struct VoicePlatformFactory;
+
+impl PlatformFactory for VoicePlatformFactory {
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        let config: VoiceConfig =
+            serde_json::from_value(config).map_err(|e| PushError::ConfigError(e.to_string()))?;
+        config.validate()?;
+        let http_client = context.client_for(&config)?;
+        let platform = VoicePlatform::with_client(config.clone(), http_client);
+        Ok(Box::new(ResilientPlatform::new(platform, &config)))
+    }
+
+    fn name(&self) -> &'static str {
+        PLATFORM_NAME
+    }
+
+    fn config_schema(&self) -> Value {
+        VoiceConfig::config_schema()
+    }
+
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        Some(platform_info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(provider: VoiceProvider) -> VoiceConfig {
+        serde_json::from_value(json!({
+            "provider": provider,
+            "account": "ACCOUNT",
+            "secret": "SECRET",
+            "caller": "+15550100",
+            "callees": ["+15550101"],
+            "tts_code": "TTS_1",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_config_defaults_and_validation() {
+        let config = config(VoiceProvider::Twilio);
+        assert_eq!(config.min_priority, Priority::Urgent);
+        assert_eq!(config.play_times, 2);
+        assert_eq!(config.retry_count(), 0);
+        assert_eq!(
+            config.webhook_url(),
+            "https://api.twilio.com/2010-04-01/Accounts/ACCOUNT/Calls.json"
+        );
+
+        let invalid = VoiceConfig {
+            tts_code: None,
+            ..self::config(VoiceProvider::Aliyun)
+        };
+        assert!(matches!(invalid.validate(), Err(PushError::ConfigError(_))));
+        let schema = VoicePlatformFactory.config_schema();
+        assert_eq!(schema["properties"]["callees"]["type"], "array");
+    }
+
+    #[test]
+    fn test_twiml() {
+        assert_eq!(
+            twiml("CPU > 90% on db&cache", Some("en-US"), 2),
+            "<Response><Say language=\"en-US\" loop=\"2\">CPU &gt; 90% on db&amp;cache</Say></Response>"
+        );
+        assert_eq!(speech("  disk\n\nfull  "), "disk full");
+        assert_eq!(speech(&"告".repeat(500)).chars().count(), MAX_SPEECH_CHARS);
+    }
+
+    #[test]
+    fn test_aliyun_signature() {
+        // 阿里云签名文档中的示例
+        let params = BTreeMap::from([
+            ("AccessKeyId", "testid".to_string()),
+            ("Action", "DescribeRegions".to_string()),
+            ("Format", "XML".to_string()),
+            ("SignatureMethod", "HMAC-SHA1".to_string()),
+            (
+                "SignatureNonce",
+                "3ee8c1b8-83d3-44af-a94f-4e0ad82fd6cf".to_string(),
+            ),
+            ("SignatureVersion", "1.0".to_string()),
+            ("Timestamp", "2016-02-23T12:46:24Z".to_string()),
+            ("Version", "2014-05-26".to_string()),
+        ]);
+        assert_eq!(
+            aliyun_signature("GET", &params, "testsecret").unwrap(),
+            "OLeaidS1JvxuMvnyHOwuJ+uX5qY="
+        );
+        assert_eq!(sign::url_encode("a b*~"), "a%20b%2A~");
+    }
+
+    #[test]
+    fn test_aliyun_error_classification() {
+        let limited = aliyun_error("isv.BUSINESS_LIMIT_CONTROL", "too frequent");
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(60)));
+        assert!(matches!(
+            aliyun_error("InvalidAccessKeyId.NotFound", "not found"),
+            PushError::AuthError(_)
+        ));
+        assert!(!aliyun_error("isv.MOBILE_NUMBER_ILLEGAL", "bad number").is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_only_calls_for_urgent_messages() {
+        let upstream = test_support::Upstream::start(
+            test_support::wiremock::ResponseTemplate::new(201).set_body_json(json!({"sid": "CA1"})),
+        )
+        .await;
+        let platform = VoicePlatform::new(config(VoiceProvider::Twilio));
+        let message = Message::new(MessageType::Markdown("**db down**".to_string()));
+        // 非紧急消息跳过，不算失败
+        let result = upstream
+            .scope(platform.send_message(message.clone()))
+            .await
+            .unwrap();
+        assert!(result.success && result.message_id.is_none());
+        assert!(result.response.unwrap().starts_with("Skipped"));
+        assert!(upstream.requests().await.is_empty());
+
+        let mut urgent = message;
+        urgent.priority = Priority::Urgent;
+        let result = upstream.scope(platform.send_message(urgent)).await.unwrap();
+        assert_eq!(result.message_id.as_deref(), Some("CA1"));
+        let requests = upstream.requests().await;
+        let form: BTreeMap<String, String> = reqwest::Url::parse(&format!(
+            "http://x/?{}",
+            String::from_utf8_lossy(&requests[0].body)
+        ))
+        .unwrap()
+        .query_pairs()
+        .into_owned()
+        .collect();
+        assert_eq!(form["To"], "+15550101");
+        assert_eq!(
+            form["Twiml"],
+            "<Response><Say loop=\"2\">db down</Say></Response>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_skips_call() {
+        let platform = VoicePlatform::new(config(VoiceProvider::Aliyun));
+        let result = common::dry_run::scope(platform.send_text("db down"))
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        let payload: Value = serde_json::from_str(result.response.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["TtsParam"], "{\"content\":\"db down\"}");
+        assert!(payload.get("Signature").is_none());
+    }
+
+    mod twilio {
+        use super::*;
+
+        test_support::conformance_suite!(
+            VoicePlatform::new(config(VoiceProvider::Twilio)),
+            test_support::CannedResponses::new(json!({"sid": "CA1", "status": "queued"}))
+        );
+    }
+
+    mod aliyun {
+        use super::*;
+        use test_support::wiremock::ResponseTemplate;
+
+        fn responses() -> test_support::CannedResponses {
+            let api_error = |code: &str| {
+                ResponseTemplate::new(400).set_body_json(json!({"Code": code, "Message": "error"}))
+            };
+            test_support::CannedResponses::new(json!({"Code": "OK", "CallId": "1"}))
+                .with_auth_error(api_error("InvalidAccessKeyId.NotFound"))
+                .with_rate_limit(api_error("isv.BUSINESS_LIMIT_CONTROL"))
+        }
+
+        test_support::conformance_suite!(
+            VoicePlatform::new(config(VoiceProvider::Aliyun)),
+            responses()
+        );
+    }
+}
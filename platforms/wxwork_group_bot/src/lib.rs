@@ -1,43 +1,70 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use common::{
-    MessageType, PlatformFactory, PlatformInfo, PushError, PushInitConfig, PushPlatform,
-    PushPlatformCapabilities, PushResult,
+    AttachmentSource, CardButton, CardSection, ConfigSchema, DRY_RUN_FEATURE, LengthUnit,
+    MENTION_FOLLOW_UP_FEATURE, MarkdownDialect, Mention, MentionSupport, Message, MessageKind,
+    MessageLimits, MessageType, PlatformContext, PlatformFactory, PlatformInfo, PushConfig,
+    PushError, PushInitConfig, PushPlatform, PushPlatformCapabilities, PushResult, RateLimit,
+    ResilientPlatform, RetryPolicy, card_to_markdown,
 };
-use log::*;
-use reqwest::Client;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeSet;
+use std::time::Duration;
 
 const PLATFORM_NAME: &str = "wxwork";
 const BASE_URL: &str = "https://qyapi.weixin.qq.com/cgi-bin/webhook/send";
+const UPLOAD_URL: &str = "https://qyapi.weixin.qq.com/cgi-bin/webhook/upload_media";
+/// 演练时代替上传素材返回的 media_id
+const DRY_RUN_MEDIA_ID: &str = "dry-run";
+/// 模板卡片跳转链接数量上限
+const MAX_CARD_JUMPS: usize = 3;
+/// 模板卡片二级标题+文本列表数量上限
+const MAX_CARD_FIELDS: usize = 6;
+/// 文件素材大小上限（20MB）
+const MAX_MEDIA_BYTES: usize = 20 * 1024 * 1024;
+/// 图片大小上限（base64 编码前 2MB）
+const MAX_IMAGE_BYTES: usize = 2 * 1024 * 1024;
+/// 语音素材大小上限（2MB）
+const MAX_VOICE_BYTES: usize = 2 * 1024 * 1024;
+/// 语音时长上限（秒）
+const MAX_VOICE_SECS: u32 = 60;
+/// AMR 文件头，语音消息只接受 AMR 格式
+const AMR_MAGIC: &[u8] = b"#!AMR";
+/// 图文消息文章数量上限
+const MAX_NEWS_ARTICLES: usize = 8;
+/// 文本消息内容上限（字节）
+const MAX_TEXT_BYTES: usize = 2048;
+/// Markdown 消息内容上限（字节）
+const MAX_MARKDOWN_BYTES: usize = 4096;
 
-/// 企业微信机器人配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 企业微信机器人配置，机器人通常不使用独立的 secret
+#[derive(Debug, Clone, Serialize, Deserialize, PushConfig)]
+#[push(
+    platform = PLATFORM_NAME,
+    webhook_url = webhook_url,
+    default_timeout = 30,
+    default_retry_count = 3
+)]
 pub struct WxWorkConfig {
+    /// 机器人 webhook 地址中的 key
     pub token: String,
+    /// 出站代理
+    #[push(proxy)]
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// 重试策略
+    #[push(retry)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
 }
 
-impl PushInitConfig for WxWorkConfig {
-    fn platform_name(&self) -> &str {
-        PLATFORM_NAME
-    }
-
-    fn webhook_url(&self) -> String {
-        format!("{BASE_URL}?key={}", self.token)
-    }
-
-    fn secret(&self) -> Option<&str> {
-        None // 企业微信机器人通常不使用独立的 secret
-    }
-
-    fn timeout(&self) -> u64 {
-        30
-    }
-
-    fn retry_count(&self) -> u32 {
-        3
-    }
+fn webhook_url(config: &WxWorkConfig) -> String {
+    format!("{BASE_URL}?key={}", config.token)
 }
 
 /// 企业微信群机器人推送平台
@@ -54,25 +81,17 @@ impl PushPlatformCapabilities for WxWorkGroupBotPlatform {
     }
 
     async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
-        let payload = WxWorkTextPayload {
-            msgtype: "text".to_string(),
-            text: WxWorkText {
-                content: content.to_string(),
-                mentioned_list: vec![],
-                mentioned_mobile_list: vec![],
-            },
-
-        };
-        self.send_request(payload).await
+        self.send_text_with_mention(content, vec![]).await
     }
-    async fn send_text_with_mention(&self, content: &str, mention_list: Vec<String>) -> std::result::Result<PushResult, PushError>  {
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
         let payload = WxWorkTextPayload {
             msgtype: "text".to_string(),
-            text: WxWorkText {
-                content: content.to_string(),
-                mentioned_list: mention_list,
-                mentioned_mobile_list: vec![],
-            },
+            text: text_with_mentions(content, mentions),
         };
         self.send_request(payload).await
     }
@@ -93,58 +112,222 @@ impl PushPlatformCapabilities for WxWorkGroupBotPlatform {
         _content: &str,
         _url: Option<&str>,
     ) -> Result<PushResult, PushError> {
-        Err(PushError::PlatformError(
-            "WxWork Bot does not support rich text messages directly.".to_string(),
-        ))
+        Err(PushError::Unsupported {
+            platform: PLATFORM_NAME.to_string(),
+            message_type: MessageKind::Rich,
+            suggestion: Some(MessageKind::Markdown),
+        })
     }
 
     async fn send_image(
         &self,
-        _image_url: &str,
-        _caption: Option<&str>,
+        image_url: &str,
+        caption: Option<&str>,
     ) -> Result<PushResult, PushError> {
-        Err(PushError::PlatformError(
-            "WxWork Bot image sending requires pre-uploading.".to_string(),
-        ))
+        let result = self
+            .send_image_source(&AttachmentSource::Url(image_url.to_string()))
+            .await?;
+        // 图片消息不支持附带文字，说明文字单独发送
+        match caption {
+            Some(caption) => self.send_text(caption).await,
+            None => Ok(result),
+        }
     }
 
     async fn send_link(
         &self,
-        _title: &str,
-        _description: &str,
-        _url: &str,
-        _image_url: Option<&str>,
+        title: &str,
+        description: &str,
+        url: &str,
+        image_url: Option<&str>,
     ) -> Result<PushResult, PushError> {
-        Err(PushError::PlatformError(
-            "WxWork Bot does not support link messages.".to_string(),
-        ))
+        self.send_news(vec![WxWorkArticle {
+            title: title.to_string(),
+            description: description.to_string(),
+            url: url.to_string(),
+            picurl: image_url.map(str::to_string),
+        }])
+        .await
+    }
+
+    async fn send_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        let bytes = source
+            .read(&self.http_client, name, MediaKind::File.max_bytes())
+            .await?;
+        self.send_media_file(name, mime, bytes).await
+    }
+
+    /// 语音消息仅支持 2MB 以内、时长不超过 60 秒的 AMR 文件，其他音频以文件消息发送
+    async fn send_audio(
+        &self,
+        source: &AttachmentSource,
+        caption: Option<&str>,
+        duration_secs: Option<u32>,
+    ) -> Result<PushResult, PushError> {
+        let name = audio_file_name(source);
+        let bytes = source
+            .read(&self.http_client, &name, MediaKind::File.max_bytes())
+            .await?;
+        let voice = bytes.starts_with(AMR_MAGIC)
+            && bytes.len() <= MediaKind::Voice.max_bytes()
+            && duration_secs.is_none_or(|secs| secs <= MAX_VOICE_SECS);
+        let result = if voice {
+            let media_id = self
+                .upload_media(MediaKind::Voice, "voice.amr", Some("audio/amr"), bytes)
+                .await?;
+            let payload = WxWorkVoicePayload {
+                msgtype: "voice".to_string(),
+                voice: WxWorkFile { media_id },
+            };
+            self.send_request(payload).await?
+        } else {
+            self.send_media_file(&name, None, bytes).await?
+        };
+        match caption {
+            Some(caption) => self.send_text(caption).await,
+            None => Ok(result),
+        }
+    }
+
+    async fn send_card(
+        &self,
+        title: &str,
+        sections: &[CardSection],
+        buttons: &[CardButton],
+    ) -> Result<PushResult, PushError> {
+        // 模板卡片必须带跳转链接，没有链接按钮时退化为 Markdown
+        let Some(template_card) = template_card(title, sections, buttons) else {
+            return self
+                .send_markdown(&card_to_markdown(title, sections, buttons))
+                .await;
+        };
+        let payload = WxWorkTemplateCardPayload {
+            msgtype: "template_card".to_string(),
+            template_card,
+        };
+        self.send_request(payload).await
     }
 
     async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
         match message {
             MessageType::Text(content) => self.send_text(&content).await,
             MessageType::Markdown(content) => self.send_markdown(&content).await,
-            _ => Err(PushError::MessageError(
-                "Unsupported message type for WxWork Bot".to_string(),
-            )),
+            MessageType::Image { url, caption } => self.send_image(&url, caption.as_deref()).await,
+            MessageType::Link {
+                title,
+                description,
+                url,
+                image_url,
+            } => {
+                self.send_link(&title, &description, &url, image_url.as_deref())
+                    .await
+            }
+            MessageType::File { name, mime, source } => {
+                self.send_file(&name, mime.as_deref(), &source).await
+            }
+            MessageType::Card {
+                title,
+                sections,
+                buttons,
+            } => self.send_card(&title, &sections, &buttons).await,
+            MessageType::Audio {
+                source,
+                caption,
+                duration_secs,
+            } => {
+                self.send_audio(&source, caption.as_deref(), duration_secs)
+                    .await
+            }
+            MessageType::Template { name, variables } => {
+                self.send_template(&name, &variables).await
+            }
+            other => Err(PushError::unsupported(&platform_info(), &other)),
         }
     }
 
+    /// 连续的链接消息合并为一条图文消息，每条最多 8 篇文章
+    async fn send_batch(&self, messages: Vec<Message>) -> Vec<Result<PushResult, PushError>> {
+        let mut results = Vec::with_capacity(messages.len());
+        let mut articles = Vec::new();
+        for message in messages {
+            match message.content {
+                MessageType::Link {
+                    title,
+                    description,
+                    url,
+                    image_url,
+                } => {
+                    articles.push(WxWorkArticle {
+                        title,
+                        description,
+                        url,
+                        picurl: image_url,
+                    });
+                    if articles.len() == MAX_NEWS_ARTICLES {
+                        self.flush_news(&mut articles, &mut results).await;
+                    }
+                }
+                content => {
+                    self.flush_news(&mut articles, &mut results).await;
+                    results.push(self.send_message(Message { content, ..message }).await);
+                }
+            }
+        }
+        self.flush_news(&mut articles, &mut results).await;
+        results
+    }
+
+    /// 群机器人没有不发消息即可校验 Key 的接口，不声明 `HEALTH_CHECK_FEATURE`，
+    /// Key 是否有效要到首次推送时才能确认
     async fn health_check(&self) -> Result<bool, PushError> {
-        // A simple health check could be trying to send a test message to a dev-only bot
-        // For now, we assume it's healthy if the client can be built.
         Ok(true)
     }
 
     fn platform_info(&self) -> PlatformInfo {
-        PlatformInfo {
-            name: PLATFORM_NAME.to_string(),
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            features: vec!["text".to_string(), "markdown".to_string()],
-            supports_markdown: true,
-            supports_rich_text: false,
-            supports_images: false,
-        }
+        platform_info()
+    }
+}
+
+/// 企业微信平台信息，与配置无关
+fn platform_info() -> PlatformInfo {
+    PlatformInfo {
+        name: PLATFORM_NAME.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        message_types: BTreeSet::from([
+            MessageKind::Text,
+            MessageKind::Markdown,
+            MessageKind::Image,
+            MessageKind::Link,
+            MessageKind::File,
+            MessageKind::Audio,
+            MessageKind::Card,
+        ]),
+        mentions: MentionSupport {
+            all: true,
+            user_id: true,
+            phone: true,
+            email: false,
+        },
+        features: vec![
+            DRY_RUN_FEATURE.to_string(),
+            MENTION_FOLLOW_UP_FEATURE.to_string(),
+        ],
+        limits: MessageLimits {
+            text: Some(MAX_TEXT_BYTES),
+            markdown: Some(MAX_MARKDOWN_BYTES),
+            unit: LengthUnit::Bytes,
+            attachment_bytes: Some(MAX_MEDIA_BYTES),
+        },
+        rate_limit: Some(RateLimit {
+            requests: 20,
+            per_secs: 60,
+        }),
+        markdown_dialect: MarkdownDialect::WxWork,
     }
 }
 
@@ -161,16 +344,134 @@ impl PushPlatform<WxWorkConfig> for WxWorkGroupBotPlatform {
 }
 
 impl WxWorkGroupBotPlatform {
+    /// 使用共享的 HTTP 客户端创建平台
+    pub fn with_client(config: WxWorkConfig, http_client: Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    /// 发送图片，远程地址会先下载，内容以 base64 内联发送
+    pub async fn send_image_source(
+        &self,
+        source: &AttachmentSource,
+    ) -> Result<PushResult, PushError> {
+        let bytes = source
+            .read(&self.http_client, "Image", MAX_IMAGE_BYTES)
+            .await?;
+        self.send_request(image_payload(&bytes)?).await
+    }
+
+    /// 发送图文消息
+    async fn send_news(&self, articles: Vec<WxWorkArticle>) -> Result<PushResult, PushError> {
+        let payload = WxWorkNewsPayload {
+            msgtype: "news".to_string(),
+            news: WxWorkNews { articles },
+        };
+        self.send_request(payload).await
+    }
+
+    /// 发送累积的文章，合并发送的每条消息得到相同的结果
+    async fn flush_news(
+        &self,
+        articles: &mut Vec<WxWorkArticle>,
+        results: &mut Vec<Result<PushResult, PushError>>,
+    ) {
+        if articles.is_empty() {
+            return;
+        }
+        let count = articles.len();
+        match self.send_news(std::mem::take(articles)).await {
+            Ok(result) => results.extend(std::iter::repeat_n(result, count).map(Ok)),
+            Err(e) => {
+                let merged: Vec<_> = (1..count).map(|_| Err(merged_error(&e))).collect();
+                results.push(Err(e));
+                results.extend(merged);
+            }
+        }
+    }
+
+    /// 上传素材，返回 media_id（3 天内有效）
+    async fn upload_media(
+        &self,
+        kind: MediaKind,
+        name: &str,
+        mime: Option<&str>,
+        bytes: Vec<u8>,
+    ) -> Result<String, PushError> {
+        check_size(name, bytes.len(), kind.max_bytes())?;
+        // 演练时不上传，载荷中的 media_id 为占位值
+        if common::dry_run::active() {
+            return Ok(DRY_RUN_MEDIA_ID.to_string());
+        }
+
+        let mut part = Part::bytes(bytes).file_name(name.to_string());
+        if let Some(mime) = mime {
+            part = part
+                .mime_str(mime)
+                .map_err(|e| PushError::MessageError(e.to_string()))?;
+        }
+        let response = self
+            .http_client
+            .post(common::endpoint(UPLOAD_URL))
+            .query(&[("key", self.config.token.as_str()), ("type", kind.as_str())])
+            .multipart(Form::new().part("media", part))
+            .send()
+            .await
+            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+        let upload: WxWorkUploadResponse = response
+            .json()
+            .await
+            .map_err(|e| PushError::PlatformError(e.to_string()))?;
+        match upload.media_id {
+            Some(media_id) if upload.errcode == 0 => Ok(media_id),
+            _ => Err(api_error(upload.errcode, &upload.errmsg)),
+        }
+    }
+
+    /// 上传文件素材并以文件消息发送
+    async fn send_media_file(
+        &self,
+        name: &str,
+        mime: Option<&str>,
+        bytes: Vec<u8>,
+    ) -> Result<PushResult, PushError> {
+        let media_id = self
+            .upload_media(MediaKind::File, name, mime, bytes)
+            .await?;
+        let payload = WxWorkFilePayload {
+            msgtype: "file".to_string(),
+            file: WxWorkFile { media_id },
+        };
+        self.send_request(payload).await
+    }
+
     async fn send_request<T: Serialize>(&self, payload: T) -> Result<PushResult, PushError> {
+        if let Some(result) = common::dry_run::intercept(&payload) {
+            return Ok(result);
+        }
         let response = self
             .http_client
-            .post(self.config.webhook_url())
+            .post(common::endpoint(&self.config.webhook_url()))
             .json(&payload)
             .send()
             .await
-            .map_err(|e| PushError::NetworkError(e.to_string()))?;
+            .map_err(|e| {
+                if e.is_timeout() {
+                    PushError::Timeout(e.to_string())
+                } else {
+                    PushError::NetworkError(e.to_string())
+                }
+            })?;
 
         let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
         let text = response
             .text()
             .await
@@ -183,21 +484,205 @@ impl WxWorkGroupBotPlatform {
                 Ok(PushResult {
                     success: true,
                     response: Some(text),
+                    http_status: Some(status.as_u16()),
+                    error_code: Some(wx_response.errcode.to_string()),
                     ..Default::default()
                 })
             } else {
-                Err(PushError::PlatformError(format!(
-                    "WxWork API Error: code={}, message={}",
-                    wx_response.errcode, wx_response.errmsg
-                )))
+                Err(api_error(wx_response.errcode, &wx_response.errmsg)
+                    .with_diagnostics(|result| result.http_status = Some(status.as_u16())))
             }
         } else {
-            Err(PushError::NetworkError(format!(
-                "Request failed with status: {}, body: {}",
-                status, text
-            )))
+            let message = format!("Request failed with status: {}, body: {}", status, text);
+            Err(match status {
+                StatusCode::TOO_MANY_REQUESTS => PushError::RateLimited {
+                    message,
+                    retry_after,
+                },
+                StatusCode::PAYLOAD_TOO_LARGE => PushError::PayloadTooLarge(message),
+                s if s.is_server_error() => PushError::NetworkError(message),
+                _ => PushError::PlatformError(message),
+            }
+            .with_diagnostics(|result| result.http_status = Some(status.as_u16())))
+        }
+    }
+}
+
+/// 将卡片映射为模板卡片，没有链接按钮时返回 `None`
+///
+/// 带配图的卡片使用图文展示型（news_notice），否则使用文本通知型（text_notice）；
+/// 第一个突出显示的段落作为关键数据，有标题的段落作为二级标题+文本列表
+fn template_card(
+    title: &str,
+    sections: &[CardSection],
+    buttons: &[CardButton],
+) -> Option<WxWorkTemplateCard> {
+    let jump_list: Vec<WxWorkJump> = buttons
+        .iter()
+        .filter_map(|b| {
+            b.link().map(|url| WxWorkJump {
+                jump_type: 1,
+                url: url.to_string(),
+                title: b.label.clone(),
+            })
+        })
+        .take(MAX_CARD_JUMPS)
+        .collect();
+    let card_action = WxWorkCardAction {
+        action_type: 1,
+        url: jump_list.first()?.url.clone(),
+    };
+
+    let card_image = sections
+        .iter()
+        .find_map(|s| s.image_url.as_ref())
+        .map(|url| WxWorkCardImage { url: url.clone() });
+    // 关键数据只在文本通知型卡片中展示
+    let emphasis = sections
+        .iter()
+        .position(|s| s.emphasis)
+        .filter(|_| card_image.is_none());
+    let mut text = Vec::new();
+    let mut horizontal_content_list = Vec::new();
+    for (index, section) in sections.iter().enumerate() {
+        if Some(index) == emphasis {
+            continue;
+        }
+        match &section.title {
+            Some(keyname) => horizontal_content_list.push(WxWorkHorizontalContent {
+                keyname: keyname.clone(),
+                value: section.content.clone(),
+            }),
+            None => text.push(section.content.as_str()),
         }
     }
+    horizontal_content_list.truncate(MAX_CARD_FIELDS);
+    let emphasis_content = emphasis.map(|index| WxWorkEmphasisContent {
+        title: sections[index].content.clone(),
+        desc: sections[index].title.clone(),
+    });
+
+    let mut main_title = WxWorkCardTitle {
+        title: title.to_string(),
+        desc: None,
+    };
+    let mut sub_title_text = String::new();
+    let card_type = if card_image.is_some() {
+        // 图文展示型卡片没有二级普通文本，放在标题辅助信息中
+        main_title.desc = Some(text.join("\n")).filter(|desc| !desc.is_empty());
+        "news_notice"
+    } else {
+        sub_title_text = text.join("\n");
+        "text_notice"
+    };
+    Some(WxWorkTemplateCard {
+        card_type: card_type.to_string(),
+        main_title,
+        emphasis_content,
+        sub_title_text,
+        card_image,
+        horizontal_content_list,
+        jump_list,
+        card_action,
+    })
+}
+
+/// 素材类型
+#[derive(Debug, Clone, Copy)]
+enum MediaKind {
+    File,
+    Voice,
+}
+
+impl MediaKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Voice => "voice",
+        }
+    }
+
+    fn max_bytes(self) -> usize {
+        match self {
+            Self::File => MAX_MEDIA_BYTES,
+            Self::Voice => MAX_VOICE_BYTES,
+        }
+    }
+}
+
+/// 音频以文件消息发送时的文件名，取本地路径或远程地址的最后一段
+fn audio_file_name(source: &AttachmentSource) -> String {
+    let name = match source {
+        AttachmentSource::Path(path) => path.file_name().and_then(|name| name.to_str()),
+        _ => source
+            .url()
+            .and_then(|url| url.split(['?', '#']).next())
+            .and_then(|url| url.rsplit('/').next()),
+    };
+    name.filter(|name| !name.is_empty())
+        .unwrap_or("audio")
+        .to_string()
+}
+
+/// 构造图片消息，企业微信要求附带原始内容的 md5
+fn image_payload(bytes: &[u8]) -> Result<WxWorkImagePayload, PushError> {
+    check_size("Image", bytes.len(), MAX_IMAGE_BYTES)?;
+    Ok(WxWorkImagePayload {
+        msgtype: "image".to_string(),
+        image: WxWorkImage {
+            base64: STANDARD.encode(bytes),
+            md5: format!("{:x}", md5::compute(bytes)),
+        },
+    })
+}
+
+fn check_size(name: &str, len: usize, max: usize) -> Result<(), PushError> {
+    if len > max {
+        return Err(PushError::PayloadTooLarge(format!(
+            "{} is {} bytes, WxWork allows at most {}",
+            name, len, max
+        )));
+    }
+    Ok(())
+}
+
+/// 将企业微信错误码映射为结构化错误，诊断信息中带上原始错误码
+fn api_error(errcode: i32, errmsg: &str) -> PushError {
+    let message = format!("WxWork API Error: code={}, message={}", errcode, errmsg);
+    let error = match errcode {
+        // 机器人每分钟最多发送 20 条消息
+        45009 => PushError::RateLimited {
+            message,
+            retry_after: Some(Duration::from_secs(60)),
+        },
+        45002 => PushError::PayloadTooLarge(message),
+        93000 => PushError::AuthError(message),
+        -1 => PushError::NetworkError(message),
+        _ => PushError::PlatformError(message),
+    };
+    error.with_diagnostics(|result| result.error_code = Some(errcode.to_string()))
+}
+
+/// 合并发送的其余消息得到的错误，保留重试分类和诊断信息
+fn merged_error(error: &PushError) -> PushError {
+    let merged = match error.inner() {
+        PushError::RateLimited {
+            message,
+            retry_after,
+        } => PushError::RateLimited {
+            message: message.clone(),
+            retry_after: *retry_after,
+        },
+        PushError::NetworkError(message) => PushError::NetworkError(message.clone()),
+        PushError::Timeout(message) => PushError::Timeout(message.clone()),
+        PushError::AuthError(message) => PushError::AuthError(message.clone()),
+        PushError::PayloadTooLarge(message) => PushError::PayloadTooLarge(message.clone()),
+        other => PushError::PlatformError(other.to_string()),
+    };
+    match error.diagnostics() {
+        Some(diagnostics) => merged.with_diagnostics(|result| *result = diagnostics.clone()),
+        None => merged,
+    }
 }
 
 // --- WxWork API Payload Structs ---
@@ -215,6 +700,38 @@ struct WxWorkText {
     mentioned_mobile_list: Vec<String>,
 }
 
+/// 用户 ID 和 @all 放入 mentioned_list，手机号放入 mentioned_mobile_list，
+/// 企业微信无法按邮箱提醒，邮箱以文字形式附在内容后
+///
+/// 企业微信 Markdown 消息不能 @ 人，提醒以空内容的文本消息补发，
+/// 而文本消息内容不能为空，此时以提醒对象作为内容
+fn text_with_mentions(content: &str, mentions: Vec<Mention>) -> WxWorkText {
+    let mut words = Vec::new();
+    if !content.is_empty() {
+        words.push(content.to_string());
+    }
+    let mut emails = Vec::new();
+    let mut text = WxWorkText {
+        content: String::new(),
+        mentioned_list: vec![],
+        mentioned_mobile_list: vec![],
+    };
+    for mention in mentions {
+        if content.is_empty() && !matches!(mention, Mention::Email(_)) {
+            words.push(mention.to_string());
+        }
+        match mention {
+            Mention::All => text.mentioned_list.push("@all".to_string()),
+            Mention::UserId(id) => text.mentioned_list.push(id),
+            Mention::Phone(phone) => text.mentioned_mobile_list.push(phone),
+            email @ Mention::Email(_) => emails.push(email.to_string()),
+        }
+    }
+    words.extend(emails);
+    text.content = words.join(" ");
+    text
+}
+
 #[derive(Serialize)]
 struct WxWorkMarkdownPayload {
     msgtype: String,
@@ -226,6 +743,125 @@ struct WxWorkMarkdown {
     content: String,
 }
 
+#[derive(Serialize)]
+struct WxWorkNewsPayload {
+    msgtype: String,
+    news: WxWorkNews,
+}
+
+#[derive(Serialize)]
+struct WxWorkNews {
+    articles: Vec<WxWorkArticle>,
+}
+
+#[derive(Serialize)]
+struct WxWorkArticle {
+    title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    description: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    picurl: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WxWorkImagePayload {
+    msgtype: String,
+    image: WxWorkImage,
+}
+
+#[derive(Serialize)]
+struct WxWorkImage {
+    base64: String,
+    md5: String,
+}
+
+#[derive(Serialize)]
+struct WxWorkFilePayload {
+    msgtype: String,
+    file: WxWorkFile,
+}
+
+#[derive(Serialize)]
+struct WxWorkFile {
+    media_id: String,
+}
+
+#[derive(Serialize)]
+struct WxWorkVoicePayload {
+    msgtype: String,
+    voice: WxWorkFile,
+}
+
+#[derive(Serialize)]
+struct WxWorkTemplateCardPayload {
+    msgtype: String,
+    template_card: WxWorkTemplateCard,
+}
+
+#[derive(Serialize)]
+struct WxWorkTemplateCard {
+    card_type: String,
+    main_title: WxWorkCardTitle,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emphasis_content: Option<WxWorkEmphasisContent>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    sub_title_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card_image: Option<WxWorkCardImage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    horizontal_content_list: Vec<WxWorkHorizontalContent>,
+    jump_list: Vec<WxWorkJump>,
+    card_action: WxWorkCardAction,
+}
+
+#[derive(Serialize)]
+struct WxWorkCardTitle {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    desc: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WxWorkEmphasisContent {
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    desc: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WxWorkCardImage {
+    url: String,
+}
+
+#[derive(Serialize)]
+struct WxWorkHorizontalContent {
+    keyname: String,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct WxWorkJump {
+    #[serde(rename = "type")]
+    jump_type: u8,
+    url: String,
+    title: String,
+}
+
+#[derive(Serialize)]
+struct WxWorkCardAction {
+    #[serde(rename = "type")]
+    action_type: u8,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct WxWorkUploadResponse {
+    errcode: i32,
+    errmsg: String,
+    media_id: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct WxWorkResponse {
     errcode: i32,
@@ -237,30 +873,432 @@ struct WxWorkResponse {
 pub struct WxWorkPlatformFactory;
 
 impl PlatformFactory for WxWorkPlatformFactory {
-    fn create(&self, config: Value) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+    fn create(
+        &self,
+        config: Value,
+        context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
         let config: WxWorkConfig =
             serde_json::from_value(config).map_err(|e| PushError::ConfigError(e.to_string()))?;
-        let platform = WxWorkGroupBotPlatform::new(config);
-        Ok(Box::new(platform))
+        let http_client = context.client_for(&config)?;
+        let platform = WxWorkGroupBotPlatform::with_client(config.clone(), http_client);
+        Ok(Box::new(ResilientPlatform::new(platform, &config)))
     }
 
     fn name(&self) -> &'static str {
         PLATFORM_NAME
     }
+
+    fn config_schema(&self) -> Value {
+        WxWorkConfig::config_schema()
+    }
+
+    fn platform_info(&self) -> Option<PlatformInfo> {
+        Some(platform_info())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::env;
     use super::*;
+    use std::env;
+
+    #[test]
+    fn test_api_error_classification() {
+        let limited = api_error(45009, "api freq out of limit");
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(60)));
+        let auth = api_error(93000, "invalid webhook url");
+        assert!(matches!(auth.inner(), PushError::AuthError(_)));
+        assert_eq!(
+            auth.diagnostics().unwrap().error_code.as_deref(),
+            Some("93000")
+        );
+        assert!(!api_error(40008, "invalid message type").is_retryable());
+    }
+
+    #[test]
+    fn test_factory_describes_platform() {
+        let schema = WxWorkPlatformFactory.config_schema();
+        assert_eq!(schema["required"], serde_json::json!(["token"]));
+        assert_eq!(schema["properties"]["token"]["type"], "string");
+        let info = WxWorkPlatformFactory.platform_info().unwrap();
+        assert_eq!(info.name, PLATFORM_NAME);
+    }
+
+    #[test]
+    fn test_image_payload() {
+        let payload = image_payload(b"hello").unwrap();
+        assert_eq!(payload.image.base64, "aGVsbG8=");
+        assert_eq!(payload.image.md5, "5d41402abc4b2a76b9719d911017c592");
+        assert!(matches!(
+            image_payload(&vec![0; MAX_IMAGE_BYTES + 1]),
+            Err(PushError::PayloadTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_template_card() {
+        let sections = vec![
+            CardSection {
+                title: Some("p99".to_string()),
+                content: "1.2s".to_string(),
+                emphasis: true,
+                ..Default::default()
+            },
+            CardSection {
+                title: Some("Service".to_string()),
+                content: "api".to_string(),
+                ..Default::default()
+            },
+            CardSection {
+                content: "Latency above threshold".to_string(),
+                ..Default::default()
+            },
+        ];
+        let buttons = vec![
+            CardButton::url("Dashboard", "http://grafana/d/1"),
+            CardButton::callback("Ack", "ack-1"),
+        ];
+        let card =
+            serde_json::to_value(template_card("High latency", &sections, &buttons)).unwrap();
+        assert_eq!(card["card_type"], "text_notice");
+        assert_eq!(
+            card["emphasis_content"],
+            serde_json::json!({"title": "1.2s", "desc": "p99"})
+        );
+        assert_eq!(card["sub_title_text"], "Latency above threshold");
+        assert_eq!(card["horizontal_content_list"][0]["keyname"], "Service");
+        assert_eq!(card["card_action"]["url"], "http://grafana/d/1");
+
+        let mut with_image = sections.clone();
+        with_image[1].image_url = Some("http://grafana/render/1.png".to_string());
+        let card =
+            serde_json::to_value(template_card("High latency", &with_image, &buttons)).unwrap();
+        assert_eq!(card["card_type"], "news_notice");
+        assert_eq!(card["card_image"]["url"], "http://grafana/render/1.png");
+        assert_eq!(card["main_title"]["desc"], "Latency above threshold");
+        assert!(card.get("emphasis_content").is_none());
+
+        assert!(template_card("High latency", &sections, &buttons[1..]).is_none());
+    }
+
+    #[test]
+    fn test_text_with_mentions() {
+        let text = text_with_mentions(
+            "Deploy failed",
+            vec![
+                Mention::All,
+                "alice".into(),
+                "phone:13800000000".into(),
+                "bob@example.com".into(),
+            ],
+        );
+        assert_eq!(text.content, "Deploy failed @bob@example.com");
+        assert_eq!(text.mentioned_list, vec!["@all", "alice"]);
+        assert_eq!(text.mentioned_mobile_list, vec!["13800000000"]);
+
+        let follow_up = text_with_mentions("", vec![Mention::All, "bob@example.com".into()]);
+        assert_eq!(follow_up.content, "@all @bob@example.com");
+        let emails_only = text_with_mentions("", vec!["bob@example.com".into()]);
+        assert_eq!(emails_only.content, "@bob@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_voice_and_file_messages() {
+        let upstream = test_support::Upstream::start(
+            test_support::wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"errcode": 0, "errmsg": "ok", "media_id": "MEDIA"}),
+            ),
+        )
+        .await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let amr = AttachmentSource::Bytes(b"#!AMR\nvoice".to_vec());
+        let mp3 = AttachmentSource::Bytes(b"ID3\x03mp3".to_vec());
+        let scenario = async {
+            platform.send_audio(&amr, None, Some(5)).await.unwrap();
+            // 非 AMR 音频和超过 60 秒的语音降级为文件消息
+            platform.send_audio(&mp3, None, Some(5)).await.unwrap();
+            platform.send_audio(&amr, None, Some(90)).await.unwrap();
+            let report = AttachmentSource::Bytes(b"report".to_vec());
+            platform
+                .send_file("report.txt", Some("text/plain"), &report)
+                .await
+                .unwrap();
+        };
+        upstream.scope(scenario).await;
+
+        let requests = upstream.requests().await;
+        let uploads: Vec<String> = requests
+            .iter()
+            .filter(|r| r.url.path().ends_with("/upload_media"))
+            .map(|r| {
+                r.url
+                    .query_pairs()
+                    .find(|(key, _)| key == "type")
+                    .unwrap()
+                    .1
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(uploads, ["voice", "file", "file", "file"]);
+        let messages: Vec<serde_json::Value> = requests
+            .iter()
+            .filter(|r| r.url.path().ends_with("/send"))
+            .map(|r| serde_json::from_slice(&r.body).unwrap())
+            .collect();
+        let msgtypes: Vec<&str> = messages
+            .iter()
+            .map(|m| m["msgtype"].as_str().unwrap())
+            .collect();
+        assert_eq!(msgtypes, ["voice", "file", "file", "file"]);
+        assert_eq!(messages[0]["voice"]["media_id"], "MEDIA");
+        assert_eq!(messages[1]["file"]["media_id"], "MEDIA");
+    }
+
+    fn link(title: &str) -> Message {
+        Message::new(MessageType::Link {
+            title: title.to_string(),
+            description: "Build passed".to_string(),
+            url: format!("http://ci/{}", title),
+            image_url: None,
+        })
+    }
+
+    /// 上游收到的消息请求
+    async fn sent_messages(upstream: &test_support::Upstream) -> Vec<serde_json::Value> {
+        upstream
+            .requests()
+            .await
+            .iter()
+            .map(|r| serde_json::from_slice(&r.body).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_link_news_payload() {
+        let upstream = test_support::Upstream::start(conformance_responses().success).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let sent = platform.send_link(
+            "Release 1.2",
+            "Changelog",
+            "http://ci/1",
+            Some("http://ci/1.png"),
+        );
+        upstream.scope(sent).await.unwrap();
+
+        let messages = sent_messages(&upstream).await;
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["msgtype"], "news");
+        assert_eq!(
+            messages[0]["news"]["articles"],
+            serde_json::json!([{
+                "title": "Release 1.2",
+                "description": "Changelog",
+                "url": "http://ci/1",
+                "picurl": "http://ci/1.png",
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_merges_links() {
+        let upstream = test_support::Upstream::start(conformance_responses().success).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let mut batch: Vec<Message> = (0..9).map(|i| link(&i.to_string())).collect();
+        batch.push(Message::new(MessageType::Text("done".to_string())));
+        batch.push(link("9"));
+        let results = upstream.scope(platform.send_batch(batch)).await;
+        assert_eq!(results.len(), 11);
+        assert!(results.iter().all(Result::is_ok));
+
+        // 每条图文消息最多 8 篇文章，其他消息打断合并
+        let messages = sent_messages(&upstream).await;
+        let msgtypes: Vec<&str> = messages
+            .iter()
+            .map(|m| m["msgtype"].as_str().unwrap())
+            .collect();
+        assert_eq!(msgtypes, ["news", "news", "text", "news"]);
+        let article_counts: Vec<usize> = messages
+            .iter()
+            .filter(|m| m["msgtype"] == "news")
+            .map(|m| m["news"]["articles"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(article_counts, [8, 1, 1]);
+        assert_eq!(messages[0]["news"]["articles"][7]["url"], "http://ci/7");
+    }
 
+    #[tokio::test]
+    async fn test_merged_batch_retried_as_unit() {
+        let unavailable = test_support::wiremock::ResponseTemplate::new(503);
+        let upstream = test_support::Upstream::start(unavailable).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let platform = ResilientPlatform::with_policy(platform, Duration::from_secs(5), 1)
+            .with_backoff(Duration::from_millis(1));
+        let results = upstream
+            .scope(platform.send_batch(vec![link("a"), link("b")]))
+            .await;
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            let error = result.as_ref().unwrap_err();
+            assert!(matches!(error.inner(), PushError::NetworkError(_)));
+            assert_eq!(error.diagnostics().unwrap().attempts, 2);
+        }
+
+        // 重试时两篇文章仍合并为一条图文消息
+        let messages = sent_messages(&upstream).await;
+        assert_eq!(messages.len(), 2);
+        for message in &messages {
+            assert_eq!(message["news"]["articles"].as_array().unwrap().len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_long_text_split_by_limits() {
+        let upstream = test_support::Upstream::start(conformance_responses().success).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let line = "x".repeat(99);
+        let content = vec![line.as_str(); 30].join("\n");
+        let mut message = Message::new(MessageType::Text(content));
+        message.mentions = vec![Mention::All];
+        upstream
+            .scope(platform.send_message(message))
+            .await
+            .unwrap();
+
+        // 按 MAX_TEXT_BYTES 拆分，@提醒只附在第一条上
+        let messages = sent_messages(&upstream).await;
+        assert_eq!(messages.len(), 2);
+        for message in &messages {
+            assert!(message["text"]["content"].as_str().unwrap().len() <= MAX_TEXT_BYTES);
+        }
+        let mentioned: Vec<&Value> = messages
+            .iter()
+            .map(|m| &m["text"]["mentioned_list"])
+            .collect();
+        assert_eq!(
+            mentioned,
+            [&serde_json::json!(["@all"]), &serde_json::json!([])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_markdown_mentions_follow_up() {
+        let upstream = test_support::Upstream::start(conformance_responses().success).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let mut message = Message::new(MessageType::Markdown("**Deploy failed**".to_string()));
+        message.mentions = vec![Mention::All, "bob@example.com".into()];
+        upstream
+            .scope(platform.send_message(message.clone()))
+            .await
+            .unwrap();
+        // 只有邮箱时无法提醒，不补发
+        message.mentions = vec!["bob@example.com".into()];
+        upstream
+            .scope(platform.send_message(message))
+            .await
+            .unwrap();
+
+        let bodies: Vec<serde_json::Value> = upstream
+            .requests()
+            .await
+            .iter()
+            .map(|r| serde_json::from_slice(&r.body).unwrap())
+            .collect();
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(bodies[0]["msgtype"], "markdown");
+        assert_eq!(bodies[1]["msgtype"], "text");
+        assert_eq!(bodies[1]["text"]["content"], "@all @bob@example.com");
+        assert_eq!(
+            bodies[1]["text"]["mentioned_list"],
+            serde_json::json!(["@all"])
+        );
+        assert_eq!(bodies[2]["msgtype"], "markdown");
+    }
+
+    fn conformance_responses() -> test_support::CannedResponses {
+        use test_support::wiremock::ResponseTemplate;
+        let api_error = |errcode: i32| {
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"errcode": errcode, "errmsg": "error"}))
+        };
+        test_support::CannedResponses::new(serde_json::json!({"errcode": 0, "errmsg": "ok"}))
+            .with_auth_error(api_error(93000))
+            .with_rate_limit(api_error(45009))
+    }
+
+    test_support::conformance_suite!(
+        WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        }),
+        conformance_responses()
+    );
+
+    #[tokio::test]
+    async fn test_dry_run_skips_request() {
+        let upstream = test_support::Upstream::start(conformance_responses().success).await;
+        let platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
+            token: "TOKEN".to_string(),
+            proxy: None,
+            retry: None,
+        });
+        let result = upstream
+            .scope(common::dry_run::scope(platform.send_text("Deploy done")))
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        let payload: serde_json::Value =
+            serde_json::from_str(result.response.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["text"]["content"], "Deploy done");
+        assert!(upstream.requests().await.is_empty());
+    }
+
+    /// 默认回放夹具；设置 `WXWORK_TOKEN` 和 `MULTI_PUSH_VCR=record` 时重新录制
     #[tokio::test]
     async fn test_text_message() {
+        let token = env::var("WXWORK_TOKEN").unwrap_or("TOKEN".to_string());
+        let recorder = common::vcr::Recorder::start(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/text_message.json"
+        ))
+        .await
+        .unwrap()
+        .redact(token.clone(), "TOKEN");
         let wx_work_platform = WxWorkGroupBotPlatform::new(WxWorkConfig {
-            token: env::var("WXWORK_TOKEN").unwrap_or("".to_string()),
+            token,
+            proxy: None,
+            retry: None,
         });
-    let result = wx_work_platform.send_text_with_mention("Test",vec!["@all".to_string()]).await;
-        assert!(result.is_ok());
-        println!("{:?}", result.unwrap());
+        let result = recorder
+            .scope(wx_work_platform.send_text_with_mention("Test", vec![Mention::All]))
+            .await;
+        recorder.finish().unwrap();
+        assert!(result.is_ok(), "{:?}", result);
     }
 }
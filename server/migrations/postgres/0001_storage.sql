@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS push_messages (
+    id TEXT NOT NULL,
+    target TEXT NOT NULL,
+    platform TEXT NOT NULL,
+    success BOOLEAN NOT NULL,
+    message_id TEXT,
+    response TEXT,
+    created_at BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS push_messages_created_at ON push_messages (created_at);
+
+CREATE TABLE IF NOT EXISTS push_schedules (
+    id TEXT PRIMARY KEY,
+    channel TEXT NOT NULL,
+    message TEXT NOT NULL,
+    send_at BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS push_silences (
+    id TEXT PRIMARY KEY,
+    matchers TEXT NOT NULL,
+    until_at BIGINT NOT NULL,
+    created_by TEXT
+);
+
+CREATE TABLE IF NOT EXISTS push_templates (
+    name TEXT PRIMARY KEY,
+    definition TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS push_tokens (
+    token_key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS push_queue (
+    id TEXT PRIMARY KEY,
+    channel TEXT NOT NULL,
+    message TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    available_at BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS push_queue_available_at ON push_queue (available_at);
+
+-- 入队时通知所有副本的 worker，参见 PostgresQueue::wait
+CREATE OR REPLACE FUNCTION push_queue_notify() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('push_queue', NEW.channel);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS push_queue_notify ON push_queue;
+CREATE TRIGGER push_queue_notify AFTER INSERT ON push_queue
+    FOR EACH ROW EXECUTE FUNCTION push_queue_notify();
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS push_locks (
+    name TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    expires_at BIGINT NOT NULL
+);
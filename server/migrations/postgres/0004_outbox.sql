@@ -0,0 +1,3 @@
+-- 领取后到租约到期前为投递中，NULL 表示等待投递
+ALTER TABLE push_queue ADD COLUMN leased_until BIGINT;
+CREATE INDEX IF NOT EXISTS push_queue_leased_until ON push_queue (leased_until);
@@ -0,0 +1,3 @@
+-- 放弃重试的条目保留在队列中作为死信，不再领取
+ALTER TABLE push_queue ADD COLUMN failed_at BIGINT;
+ALTER TABLE push_queue ADD COLUMN last_error TEXT;
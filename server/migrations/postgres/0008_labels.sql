@@ -0,0 +1,2 @@
+-- 投递记录的消息标签，JSON 文本
+ALTER TABLE push_messages ADD COLUMN labels TEXT NOT NULL DEFAULT '{}';
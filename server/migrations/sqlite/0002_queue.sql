@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS push_queue (
+    id TEXT PRIMARY KEY,
+    channel TEXT NOT NULL,
+    message TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    available_at BIGINT NOT NULL,
+    created_at BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS push_queue_available_at ON push_queue (available_at);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS push_dedup (
+    key TEXT PRIMARY KEY,
+    expires_at BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS push_dedup_expires_at ON push_dedup (expires_at);
@@ -0,0 +1,2 @@
+-- 演练模式的投递记录，没有实际发送
+ALTER TABLE push_messages ADD COLUMN dry_run BOOLEAN NOT NULL DEFAULT FALSE;
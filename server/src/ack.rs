@@ -0,0 +1,246 @@
+use crate::dispatch::Dispatcher;
+use common::{BuiltinText, Message, MessageType};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 检查是否有到期提醒的最长间隔
+const MAX_TICK: Duration = Duration::from_secs(10);
+
+/// 确认提醒配置，作用于 `require_ack` 的消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AckConfig {
+    /// 未确认时重复发送的间隔（秒）
+    pub interval_secs: u64,
+    /// 最多发送次数，包括第一次发送
+    pub max_attempts: u32,
+}
+
+impl Default for AckConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 300,
+            max_attempts: 5,
+        }
+    }
+}
+
+impl AckConfig {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+}
+
+/// 待确认的消息，按请求 ID 索引
+pub struct AckTracker {
+    config: AckConfig,
+    pending: Mutex<HashMap<String, Pending>>,
+}
+
+struct Pending {
+    channels: Vec<String>,
+    message: Message,
+    attempts: u32,
+    next: Instant,
+    /// 第一次发送的时间
+    sent_at: Instant,
+}
+
+/// 到期需要重新发送的提醒
+pub struct Reminder {
+    /// 原消息的请求 ID
+    pub id: String,
+    /// 投递的通道
+    pub channels: Vec<String>,
+    /// 带有提醒次数的消息
+    pub message: Message,
+}
+
+impl AckTracker {
+    pub fn new(config: AckConfig) -> Self {
+        Self {
+            config,
+            pending: Mutex::default(),
+        }
+    }
+
+    /// 记录已发送、等待确认的消息；已在跟踪时追加通道，如 outbox 逐个通道投递的消息
+    pub fn track(&self, id: &str, channels: Vec<String>, message: Message) {
+        if channels.is_empty() || self.config.max_attempts <= 1 {
+            return;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(entry) = pending.get_mut(id) {
+            for channel in channels {
+                if !entry.channels.contains(&channel) {
+                    entry.channels.push(channel);
+                }
+            }
+            return;
+        }
+        pending.insert(
+            id.to_string(),
+            Pending {
+                channels,
+                message,
+                attempts: 1,
+                next: Instant::now() + self.config.interval(),
+                sent_at: Instant::now(),
+            },
+        );
+    }
+
+    /// 确认消息，停止提醒，返回已发送的次数；消息不存在或已确认时返回 `None`
+    pub fn ack(&self, id: &str) -> Option<u32> {
+        self.pending
+            .lock()
+            .unwrap()
+            .remove(id)
+            .map(|pending| pending.attempts)
+    }
+
+    /// 最早一条仍未确认的消息距第一次发送的时间
+    pub fn oldest(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .lock()
+            .unwrap()
+            .values()
+            .map(|pending| now.saturating_duration_since(pending.sent_at))
+            .max()
+    }
+
+    /// 取出到期的提醒，达到最大次数的消息不再跟踪
+    pub fn due(&self, now: Instant) -> Vec<Reminder> {
+        let max_attempts = self.config.max_attempts;
+        let mut pending = self.pending.lock().unwrap();
+        let mut reminders = Vec::new();
+        pending.retain(|id, entry| {
+            if entry.next > now {
+                return true;
+            }
+            entry.attempts += 1;
+            entry.next = now + self.config.interval();
+            reminders.push(Reminder {
+                id: id.clone(),
+                channels: entry.channels.clone(),
+                message: reminder(&entry.message, entry.attempts, max_attempts),
+            });
+            if entry.attempts >= max_attempts {
+                warn!(
+                    "[{}] Not acknowledged after {} attempts, giving up",
+                    id, entry.attempts
+                );
+                return false;
+            }
+            true
+        });
+        reminders
+    }
+}
+
+/// 在消息标题或正文前按消息语言加上提醒次数，提醒本身不再要求确认
+fn reminder(message: &Message, attempt: u32, max_attempts: u32) -> Message {
+    let mut message = message.clone();
+    message.require_ack = false;
+    let prefix = BuiltinText::Reminder.localize(
+        message.locale(),
+        &[("attempt", &attempt), ("max", &max_attempts)],
+    );
+    match &mut message.content {
+        MessageType::Text(text) | MessageType::Markdown(text) | MessageType::Html(text) => {
+            text.insert_str(0, &prefix)
+        }
+        MessageType::Rich { title, .. }
+        | MessageType::Link { title, .. }
+        | MessageType::Card { title, .. } => title.insert_str(0, &prefix),
+        _ => {}
+    }
+    message
+}
+
+/// 启动提醒任务，定期重新发送到期未确认的消息
+pub fn spawn_reminder_task(dispatcher: Arc<Dispatcher>, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval.min(MAX_TICK));
+        loop {
+            ticker.tick().await;
+            for reminder in dispatcher.acks().due(Instant::now()) {
+                info!(
+                    "[{}] Re-sending unacknowledged message to {:?}",
+                    reminder.id, reminder.channels
+                );
+                dispatcher
+                    .send_to_all(&reminder.channels, reminder.message)
+                    .await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remind_until_acked() {
+        let tracker = AckTracker::new(AckConfig {
+            interval_secs: 60,
+            max_attempts: 3,
+        });
+        let mut message = Message::new(MessageType::Text("db down".to_string()));
+        message.require_ack = true;
+        tracker.track("a", vec!["oncall".to_string()], message.clone());
+        tracker.track(
+            "b",
+            vec!["oncall".to_string()],
+            message.with_locale("zh-CN"),
+        );
+
+        let now = Instant::now();
+        assert!(tracker.due(now).is_empty());
+        let reminders = tracker.due(now + Duration::from_secs(61));
+        assert_eq!(reminders.len(), 2);
+        assert!(!reminders[0].message.require_ack);
+        let first = reminders.iter().find(|r| r.id == "a").unwrap();
+        assert!(matches!(
+            &first.message.content,
+            MessageType::Text(text) if text == "[Reminder 2/3] db down"
+        ));
+
+        assert_eq!(tracker.ack("a"), Some(2));
+        assert_eq!(tracker.ack("a"), None);
+        let reminders = tracker.due(now + Duration::from_secs(122));
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].id, "b");
+        assert!(matches!(
+            &reminders[0].message.content,
+            MessageType::Text(text) if text == "[提醒 3/3] db down"
+        ));
+        // 达到最大次数后不再提醒
+        assert!(tracker.due(now + Duration::from_secs(300)).is_empty());
+        assert_eq!(tracker.ack("b"), None);
+    }
+
+    #[test]
+    fn test_track_merges_channels() {
+        let tracker = AckTracker::new(AckConfig {
+            interval_secs: 60,
+            max_attempts: 3,
+        });
+        let message = Message::new(MessageType::Text("db down".to_string()));
+        tracker.track("a", vec!["oncall".to_string()], message.clone());
+        tracker.track("a", vec!["ops".to_string()], message.clone());
+        tracker.track("a", vec!["oncall".to_string()], message);
+
+        let now = Instant::now() + Duration::from_secs(61);
+        assert!(tracker.oldest(now).unwrap() >= Duration::from_secs(61));
+        let reminders = tracker.due(now);
+        assert_eq!(reminders.len(), 1);
+        assert_eq!(reminders[0].channels, ["oncall", "ops"]);
+        tracker.ack("a");
+        assert_eq!(tracker.oldest(now), None);
+    }
+}
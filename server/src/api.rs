@@ -1,6 +1,12 @@
-use common::{MessageType, PushResult};
+use crate::error::ErrorBody;
+use crate::status::IncidentUpdate;
+use chrono::{DateTime, NaiveDate, Utc};
+use common::{
+    DeliveryStatus, Mention, Message, MessageType, MetricSeries, PlatformInfo, Priority, PushResult,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 
 /// 推送请求体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +18,69 @@ pub struct PushRequest {
     pub config: Value,
     /// 消息内容
     pub message: MessageType,
+    /// 优先级
+    #[serde(default)]
+    pub priority: Priority,
+    /// @提及列表
+    #[serde(default)]
+    pub mentions: Vec<Mention>,
+    /// 附加元数据
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// 标签，如 `service`、`env`、`team`
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// 会话键，相同键的后续消息发到同一会话
+    #[serde(default)]
+    pub thread_key: Option<String>,
+    /// 消息语言，如 `zh-CN`，用于选择模板的语言版本
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// 指标趋势，随消息附带趋势图
+    #[serde(default)]
+    pub chart: Option<MetricSeries>,
+    /// 事件标记，启用状态页时会被记录
+    #[serde(default)]
+    pub incident: Option<IncidentUpdate>,
+    /// 演练，照常渲染但不实际发送，结果的 `response` 为渲染后的请求载荷
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 单次请求超时（毫秒），覆盖平台配置
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 重试次数，覆盖平台配置
+    #[serde(default)]
+    pub retry_count: Option<u32>,
+}
+
+impl PushRequest {
+    /// 转换为消息信封
+    pub fn to_message(&self) -> Message {
+        let message = Message {
+            content: self.message.clone(),
+            priority: self.priority,
+            mentions: self.mentions.clone(),
+            metadata: self.metadata.clone(),
+            labels: self.labels.clone(),
+            require_ack: false,
+            thread_key: self.thread_key.clone(),
+            chart: self.chart.clone(),
+            timeout_ms: self.timeout_ms,
+            retry_count: self.retry_count,
+        };
+        match &self.locale {
+            Some(locale) => message.with_locale(locale),
+            None => message,
+        }
+    }
+}
+
+/// 请求体为消息本身的推送接口的查询参数，`POST /push` 在请求体中设置
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PushQuery {
+    /// 演练，照常路由和渲染但不实际发送，结果的 `response` 为渲染后的请求载荷
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// 推送响应体
@@ -20,3 +89,168 @@ pub struct PushResponse {
     /// 推送结果
     pub result: PushResult,
 }
+
+/// 预览请求体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewRequest {
+    pub message: Message,
+    /// 预览的通道，为空时按路由规则选择
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+/// 消息在一个通道上的预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelPreview {
+    pub channel: String,
+    /// 平台名称
+    pub platform: String,
+    /// 平台会收到的请求载荷，超长消息拆分后每条一个；
+    /// 平台不支持演练时为按平台能力降级和拆分后的消息
+    pub payloads: Vec<Value>,
+    /// 渲染失败的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 通道测试响应体，发送失败时同样返回 200，失败原因在 `error` 中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelTestResponse {
+    pub channel: String,
+    pub success: bool,
+    /// 发送结果，包含上游响应体和 HTTP 状态码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<PushResult>,
+    /// 发送失败的原因，包含上游返回的错误信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorBody>,
+}
+
+/// 付费通道当日的预算使用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub channel: String,
+    /// 统计日期，按 UTC 计算
+    pub date: NaiveDate,
+    /// 每条消息的费用
+    pub cost_per_message: f64,
+    /// 每日预算
+    pub limit: f64,
+    /// 当日已花费
+    pub spent: f64,
+    /// 当日剩余预算
+    pub remaining: f64,
+    /// 当日发送成功的消息数
+    pub sent: u64,
+    /// 当日因预算用尽被拒绝或改道的消息数
+    pub refused: u64,
+    /// 预算是否已不足以再发送一条
+    pub exhausted: bool,
+}
+
+/// 二维码推送请求体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QrRequest {
+    /// 编码进二维码的文字或链接，如配对链接、支付链接
+    pub text: String,
+    /// 图片说明
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+/// 定时推送请求体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRequest {
+    pub message: Message,
+    /// 计划发送时间，如 `2024-01-02T09:00:00+08:00`，已过去的时间在下一次检查时发送
+    pub send_at: DateTime<Utc>,
+}
+
+/// 定时推送响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleResponse {
+    /// 定时消息 ID，同时是发送时的请求 ID，可用于取消
+    pub id: String,
+    pub channel: String,
+    pub send_at: DateTime<Utc>,
+}
+
+/// 推送历史查询参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct HistoryQuery {
+    /// 返回的条数，默认 50，最多 1000
+    #[serde(default = "default_history_limit")]
+    pub limit: usize,
+    /// 按消息标签过滤，逗号分隔的 `key=value`，全部匹配的记录才返回
+    #[serde(default)]
+    pub labels: Option<String>,
+}
+
+fn default_history_limit() -> usize {
+    50
+}
+
+impl HistoryQuery {
+    /// 解析标签过滤条件
+    pub fn label_filter(&self) -> Result<HashMap<String, String>, String> {
+        self.labels
+            .iter()
+            .flat_map(|labels| labels.split(','))
+            .filter(|pair| !pair.trim().is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    Ok((key.trim().to_string(), value.trim().to_string()))
+                }
+                _ => Err(format!(
+                    "Invalid label filter '{}', expected key=value",
+                    pair
+                )),
+            })
+            .collect()
+    }
+}
+
+/// 确认响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AckResponse {
+    /// 被确认消息的请求 ID
+    pub id: String,
+    /// 确认前已发送的次数
+    pub attempts: u32,
+}
+
+/// 投递状态响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptsResponse {
+    /// 推送时的请求 ID
+    pub id: String,
+    /// 每个通道或平台的最新投递状态
+    pub statuses: BTreeMap<String, DeliveryStatus>,
+}
+
+/// 回执 Webhook 响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptWebhookResponse {
+    /// 更新了状态的回执数，未跟踪或已是最终状态的回执不计入
+    pub applied: usize,
+}
+
+/// 请求中不合法的字段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    /// 字段路径，如 `message.url`
+    pub field: String,
+    /// 不合法的原因
+    pub message: String,
+}
+
+/// 平台发现条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformDescriptor {
+    /// 平台名称
+    pub name: String,
+    /// 平台信息，平台无法脱离配置提供时为空
+    pub info: Option<PlatformInfo>,
+    /// 平台配置的 JSON Schema
+    pub config_schema: Value,
+}
@@ -0,0 +1,71 @@
+use crate::error::{ApiError, ErrorCode};
+use actix_web::HttpRequest;
+use actix_web::http::{StatusCode, header};
+
+/// 推送接口的 API Key，未配置任何 Key 时不校验
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys(Vec<String>);
+
+impl ApiKeys {
+    pub fn new(keys: Vec<String>) -> Self {
+        Self(keys)
+    }
+
+    /// 校验 `Authorization: Bearer <key>` 头，缺少时返回 401，Key 无效时返回 403
+    pub fn check(&self, req: &HttpRequest) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let token = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match token {
+            Some(token) if self.0.iter().any(|key| key == token) => Ok(()),
+            Some(_) => Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                ErrorCode::Forbidden,
+                "Invalid API key",
+            )),
+            None => Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                ErrorCode::Unauthorized,
+                "Missing API key",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_check() {
+        let request = |auth: Option<&str>| {
+            let req = TestRequest::default();
+            match auth {
+                Some(auth) => req.insert_header((header::AUTHORIZATION, auth)),
+                None => req,
+            }
+            .to_http_request()
+        };
+        let status = |result: Result<(), ApiError>| result.unwrap_err().status_code();
+        assert!(ApiKeys::default().check(&request(None)).is_ok());
+
+        let keys = ApiKeys::new(vec!["k1".to_string(), "k2".to_string()]);
+        assert!(keys.check(&request(Some("Bearer k2"))).is_ok());
+        assert_eq!(
+            status(keys.check(&request(Some("Bearer k3")))),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status(keys.check(&request(Some("k1")))),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status(keys.check(&request(None))), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::api::BudgetUsage;
+use chrono::{DateTime, NaiveDate, Utc};
+use common::{Budget, PushResult};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 各付费通道当日的花费，按 UTC 日期重置
+///
+/// 只记录在本副本内，发送成功后才计费，并发发送时可能略微超出预算
+#[derive(Default)]
+pub struct Budgets {
+    channels: Mutex<HashMap<String, Spend>>,
+}
+
+/// 一个通道当日的花费
+#[derive(Debug, Clone, Copy, Default)]
+struct Spend {
+    date: NaiveDate,
+    spent: f64,
+    sent: u64,
+    refused: u64,
+}
+
+impl Budgets {
+    /// 通道当日预算是否还能再发送一条
+    pub fn allows(&self, channel: &str, budget: &Budget, now: DateTime<Utc>) -> bool {
+        budget.allows(self.today(channel, now).spent)
+    }
+
+    /// 记录一条成功发送的消息
+    pub fn charge(&self, channel: &str, budget: &Budget, now: DateTime<Utc>) {
+        self.update(channel, now, |spend| {
+            spend.spent += budget.cost_per_message;
+            spend.sent += 1;
+        });
+    }
+
+    /// 记录一条因预算用尽被拒绝或改道的消息
+    pub fn refuse(&self, channel: &str, now: DateTime<Utc>) {
+        self.update(channel, now, |spend| spend.refused += 1);
+    }
+
+    /// 通道当日的预算使用情况
+    pub fn usage(&self, channel: &str, budget: &Budget, now: DateTime<Utc>) -> BudgetUsage {
+        let spend = self.today(channel, now);
+        BudgetUsage {
+            channel: channel.to_string(),
+            date: spend.date,
+            cost_per_message: budget.cost_per_message,
+            limit: budget.daily_limit,
+            spent: spend.spent,
+            remaining: (budget.daily_limit - spend.spent).max(0.0),
+            sent: spend.sent,
+            refused: spend.refused,
+            exhausted: !budget.allows(spend.spent),
+        }
+    }
+
+    fn today(&self, channel: &str, now: DateTime<Utc>) -> Spend {
+        let mut spend = Spend::default();
+        self.update(channel, now, |today| spend = *today);
+        spend
+    }
+
+    /// 修改通道当日的花费，跨日时先清零
+    fn update(&self, channel: &str, now: DateTime<Utc>, f: impl FnOnce(&mut Spend)) {
+        let date = now.date_naive();
+        let mut channels = self.channels.lock().unwrap();
+        let spend = channels.entry(channel.to_string()).or_default();
+        if spend.date != date {
+            *spend = Spend {
+                date,
+                ..Default::default()
+            };
+        }
+        f(spend);
+    }
+}
+
+/// 预算用尽被拒绝的消息在推送结果中的表示
+pub fn refused(channel: &str, budget: &Budget) -> PushResult {
+    PushResult {
+        success: false,
+        response: Some(format!(
+            "Daily budget of {} exhausted for channel '{}'",
+            budget.daily_limit, channel
+        )),
+        channel: Some(channel.to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_budget() {
+        let budgets = Budgets::default();
+        let budget = Budget {
+            cost_per_message: 0.1,
+            daily_limit: 0.3,
+            downgrade_to: None,
+        };
+        let now: DateTime<Utc> = "2024-01-01T12:00:00Z".parse().unwrap();
+        for _ in 0..3 {
+            assert!(budgets.allows("sms", &budget, now));
+            budgets.charge("sms", &budget, now);
+        }
+        assert!(!budgets.allows("sms", &budget, now));
+        budgets.refuse("sms", now);
+
+        let usage = budgets.usage("sms", &budget, now);
+        assert_eq!(usage.sent, 3);
+        assert_eq!(usage.refused, 1);
+        assert!(usage.exhausted);
+        assert!(usage.remaining < 1e-9);
+
+        // 次日重置
+        let tomorrow = now + Duration::days(1);
+        assert!(budgets.allows("sms", &budget, tomorrow));
+        let usage = budgets.usage("sms", &budget, tomorrow);
+        assert_eq!((usage.sent, usage.refused), (0, 0));
+        assert_eq!(usage.date.to_string(), "2024-01-02");
+    }
+}
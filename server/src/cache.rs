@@ -0,0 +1,146 @@
+use common::PushPlatformCapabilities;
+use common::sign;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 默认最多缓存的实例数
+pub const DEFAULT_MAX_ENTRIES: usize = 256;
+
+/// 平台实例缓存，按平台名称和配置复用已初始化的实例，以共用连接池并避免反复初始化
+///
+/// 实例创建后超过 TTL 即重新创建，以便凭据轮换等变更生效；
+/// 超过最大数量时淘汰最久未使用的实例，避免直接推送的配置各不相同时无限增长
+pub struct PlatformCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+struct CacheEntry {
+    platform: Arc<dyn PushPlatformCapabilities>,
+    created: Instant,
+    last_used: Instant,
+}
+
+impl PlatformCache {
+    /// 创建缓存，TTL 为零时不缓存
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// 设置最多缓存的实例数，为零时不缓存
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// 获取未过期的实例
+    pub fn get(&self, platform: &str, config: &Value) -> Option<Arc<dyn PushPlatformCapabilities>> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| now.duration_since(entry.created) < self.ttl);
+        let entry = entries.get_mut(&cache_key(platform, config))?;
+        entry.last_used = now;
+        Some(entry.platform.clone())
+    }
+
+    /// 放入已初始化的实例，返回共享的实例
+    pub fn insert(
+        &self,
+        platform: &str,
+        config: &Value,
+        instance: Box<dyn PushPlatformCapabilities>,
+    ) -> Arc<dyn PushPlatformCapabilities> {
+        let instance: Arc<dyn PushPlatformCapabilities> = Arc::from(instance);
+        if self.ttl.is_zero() || self.max_entries == 0 {
+            return instance;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            cache_key(platform, config),
+            CacheEntry {
+                platform: instance.clone(),
+                created: now,
+                last_used: now,
+            },
+        );
+        while entries.len() > self.max_entries {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            entries.remove(&oldest);
+        }
+        instance
+    }
+}
+
+/// 平台名称和配置的摘要，配置中的凭据不会以明文留在内存中的键里；
+/// 对象键按字典序序列化，相同配置得到相同的键
+fn cache_key(platform: &str, config: &Value) -> String {
+    let key = format!("{}:{}", platform, config);
+    sign::hex(&sign::sha256(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::FallbackPlatform;
+    use serde_json::json;
+
+    fn instance() -> Box<dyn PushPlatformCapabilities> {
+        Box::new(FallbackPlatform::new(Vec::new()))
+    }
+
+    #[test]
+    fn test_reuses_instances_per_config() {
+        let cache = PlatformCache::new(Duration::from_secs(60));
+        let config = json!({"token": "a"});
+        assert!(cache.get("wxwork", &config).is_none());
+        let inserted = cache.insert("wxwork", &config, instance());
+        let cached = cache.get("wxwork", &config).unwrap();
+        assert!(Arc::ptr_eq(&inserted, &cached));
+        assert!(cache.get("wxwork", &json!({"token": "b"})).is_none());
+
+        let disabled = PlatformCache::new(Duration::ZERO);
+        disabled.insert("wxwork", &config, instance());
+        assert!(disabled.get("wxwork", &config).is_none());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = PlatformCache::new(Duration::from_secs(60)).with_max_entries(2);
+        let (a, b, c) = (
+            json!({"token": "a"}),
+            json!({"token": "b"}),
+            json!({"token": "c"}),
+        );
+        cache.insert("wxwork", &a, instance());
+        cache.insert("wxwork", &b, instance());
+        // 访问 a 之后 b 成为最久未使用的实例
+        assert!(cache.get("wxwork", &a).is_some());
+        cache.insert("wxwork", &c, instance());
+        assert!(cache.get("wxwork", &a).is_some());
+        assert!(cache.get("wxwork", &b).is_none());
+        assert!(cache.get("wxwork", &c).is_some());
+        assert_eq!(cache.entries.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_key_does_not_contain_config() {
+        let key = cache_key("wxwork", &json!({"token": "SECRET"}));
+        assert!(!key.contains("SECRET"));
+        assert_eq!(key, cache_key("wxwork", &json!({"token": "SECRET"})));
+        assert_ne!(key, cache_key("slack", &json!({"token": "SECRET"})));
+    }
+}
@@ -0,0 +1,314 @@
+//! 聊天平台的机器人命令，接收方在群里回复 `/ack`、`/silence` 等命令更新服务端状态
+
+use crate::dispatch::Dispatcher;
+use actix_web::web;
+use chrono::{TimeDelta, Utc};
+use common::PushError;
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod slack;
+pub mod telegram;
+pub mod wxwork;
+
+/// 命令用法，无法解析时回复
+const USAGE: &str = "Usage: /ack <id> | /silence <duration> key=value... | /unsilence <id>";
+
+/// 机器人命令接入配置，未配置的平台不注册回调路由
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommandConfig {
+    /// Telegram bot webhook，路由 `/commands/telegram`
+    pub telegram: Option<telegram::TelegramConfig>,
+    /// Slack slash command，路由 `/commands/slack`
+    pub slack: Option<slack::SlackConfig>,
+    /// 企业微信回调，路由 `/commands/wxwork`
+    pub wxwork: Option<wxwork::WxworkConfig>,
+}
+
+/// 聊天平台发来的命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// 确认消息，停止重复提醒
+    Ack(String),
+    /// 在一段时间内不发送标签匹配的消息
+    Silence {
+        duration: TimeDelta,
+        matchers: BTreeMap<String, String>,
+    },
+    /// 提前解除静默
+    Unsilence(String),
+    /// 显示用法
+    Help,
+}
+
+impl Command {
+    /// 解析一行命令，开头的 @提及和命令名后的 `@机器人名` 会被忽略，不是命令时返回 `None`
+    pub fn parse(text: &str) -> Option<Result<Self, String>> {
+        let mut words = text
+            .split_whitespace()
+            .skip_while(|word| word.starts_with('@'));
+        let name = words.next()?.strip_prefix('/')?;
+        let name = name.split('@').next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        Some(Self::from_args(name, &args))
+    }
+
+    fn from_args(name: &str, args: &[&str]) -> Result<Self, String> {
+        match (name, args) {
+            ("ack", [id]) => Ok(Self::Ack(id.to_string())),
+            ("silence", [duration, matchers @ ..]) if !matchers.is_empty() => {
+                let duration = parse_duration(duration)?;
+                let matchers = matchers
+                    .iter()
+                    .map(|matcher| {
+                        matcher
+                            .split_once('=')
+                            .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .ok_or_else(|| {
+                                format!("Invalid matcher '{}', expected key=value", matcher)
+                            })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Self::Silence { duration, matchers })
+            }
+            ("unsilence", [id]) => Ok(Self::Unsilence(id.to_string())),
+            ("help" | "start", _) => Ok(Self::Help),
+            ("ack" | "silence" | "unsilence", _) => Err(USAGE.to_string()),
+            _ => Err(format!("Unknown command '/{}'. {}", name, USAGE)),
+        }
+    }
+
+    /// 执行命令，返回回复给发送者的文字
+    pub async fn execute(self, dispatcher: &Dispatcher, user: &str) -> String {
+        match self {
+            Self::Ack(id) => match dispatcher.acks().ack(&id) {
+                Some(attempts) => {
+                    info!(
+                        "[{}] Acknowledged by {} after {} attempt(s)",
+                        id, user, attempts
+                    );
+                    format!("Acknowledged {} after {} attempt(s)", id, attempts)
+                }
+                None => format!("No pending acknowledgement for '{}'", id),
+            },
+            Self::Silence { duration, matchers } => {
+                let silence = dispatcher
+                    .add_silence(matchers, Utc::now() + duration, Some(user.to_string()))
+                    .await;
+                info!(
+                    "Silence {} created by {} until {}: {}",
+                    silence.id,
+                    user,
+                    silence.until,
+                    silence.describe()
+                );
+                format!(
+                    "Silenced {} until {} (id {}), undo with /unsilence {}",
+                    silence.describe(),
+                    silence.until.format("%Y-%m-%d %H:%M UTC"),
+                    silence.id,
+                    silence.id
+                )
+            }
+            Self::Unsilence(id) => {
+                if dispatcher.remove_silence(&id).await {
+                    info!("Silence {} removed by {}", id, user);
+                    format!("Removed silence {}", id)
+                } else {
+                    format!("No active silence '{}'", id)
+                }
+            }
+            Self::Help => USAGE.to_string(),
+        }
+    }
+}
+
+/// 解析 `30m`、`2h`、`1d12h` 形式的时长，单位为 s/m/h/d
+pub fn parse_duration(value: &str) -> Result<TimeDelta, String> {
+    let invalid = || format!("Invalid duration '{}', expected e.g. 30m, 2h or 1d", value);
+    let mut total = TimeDelta::zero();
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let amount: i64 = rest[..digits].parse().map_err(|_| invalid())?;
+        let unit = match rest[digits..].chars().next() {
+            Some('s') => TimeDelta::try_seconds(amount),
+            Some('m') => TimeDelta::try_minutes(amount),
+            Some('h') => TimeDelta::try_hours(amount),
+            Some('d') => TimeDelta::try_days(amount),
+            _ => None,
+        };
+        total = unit
+            .and_then(|unit| total.checked_add(&unit))
+            .ok_or_else(invalid)?;
+        rest = &rest[digits + 1..];
+    }
+    if total <= TimeDelta::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+/// 检查发送者并执行命令，`users` 为发送者的各个标识，任一在允许列表中即可；不是命令时返回 `None`
+pub async fn handle(
+    dispatcher: &Dispatcher,
+    allowed_users: &[String],
+    users: &[&str],
+    text: &str,
+) -> Option<String> {
+    let command = Command::parse(text)?;
+    let user = users.first().copied().unwrap_or("unknown");
+    if !allowed_users.is_empty() && !users.iter().any(|u| allowed_users.iter().any(|a| a == u)) {
+        warn!("Rejected command from unauthorized user {}: {}", user, text);
+        return Some(format!("User '{}' is not allowed to run commands", user));
+    }
+    Some(match command {
+        Ok(command) => command.execute(dispatcher, user).await,
+        Err(usage) => usage,
+    })
+}
+
+/// 已启用的命令回调
+#[derive(Clone, Default)]
+pub struct Commands {
+    telegram: Option<web::Data<telegram::TelegramConfig>>,
+    slack: Option<web::Data<slack::SlackConfig>>,
+    wxwork: Option<web::Data<wxwork::WxworkCallback>>,
+}
+
+impl Commands {
+    /// 由配置创建，企业微信的 EncodingAESKey 无效时返回配置错误
+    pub fn new(config: CommandConfig) -> Result<Self, PushError> {
+        if config
+            .telegram
+            .as_ref()
+            .is_some_and(|telegram| telegram.secret_token.is_none())
+        {
+            warn!("No Telegram secret token configured, command webhook is unauthenticated");
+        }
+        Ok(Self {
+            telegram: config.telegram.map(web::Data::new),
+            slack: config.slack.map(web::Data::new),
+            wxwork: config
+                .wxwork
+                .map(wxwork::WxworkCallback::new)
+                .transpose()?
+                .map(web::Data::new),
+        })
+    }
+
+    /// 注册已启用平台的回调路由
+    pub fn configure(&self, cfg: &mut web::ServiceConfig) {
+        if let Some(telegram) = &self.telegram {
+            cfg.app_data(telegram.clone()).service(telegram::receive);
+        }
+        if let Some(slack) = &self.slack {
+            cfg.app_data(slack.clone()).service(slack::receive);
+        }
+        if let Some(wxwork) = &self.wxwork {
+            cfg.app_data(wxwork.clone())
+                .service(wxwork::verify)
+                .service(wxwork::receive);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Message, MessageType, PlatformRegistry};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse("hello bot"), None);
+        assert_eq!(
+            Command::parse("@oncall-bot /ack 123"),
+            Some(Ok(Command::Ack("123".to_string())))
+        );
+        assert_eq!(
+            Command::parse("/silence@oncall_bot 1h30m service=db env=prod"),
+            Some(Ok(Command::Silence {
+                duration: TimeDelta::minutes(90),
+                matchers: BTreeMap::from([
+                    ("env".to_string(), "prod".to_string()),
+                    ("service".to_string(), "db".to_string()),
+                ]),
+            }))
+        );
+        assert_eq!(Command::parse("/ack"), Some(Err(USAGE.to_string())));
+        assert_eq!(Command::parse("/silence 2h"), Some(Err(USAGE.to_string())));
+        assert!(
+            matches!(Command::parse("/silence 2x service=db"), Some(Err(e)) if e.starts_with("Invalid duration"))
+        );
+        assert!(
+            matches!(Command::parse("/silence 2h service"), Some(Err(e)) if e.starts_with("Invalid matcher"))
+        );
+        assert!(
+            matches!(Command::parse("/deploy prod"), Some(Err(e)) if e.starts_with("Unknown command '/deploy'"))
+        );
+
+        assert_eq!(parse_duration("45s"), Ok(TimeDelta::seconds(45)));
+        assert_eq!(parse_duration("1d"), Ok(TimeDelta::days(1)));
+        for invalid in ["", "0m", "h", "10", "5w"] {
+            assert!(parse_duration(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle() {
+        let dispatcher = Dispatcher::new(
+            Arc::new(PlatformRegistry::new()),
+            Default::default(),
+            Duration::ZERO,
+        );
+        let mut message = Message::new(MessageType::Text("db down".to_string()));
+        message.require_ack = true;
+        dispatcher
+            .acks()
+            .track("42", vec!["oncall".to_string()], message);
+
+        let allowed = ["alice".to_string()];
+        assert_eq!(
+            handle(&dispatcher, &allowed, &["bob"], "/ack 42")
+                .await
+                .unwrap(),
+            "User 'bob' is not allowed to run commands"
+        );
+        assert_eq!(
+            handle(&dispatcher, &allowed, &["bob"], "thanks").await,
+            None
+        );
+        assert_eq!(
+            handle(&dispatcher, &allowed, &["U123", "alice"], "/ack 42")
+                .await
+                .unwrap(),
+            "Acknowledged 42 after 1 attempt(s)"
+        );
+        assert_eq!(
+            handle(&dispatcher, &[], &["bob"], "/ack 42").await.unwrap(),
+            "No pending acknowledgement for '42'"
+        );
+
+        let reply = handle(&dispatcher, &[], &["bob"], "/silence 2h service=db")
+            .await
+            .unwrap();
+        assert!(reply.starts_with("Silenced service=db until "));
+        let db = Message::new(MessageType::Text("db down".to_string())).with_label("service", "db");
+        let silence = dispatcher.find_silence(&db).unwrap();
+        assert_eq!(silence.created_by.as_deref(), Some("bob"));
+        let unsilence = format!("/unsilence {}", silence.id);
+        assert_eq!(
+            handle(&dispatcher, &[], &["bob"], &unsilence)
+                .await
+                .unwrap(),
+            format!("Removed silence {}", silence.id)
+        );
+        assert!(dispatcher.find_silence(&db).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::dispatch::Dispatcher;
+use crate::error::{ApiError, ErrorCode};
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use common::sign;
+use serde::{Deserialize, Serialize};
+
+/// 请求时间戳与本机时间允许的最大偏差（秒），超出视为重放
+const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
+/// Slack slash command 配置
+///
+/// 可以为 `/ack`、`/silence`、`/unsilence` 分别创建 slash command，也可以只创建一个如
+/// `/multipush`，用 `/multipush ack 123` 的形式调用，Request URL 均为 `/commands/slack`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Slack 应用的 Signing Secret，用于校验请求签名
+    pub signing_secret: String,
+    /// 允许执行命令的用户 ID 或用户名，为空时不限制
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+}
+
+/// Slack slash command 请求（只包含用到的字段）
+#[derive(Debug, Clone, Deserialize)]
+pub struct SlashCommand {
+    pub command: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub user_id: String,
+    #[serde(default)]
+    pub user_name: String,
+}
+
+impl SlashCommand {
+    /// 转换为 `/ack 123` 形式的命令行
+    fn line(&self) -> String {
+        match self.command.trim_start_matches('/') {
+            "ack" | "silence" | "unsilence" => format!("{} {}", self.command, self.text),
+            _ => format!("/{}", self.text.trim_start_matches('/')),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SlackReply {
+    response_type: &'static str,
+    text: String,
+}
+
+/// 校验 Slack 请求签名 `v0=HMAC-SHA256(signing_secret, "v0:{timestamp}:{body}")`
+pub fn verify_signature(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+) -> bool {
+    let Ok(ts) = timestamp.parse::<i64>() else {
+        return false;
+    };
+    if (now - ts).abs() > MAX_CLOCK_SKEW_SECS {
+        return false;
+    }
+    let Some(signature) = signature.strip_prefix("v0=") else {
+        return false;
+    };
+    let data = [format!("v0:{}:", timestamp).as_bytes(), body].concat();
+    sign::hmac_sha256(signing_secret.as_bytes(), &data).is_ok_and(|digest| {
+        sign::constant_time_eq(sign::hex(&digest).as_bytes(), signature.as_bytes())
+    })
+}
+
+/// 接收 Slack slash command，执行命令并回复到频道
+#[post("/commands/slack")]
+pub async fn receive(
+    req: HttpRequest,
+    body: web::Bytes,
+    dispatcher: web::Data<Dispatcher>,
+    config: web::Data<SlackConfig>,
+) -> Result<HttpResponse, ApiError> {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+    };
+    if !verify_signature(
+        &config.signing_secret,
+        header("X-Slack-Request-Timestamp"),
+        &body,
+        header("X-Slack-Signature"),
+        sign::timestamp_secs(),
+    ) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            ErrorCode::Unauthorized,
+            "Invalid Slack request signature",
+        ));
+    }
+    let command: SlashCommand = serde_urlencoded::from_bytes(&body).map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidBody,
+            format!("Invalid slash command: {}", e),
+        )
+    })?;
+
+    let users = [command.user_name.as_str(), command.user_id.as_str()];
+    let reply = super::handle(&dispatcher, &config.allowed_users, &users, &command.line())
+        .await
+        .unwrap_or_else(|| super::USAGE.to_string());
+    Ok(HttpResponse::Ok().json(SlackReply {
+        response_type: "in_channel",
+        text: reply,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        // Slack 文档中的示例请求
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let timestamp = "1531420618";
+        let body = b"token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+        let signature = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+        let now = 1531420618 + 60;
+        assert!(verify_signature(secret, timestamp, body, signature, now));
+        assert!(!verify_signature(
+            secret,
+            timestamp,
+            b"text=ack",
+            signature,
+            now
+        ));
+        assert!(!verify_signature("other", timestamp, body, signature, now));
+        assert!(!verify_signature(
+            secret,
+            timestamp,
+            body,
+            signature,
+            now + 600
+        ));
+
+        let command: SlashCommand = serde_urlencoded::from_bytes(body).unwrap();
+        assert_eq!(command.user_name, "roadrunner");
+    }
+
+    #[test]
+    fn test_line() {
+        let command = |command: &str, text: &str| SlashCommand {
+            command: command.to_string(),
+            text: text.to_string(),
+            user_id: String::new(),
+            user_name: String::new(),
+        };
+        assert_eq!(command("/ack", "123").line(), "/ack 123");
+        assert_eq!(
+            command("/multipush", "silence 2h service=db").line(),
+            "/silence 2h service=db"
+        );
+    }
+}
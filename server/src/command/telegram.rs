@@ -0,0 +1,101 @@
+use crate::dispatch::Dispatcher;
+use crate::error::{ApiError, ErrorCode};
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use serde::{Deserialize, Serialize};
+
+/// `setWebhook` 时设置 `secret_token` 后 Telegram 在每个请求中携带的头
+const SECRET_TOKEN_HEADER: &str = "X-Telegram-Bot-Api-Secret-Token";
+
+/// Telegram bot 命令配置，需通过 `setWebhook` 将 bot 的 webhook 指向 `/commands/telegram`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    /// `setWebhook` 时设置的 `secret_token`，未设置时不校验请求来源
+    pub secret_token: Option<String>,
+    /// 允许执行命令的用户名或用户 ID，为空时不限制
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+}
+
+/// Telegram webhook 推送的更新（只包含用到的字段）
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramUpdate {
+    pub message: Option<TelegramMessage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramMessage {
+    pub message_id: i64,
+    pub chat: TelegramChat,
+    pub from: Option<TelegramUser>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramChat {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramUser {
+    pub id: i64,
+    pub username: Option<String>,
+}
+
+/// 通过 webhook 响应直接调用 `sendMessage` 回复命令
+#[derive(Debug, Serialize)]
+struct TelegramReply {
+    method: &'static str,
+    chat_id: i64,
+    reply_to_message_id: i64,
+    text: String,
+}
+
+/// 接收 Telegram bot 的消息，执行其中的命令并回复到原会话
+#[post("/commands/telegram")]
+pub async fn receive(
+    req: HttpRequest,
+    update: web::Json<TelegramUpdate>,
+    dispatcher: web::Data<Dispatcher>,
+    config: web::Data<TelegramConfig>,
+) -> Result<HttpResponse, ApiError> {
+    if let Some(secret) = &config.secret_token {
+        let token = req
+            .headers()
+            .get(SECRET_TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok());
+        if token != Some(secret.as_str()) {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                ErrorCode::Forbidden,
+                "Invalid Telegram secret token",
+            ));
+        }
+    }
+    let Some(message) = &update.message else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let Some(text) = &message.text else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let id = message
+        .from
+        .as_ref()
+        .map(|user| user.id.to_string())
+        .unwrap_or_default();
+    let mut users = Vec::new();
+    if let Some(username) = message.from.as_ref().and_then(|u| u.username.as_deref()) {
+        users.push(username);
+    }
+    users.push(&id);
+
+    match super::handle(&dispatcher, &config.allowed_users, &users, text).await {
+        Some(reply) => Ok(HttpResponse::Ok().json(TelegramReply {
+            method: "sendMessage",
+            chat_id: message.chat.id,
+            reply_to_message_id: message.message_id,
+            text: reply,
+        })),
+        None => Ok(HttpResponse::Ok().finish()),
+    }
+}
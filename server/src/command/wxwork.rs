@@ -0,0 +1,300 @@
+use crate::dispatch::Dispatcher;
+use crate::error::{ApiError, ErrorCode};
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, get, post, web};
+use base64::Engine;
+use base64::engine::DecodePaddingMode;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD};
+use common::{PushError, sign};
+use openssl::symm::{Cipher, Crypter, Mode};
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+
+/// 加密消息的补位块大小
+const BLOCK_SIZE: usize = 32;
+
+/// EncodingAESKey 的最后一个字符可能带有多余的位，需要宽松解码
+const KEY_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &base64::alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_allow_trailing_bits(true)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// 企业微信回调配置，群机器人或自建应用的“接收消息”服务器地址设置为 `/commands/wxwork`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WxworkConfig {
+    /// 回调配置中的 Token
+    pub token: String,
+    /// 回调配置中的 EncodingAESKey
+    pub encoding_aes_key: String,
+    /// 允许执行命令的成员 UserID 或名称，为空时不限制
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+}
+
+/// 回调的签名校验和加解密
+pub struct WxworkCallback {
+    token: String,
+    key: Vec<u8>,
+    allowed_users: Vec<String>,
+}
+
+/// 回调请求的签名参数
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallbackQuery {
+    pub msg_signature: String,
+    pub timestamp: String,
+    pub nonce: String,
+    /// 验证回调地址时的加密字符串
+    pub echostr: Option<String>,
+}
+
+/// 加密的回调请求体
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    #[serde(rename = "Encrypt")]
+    encrypt: String,
+}
+
+/// 解密后的消息，兼容群机器人和自建应用两种格式（只包含用到的字段）
+#[derive(Debug, Deserialize)]
+struct CallbackMessage {
+    #[serde(rename = "MsgType")]
+    msg_type: String,
+    /// 群机器人的消息内容
+    #[serde(rename = "Text")]
+    text: Option<TextContent>,
+    /// 自建应用的消息内容
+    #[serde(rename = "Content")]
+    content: Option<String>,
+    /// 群机器人的发送者
+    #[serde(rename = "From")]
+    from: Option<Sender>,
+    /// 自建应用的发送者
+    #[serde(rename = "FromUserName")]
+    from_user_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextContent {
+    #[serde(rename = "Content")]
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Sender {
+    #[serde(rename = "UserId")]
+    user_id: String,
+    #[serde(rename = "Name")]
+    name: Option<String>,
+}
+
+impl WxworkCallback {
+    /// EncodingAESKey 无法解码为 32 字节的密钥时返回配置错误
+    pub fn new(config: WxworkConfig) -> Result<Self, PushError> {
+        let key = KEY_ENGINE
+            .decode(&config.encoding_aes_key)
+            .ok()
+            .filter(|key| key.len() == 32)
+            .ok_or_else(|| PushError::ConfigError("Invalid WxWork EncodingAESKey".to_string()))?;
+        Ok(Self {
+            token: config.token,
+            key,
+            allowed_users: config.allowed_users,
+        })
+    }
+
+    /// 签名为 Token、时间戳、随机数和密文排序后拼接的 SHA1
+    pub fn signature(&self, timestamp: &str, nonce: &str, encrypted: &str) -> String {
+        let mut parts = [self.token.as_str(), timestamp, nonce, encrypted];
+        parts.sort_unstable();
+        sign::hex(&sign::sha1(parts.concat().as_bytes()))
+    }
+
+    fn verify(&self, query: &CallbackQuery, encrypted: &str) -> Result<(), ApiError> {
+        let expected = self.signature(&query.timestamp, &query.nonce, encrypted);
+        if sign::constant_time_eq(expected.as_bytes(), query.msg_signature.as_bytes()) {
+            return Ok(());
+        }
+        Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            ErrorCode::Forbidden,
+            "Invalid WxWork callback signature",
+        ))
+    }
+
+    /// 解密消息，明文为 16 字节随机数、4 字节网络序长度、消息和接收方 ID
+    pub fn decrypt(&self, encrypted: &str) -> Result<(String, String), String> {
+        let data = STANDARD.decode(encrypted).map_err(|e| e.to_string())?;
+        let plain = self.cipher(Mode::Decrypt, &data)?;
+        let pad = plain.last().copied().unwrap_or_default() as usize;
+        if !(1..=BLOCK_SIZE).contains(&pad) || plain.len() < 20 + pad {
+            return Err("invalid padding".to_string());
+        }
+        let plain = &plain[16..plain.len() - pad];
+        let len = u32::from_be_bytes([plain[0], plain[1], plain[2], plain[3]]) as usize;
+        let body = plain.get(4..4 + len).ok_or("invalid message length")?;
+        let message = String::from_utf8(body.to_vec()).map_err(|e| e.to_string())?;
+        let receive_id = String::from_utf8_lossy(&plain[4 + len..]).into_owned();
+        Ok((message, receive_id))
+    }
+
+    /// 加密回复，格式与 [`Self::decrypt`] 相同
+    pub fn encrypt(&self, message: &str, receive_id: &str) -> Result<String, String> {
+        let mut plain = uuid::Uuid::new_v4().as_bytes().to_vec();
+        plain.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        plain.extend_from_slice(message.as_bytes());
+        plain.extend_from_slice(receive_id.as_bytes());
+        let pad = BLOCK_SIZE - plain.len() % BLOCK_SIZE;
+        plain.resize(plain.len() + pad, pad as u8);
+        Ok(STANDARD.encode(self.cipher(Mode::Encrypt, &plain)?))
+    }
+
+    /// AES-256-CBC，IV 为密钥前 16 字节，补位由调用方处理
+    fn cipher(&self, mode: Mode, data: &[u8]) -> Result<Vec<u8>, String> {
+        let cipher = Cipher::aes_256_cbc();
+        let mut crypter = Crypter::new(cipher, mode, &self.key, Some(&self.key[..16]))
+            .map_err(|e| e.to_string())?;
+        crypter.pad(false);
+        let mut out = vec![0; data.len() + cipher.block_size()];
+        let mut len = crypter.update(data, &mut out).map_err(|e| e.to_string())?;
+        len += crypter
+            .finalize(&mut out[len..])
+            .map_err(|e| e.to_string())?;
+        out.truncate(len);
+        Ok(out)
+    }
+
+    /// 加密的被动回复消息
+    fn reply(&self, query: &CallbackQuery, text: &str, receive_id: &str) -> Result<String, String> {
+        let message = format!(
+            "<xml><MsgType>text</MsgType><Text><Content>{}</Content></Text></xml>",
+            escape(text)
+        );
+        let encrypted = self.encrypt(&message, receive_id)?;
+        Ok(format!(
+            "<xml><Encrypt>{}</Encrypt><MsgSignature>{}</MsgSignature><TimeStamp>{}</TimeStamp><Nonce>{}</Nonce></xml>",
+            encrypted,
+            self.signature(&query.timestamp, &query.nonce, &encrypted),
+            escape(&query.timestamp),
+            escape(&query.nonce)
+        ))
+    }
+}
+
+fn invalid_body(message: String) -> ApiError {
+    ApiError::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidBody, message)
+}
+
+/// 验证回调地址，返回解密后的 `echostr`
+#[get("/commands/wxwork")]
+pub async fn verify(
+    query: web::Query<CallbackQuery>,
+    callback: web::Data<WxworkCallback>,
+) -> Result<HttpResponse, ApiError> {
+    let echostr = query.echostr.as_deref().unwrap_or_default();
+    callback.verify(&query, echostr)?;
+    let (echo, _) = callback
+        .decrypt(echostr)
+        .map_err(|e| invalid_body(format!("Invalid echostr: {}", e)))?;
+    Ok(HttpResponse::Ok().body(echo))
+}
+
+/// 接收企业微信回调消息，执行其中的命令并被动回复
+#[post("/commands/wxwork")]
+pub async fn receive(
+    query: web::Query<CallbackQuery>,
+    body: String,
+    dispatcher: web::Data<Dispatcher>,
+    callback: web::Data<WxworkCallback>,
+) -> Result<HttpResponse, ApiError> {
+    let envelope: Envelope = quick_xml::de::from_str(&body)
+        .map_err(|e| invalid_body(format!("Invalid callback body: {}", e)))?;
+    callback.verify(&query, &envelope.encrypt)?;
+    let (xml, receive_id) = callback
+        .decrypt(&envelope.encrypt)
+        .map_err(|e| invalid_body(format!("Invalid encrypted message: {}", e)))?;
+    let message: CallbackMessage = quick_xml::de::from_str(&xml)
+        .map_err(|e| invalid_body(format!("Invalid callback message: {}", e)))?;
+    if message.msg_type != "text" {
+        return Ok(HttpResponse::Ok().finish());
+    }
+    let Some(text) = message.text.map(|t| t.content).or(message.content) else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let mut users = Vec::new();
+    if let Some(from) = &message.from {
+        users.push(from.user_id.as_str());
+        users.extend(from.name.as_deref());
+    }
+    users.extend(message.from_user_name.as_deref());
+
+    let Some(reply) = super::handle(&dispatcher, &callback.allowed_users, &users, &text).await
+    else {
+        return Ok(HttpResponse::Ok().finish());
+    };
+    let reply = callback
+        .reply(&query, &reply, &receive_id)
+        .map_err(|e| ApiError::from(PushError::PlatformError(e)))?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/xml")
+        .body(reply))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callback() -> WxworkCallback {
+        WxworkCallback::new(WxworkConfig {
+            token: "QDG6eK".to_string(),
+            encoding_aes_key: "jWmYm7qr5nMoAUwZRjGtBxmz3KA1tkAj3ykkR6q2B2C".to_string(),
+            allowed_users: Vec::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let callback = callback();
+        let xml = "<xml><ChatId>wrkSFfCgAA</ChatId><From><UserId>zhangsan</UserId><Name>张三</Name></From>\
+                   <MsgType>text</MsgType><Text><Content><![CDATA[@oncall /ack 42]]></Content></Text></xml>";
+        let encrypted = callback.encrypt(xml, "").unwrap();
+        let (decrypted, receive_id) = callback.decrypt(&encrypted).unwrap();
+        assert_eq!(decrypted, xml);
+        assert_eq!(receive_id, "");
+
+        let message: CallbackMessage = quick_xml::de::from_str(&decrypted).unwrap();
+        assert_eq!(message.msg_type, "text");
+        assert_eq!(message.text.unwrap().content, "@oncall /ack 42");
+        assert_eq!(message.from.unwrap().name.as_deref(), Some("张三"));
+
+        let query = CallbackQuery {
+            msg_signature: callback.signature("1409659813", "1372623149", &encrypted),
+            timestamp: "1409659813".to_string(),
+            nonce: "1372623149".to_string(),
+            echostr: None,
+        };
+        assert!(callback.verify(&query, &encrypted).is_ok());
+        assert!(callback.verify(&query, "tampered").is_err());
+
+        let reply = callback.reply(&query, "Acknowledged 42 <ok>", "").unwrap();
+        let envelope: Envelope = quick_xml::de::from_str(&reply).unwrap();
+        let (reply, _) = callback.decrypt(&envelope.encrypt).unwrap();
+        assert!(reply.contains("<Content>Acknowledged 42 &lt;ok&gt;</Content>"));
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let config = WxworkConfig {
+            token: "t".to_string(),
+            encoding_aes_key: "short".to_string(),
+            allowed_users: Vec::new(),
+        };
+        assert!(matches!(
+            WxworkCallback::new(config),
+            Err(PushError::ConfigError(_))
+        ));
+    }
+}
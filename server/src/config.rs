@@ -0,0 +1,451 @@
+use crate::ack::AckConfig;
+use crate::command::{CommandConfig, Commands};
+use crate::dedup::DedupConfig;
+use crate::ingest::alertmanager::AlertmanagerConfig;
+use crate::ingest::harbor::HarborConfig;
+use crate::ingest::jira::JiraConfig;
+#[cfg(feature = "kafka")]
+use crate::ingest::kafka::KafkaConfig;
+use crate::ingest::mqtt::MqttConfig;
+use crate::ingest::rss::FeedConfig;
+use crate::ingest::syslog::SyslogConfig;
+use crate::lock::ClusterConfig;
+use crate::queue::QueueConfig;
+use crate::receipt::ReceiptConfig;
+use crate::status::StatusPageConfig;
+use crate::storage::StorageConfig;
+use crate::validate::ValidationConfig;
+use common::{
+    ChannelConfig, Directory, MultiPushConfig, PushError, RedactionRule, Redactor,
+    TemplateDefinition, load_config,
+};
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// 平台实例默认缓存时间（秒）
+const DEFAULT_INSTANCE_TTL_SECS: u64 = 300;
+/// 会话 ID 默认保留时间（秒）
+const DEFAULT_THREAD_TTL_SECS: u64 = 24 * 60 * 60;
+/// 已发送消息默认保留 24 小时
+const DEFAULT_SENT_TTL_SECS: u64 = 24 * 60 * 60;
+/// 默认监听地址
+const DEFAULT_BIND: &str = "0.0.0.0:8888";
+/// 默认请求体上限（字节）
+const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+/// 结构化配置环境变量的前缀
+const ENV_PREFIX: &str = "MP_";
+
+/// 服务端配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerConfig {
+    /// 命名通道、平台默认配置和路由规则
+    #[serde(flatten)]
+    pub push: MultiPushConfig,
+    /// 监听地址，默认 `0.0.0.0:8888`
+    pub bind: Option<String>,
+    /// 工作线程数，默认为 CPU 核数
+    pub workers: Option<usize>,
+    /// HTTP keep-alive 时间（秒），0 表示关闭
+    pub keep_alive_secs: Option<u64>,
+    /// 请求体上限（字节），默认 2 MiB
+    pub max_body_bytes: Option<usize>,
+    /// 所有路由的前缀，如部署在反向代理的子路径下时设置为 `/multi_push`
+    #[serde(default)]
+    pub base_path: String,
+    /// 推送请求校验
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    /// 推送接口的 API Key，为空时不校验
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    /// RSS/Atom 订阅源
+    #[serde(default)]
+    pub feeds: Vec<FeedConfig>,
+    /// Syslog 监听
+    pub syslog: Option<SyslogConfig>,
+    /// MQTT 订阅
+    pub mqtt: Option<MqttConfig>,
+    /// Kafka 消费
+    #[cfg(feature = "kafka")]
+    pub kafka: Option<KafkaConfig>,
+    /// Alertmanager webhook 接入
+    #[serde(default)]
+    pub alertmanager: AlertmanagerConfig,
+    /// Jira webhook 接入
+    #[serde(default)]
+    pub jira: JiraConfig,
+    /// Harbor webhook 接入
+    #[serde(default)]
+    pub harbor: HarborConfig,
+    /// 状态页，未配置时不启用
+    pub status_page: Option<StatusPageConfig>,
+    /// 消息模板，供不支持原生模板的平台渲染模板消息
+    #[serde(default)]
+    pub templates: HashMap<String, TemplateDefinition>,
+    /// 存储后端，保存推送历史、定时消息、静默规则和模板，默认保存在内存中
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// 投递队列，可重试的推送失败后在后台重试，队列与存储共用同一个后端
+    #[serde(default)]
+    pub queue: QueueConfig,
+    /// 多副本部署时的锁配置，选举发送定时消息的副本并独占队列条目
+    #[serde(default)]
+    pub cluster: ClusterConfig,
+    /// 去重存储，用于调用方提供请求 ID 的幂等推送和订阅源条目去重
+    #[serde(default)]
+    pub dedup: DedupConfig,
+    /// 用户目录，@提及逻辑用户时按目标平台替换为对应标识
+    #[serde(default)]
+    pub directory: Directory,
+    /// `require_ack` 消息的提醒设置
+    #[serde(default)]
+    pub ack: AckConfig,
+    /// 投递回执的查询设置
+    #[serde(default)]
+    pub receipts: ReceiptConfig,
+    /// 聊天平台的机器人命令，用于确认消息和静默告警
+    #[serde(default)]
+    pub commands: CommandConfig,
+    /// 脱敏规则，消息进入分发器时替换匹配的内容，入队和定时保存前生效
+    #[serde(default)]
+    pub redactions: Vec<RedactionRule>,
+    /// 平台实例缓存时间（秒），默认 300，0 表示每次请求都重新创建
+    pub instance_ttl_secs: Option<u64>,
+    /// 最多缓存的平台实例数，默认 256，超出后淘汰最久未使用的实例
+    pub instance_cache_size: Option<usize>,
+    /// 会话键对应的平台会话 ID 保留时间（秒），默认 24 小时，超过后相同键的消息开启新会话
+    pub thread_ttl_secs: Option<u64>,
+    /// 已发送消息的保留时间（秒），默认 24 小时，超过后无法通过 `PATCH/DELETE /push/{id}` 编辑或撤回
+    pub sent_ttl_secs: Option<u64>,
+    /// 启动时的通道检查策略
+    #[serde(default)]
+    pub startup_check: StartupCheck,
+    /// 演练模式，所有推送照常路由、渲染和记录历史但不实际发送，用于验证配置变更
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 平台插件动态库路径，启动时加载
+    #[cfg(feature = "plugins")]
+    #[serde(default)]
+    pub plugins: Vec<std::path::PathBuf>,
+}
+
+/// 启动时的通道检查策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupCheck {
+    /// 不检查，首次推送时才创建通道
+    Off,
+    /// 检查失败时记录错误，仍然启动
+    #[default]
+    Degraded,
+    /// 任一通道检查失败时终止启动
+    FailFast,
+}
+
+impl ServerConfig {
+    /// 平台实例缓存时间
+    pub fn instance_ttl(&self) -> Duration {
+        Duration::from_secs(self.instance_ttl_secs.unwrap_or(DEFAULT_INSTANCE_TTL_SECS))
+    }
+
+    /// 最多缓存的平台实例数
+    pub fn instance_cache_size(&self) -> usize {
+        self.instance_cache_size
+            .unwrap_or(crate::cache::DEFAULT_MAX_ENTRIES)
+    }
+
+    /// 会话 ID 保留时间
+    pub fn thread_ttl(&self) -> Duration {
+        Duration::from_secs(self.thread_ttl_secs.unwrap_or(DEFAULT_THREAD_TTL_SECS))
+    }
+
+    /// 已发送消息保留时间
+    pub fn sent_ttl(&self) -> Duration {
+        Duration::from_secs(self.sent_ttl_secs.unwrap_or(DEFAULT_SENT_TTL_SECS))
+    }
+
+    /// 监听地址
+    pub fn bind_address(&self) -> &str {
+        self.bind.as_deref().unwrap_or(DEFAULT_BIND)
+    }
+
+    /// 请求体上限
+    pub fn max_body_bytes(&self) -> usize {
+        self.max_body_bytes.unwrap_or(DEFAULT_MAX_BODY_BYTES)
+    }
+
+    /// 规范化的路由前缀，以 `/` 开头且不以 `/` 结尾，未设置时为空
+    pub fn base_path(&self) -> String {
+        let path = self.base_path.trim_matches('/');
+        if path.is_empty() {
+            String::new()
+        } else {
+            format!("/{}", path)
+        }
+    }
+
+    /// 加载配置文件，文件不存在时使用空配置
+    ///
+    /// 支持 JSON、TOML 和 YAML 格式，按扩展名识别，配置中可以用 `${VAR}` 引用环境变量；
+    /// 之后再用 `MP_` 前缀的环境变量覆盖，见 [`ServerConfig::apply_env`]
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let invalid = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+        if path.exists() {
+            let config = Self::read(path).map_err(invalid)?;
+            info!("Loaded config from {}", path.display());
+            return Ok(config);
+        }
+        warn!(
+            "Config file {} not found, using environment variables only",
+            path.display()
+        );
+        let mut config = Self::default();
+        config.apply_env(std::env::vars()).map_err(invalid)?;
+        config.push.apply_defaults();
+        Ok(config)
+    }
+
+    /// 读取配置文件并应用环境变量覆盖，文件必须存在
+    pub fn read(path: &Path) -> Result<Self, PushError> {
+        let mut config: Self = load_config(path)?;
+        config.apply_env(std::env::vars())?;
+        config.push.apply_defaults();
+        Ok(config)
+    }
+
+    /// 静态检查服务端专有的配置，不连接外部服务，返回出错的配置项和错误；
+    /// 通道配置由调用方按平台 schema 检查
+    pub fn check(&self) -> Vec<(&'static str, PushError)> {
+        let mut problems = Vec::new();
+        if let Err(e) = self.storage.check() {
+            problems.push(("storage", e));
+        }
+        if let Err(e) = self.dedup.check() {
+            problems.push(("dedup", e));
+        }
+        if let Err(e) = Commands::new(self.commands.clone()) {
+            problems.push(("commands", e));
+        }
+        if let Err(e) = Redactor::new(&self.redactions) {
+            problems.push(("redactions", e));
+        }
+        problems
+    }
+
+    /// 用结构化环境变量覆盖配置，便于容器部署时不挂载配置文件
+    ///
+    /// - `MP_BIND`：监听地址
+    /// - `MP_WORKERS`、`MP_KEEP_ALIVE_SECS`、`MP_MAX_BODY_BYTES`、`MP_BASE_PATH`：HTTP 服务设置
+    /// - `MP_API_KEYS`：逗号分隔的 API Key
+    /// - `MP_CHANNEL_<NAME>_PLATFORM`：定义通道，通道名为 `<NAME>` 的小写形式
+    /// - `MP_CHANNEL_<NAME>_CONFIG`：JSON 格式的平台配置，可以包含非字符串字段
+    /// - `MP_CHANNEL_<NAME>_PROXY`：通道出站代理
+    /// - `MP_CHANNEL_<NAME>_<FIELD>`：平台配置中的字符串字段，字段名为 `<FIELD>` 的小写形式
+    pub fn apply_env(
+        &mut self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(), PushError> {
+        let mut vars: Vec<(String, String)> = vars
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(ENV_PREFIX)?.to_string(), value)))
+            .collect();
+        vars.sort();
+
+        let mut fields = Vec::new();
+        for (key, value) in &vars {
+            match key.as_str() {
+                "BIND" => self.bind = Some(value.clone()),
+                "WORKERS" => self.workers = Some(parse_env(key, value)?),
+                "KEEP_ALIVE_SECS" => self.keep_alive_secs = Some(parse_env(key, value)?),
+                "MAX_BODY_BYTES" => self.max_body_bytes = Some(parse_env(key, value)?),
+                "BASE_PATH" => self.base_path = value.clone(),
+                "API_KEYS" => {
+                    self.api_keys = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|key| !key.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                }
+                _ => {
+                    if let Some(rest) = key.strip_prefix("CHANNEL_") {
+                        fields.push((rest, value));
+                    }
+                }
+            }
+        }
+
+        // 通道名本身可能包含下划线，先由 `_PLATFORM` 确定通道名，再按最长前缀匹配字段
+        for (rest, platform) in &fields {
+            if let Some(name) = rest.strip_suffix("_PLATFORM") {
+                let channel = self
+                    .push
+                    .channels
+                    .entry(name.to_lowercase())
+                    .or_insert_with(|| ChannelConfig {
+                        platform: String::new(),
+                        config: Value::Object(Map::new()),
+                        proxy: None,
+                        retry: None,
+                        decorations: Default::default(),
+                        capabilities: Default::default(),
+                        timezone: None,
+                        policy: None,
+                        quiet_hours: None,
+                        budget: None,
+                        emoji: None,
+                        locale: None,
+                        markdown_image: None,
+                        sms: None,
+                    });
+                channel.platform = platform.to_string();
+            }
+        }
+        let mut names: Vec<(String, String)> = self
+            .push
+            .channels
+            .keys()
+            .map(|name| (name.to_uppercase().replace('-', "_"), name.clone()))
+            .collect();
+        names.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        // JSON 配置先合并，单独设置的字段随后覆盖
+        fields.sort_by_key(|(rest, _)| !rest.ends_with("_CONFIG"));
+        for (rest, value) in fields {
+            let Some((name, field)) = names.iter().find_map(|(prefix, name)| {
+                let field = rest.strip_prefix(prefix.as_str())?.strip_prefix('_')?;
+                Some((name, field))
+            }) else {
+                warn!(
+                    "Ignoring {}CHANNEL_{}: channel has no platform",
+                    ENV_PREFIX, rest
+                );
+                continue;
+            };
+            let channel = self.push.channels.get_mut(name).expect("channel exists");
+            let Value::Object(config) = &mut channel.config else {
+                return Err(PushError::ConfigError(format!(
+                    "Config of channel '{}' must be an object",
+                    name
+                )));
+            };
+            match field {
+                "PLATFORM" => {}
+                "PROXY" => channel.proxy = Some(value.clone()),
+                "CONFIG" => {
+                    let Value::Object(values) = serde_json::from_str(value).map_err(|e| {
+                        PushError::ConfigError(format!(
+                            "Invalid {}CHANNEL_{}: {}",
+                            ENV_PREFIX, rest, e
+                        ))
+                    })?
+                    else {
+                        return Err(PushError::ConfigError(format!(
+                            "{}CHANNEL_{} must be a JSON object",
+                            ENV_PREFIX, rest
+                        )));
+                    };
+                    config.extend(values);
+                }
+                field => {
+                    config.insert(field.to_lowercase(), Value::String(value.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, PushError> {
+    value
+        .parse()
+        .map_err(|_| PushError::ConfigError(format!("Invalid {}{}: {}", ENV_PREFIX, key, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_config_is_flattened() {
+        let config: ServerConfig = common::parse_config(
+            r#"{
+                "channels": {"ops": {"platform": "wxwork", "config": {"token": "a"}}},
+                "routes": [{"channels": ["ops"]}],
+                "instance_ttl_secs": 0
+            }"#,
+            common::ConfigFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(config.push.channels["ops"].platform, "wxwork");
+        assert_eq!(config.push.routes.len(), 1);
+        assert_eq!(config.instance_ttl(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_apply_env() {
+        let mut config: ServerConfig = common::parse_config(
+            r#"{"channels": {"dev-team": {"platform": "wxwork", "config": {"token": "a"}}}}"#,
+            common::ConfigFormat::Json,
+        )
+        .unwrap();
+        let vars = [
+            ("MP_BIND", "127.0.0.1:9000"),
+            ("MP_WORKERS", "4"),
+            ("MP_BASE_PATH", "/multi_push/"),
+            ("MP_API_KEYS", "k1, k2,"),
+            ("MP_CHANNEL_OPS_ALERTS_PLATFORM", "wxwork"),
+            ("MP_CHANNEL_OPS_ALERTS_TOKEN", "t"),
+            (
+                "MP_CHANNEL_OPS_ALERTS_CONFIG",
+                r#"{"token": "x", "timeout": 10}"#,
+            ),
+            ("MP_CHANNEL_OPS_ALERTS_PROXY", "socks5://127.0.0.1:1080"),
+            ("MP_CHANNEL_DEV_TEAM_TOKEN", "b"),
+            ("MP_CHANNEL_QA_TOKEN", "c"),
+            ("HOME", "/root"),
+        ];
+        config
+            .apply_env(vars.map(|(k, v)| (k.to_string(), v.to_string())))
+            .unwrap();
+
+        assert_eq!(config.bind_address(), "127.0.0.1:9000");
+        assert_eq!(config.workers, Some(4));
+        assert_eq!(config.base_path(), "/multi_push");
+        assert_eq!(config.api_keys, ["k1", "k2"]);
+        let ops = &config.push.channels["ops_alerts"];
+        assert_eq!(ops.platform, "wxwork");
+        assert_eq!(ops.config, serde_json::json!({"token": "t", "timeout": 10}));
+        assert_eq!(ops.proxy.as_deref(), Some("socks5://127.0.0.1:1080"));
+        assert_eq!(config.push.channels["dev-team"].config["token"], "b");
+        assert!(!config.push.channels.contains_key("qa"));
+
+        let invalid = [(
+            "MP_CHANNEL_OPS_ALERTS_CONFIG".to_string(),
+            "[1]".to_string(),
+        )];
+        assert!(config.apply_env(invalid).is_err());
+        let invalid = [("MP_WORKERS".to_string(), "many".to_string())];
+        assert!(config.apply_env(invalid).is_err());
+    }
+
+    #[test]
+    fn test_check_server_sections() {
+        let config: ServerConfig = common::parse_config(
+            r#"{
+                "storage": {"url": "mongodb://db/multi_push"},
+                "dedup": {"url": "memory://"},
+                "commands": {"wxwork": {"token": "t", "encoding_aes_key": "short"}}
+            }"#,
+            common::ConfigFormat::Json,
+        )
+        .unwrap();
+        let sections: Vec<&str> = config.check().iter().map(|(section, _)| *section).collect();
+        assert_eq!(sections, ["storage", "commands"]);
+        assert!(ServerConfig::default().check().is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+//! 去重存储，用于调用方提供请求 ID 的幂等推送和订阅源条目去重
+//!
+//! 默认与存储后端共用（SQL 后端重启后仍然有效，多个副本共享），也可以单独使用
+//! 进程内 LRU 或 Redis
+
+use async_trait::async_trait;
+use common::PushError;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 记录保留时间上限，避免计算过期时间时溢出
+pub(crate) const MAX_TTL: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+/// 去重配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// 去重存储地址：`memory://`（进程内 LRU）、`redis://...`（需要 `redis` 特性），
+    /// 默认使用存储后端
+    pub url: Option<String>,
+    /// 记录保留时间（秒），之内相同的键视为重复
+    pub ttl_secs: u64,
+    /// 进程内 LRU 的最大条数
+    pub capacity: usize,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            ttl_secs: 24 * 60 * 60,
+            capacity: 10_000,
+        }
+    }
+}
+
+impl DedupConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs.max(1))
+    }
+
+    /// 连接配置的去重存储，未配置时使用 `storage`
+    pub async fn connect(
+        &self,
+        storage: Arc<dyn DedupStore>,
+    ) -> Result<Arc<dyn DedupStore>, PushError> {
+        self.check()?;
+        let Some(url) = &self.url else {
+            return Ok(storage);
+        };
+        match url.split_once("://").map_or("", |(scheme, _)| scheme) {
+            #[cfg(feature = "redis")]
+            "redis" | "rediss" => Ok(Arc::new(
+                RedisDedup::connect(url, "multi_push:dedup:").await?,
+            )),
+            // 其他协议已由 `check` 排除
+            _ => Ok(Arc::new(MemoryDedup::new(self.capacity))),
+        }
+    }
+
+    /// 检查去重存储地址的协议是否受支持，不连接存储
+    pub fn check(&self) -> Result<(), PushError> {
+        let Some(url) = &self.url else {
+            return Ok(());
+        };
+        match url.split_once("://").map_or("", |(scheme, _)| scheme) {
+            "memory" => Ok(()),
+            #[cfg(feature = "redis")]
+            "redis" | "rediss" => Ok(()),
+            #[allow(unreachable_patterns)]
+            "redis" | "rediss" => Err(PushError::ConfigError(
+                "Redis dedup store requires the `redis` feature".to_string(),
+            )),
+            _ => Err(PushError::ConfigError(format!(
+                "Unsupported dedup URL '{}'",
+                url
+            ))),
+        }
+    }
+}
+
+/// 去重存储
+#[async_trait]
+pub trait DedupStore: Send + Sync {
+    /// 记录 `key`，`ttl` 内第一次出现时返回 `true`，重复时返回 `false`
+    async fn insert(&self, key: &str, ttl: Duration) -> Result<bool, PushError>;
+
+    /// 删除记录，如推送失败后允许调用方重试
+    async fn remove(&self, key: &str) -> Result<(), PushError>;
+
+    /// 删除已过期的记录，返回删除的条数；记录随键自动过期的存储无需实现
+    async fn purge(&self) -> Result<usize, PushError> {
+        Ok(0)
+    }
+}
+
+/// 进程内的去重存储，超出容量时淘汰最早写入的记录
+pub struct MemoryDedup {
+    capacity: usize,
+    entries: Mutex<(HashMap<String, Instant>, VecDeque<String>)>,
+}
+
+impl MemoryDedup {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::default(),
+        }
+    }
+}
+
+impl Default for MemoryDedup {
+    fn default() -> Self {
+        Self::new(DedupConfig::default().capacity)
+    }
+}
+
+#[async_trait]
+impl DedupStore for MemoryDedup {
+    async fn insert(&self, key: &str, ttl: Duration) -> Result<bool, PushError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let (expiry, order) = &mut *entries;
+        if expiry.get(key).is_some_and(|expires_at| *expires_at > now) {
+            return Ok(false);
+        }
+        if expiry
+            .insert(key.to_string(), now + ttl.min(MAX_TTL))
+            .is_some()
+        {
+            order.retain(|k| k != key);
+        }
+        order.push_back(key.to_string());
+        while order.len() > self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                expiry.remove(&oldest);
+            }
+        }
+        Ok(true)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), PushError> {
+        let mut entries = self.entries.lock().unwrap();
+        let (expiry, order) = &mut *entries;
+        if expiry.remove(key).is_some() {
+            order.retain(|k| k != key);
+        }
+        Ok(())
+    }
+
+    async fn purge(&self) -> Result<usize, PushError> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let (expiry, order) = &mut *entries;
+        let before = expiry.len();
+        expiry.retain(|_, expires_at| *expires_at > now);
+        order.retain(|k| expiry.contains_key(k));
+        Ok(before - expiry.len())
+    }
+}
+
+/// 基于 Redis 的去重存储，记录随键过期
+#[cfg(feature = "redis")]
+pub struct RedisDedup {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisDedup {
+    /// 连接 Redis，键名加上 `prefix` 前缀
+    pub async fn connect(url: &str, prefix: impl Into<String>) -> Result<Self, PushError> {
+        let connection = redis::Client::open(url)
+            .map_err(|e| PushError::ConfigError(format!("Invalid Redis URL: {}", e)))?
+            .get_connection_manager()
+            .await
+            .map_err(redis_error)?;
+        Ok(Self {
+            connection,
+            prefix: prefix.into(),
+        })
+    }
+}
+
+#[cfg(feature = "redis")]
+fn redis_error(e: redis::RedisError) -> PushError {
+    PushError::NetworkError(format!("Redis error: {}", e))
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl DedupStore for RedisDedup {
+    async fn insert(&self, key: &str, ttl: Duration) -> Result<bool, PushError> {
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}", self.prefix, key))
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.min(MAX_TTL).as_millis().max(1) as u64)
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(redis_error)?;
+        Ok(reply.is_some())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), PushError> {
+        redis::AsyncCommands::del::<_, ()>(
+            &mut self.connection.clone(),
+            format!("{}{}", self.prefix, key),
+        )
+        .await
+        .map_err(redis_error)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// 各后端共用的检查
+    pub async fn exercise(store: &dyn DedupStore) {
+        let ttl = Duration::from_secs(60);
+        assert!(store.insert("request:a", ttl).await.unwrap());
+        assert!(!store.insert("request:a", ttl).await.unwrap());
+        store.remove("request:a").await.unwrap();
+        assert!(store.insert("request:a", ttl).await.unwrap());
+
+        // 过期后重新计为第一次
+        assert!(store.insert("request:b", Duration::ZERO).await.unwrap());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(store.insert("request:b", ttl).await.unwrap());
+
+        // 超长的保留时间不会溢出
+        assert!(store.insert("request:c", Duration::MAX).await.unwrap());
+        assert!(!store.insert("request:c", ttl).await.unwrap());
+
+        // 清理已过期的记录
+        assert!(store.insert("request:d", Duration::ZERO).await.unwrap());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(store.purge().await.unwrap(), 1);
+        assert!(!store.insert("request:a", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_dedup() {
+        exercise(&MemoryDedup::default()).await;
+
+        let store = MemoryDedup::new(2);
+        let ttl = Duration::from_secs(60);
+        for key in ["a", "b", "c"] {
+            assert!(store.insert(key, ttl).await.unwrap());
+        }
+        // 超出容量时淘汰最早写入的记录
+        assert!(store.insert("a", ttl).await.unwrap());
+        assert!(!store.insert("c", ttl).await.unwrap());
+    }
+}
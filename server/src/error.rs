@@ -0,0 +1,277 @@
+use actix_web::http::{StatusCode, header};
+use actix_web::{HttpResponse, ResponseError};
+use common::PushError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// 机器可读的错误码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// 缺少 API Key
+    Unauthorized,
+    /// API Key 无效
+    Forbidden,
+    /// 请求体无法解析
+    InvalidBody,
+    /// 请求字段校验失败，`details` 中列出全部不合法的字段
+    ValidationFailed,
+    /// 请求体超出大小限制
+    PayloadTooLarge,
+    /// 平台不存在
+    PlatformNotFound,
+    /// 通道不存在
+    ChannelNotFound,
+    /// 待确认的消息不存在、已确认或已停止提醒
+    AckNotFound,
+    /// 消息不存在、已过期或平台不支持编辑和撤回
+    MessageNotFound,
+    /// 相同请求 ID 的请求已在去重窗口内处理过
+    DuplicateRequest,
+    /// 平台或通道配置错误
+    ConfigError,
+    /// 消息内容不被平台接受
+    MessageError,
+    /// 平台不支持该消息类型，`details` 中给出平台、消息类型和建议改用的类型
+    UnsupportedMessageType,
+    /// 平台拒绝了凭据
+    PlatformAuthError,
+    /// 平台返回错误
+    PlatformError,
+    /// 平台限流，`details.retry_after_secs` 为建议的等待时间
+    RateLimited,
+    /// 无法连接平台
+    NetworkError,
+    /// 平台响应超时
+    Timeout,
+}
+
+/// 错误响应体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// 错误码
+    pub code: ErrorCode,
+    /// 面向人的错误描述
+    pub message: String,
+    /// 附加信息，结构随错误码而定
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub details: Value,
+    /// 稍后重试是否可能成功
+    pub retryable: bool,
+}
+
+/// 接口错误，作为 handler 的错误类型时自动转换为对应状态码的响应
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+    retry_after: Option<u64>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                code,
+                message: message.into(),
+                details: Value::Null,
+                retryable: false,
+            },
+            retry_after: None,
+        }
+    }
+
+    /// 设置附加信息
+    pub fn with_details(mut self, details: impl Serialize) -> Self {
+        self.body.details = serde_json::to_value(details).unwrap_or_default();
+        self
+    }
+
+    /// 错误响应体
+    pub fn into_body(self) -> ErrorBody {
+        self.body
+    }
+
+    /// 通道不存在
+    pub fn channel_not_found(channel: &str) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::ChannelNotFound,
+            format!("Channel '{}' not found", channel),
+        )
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.body.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status);
+        if let Some(secs) = self.retry_after {
+            response.insert_header((header::RETRY_AFTER, secs.to_string()));
+        }
+        response.json(&self.body)
+    }
+}
+
+impl From<PushError> for ApiError {
+    fn from(e: PushError) -> Self {
+        let (status, code) = match e.inner() {
+            PushError::NetworkError(_) => (StatusCode::BAD_GATEWAY, ErrorCode::NetworkError),
+            PushError::AuthError(_) => (StatusCode::BAD_GATEWAY, ErrorCode::PlatformAuthError),
+            PushError::ConfigError(_) => (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::ConfigError),
+            PushError::MessageError(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::MessageError)
+            }
+            // `inner()` 已去掉诊断信息，不会再是 `Failure`
+            PushError::PlatformError(_) | PushError::Failure { .. } => {
+                (StatusCode::BAD_GATEWAY, ErrorCode::PlatformError)
+            }
+            PushError::RateLimited { .. } => {
+                (StatusCode::TOO_MANY_REQUESTS, ErrorCode::RateLimited)
+            }
+            PushError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, ErrorCode::Timeout),
+            PushError::PayloadTooLarge(_) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, ErrorCode::PayloadTooLarge)
+            }
+            PushError::Unsupported { .. } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCode::UnsupportedMessageType,
+            ),
+        };
+        let mut error = Self::new(status, code, e.to_string());
+        error.body.retryable = e.is_retryable();
+        let mut details = serde_json::Map::new();
+        if let Some(retry_after) = e.retry_after() {
+            let secs = retry_after.as_secs().max(1);
+            error.retry_after = Some(secs);
+            details.insert("retry_after_secs".to_string(), secs.into());
+        }
+        if let PushError::Unsupported {
+            platform,
+            message_type,
+            suggestion,
+        } = e.inner()
+        {
+            details.insert("platform".to_string(), platform.clone().into());
+            details.insert("message_type".to_string(), serde_json::json!(message_type));
+            details.insert("suggestion".to_string(), serde_json::json!(suggestion));
+        }
+        // 失败时的尝试次数、耗时、HTTP 状态码和平台错误码
+        if let Some(result) = e.diagnostics() {
+            if result.attempts > 0 {
+                details.insert("attempts".to_string(), result.attempts.into());
+            }
+            let fields = [
+                ("elapsed_ms", serde_json::json!(result.elapsed_ms)),
+                ("http_status", serde_json::json!(result.http_status)),
+                ("error_code", serde_json::json!(result.error_code)),
+            ];
+            for (key, value) in fields {
+                if !value.is_null() {
+                    details.insert(key.to_string(), value);
+                }
+            }
+        }
+        if !details.is_empty() {
+            error = error.with_details(details);
+        }
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::MessageBody;
+    use std::time::Duration;
+
+    #[test]
+    fn test_push_error_response() {
+        let error = ApiError::from(PushError::RateLimited {
+            message: "slow down".to_string(),
+            retry_after: Some(Duration::from_secs(30)),
+        });
+        let response = error.error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "30");
+        let body: Value =
+            serde_json::from_slice(&response.into_body().try_into_bytes().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "code": "rate_limited",
+                "message": "Rate limited: slow down",
+                "details": {"retry_after_secs": 30},
+                "retryable": true
+            })
+        );
+
+        let error = ApiError::from(PushError::PlatformError("bad token".to_string()));
+        assert_eq!(error.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(error.body.code, ErrorCode::PlatformError);
+        assert!(!error.body.retryable);
+    }
+
+    #[test]
+    fn test_failure_diagnostics() {
+        let error = PushError::RateLimited {
+            message: "slow down".to_string(),
+            retry_after: Some(Duration::from_secs(60)),
+        }
+        .with_diagnostics(|result| {
+            result.attempts = 2;
+            result.elapsed_ms = Some(120);
+            result.http_status = Some(200);
+            result.error_code = Some("45009".to_string());
+        });
+        let error = ApiError::from(error);
+        assert_eq!(error.status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.body.code, ErrorCode::RateLimited);
+        assert_eq!(error.body.message, "Rate limited: slow down");
+        assert!(error.body.retryable);
+        assert_eq!(
+            error.body.details,
+            serde_json::json!({
+                "retry_after_secs": 60,
+                "attempts": 2,
+                "elapsed_ms": 120,
+                "http_status": 200,
+                "error_code": "45009"
+            })
+        );
+    }
+
+    #[test]
+    fn test_unsupported_error_details() {
+        let error = ApiError::from(PushError::Unsupported {
+            platform: "wxwork".to_string(),
+            message_type: common::MessageKind::Rich,
+            suggestion: Some(common::MessageKind::Markdown),
+        });
+        assert_eq!(error.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.body.code, ErrorCode::UnsupportedMessageType);
+        assert_eq!(
+            error.body.message,
+            "Unsupported: wxwork does not support Rich messages, send it as Markdown instead"
+        );
+        assert_eq!(
+            error.body.details,
+            serde_json::json!({
+                "platform": "wxwork",
+                "message_type": "rich",
+                "suggestion": "markdown"
+            })
+        );
+    }
+}
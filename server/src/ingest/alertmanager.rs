@@ -0,0 +1,254 @@
+use super::{DeliveryReport, GROUP_KEY, no_channels};
+use crate::dispatch::Dispatcher;
+use actix_web::{HttpResponse, post, web};
+use common::{Message, MessageType};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Alertmanager webhook 接入配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertmanagerConfig {
+    /// receiver 名称到通道的映射
+    #[serde(default)]
+    pub receivers: HashMap<String, Vec<String>>,
+    /// 未匹配 receiver 时使用的通道
+    #[serde(default)]
+    pub default_channels: Vec<String>,
+}
+
+/// Alertmanager `webhook_config` 请求体（version 4）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookMessage {
+    pub version: String,
+    pub group_key: String,
+    #[serde(default)]
+    pub truncated_alerts: u64,
+    pub status: String,
+    pub receiver: String,
+    #[serde(default)]
+    pub group_labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub common_labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub common_annotations: BTreeMap<String, String>,
+    #[serde(rename = "externalURL", default)]
+    pub external_url: String,
+    pub alerts: Vec<Alert>,
+}
+
+/// 单条告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    pub status: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub annotations: BTreeMap<String, String>,
+    #[serde(default)]
+    pub starts_at: String,
+    #[serde(default)]
+    pub ends_at: String,
+    #[serde(rename = "generatorURL", default)]
+    pub generator_url: String,
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// 按配置中的 receiver 映射路由告警
+#[post("/ingest/alertmanager")]
+async fn receive(
+    payload: web::Json<WebhookMessage>,
+    config: web::Data<AlertmanagerConfig>,
+    dispatcher: web::Data<Dispatcher>,
+) -> HttpResponse {
+    let channels = config
+        .receivers
+        .get(&payload.receiver)
+        .unwrap_or(&config.default_channels)
+        .clone();
+    deliver(payload.into_inner(), channels, &dispatcher).await
+}
+
+/// 直接推送到路径中指定的通道，便于为每个 receiver 配置独立的 webhook url
+#[post("/ingest/alertmanager/{channel}")]
+async fn receive_for_channel(
+    payload: web::Json<WebhookMessage>,
+    channel: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+) -> HttpResponse {
+    deliver(
+        payload.into_inner(),
+        vec![channel.into_inner()],
+        &dispatcher,
+    )
+    .await
+}
+
+async fn deliver(
+    payload: WebhookMessage,
+    channels: Vec<String>,
+    dispatcher: &Dispatcher,
+) -> HttpResponse {
+    if payload.version != "4" {
+        warn!(
+            "Unexpected Alertmanager webhook version '{}'",
+            payload.version
+        );
+    }
+    if channels.is_empty() {
+        warn!(
+            "No channels configured for Alertmanager receiver '{}'",
+            payload.receiver
+        );
+        return no_channels(format!(
+            "No channels configured for receiver '{}'",
+            payload.receiver
+        ));
+    }
+
+    info!(
+        "Received {} {} alerts for group {}",
+        payload.alerts.len(),
+        payload.status,
+        payload.group_key
+    );
+    let mut message = Message::new(MessageType::Markdown(render(&payload)))
+        .with_metadata(GROUP_KEY, &payload.group_key)
+        .with_thread_key(&payload.group_key);
+    message.labels.extend(payload.common_labels.clone());
+    let results = dispatcher.send_to_all(&channels, message).await;
+
+    DeliveryReport::new(results)
+        .with_group_key(payload.group_key)
+        .into_response()
+}
+
+/// 将告警组渲染为 Markdown
+fn render(payload: &WebhookMessage) -> String {
+    let firing = payload
+        .alerts
+        .iter()
+        .filter(|a| a.status == "firing")
+        .count();
+    let resolved = payload.alerts.len() - firing;
+    let name = payload
+        .group_labels
+        .get("alertname")
+        .or_else(|| payload.common_labels.get("alertname"))
+        .map(String::as_str)
+        .unwrap_or(&payload.receiver);
+
+    let mut text = if payload.status == "firing" {
+        format!("**[FIRING:{}] {}**", firing, name)
+    } else {
+        format!("**[RESOLVED] {}**", name)
+    };
+
+    let labels = format_labels(&payload.group_labels, Some("alertname"));
+    if !labels.is_empty() {
+        text.push_str(&format!("\n> {}", labels));
+    }
+    if let Some(summary) = payload.common_annotations.get("summary") {
+        text.push_str(&format!("\n{}", summary));
+    }
+
+    for (status, heading, count) in [
+        ("firing", "Firing", firing),
+        ("resolved", "Resolved", resolved),
+    ] {
+        if count == 0 {
+            continue;
+        }
+        text.push_str(&format!("\n\n**{}:**", heading));
+        for alert in payload.alerts.iter().filter(|a| a.status == status) {
+            text.push_str(&format!(
+                "\n- {}",
+                describe_alert(alert, &payload.common_labels)
+            ));
+        }
+    }
+
+    if payload.truncated_alerts > 0 {
+        text.push_str(&format!(
+            "\n\n{} more alerts truncated",
+            payload.truncated_alerts
+        ));
+    }
+    if !payload.external_url.is_empty() {
+        text.push_str(&format!("\n\n[Alertmanager]({})", payload.external_url));
+    }
+    text
+}
+
+fn describe_alert(alert: &Alert, common_labels: &BTreeMap<String, String>) -> String {
+    let summary = alert
+        .annotations
+        .get("summary")
+        .or_else(|| alert.annotations.get("description"));
+    let distinct: BTreeMap<String, String> = alert
+        .labels
+        .iter()
+        .filter(|(k, v)| common_labels.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let labels = format_labels(&distinct, None);
+
+    let mut line = match (summary, labels.is_empty()) {
+        (Some(summary), false) => format!("{} ({})", summary, labels),
+        (Some(summary), true) => summary.clone(),
+        (None, _) => format_labels(&alert.labels, None),
+    };
+    if !alert.generator_url.is_empty() {
+        line.push_str(&format!(" [source]({})", alert.generator_url));
+    }
+    line
+}
+
+fn format_labels(labels: &BTreeMap<String, String>, skip: Option<&str>) -> String {
+    labels
+        .iter()
+        .filter(|(k, _)| Some(k.as_str()) != skip)
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: &str = r#"{
+        "version": "4",
+        "groupKey": "{}:{alertname=\"HighLatency\"}",
+        "truncatedAlerts": 0,
+        "status": "firing",
+        "receiver": "ops",
+        "groupLabels": {"alertname": "HighLatency"},
+        "commonLabels": {"alertname": "HighLatency", "job": "api"},
+        "commonAnnotations": {},
+        "externalURL": "http://alertmanager:9093",
+        "alerts": [
+            {"status": "firing", "labels": {"alertname": "HighLatency", "job": "api", "instance": "a:80"},
+             "annotations": {"summary": "p99 > 1s"}, "startsAt": "2024-01-01T00:00:00Z",
+             "endsAt": "0001-01-01T00:00:00Z", "generatorURL": "http://prom/graph", "fingerprint": "abc"},
+            {"status": "resolved", "labels": {"alertname": "HighLatency", "job": "api", "instance": "b:80"},
+             "annotations": {}, "startsAt": "2024-01-01T00:00:00Z",
+             "endsAt": "2024-01-01T01:00:00Z", "generatorURL": "", "fingerprint": "def"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_and_render() {
+        let payload: WebhookMessage = serde_json::from_str(PAYLOAD).unwrap();
+        assert_eq!(payload.group_key, "{}:{alertname=\"HighLatency\"}");
+
+        let text = render(&payload);
+        assert!(text.starts_with("**[FIRING:1] HighLatency**"));
+        assert!(text.contains("- p99 > 1s (instance=a:80) [source](http://prom/graph)"));
+        assert!(text.contains("**Resolved:**\n- alertname=HighLatency, instance=b:80, job=api"));
+        assert!(text.ends_with("[Alertmanager](http://alertmanager:9093)"));
+    }
+}
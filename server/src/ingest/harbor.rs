@@ -0,0 +1,306 @@
+use super::{DeliveryReport, GROUP_KEY, no_channels};
+use crate::dispatch::Dispatcher;
+use actix_web::{HttpResponse, post, web};
+use common::{Message, MessageType};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 漏洞严重级别，按从高到低排列
+const SEVERITIES: [&str; 6] = ["Critical", "High", "Medium", "Low", "Negligible", "Unknown"];
+
+/// Harbor webhook 接入配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HarborConfig {
+    /// 项目（命名空间）到通道的映射
+    #[serde(default)]
+    pub projects: HashMap<String, Vec<String>>,
+    /// 未匹配项目时使用的通道
+    #[serde(default)]
+    pub default_channels: Vec<String>,
+    /// 扫描未发现漏洞时是否也推送
+    #[serde(default)]
+    pub notify_clean_scans: bool,
+}
+
+/// Harbor webhook 请求体
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarborEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub operator: String,
+    pub event_data: HarborEventData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarborEventData {
+    #[serde(default)]
+    pub resources: Vec<HarborResource>,
+    pub repository: HarborRepository,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarborResource {
+    #[serde(default)]
+    pub digest: String,
+    #[serde(default)]
+    pub tag: String,
+    #[serde(default)]
+    pub resource_url: String,
+    /// 键为报告的 MIME 类型
+    #[serde(default)]
+    pub scan_overview: HashMap<String, ScanReport>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarborRepository {
+    pub name: String,
+    pub namespace: String,
+    pub repo_full_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScanReport {
+    #[serde(default)]
+    pub scan_status: String,
+    #[serde(default)]
+    pub severity: String,
+    pub summary: Option<ScanSummary>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScanSummary {
+    #[serde(default)]
+    pub total: u64,
+    #[serde(default)]
+    pub fixable: u64,
+    /// 各严重级别的漏洞数量
+    #[serde(default)]
+    pub summary: HashMap<String, u64>,
+}
+
+/// 接收 Harbor 事件并按项目路由
+#[post("/ingest/harbor")]
+async fn receive(
+    event: web::Json<HarborEvent>,
+    config: web::Data<HarborConfig>,
+    dispatcher: web::Data<Dispatcher>,
+) -> HttpResponse {
+    let repository = &event.event_data.repository;
+    let Some(content) = render(&event, config.notify_clean_scans) else {
+        debug!(
+            "Ignoring Harbor {} event for {}",
+            event.event_type, repository.repo_full_name
+        );
+        return HttpResponse::NoContent().finish();
+    };
+
+    let channels = config
+        .projects
+        .get(&repository.namespace)
+        .unwrap_or(&config.default_channels);
+    if channels.is_empty() {
+        warn!(
+            "No channels configured for Harbor project '{}'",
+            repository.namespace
+        );
+        return no_channels(format!(
+            "No channels configured for project '{}'",
+            repository.namespace
+        ));
+    }
+
+    info!(
+        "Received Harbor {} event for {}",
+        event.event_type, repository.repo_full_name
+    );
+    let message = Message::new(MessageType::Markdown(content))
+        .with_metadata(GROUP_KEY, &repository.repo_full_name);
+    let results = dispatcher.send_to_all(channels, message).await;
+    DeliveryReport::new(results)
+        .with_group_key(&repository.repo_full_name)
+        .into_response()
+}
+
+/// 将事件渲染为 Markdown，不需要推送时返回 None
+fn render(event: &HarborEvent, notify_clean_scans: bool) -> Option<String> {
+    let repository = &event.event_data.repository;
+    let resources = &event.event_data.resources;
+    let header = format!("**[{}]**", repository.repo_full_name);
+
+    let lines: Vec<String> = match event.event_type.as_str() {
+        "PUSH_ARTIFACT" => resources
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} pushed `{}`\n> {}",
+                    event.operator,
+                    artifact_name(repository, r),
+                    r.digest
+                )
+            })
+            .collect(),
+        "SCANNING_COMPLETED" => resources
+            .iter()
+            .filter_map(|r| render_scan(repository, r, notify_clean_scans))
+            .collect(),
+        "SCANNING_FAILED" | "SCANNING_STOPPED" => {
+            let outcome = event.event_type["SCANNING_".len()..].to_lowercase();
+            resources
+                .iter()
+                .map(|r| {
+                    format!(
+                        "<font color=\"warning\">Scan {}</font> for `{}`",
+                        outcome,
+                        artifact_name(repository, r)
+                    )
+                })
+                .collect()
+        }
+        "DELETE_ARTIFACT" => resources
+            .iter()
+            .map(|r| {
+                format!(
+                    "{} deleted `{}`",
+                    event.operator,
+                    artifact_name(repository, r)
+                )
+            })
+            .collect(),
+        _ => return None,
+    };
+
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!("{}\n{}", header, lines.join("\n\n")))
+}
+
+fn render_scan(
+    repository: &HarborRepository,
+    resource: &HarborResource,
+    notify_clean_scans: bool,
+) -> Option<String> {
+    let report = resource.scan_overview.values().next()?;
+    let summary = report.summary.as_ref();
+    let total = summary.map(|s| s.total).unwrap_or_default();
+    if total == 0 && !notify_clean_scans {
+        return None;
+    }
+
+    let mut line = format!(
+        "Scan {} for `{}`: {}",
+        report.scan_status.to_lowercase(),
+        artifact_name(repository, resource),
+        colorize(&report.severity, &report.severity)
+    );
+    if let Some(summary) = summary {
+        let counts: Vec<String> = SEVERITIES
+            .iter()
+            .filter_map(|severity| {
+                let count = summary.summary.get(*severity).copied().unwrap_or_default();
+                (count > 0).then(|| colorize(severity, &format!("{} {}", severity, count)))
+            })
+            .collect();
+        if !counts.is_empty() {
+            line.push_str(&format!("\n> {}", counts.join(" · ")));
+        }
+        line.push_str(&format!(
+            "\n> {} vulnerabilities, {} fixable",
+            summary.total, summary.fixable
+        ));
+    }
+    Some(line)
+}
+
+/// 按严重级别上色（企业微信 Markdown 颜色）
+fn colorize(severity: &str, text: &str) -> String {
+    let color = match severity {
+        "Critical" | "High" => "warning",
+        "Medium" | "Low" => "comment",
+        _ => "info",
+    };
+    format!("<font color=\"{}\">{}</font>", color, text)
+}
+
+fn artifact_name(repository: &HarborRepository, resource: &HarborResource) -> String {
+    if !resource.tag.is_empty() {
+        format!("{}:{}", repository.name, resource.tag)
+    } else if !resource.digest.is_empty() {
+        format!("{}@{}", repository.name, resource.digest)
+    } else {
+        resource.resource_url.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn parse(value: Value) -> HarborEvent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn scan_event(summary: Value) -> HarborEvent {
+        parse(json!({
+            "type": "SCANNING_COMPLETED",
+            "occur_at": 1680000000,
+            "operator": "auto",
+            "event_data": {
+                "resources": [{
+                    "digest": "sha256:abc",
+                    "tag": "v1",
+                    "resource_url": "harbor.example.com/library/nginx:v1",
+                    "scan_overview": {
+                        "application/vnd.security.vulnerability.report; version=1.1": {
+                            "report_id": "r1",
+                            "scan_status": "Success",
+                            "severity": "High",
+                            "summary": summary
+                        }
+                    }
+                }],
+                "repository": {
+                    "name": "nginx",
+                    "namespace": "library",
+                    "repo_full_name": "library/nginx",
+                    "repo_type": "private"
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_render_scan_with_vulnerabilities() {
+        let event = scan_event(json!({
+            "total": 4, "fixable": 2, "summary": {"High": 1, "Medium": 3}
+        }));
+        let text = render(&event, false).unwrap();
+        assert!(text.starts_with("**[library/nginx]**\nScan success for `nginx:v1`"));
+        assert!(text.contains(
+            "<font color=\"warning\">High 1</font> · <font color=\"comment\">Medium 3</font>"
+        ));
+        assert!(text.contains("4 vulnerabilities, 2 fixable"));
+    }
+
+    #[test]
+    fn test_clean_scan_is_skipped_by_default() {
+        let event = scan_event(json!({"total": 0, "fixable": 0, "summary": {}}));
+        assert!(render(&event, false).is_none());
+        assert!(render(&event, true).is_some());
+    }
+
+    #[test]
+    fn test_render_push_artifact() {
+        let mut event = scan_event(json!({"total": 0}));
+        event.event_type = "PUSH_ARTIFACT".to_string();
+        event.operator = "admin".to_string();
+        let text = render(&event, false).unwrap();
+        assert_eq!(
+            text,
+            "**[library/nginx]**\nadmin pushed `nginx:v1`\n> sha256:abc"
+        );
+    }
+}
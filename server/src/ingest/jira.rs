@@ -0,0 +1,310 @@
+use super::{DeliveryReport, GROUP_KEY, no_channels};
+use crate::dispatch::Dispatcher;
+use actix_web::{HttpResponse, post, web};
+use common::{Mention, Message, MessageType};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 评论内容的最大展示长度（字符）
+const MAX_COMMENT_CHARS: usize = 500;
+
+/// Jira webhook 接入配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JiraConfig {
+    /// 项目 key 到通道的映射
+    #[serde(default)]
+    pub projects: HashMap<String, Vec<String>>,
+    /// 未匹配项目时使用的通道
+    #[serde(default)]
+    pub default_channels: Vec<String>,
+    /// Jira accountId 到平台@提及 ID 的映射
+    #[serde(default)]
+    pub mentions: HashMap<String, Mention>,
+}
+
+/// Jira webhook 请求体（只包含用到的字段）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraEvent {
+    pub webhook_event: String,
+    #[serde(rename = "issue_event_type_name")]
+    pub issue_event_type_name: Option<String>,
+    pub user: Option<JiraUser>,
+    pub issue: Option<JiraIssue>,
+    pub changelog: Option<JiraChangelog>,
+    pub comment: Option<JiraComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraUser {
+    pub account_id: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraIssue {
+    pub key: String,
+    #[serde(rename = "self")]
+    pub self_url: Option<String>,
+    pub fields: JiraIssueFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraIssueFields {
+    pub summary: Option<String>,
+    pub project: Option<JiraProject>,
+    pub assignee: Option<JiraUser>,
+    pub priority: Option<JiraNamed>,
+    pub issuetype: Option<JiraNamed>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraProject {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraNamed {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraChangelog {
+    #[serde(default)]
+    pub items: Vec<JiraChange>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraChange {
+    pub field: String,
+    pub from_string: Option<String>,
+    pub to_string: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraComment {
+    pub body: String,
+    pub author: Option<JiraUser>,
+}
+
+/// 接收 Jira 事件并按项目路由
+#[post("/ingest/jira")]
+async fn receive(
+    event: web::Json<JiraEvent>,
+    config: web::Data<JiraConfig>,
+    dispatcher: web::Data<Dispatcher>,
+) -> HttpResponse {
+    let Some(issue) = &event.issue else {
+        debug!("Ignoring Jira event {} without issue", event.webhook_event);
+        return HttpResponse::NoContent().finish();
+    };
+    let Some(content) = render(&event, issue) else {
+        debug!("Ignoring Jira event {}", event.webhook_event);
+        return HttpResponse::NoContent().finish();
+    };
+
+    let project = issue.fields.project.as_ref().map(|p| p.key.as_str());
+    let channels = project
+        .and_then(|key| config.projects.get(key))
+        .unwrap_or(&config.default_channels);
+    if channels.is_empty() {
+        warn!("No channels configured for Jira project {:?}", project);
+        return no_channels("No channels configured for Jira project");
+    }
+
+    let mentions: Vec<Mention> = issue
+        .fields
+        .assignee
+        .as_ref()
+        .and_then(|assignee| assignee.account_id.as_ref())
+        .and_then(|account_id| config.mentions.get(account_id))
+        .cloned()
+        .into_iter()
+        .collect();
+
+    info!("Received Jira {} for {}", event.webhook_event, issue.key);
+    let message = Message {
+        mentions,
+        ..Message::new(MessageType::Text(content))
+            .with_metadata(GROUP_KEY, &issue.key)
+            .with_thread_key(&issue.key)
+    };
+    let results = dispatcher.send_to_all(channels, message).await;
+    DeliveryReport::new(results)
+        .with_group_key(&issue.key)
+        .into_response()
+}
+
+/// 渲染事件文本，不关心的事件返回 None
+fn render(event: &JiraEvent, issue: &JiraIssue) -> Option<String> {
+    let actor = event
+        .user
+        .as_ref()
+        .or_else(|| event.comment.as_ref().and_then(|c| c.author.as_ref()))
+        .map(display_name)
+        .unwrap_or("Someone");
+    let summary = issue.fields.summary.as_deref().unwrap_or("");
+
+    let mut text = match event.webhook_event.as_str() {
+        "jira:issue_created" => {
+            let mut text = format!("{} created [{}] {}", actor, issue.key, summary);
+            let details: Vec<String> = [
+                issue.fields.issuetype.as_ref().map(|t| t.name.clone()),
+                issue
+                    .fields
+                    .priority
+                    .as_ref()
+                    .map(|p| format!("Priority: {}", p.name)),
+                issue
+                    .fields
+                    .assignee
+                    .as_ref()
+                    .map(|a| format!("Assignee: {}", display_name(a))),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if !details.is_empty() {
+                text.push_str(&format!("\n{}", details.join(" | ")));
+            }
+            text
+        }
+        "comment_created" | "comment_updated" => comment_text(event, actor, issue, summary)?,
+        "jira:issue_updated" => {
+            if event.issue_event_type_name.as_deref() == Some("issue_commented") {
+                comment_text(event, actor, issue, summary)?
+            } else {
+                let changes: Vec<String> = event
+                    .changelog
+                    .as_ref()
+                    .map(|changelog| changelog.items.iter().map(describe_change).collect())
+                    .unwrap_or_default();
+                if changes.is_empty() {
+                    return None;
+                }
+                format!(
+                    "{} updated [{}] {}\n{}",
+                    actor,
+                    issue.key,
+                    summary,
+                    changes.join("\n")
+                )
+            }
+        }
+        _ => return None,
+    };
+
+    if let Some(url) = browse_url(issue) {
+        text.push('\n');
+        text.push_str(&url);
+    }
+    Some(text)
+}
+
+fn comment_text(
+    event: &JiraEvent,
+    actor: &str,
+    issue: &JiraIssue,
+    summary: &str,
+) -> Option<String> {
+    let comment = event.comment.as_ref()?;
+    let mut body: String = comment.body.chars().take(MAX_COMMENT_CHARS).collect();
+    if body.len() < comment.body.len() {
+        body.push('…');
+    }
+    Some(format!(
+        "{} commented on [{}] {}\n{}",
+        actor, issue.key, summary, body
+    ))
+}
+
+fn describe_change(change: &JiraChange) -> String {
+    format!(
+        "- {}: {} → {}",
+        change.field,
+        change.from_string.as_deref().unwrap_or("(none)"),
+        change.to_string.as_deref().unwrap_or("(none)")
+    )
+}
+
+fn display_name(user: &JiraUser) -> &str {
+    user.display_name
+        .as_deref()
+        .or(user.account_id.as_deref())
+        .unwrap_or("Unknown")
+}
+
+/// 由 REST 地址推导出浏览器地址，如 `https://x.atlassian.net/browse/PROJ-1`
+fn browse_url(issue: &JiraIssue) -> Option<String> {
+    let self_url = issue.self_url.as_ref()?;
+    let base = &self_url[..self_url.find("/rest/")?];
+    Some(format!("{}/browse/{}", base, issue.key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: &str) -> JiraEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    const ISSUE: &str = r#"{
+        "key": "OPS-7",
+        "self": "https://acme.atlassian.net/rest/api/2/issue/10007",
+        "fields": {
+            "summary": "Disk full on db-1",
+            "project": {"key": "OPS"},
+            "assignee": {"accountId": "5b10a", "displayName": "Alice"},
+            "priority": {"name": "High"},
+            "issuetype": {"name": "Bug"}
+        }
+    }"#;
+
+    #[test]
+    fn test_render_issue_created() {
+        let event = event(&format!(
+            r#"{{"webhookEvent": "jira:issue_created", "user": {{"displayName": "Bob"}}, "issue": {}}}"#,
+            ISSUE
+        ));
+        let text = render(&event, event.issue.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            text,
+            "Bob created [OPS-7] Disk full on db-1\nBug | Priority: High | Assignee: Alice\n\
+             https://acme.atlassian.net/browse/OPS-7"
+        );
+    }
+
+    #[test]
+    fn test_render_issue_updated_changelog() {
+        let event = event(&format!(
+            r#"{{"webhookEvent": "jira:issue_updated", "issue_event_type_name": "issue_generic",
+                "user": {{"displayName": "Bob"}}, "issue": {},
+                "changelog": {{"items": [{{"field": "status", "fromString": "To Do", "toString": "Done"}}]}}}}"#,
+            ISSUE
+        ));
+        let text = render(&event, event.issue.as_ref().unwrap()).unwrap();
+        assert!(text.contains("Bob updated [OPS-7]"));
+        assert!(text.contains("- status: To Do → Done"));
+    }
+
+    #[test]
+    fn test_render_comment_and_ignore_unknown() {
+        let commented = event(&format!(
+            r#"{{"webhookEvent": "comment_created", "issue": {},
+                "comment": {{"body": "Cleaned up logs", "author": {{"displayName": "Carol"}}}}}}"#,
+            ISSUE
+        ));
+        let text = render(&commented, commented.issue.as_ref().unwrap()).unwrap();
+        assert!(text.starts_with("Carol commented on [OPS-7] Disk full on db-1\nCleaned up logs"));
+
+        let unknown = event(&format!(
+            r#"{{"webhookEvent": "worklog_created", "issue": {}}}"#,
+            ISSUE
+        ));
+        assert!(render(&unknown, unknown.issue.as_ref().unwrap()).is_none());
+    }
+}
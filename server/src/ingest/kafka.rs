@@ -0,0 +1,300 @@
+use super::template::render_template;
+use crate::api::PushRequest;
+use crate::dispatch::Dispatcher;
+use crate::validate::ValidationConfig;
+use common::{Message as PushMessage, MessageType, PushError};
+use log::*;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::{ClientConfig, Message};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 投递失败后重试的初始间隔
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Kafka 消费接入配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KafkaConfig {
+    /// Broker 列表，如 `kafka-1:9092,kafka-2:9092`
+    pub brokers: String,
+    /// 消费组 ID
+    pub group_id: String,
+    /// 订阅的主题
+    pub topics: Vec<String>,
+    /// 事件格式
+    #[serde(default)]
+    pub format: KafkaEventFormat,
+    /// 单条事件的最大投递次数，超过后记录日志并提交位移
+    #[serde(default = "default_max_delivery_attempts")]
+    pub max_delivery_attempts: u32,
+    /// 额外的 librdkafka 配置项（如 SASL/SSL）
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+/// Kafka 事件格式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KafkaEventFormat {
+    /// 事件内容为 `PushRequest` JSON
+    #[default]
+    PushRequest,
+    /// 事件内容为任意 JSON，通过模板映射为消息
+    Mapped {
+        /// 推送到的通道
+        channels: Vec<String>,
+        /// 消息模板，语法同 MQTT 模板
+        template: String,
+        /// 是否以 Markdown 格式推送
+        #[serde(default)]
+        markdown: bool,
+    },
+}
+
+fn default_max_delivery_attempts() -> u32 {
+    3
+}
+
+/// 启动 Kafka 消费任务，事件中的消息与 HTTP 请求一样先经过校验
+pub fn spawn_consumer(
+    config: KafkaConfig,
+    validation: ValidationConfig,
+    dispatcher: Arc<Dispatcher>,
+) -> std::io::Result<()> {
+    let mut client_config = ClientConfig::new();
+    client_config
+        .set("bootstrap.servers", &config.brokers)
+        .set("group.id", &config.group_id)
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "earliest");
+    for (key, value) in &config.properties {
+        client_config.set(key, value);
+    }
+
+    let consumer: StreamConsumer = client_config.create().map_err(std::io::Error::other)?;
+    let topics: Vec<&str> = config.topics.iter().map(String::as_str).collect();
+    consumer.subscribe(&topics).map_err(std::io::Error::other)?;
+
+    info!(
+        "Consuming Kafka topics {:?} from {} as group {}",
+        config.topics, config.brokers, config.group_id
+    );
+    tokio::spawn(run(consumer, config, validation, dispatcher));
+    Ok(())
+}
+
+async fn run(
+    consumer: StreamConsumer,
+    config: KafkaConfig,
+    validation: ValidationConfig,
+    dispatcher: Arc<Dispatcher>,
+) {
+    loop {
+        let message = match consumer.recv().await {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Kafka receive error: {}", e);
+                tokio::time::sleep(RETRY_BACKOFF).await;
+                continue;
+            }
+        };
+
+        let payload = String::from_utf8_lossy(message.payload().unwrap_or_default());
+        match to_deliveries(&config.format, &validation, message.topic(), &payload) {
+            Ok(deliveries) => {
+                deliver_with_retry(&dispatcher, deliveries, config.max_delivery_attempts).await
+            }
+            Err(e) => error!(
+                "Skipping malformed Kafka event at {}/{}@{}: {}",
+                message.topic(),
+                message.partition(),
+                message.offset(),
+                e
+            ),
+        }
+
+        // 投递完成（或放弃）后才提交位移，进程崩溃时未投递的事件会被重新消费
+        if let Err(e) = consumer.commit_message(&message, CommitMode::Async) {
+            warn!("Failed to commit Kafka offset: {}", e);
+        }
+    }
+}
+
+/// 单个投递目标
+enum Delivery {
+    Channel(String, MessageType),
+    Platform(Box<PushRequest>),
+}
+
+/// 解析事件并校验其中的消息，不合法的事件整条拒绝
+fn to_deliveries(
+    format: &KafkaEventFormat,
+    validation: &ValidationConfig,
+    topic: &str,
+    payload: &str,
+) -> Result<Vec<Delivery>, String> {
+    match format {
+        KafkaEventFormat::PushRequest => {
+            let request: PushRequest = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+            validate(validation, &request.to_message())?;
+            Ok(vec![Delivery::Platform(Box::new(request))])
+        }
+        KafkaEventFormat::Mapped {
+            channels,
+            template,
+            markdown,
+        } => {
+            let content = render_template(template, topic, payload);
+            validate(
+                validation,
+                &PushMessage::new(MessageType::Text(content.clone())),
+            )?;
+            Ok(channels
+                .iter()
+                .map(|channel| {
+                    let message = if *markdown {
+                        MessageType::Markdown(content.clone())
+                    } else {
+                        MessageType::Text(content.clone())
+                    };
+                    Delivery::Channel(channel.clone(), message)
+                })
+                .collect())
+        }
+    }
+}
+
+fn validate(validation: &ValidationConfig, message: &PushMessage) -> Result<(), String> {
+    let violations = validation.validate(message);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let reasons: Vec<String> = violations
+        .iter()
+        .map(|v| format!("{}: {}", v.field, v.message))
+        .collect();
+    Err(format!("Validation failed ({})", reasons.join("; ")))
+}
+
+async fn deliver(dispatcher: &Dispatcher, delivery: &Delivery) -> Result<(), PushError> {
+    match delivery {
+        Delivery::Channel(channel, message) => {
+            dispatcher.send(channel, message.clone()).await?;
+        }
+        Delivery::Platform(request) => {
+            dispatcher
+                .send_to_platform(
+                    &request.platform,
+                    request.config.clone(),
+                    request.to_message(),
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 投递所有目标，可重试的失败目标按指数退避重试
+async fn deliver_with_retry(dispatcher: &Dispatcher, deliveries: Vec<Delivery>, max_attempts: u32) {
+    let mut pending = deliveries;
+    let mut backoff = RETRY_BACKOFF;
+
+    for attempt in 1..=max_attempts.max(1) {
+        let mut failed = Vec::new();
+        for delivery in pending {
+            match deliver(dispatcher, &delivery).await {
+                Ok(()) => {}
+                Err(e) if e.is_retryable() => {
+                    warn!("Kafka event delivery attempt {} failed: {}", attempt, e);
+                    failed.push(delivery);
+                }
+                Err(e) => error!("Dropping Kafka event delivery: {}", e),
+            }
+        }
+        if failed.is_empty() {
+            return;
+        }
+        pending = failed;
+        if attempt < max_attempts {
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    error!(
+        "Giving up on {} Kafka event deliveries after {} attempts",
+        pending.len(),
+        max_attempts
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_event_fans_out_to_channels() {
+        let format = KafkaEventFormat::Mapped {
+            channels: vec!["ops".to_string(), "dev".to_string()],
+            template: "{{service}} is {{status}}".to_string(),
+            markdown: false,
+        };
+        let deliveries = to_deliveries(
+            &format,
+            &ValidationConfig::default(),
+            "events",
+            r#"{"service": "db", "status": "down"}"#,
+        )
+        .unwrap();
+        assert_eq!(deliveries.len(), 2);
+        match &deliveries[1] {
+            Delivery::Channel(channel, MessageType::Text(content)) => {
+                assert_eq!(channel, "dev");
+                assert_eq!(content, "db is down");
+            }
+            _ => panic!("Expected text delivery to channel"),
+        }
+    }
+
+    #[test]
+    fn test_push_request_event() {
+        let payload = r#"{"platform": "wxwork", "config": {"token": "t"},
+            "message": {"type": "Text", "payload": "hi"}}"#;
+        let validation = ValidationConfig::default();
+        let deliveries = to_deliveries(
+            &KafkaEventFormat::PushRequest,
+            &validation,
+            "events",
+            payload,
+        )
+        .unwrap();
+        assert!(matches!(&deliveries[..], [Delivery::Platform(r)] if r.platform == "wxwork"));
+        assert!(
+            to_deliveries(
+                &KafkaEventFormat::PushRequest,
+                &validation,
+                "events",
+                "not json"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_push_request() {
+        let payload = r#"{"platform": "wxwork", "config": {"token": "t"},
+            "message": {"type": "File", "payload": {"name": "passwd",
+                "source": {"type": "path", "value": "/etc/passwd"}}}}"#;
+        let error = to_deliveries(
+            &KafkaEventFormat::PushRequest,
+            &ValidationConfig::default(),
+            "events",
+            payload,
+        )
+        .err()
+        .unwrap();
+        assert!(error.contains("message.source"), "{}", error);
+    }
+}
@@ -0,0 +1,74 @@
+//! 外部事件接入，将各类来源转换为推送消息
+
+use crate::error::{ApiError, ErrorCode};
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use common::{PushError, PushResult};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub mod alertmanager;
+pub mod harbor;
+pub mod jira;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+pub mod mqtt;
+pub mod rss;
+pub mod syslog;
+pub mod template;
+
+/// 来源系统分组键在消息元数据中的键名
+pub const GROUP_KEY: &str = "group_key";
+
+/// 来源事件没有匹配的通道时的 404 响应
+pub fn no_channels(message: impl Into<String>) -> HttpResponse {
+    ApiError::new(StatusCode::NOT_FOUND, ErrorCode::ChannelNotFound, message).error_response()
+}
+
+/// Webhook 接入的投递结果汇总
+#[derive(Debug, Serialize)]
+pub struct DeliveryReport {
+    /// 来源系统的分组键
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_key: Option<String>,
+    /// 每个通道的推送结果
+    pub results: BTreeMap<String, PushResult>,
+}
+
+impl DeliveryReport {
+    /// 由各通道的投递结果创建汇总
+    pub fn new(results: Vec<(String, Result<PushResult, PushError>)>) -> Self {
+        let results = results
+            .into_iter()
+            .map(|(channel, result)| {
+                // 失败时保留尝试次数、耗时、HTTP 状态码和平台错误码
+                let result = result.unwrap_or_else(|e| PushResult {
+                    success: false,
+                    response: Some(e.to_string()),
+                    channel: Some(channel.clone()),
+                    ..e.diagnostics().cloned().unwrap_or_default()
+                });
+                (channel, result)
+            })
+            .collect();
+        Self {
+            group_key: None,
+            results,
+        }
+    }
+
+    /// 设置分组键
+    pub fn with_group_key(mut self, group_key: impl Into<String>) -> Self {
+        self.group_key = Some(group_key.into());
+        self
+    }
+
+    /// 转换为 HTTP 响应，全部失败时返回 502 以便来源系统重试
+    pub fn into_response(self) -> HttpResponse {
+        if self.results.values().all(|r| !r.success) {
+            HttpResponse::BadGateway().json(self)
+        } else {
+            HttpResponse::Ok().json(self)
+        }
+    }
+}
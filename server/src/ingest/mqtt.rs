@@ -0,0 +1,142 @@
+use super::template::render_template;
+use crate::dispatch::Dispatcher;
+use common::MessageType;
+use log::*;
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, Publish, QoS};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 连接断开后的重连间隔
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// MQTT 订阅接入配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttConfig {
+    /// Broker 地址
+    pub host: String,
+    /// Broker 端口
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// 客户端 ID，为空时自动生成
+    pub client_id: Option<String>,
+    /// 用户名
+    pub username: Option<String>,
+    /// 密码
+    pub password: Option<String>,
+    /// 心跳间隔（秒）
+    #[serde(default = "default_keep_alive_secs")]
+    pub keep_alive_secs: u64,
+    /// 订阅列表
+    pub subscriptions: Vec<MqttSubscription>,
+}
+
+/// 单个主题订阅
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MqttSubscription {
+    /// 主题过滤器，支持 `+` 和 `#` 通配符
+    pub topic: String,
+    /// 订阅 QoS（0/1/2）
+    #[serde(default)]
+    pub qos: u8,
+    /// 推送到的通道
+    pub channels: Vec<String>,
+    /// 消息模板，`{{topic}}` 为主题，`{{payload}}` 为原始内容，
+    /// `{{a.b}}` 取 JSON 负载中的字段；为空时原样推送负载
+    pub template: Option<String>,
+    /// 是否以 Markdown 格式推送
+    #[serde(default)]
+    pub markdown: bool,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_keep_alive_secs() -> u64 {
+    30
+}
+
+/// 启动 MQTT 订阅任务
+pub fn spawn_subscriber(config: MqttConfig, dispatcher: Arc<Dispatcher>) {
+    let client_id = config
+        .client_id
+        .clone()
+        .unwrap_or_else(|| format!("multi_push-{}", std::process::id()));
+    let mut options = MqttOptions::new(client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(config.keep_alive_secs.max(5)));
+    if let Some(username) = &config.username {
+        options.set_credentials(username, config.password.clone().unwrap_or_default());
+    }
+
+    info!(
+        "Subscribing to {} MQTT topics on {}:{}",
+        config.subscriptions.len(),
+        config.host,
+        config.port
+    );
+    tokio::spawn(run(options, config.subscriptions, dispatcher));
+}
+
+async fn run(
+    options: MqttOptions,
+    subscriptions: Vec<MqttSubscription>,
+    dispatcher: Arc<Dispatcher>,
+) {
+    let (client, mut eventloop) = AsyncClient::new(options, 64);
+    let subscriptions = Arc::new(subscriptions);
+
+    loop {
+        match eventloop.poll().await {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                info!("Connected to MQTT broker");
+                for subscription in subscriptions.iter() {
+                    let qos = rumqttc::qos(subscription.qos).unwrap_or(QoS::AtMostOnce);
+                    if let Err(e) = client.subscribe(&subscription.topic, qos).await {
+                        error!("Failed to subscribe to {}: {}", subscription.topic, e);
+                    }
+                }
+            }
+            Ok(Event::Incoming(Packet::Publish(publish))) => {
+                let subscriptions = subscriptions.clone();
+                let dispatcher = dispatcher.clone();
+                tokio::spawn(async move {
+                    handle_publish(&publish, &subscriptions, &dispatcher).await;
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(
+                    "MQTT connection error: {}, reconnecting in {:?}",
+                    e, RECONNECT_DELAY
+                );
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+async fn handle_publish(
+    publish: &Publish,
+    subscriptions: &[MqttSubscription],
+    dispatcher: &Dispatcher,
+) {
+    let payload = String::from_utf8_lossy(&publish.payload);
+    for subscription in subscriptions
+        .iter()
+        .filter(|s| rumqttc::matches(&publish.topic, &s.topic))
+    {
+        let content = match &subscription.template {
+            Some(template) => render_template(template, &publish.topic, &payload),
+            None => payload.to_string(),
+        };
+        let message = if subscription.markdown {
+            MessageType::Markdown(content)
+        } else {
+            MessageType::Text(content)
+        };
+        dispatcher
+            .send_to_all(&subscription.channels, message)
+            .await;
+    }
+}
@@ -0,0 +1,272 @@
+use crate::dispatch::Dispatcher;
+use common::MessageType;
+use feed_rs::model::{Entry, Feed};
+use log::*;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// RSS/Atom 订阅源配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedConfig {
+    /// 订阅源地址
+    pub url: String,
+    /// 新条目推送到的通道
+    pub channels: Vec<String>,
+    /// 轮询间隔（秒）
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    /// 首次轮询时是否推送已有条目
+    #[serde(default)]
+    pub notify_existing: bool,
+}
+
+fn default_interval_secs() -> u64 {
+    300
+}
+
+/// 为每个订阅源启动一个轮询任务
+pub fn spawn_pollers(feeds: Vec<FeedConfig>, dispatcher: Arc<Dispatcher>) {
+    let client = Client::new();
+    for feed in feeds {
+        info!(
+            "Polling feed {} every {}s for channels {:?}",
+            feed.url, feed.interval_secs, feed.channels
+        );
+        let poller = FeedPoller::new(feed, client.clone());
+        tokio::spawn(poller.run(dispatcher.clone()));
+    }
+}
+
+/// 单个订阅源的轮询器，按 GUID 去重；推送前再经过分发器的去重存储，
+/// 多个副本只推送一次，重启后补推停止期间的新条目
+struct FeedPoller {
+    config: FeedConfig,
+    client: Client,
+    seen: HashSet<String>,
+    initialized: bool,
+}
+
+impl FeedPoller {
+    fn new(config: FeedConfig, client: Client) -> Self {
+        Self {
+            config,
+            client,
+            seen: HashSet::new(),
+            initialized: false,
+        }
+    }
+
+    async fn run(mut self, dispatcher: Arc<Dispatcher>) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            let feed = match self.fetch().await {
+                Ok(feed) => feed,
+                Err(e) => {
+                    warn!("Failed to poll feed {}: {}", self.config.url, e);
+                    continue;
+                }
+            };
+
+            let resumed = if self.initialized || self.config.notify_existing {
+                Vec::new()
+            } else {
+                self.resume(&dispatcher, &feed).await
+            };
+            for entry in resumed.into_iter().chain(self.take_new_entries(feed)) {
+                if !dispatcher.first_seen(&self.dedup_key(&entry)).await {
+                    debug!("Skipping feed entry {} already pushed", entry.id);
+                    continue;
+                }
+                match entry_to_message(&entry) {
+                    Some(message) => {
+                        dispatcher.send_to_all(&self.config.channels, message).await;
+                    }
+                    None => debug!("Skipping feed entry {} without link", entry.id),
+                }
+            }
+        }
+    }
+
+    fn dedup_key(&self, entry: &Entry) -> String {
+        format!("feed:{}:{}", self.config.url, entry.id)
+    }
+
+    /// 首次轮询时记录已有条目；去重存储中已有该订阅源的条目时说明是重启，
+    /// 返回停止期间出现的条目（按时间从旧到新），全新的订阅源不推送已有条目
+    async fn resume(&self, dispatcher: &Dispatcher, feed: &Feed) -> Vec<Entry> {
+        let mut unseen = Vec::new();
+        let mut known = false;
+        for entry in &feed.entries {
+            if dispatcher.first_seen(&self.dedup_key(entry)).await {
+                unseen.push(entry.clone());
+            } else {
+                known = true;
+            }
+        }
+        if !known {
+            return Vec::new();
+        }
+        for entry in &unseen {
+            // 已在上面记录，推送前允许再次通过去重检查
+            dispatcher.forget(&self.dedup_key(entry)).await;
+        }
+        unseen.sort_by_key(|e| e.published.or(e.updated));
+        unseen
+    }
+
+    async fn fetch(&self) -> Result<Feed, String> {
+        let body = self
+            .client
+            .get(&self.config.url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?
+            .bytes()
+            .await
+            .map_err(|e| e.to_string())?;
+        feed_rs::parser::parse(body.as_ref()).map_err(|e| e.to_string())
+    }
+
+    /// 返回未见过的条目（按时间从旧到新），并只保留当前订阅源中仍存在的 GUID
+    fn take_new_entries(&mut self, feed: Feed) -> Vec<Entry> {
+        let current: HashSet<String> = feed.entries.iter().map(|e| e.id.clone()).collect();
+        let mut new_entries: Vec<Entry> = feed
+            .entries
+            .into_iter()
+            .filter(|e| !self.seen.contains(&e.id))
+            .collect();
+        self.seen = current;
+
+        if !self.initialized {
+            self.initialized = true;
+            if !self.config.notify_existing {
+                return Vec::new();
+            }
+        }
+
+        new_entries.sort_by_key(|e| e.published.or(e.updated));
+        new_entries
+    }
+}
+
+/// 将订阅条目转换为链接消息
+fn entry_to_message(entry: &Entry) -> Option<MessageType> {
+    let url = entry.links.first()?.href.clone();
+    let title = entry
+        .title
+        .as_ref()
+        .map(|t| t.content.clone())
+        .unwrap_or_else(|| url.clone());
+    let description = entry
+        .summary
+        .as_ref()
+        .map(|s| s.content.trim().to_string())
+        .unwrap_or_default();
+    let image_url = entry
+        .media
+        .iter()
+        .flat_map(|m| m.thumbnails.iter())
+        .map(|t| t.image.uri.clone())
+        .next();
+
+    Some(MessageType::Link {
+        title,
+        description,
+        url,
+        image_url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rss(items: &[&str]) -> Feed {
+        let items: String = items
+            .iter()
+            .map(|id| {
+                format!(
+                    "<item><guid>{id}</guid><title>Post {id}</title><link>https://example.com/{id}</link></item>"
+                )
+            })
+            .collect();
+        let xml = format!(
+            r#"<?xml version="1.0"?><rss version="2.0"><channel><title>Test</title>{items}</channel></rss>"#
+        );
+        feed_rs::parser::parse(xml.as_bytes()).unwrap()
+    }
+
+    fn poller(notify_existing: bool) -> FeedPoller {
+        FeedPoller::new(
+            FeedConfig {
+                url: "https://example.com/feed".to_string(),
+                channels: vec!["ops".to_string()],
+                interval_secs: 60,
+                notify_existing,
+            },
+            Client::new(),
+        )
+    }
+
+    #[test]
+    fn test_first_poll_seeds_without_notifying() {
+        let mut poller = poller(false);
+        assert!(poller.take_new_entries(rss(&["a", "b"])).is_empty());
+
+        let new_entries = poller.take_new_entries(rss(&["c", "a", "b"]));
+        assert_eq!(new_entries.len(), 1);
+        assert_eq!(new_entries[0].id, "c");
+        assert!(poller.take_new_entries(rss(&["c", "a", "b"])).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_restart() {
+        let dispatcher = Dispatcher::new(
+            Arc::new(common::PlatformRegistry::new()),
+            Default::default(),
+            Duration::ZERO,
+        );
+        // 全新的订阅源不推送已有条目
+        assert!(
+            poller(false)
+                .resume(&dispatcher, &rss(&["a", "b"]))
+                .await
+                .is_empty()
+        );
+
+        // 重启后只返回停止期间出现的条目，且推送前仍能通过去重检查
+        let restarted = poller(false);
+        let resumed = restarted.resume(&dispatcher, &rss(&["c", "a", "b"])).await;
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].id, "c");
+        assert!(
+            dispatcher
+                .first_seen(&restarted.dedup_key(&resumed[0]))
+                .await
+        );
+    }
+
+    #[test]
+    fn test_notify_existing() {
+        let mut poller = poller(true);
+        assert_eq!(poller.take_new_entries(rss(&["a", "b"])).len(), 2);
+    }
+
+    #[test]
+    fn test_entry_to_link_message() {
+        let feed = rss(&["a"]);
+        match entry_to_message(&feed.entries[0]) {
+            Some(MessageType::Link { title, url, .. }) => {
+                assert_eq!(title, "Post a");
+                assert_eq!(url, "https://example.com/a");
+            }
+            other => panic!("Expected link message, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use crate::dispatch::Dispatcher;
+use common::MessageType;
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::mpsc;
+
+/// 汇总消息中保留的样例行数
+const MAX_SAMPLES: usize = 5;
+/// 单条 syslog 的最大长度
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// Syslog 监听配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogConfig {
+    /// UDP 监听地址，如 `0.0.0.0:5514`
+    pub udp_bind: Option<String>,
+    /// TCP 监听地址，如 `0.0.0.0:5514`
+    pub tcp_bind: Option<String>,
+    /// 路由规则，按顺序匹配，命中第一条即停止
+    #[serde(default)]
+    pub rules: Vec<SyslogRule>,
+}
+
+/// Syslog 路由规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyslogRule {
+    /// 匹配的最低严重级别（含），如 `warning` 会匹配 warning 及更严重的级别
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+    /// 只匹配该 APP-NAME
+    pub app_name: Option<String>,
+    /// 只匹配该 HOSTNAME
+    pub hostname: Option<String>,
+    /// 只匹配包含该子串的消息
+    pub contains: Option<String>,
+    /// 推送到的通道
+    pub channels: Vec<String>,
+    /// 每个时间窗口内直接推送的最大条数，超出部分汇总推送
+    #[serde(default = "default_rate_limit")]
+    pub rate_limit: u32,
+    /// 时间窗口（秒）
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+}
+
+fn default_min_severity() -> Severity {
+    Severity::Warning
+}
+
+fn default_rate_limit() -> u32 {
+    10
+}
+
+fn default_window_secs() -> u64 {
+    60
+}
+
+/// Syslog 严重级别（RFC5424 第 6.2.1 节），数值越小越严重
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[serde(alias = "emerg")]
+    Emergency = 0,
+    Alert = 1,
+    #[serde(alias = "crit")]
+    Critical = 2,
+    #[serde(alias = "err")]
+    Error = 3,
+    #[serde(alias = "warn")]
+    Warning = 4,
+    Notice = 5,
+    #[serde(alias = "info")]
+    Informational = 6,
+    Debug = 7,
+}
+
+impl Severity {
+    fn from_code(code: u8) -> Self {
+        match code {
+            0 => Self::Emergency,
+            1 => Self::Alert,
+            2 => Self::Critical,
+            3 => Self::Error,
+            4 => Self::Warning,
+            5 => Self::Notice,
+            6 => Self::Informational,
+            _ => Self::Debug,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Emergency => "EMERG",
+            Self::Alert => "ALERT",
+            Self::Critical => "CRIT",
+            Self::Error => "ERROR",
+            Self::Warning => "WARN",
+            Self::Notice => "NOTICE",
+            Self::Informational => "INFO",
+            Self::Debug => "DEBUG",
+        }
+    }
+}
+
+/// 解析后的 RFC5424 消息
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyslogMessage {
+    pub facility: u8,
+    pub severity: Severity,
+    pub timestamp: Option<String>,
+    pub hostname: Option<String>,
+    pub app_name: Option<String>,
+    pub proc_id: Option<String>,
+    pub msg_id: Option<String>,
+    pub structured_data: Option<String>,
+    pub message: String,
+}
+
+impl SyslogMessage {
+    /// 渲染为单行文本
+    fn render(&self) -> String {
+        format!(
+            "[{}] {} {}: {}",
+            self.severity.label(),
+            self.hostname.as_deref().unwrap_or("-"),
+            self.app_name.as_deref().unwrap_or("-"),
+            self.message
+        )
+    }
+}
+
+/// 解析 RFC5424 格式的 syslog 消息
+pub fn parse_rfc5424(line: &str) -> Result<SyslogMessage, String> {
+    let line = line.trim_end_matches(['\r', '\n', '\0']);
+    let rest = line.strip_prefix('<').ok_or("missing PRI")?;
+    let (pri, rest) = rest.split_once('>').ok_or("unterminated PRI")?;
+    let pri: u8 = pri.parse().map_err(|_| format!("invalid PRI '{}'", pri))?;
+    if pri > 191 {
+        return Err(format!("PRI {} out of range", pri));
+    }
+
+    let (version, rest) = rest.split_once(' ').ok_or("missing VERSION")?;
+    if version != "1" {
+        return Err(format!("unsupported syslog version '{}'", version));
+    }
+
+    let mut rest = rest;
+    let mut header = Vec::with_capacity(5);
+    for name in ["TIMESTAMP", "HOSTNAME", "APP-NAME", "PROCID", "MSGID"] {
+        let (field, remaining) = rest
+            .split_once(' ')
+            .ok_or_else(|| format!("missing {}", name))?;
+        header.push(nil_to_none(field));
+        rest = remaining;
+    }
+
+    let (structured_data, message) = split_structured_data(rest)?;
+    let message = message.trim_start_matches('\u{feff}').to_string();
+
+    let mut header = header.into_iter();
+    Ok(SyslogMessage {
+        facility: pri / 8,
+        severity: Severity::from_code(pri % 8),
+        timestamp: header.next().flatten(),
+        hostname: header.next().flatten(),
+        app_name: header.next().flatten(),
+        proc_id: header.next().flatten(),
+        msg_id: header.next().flatten(),
+        structured_data,
+        message,
+    })
+}
+
+fn nil_to_none(field: &str) -> Option<String> {
+    (field != "-").then(|| field.to_string())
+}
+
+/// 拆分 STRUCTURED-DATA 和 MSG 部分
+fn split_structured_data(rest: &str) -> Result<(Option<String>, &str), String> {
+    if let Some(message) = rest.strip_prefix('-') {
+        return Ok((None, message.strip_prefix(' ').unwrap_or(message)));
+    }
+    if !rest.starts_with('[') {
+        return Err("invalid STRUCTURED-DATA".to_string());
+    }
+
+    let mut in_element = false;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in rest.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' if in_element => in_quotes = !in_quotes,
+            '[' if !in_quotes => in_element = true,
+            ']' if !in_quotes => in_element = false,
+            ' ' if !in_element => {
+                return Ok((Some(rest[..i].to_string()), &rest[i + 1..]));
+            }
+            _ => {}
+        }
+    }
+    if in_element {
+        return Err("unterminated STRUCTURED-DATA".to_string());
+    }
+    Ok((Some(rest.to_string()), ""))
+}
+
+impl SyslogRule {
+    fn matches(&self, message: &SyslogMessage) -> bool {
+        message.severity <= self.min_severity
+            && self
+                .app_name
+                .as_ref()
+                .is_none_or(|app| message.app_name.as_ref() == Some(app))
+            && self
+                .hostname
+                .as_ref()
+                .is_none_or(|host| message.hostname.as_ref() == Some(host))
+            && self
+                .contains
+                .as_ref()
+                .is_none_or(|needle| message.message.contains(needle.as_str()))
+    }
+}
+
+/// 单条规则的限流与汇总状态
+struct RuleState {
+    window_start: Instant,
+    sent: u32,
+    suppressed: u32,
+    samples: Vec<String>,
+}
+
+impl RuleState {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            sent: 0,
+            suppressed: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    /// 记录一条命中消息，返回是否应立即推送
+    fn admit(&mut self, rule: &SyslogRule, line: String) -> bool {
+        if self.sent < rule.rate_limit {
+            self.sent += 1;
+            return true;
+        }
+        self.suppressed += 1;
+        if self.samples.len() < MAX_SAMPLES {
+            self.samples.push(line);
+        }
+        false
+    }
+
+    /// 时间窗口结束时重置状态，若有被抑制的消息则返回汇总文本
+    fn roll_over(&mut self, rule: &SyslogRule, now: Instant) -> Option<String> {
+        if now.duration_since(self.window_start) < Duration::from_secs(rule.window_secs) {
+            return None;
+        }
+        let summary = (self.suppressed > 0).then(|| {
+            let mut text = format!(
+                "{} more syslog messages suppressed in the last {}s, samples:",
+                self.suppressed, rule.window_secs
+            );
+            for sample in &self.samples {
+                text.push('\n');
+                text.push_str(sample);
+            }
+            text
+        });
+        *self = Self::new(now);
+        summary
+    }
+}
+
+/// 启动 syslog 监听任务
+pub async fn spawn_listener(
+    config: SyslogConfig,
+    dispatcher: Arc<Dispatcher>,
+) -> std::io::Result<()> {
+    let (tx, rx) = mpsc::channel::<String>(1024);
+
+    if let Some(addr) = &config.udp_bind {
+        let socket = UdpSocket::bind(addr).await?;
+        info!("Syslog UDP listener bound to {}", addr);
+        tokio::spawn(run_udp(socket, tx.clone()));
+    }
+    if let Some(addr) = &config.tcp_bind {
+        let listener = TcpListener::bind(addr).await?;
+        info!("Syslog TCP listener bound to {}", addr);
+        tokio::spawn(run_tcp(listener, tx.clone()));
+    }
+
+    tokio::spawn(route_messages(config.rules, rx, dispatcher));
+    Ok(())
+}
+
+async fn run_udp(socket: UdpSocket, tx: mpsc::Sender<String>) {
+    let mut buf = vec![0u8; MAX_MESSAGE_LEN];
+    loop {
+        match socket.recv_from(&mut buf).await {
+            Ok((len, _)) => {
+                let line = String::from_utf8_lossy(&buf[..len]).into_owned();
+                if tx.send(line).await.is_err() {
+                    return;
+                }
+            }
+            Err(e) => warn!("Syslog UDP receive error: {}", e),
+        }
+    }
+}
+
+async fn run_tcp(listener: TcpListener, tx: mpsc::Sender<String>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                debug!("Syslog TCP connection from {}", peer);
+                tokio::spawn(read_tcp_stream(stream, tx.clone()));
+            }
+            Err(e) => warn!("Syslog TCP accept error: {}", e),
+        }
+    }
+}
+
+/// 读取 TCP 连接，支持 RFC6587 的八位组计数和换行分帧两种方式
+async fn read_tcp_stream(stream: TcpStream, tx: mpsc::Sender<String>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let first = match reader.fill_buf().await {
+            Ok([]) => return,
+            Ok(buf) => buf[0],
+            Err(e) => {
+                debug!("Syslog TCP read error: {}", e);
+                return;
+            }
+        };
+
+        let frame = if first.is_ascii_digit() {
+            read_octet_counted(&mut reader).await
+        } else {
+            let mut line = Vec::new();
+            reader.read_until(b'\n', &mut line).await.map(|_| line)
+        };
+
+        match frame {
+            Ok(frame) if frame.is_empty() => return,
+            Ok(frame) => {
+                if tx
+                    .send(String::from_utf8_lossy(&frame).into_owned())
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            Err(e) => {
+                debug!("Syslog TCP framing error: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+async fn read_octet_counted(reader: &mut BufReader<TcpStream>) -> std::io::Result<Vec<u8>> {
+    let mut len = Vec::new();
+    reader.read_until(b' ', &mut len).await?;
+    let len: usize = std::str::from_utf8(&len)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+        .filter(|len| *len <= MAX_MESSAGE_LEN)
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid frame length")
+        })?;
+    let mut frame = vec![0u8; len];
+    reader.read_exact(&mut frame).await?;
+    Ok(frame)
+}
+
+async fn route_messages(
+    rules: Vec<SyslogRule>,
+    mut rx: mpsc::Receiver<String>,
+    dispatcher: Arc<Dispatcher>,
+) {
+    let now = Instant::now();
+    let mut states: Vec<RuleState> = rules.iter().map(|_| RuleState::new(now)).collect();
+    let mut flush = tokio::time::interval(Duration::from_secs(1));
+
+    loop {
+        tokio::select! {
+            line = rx.recv() => {
+                let Some(line) = line else { return };
+                let message = match parse_rfc5424(&line) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        debug!("Dropping unparsable syslog line ({}): {}", e, line.trim_end());
+                        continue;
+                    }
+                };
+                let Some(index) = rules.iter().position(|rule| rule.matches(&message)) else {
+                    continue;
+                };
+                let rendered = message.render();
+                if states[index].admit(&rules[index], rendered.clone()) {
+                    dispatcher
+                        .send_to_all(&rules[index].channels, MessageType::Text(rendered))
+                        .await;
+                }
+            }
+            _ = flush.tick() => {
+                let now = Instant::now();
+                for (rule, state) in rules.iter().zip(states.iter_mut()) {
+                    if let Some(summary) = state.roll_over(rule, now) {
+                        dispatcher.send_to_all(&rule.channels, MessageType::Text(summary)).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule() -> SyslogRule {
+        SyslogRule {
+            min_severity: Severity::Warning,
+            app_name: None,
+            hostname: None,
+            contains: None,
+            channels: vec!["ops".to_string()],
+            rate_limit: 2,
+            window_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_parse_rfc5424() {
+        let message = parse_rfc5424(
+            r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="Application"] An application event"#,
+        )
+        .unwrap();
+        assert_eq!(message.facility, 20);
+        assert_eq!(message.severity, Severity::Notice);
+        assert_eq!(message.hostname.as_deref(), Some("mymachine.example.com"));
+        assert_eq!(message.app_name.as_deref(), Some("evntslog"));
+        assert_eq!(message.proc_id, None);
+        assert_eq!(message.msg_id.as_deref(), Some("ID47"));
+        assert_eq!(
+            message.structured_data.as_deref(),
+            Some(r#"[exampleSDID@32473 iut="3" eventSource="Application"]"#)
+        );
+        assert_eq!(message.message, "An application event");
+    }
+
+    #[test]
+    fn test_parse_nil_structured_data() {
+        let message =
+            parse_rfc5424("<34>1 2003-10-11T22:14:15.003Z host su - ID47 - 'su root' failed\n")
+                .unwrap();
+        assert_eq!(message.severity, Severity::Critical);
+        assert_eq!(message.structured_data, None);
+        assert_eq!(message.message, "'su root' failed");
+    }
+
+    #[test]
+    fn test_parse_rejects_bsd_format() {
+        assert!(parse_rfc5424("<34>Oct 11 22:14:15 mymachine su: failed").is_err());
+    }
+
+    #[test]
+    fn test_rule_matches_severity() {
+        let rule = rule();
+        let mut message = parse_rfc5424("<11>1 - host app - - - disk failure").unwrap();
+        assert!(rule.matches(&message));
+        message.severity = Severity::Informational;
+        assert!(!rule.matches(&message));
+    }
+
+    #[test]
+    fn test_rate_limit_aggregation() {
+        let rule = rule();
+        let start = Instant::now();
+        let mut state = RuleState::new(start);
+        assert!(state.admit(&rule, "1".to_string()));
+        assert!(state.admit(&rule, "2".to_string()));
+        assert!(!state.admit(&rule, "3".to_string()));
+        assert!(!state.admit(&rule, "4".to_string()));
+
+        assert!(
+            state
+                .roll_over(&rule, start + Duration::from_secs(1))
+                .is_none()
+        );
+        let summary = state
+            .roll_over(&rule, start + Duration::from_secs(60))
+            .unwrap();
+        assert!(summary.starts_with("2 more syslog messages suppressed"));
+        assert!(summary.contains("\n3\n4"));
+        assert!(state.admit(&rule, "5".to_string()));
+    }
+}
@@ -0,0 +1,67 @@
+use serde_json::Value;
+
+/// 渲染消息模板，`{{topic}}` 为来源主题，`{{payload}}` 为原始内容，`{{a.b}}` 取 JSON 负载中的字段；
+/// 负载不是 JSON 时字段占位符渲染为空
+pub fn render_template(template: &str, topic: &str, payload: &str) -> String {
+    let json: Option<Value> = serde_json::from_str(payload).ok();
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let key = rest[start + 2..start + 2 + end].trim();
+        match key {
+            "topic" => output.push_str(topic),
+            "payload" => output.push_str(payload),
+            path => {
+                let value = json.as_ref().and_then(|json| lookup(json, path));
+                match value {
+                    Some(Value::String(s)) => output.push_str(s),
+                    Some(Value::Null) | None => {}
+                    Some(other) => output.push_str(&other.to_string()),
+                }
+            }
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 按点分路径取 JSON 字段，数组下标用数字表示
+fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(json, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(key),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json_template() {
+        let rendered = render_template(
+            "{{ sensor.name }} on {{topic}}: {{value}}°C {{missing}}",
+            "home/kitchen/temp",
+            r#"{"sensor": {"name": "kitchen"}, "value": 41.5}"#,
+        );
+        assert_eq!(rendered, "kitchen on home/kitchen/temp: 41.5°C ");
+    }
+
+    #[test]
+    fn test_render_raw_payload() {
+        let rendered = render_template("alarm: {{payload}}", "alarms", "smoke detected");
+        assert_eq!(rendered, "alarm: smoke detected");
+    }
+
+    #[test]
+    fn test_lookup_array_index() {
+        let json: Value = serde_json::from_str(r#"{"readings": [1, 2, 3]}"#).unwrap();
+        assert_eq!(lookup(&json, "readings.1"), Some(&Value::from(2)));
+    }
+}
@@ -0,0 +1,28 @@
+//! multi_push 服务端，命令行工具复用其中的配置定义校验服务端配置
+
+pub mod ack;
+pub mod api;
+pub mod auth;
+pub mod budget;
+pub mod cache;
+pub mod command;
+pub mod config;
+pub mod dedup;
+pub mod dispatch;
+pub mod error;
+pub mod ingest;
+pub mod lock;
+pub mod metrics;
+pub mod queue;
+pub mod quiet;
+pub mod receipt;
+pub mod request_id;
+pub mod schedule;
+pub mod sent;
+pub mod silence;
+pub mod status;
+pub mod storage;
+/// 单元测试共用的模拟平台
+#[cfg(test)]
+mod testing;
+pub mod validate;
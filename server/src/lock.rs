@@ -0,0 +1,258 @@
+//! 分布式锁，多个副本共享存储时用于选举定时任务的执行者和独占队列条目，避免重复投递
+//!
+//! 锁带有过期时间，持有者崩溃后自动释放；默认使用存储后端（SQL 表或内存），
+//! 配置 `cluster.lock_url` 后使用 Redis
+
+use async_trait::async_trait;
+use common::PushError;
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 集群配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClusterConfig {
+    /// 当前副本的标识，默认随机生成
+    pub instance_id: String,
+    /// 锁服务地址，如 `redis://127.0.0.1:6379/0`（需要 `redis` 特性），默认使用存储后端
+    pub lock_url: Option<String>,
+    /// 选举锁的有效期（秒），应大于定时任务的检查间隔
+    pub lock_ttl_secs: u64,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            instance_id: uuid::Uuid::new_v4().to_string(),
+            lock_url: None,
+            lock_ttl_secs: 30,
+        }
+    }
+}
+
+impl ClusterConfig {
+    pub fn lock_ttl(&self) -> Duration {
+        Duration::from_secs(self.lock_ttl_secs.max(1))
+    }
+
+    /// 连接配置的锁服务，未配置时返回 `None`
+    pub async fn connect(&self) -> Result<Option<Arc<dyn Lock>>, PushError> {
+        let Some(url) = &self.lock_url else {
+            return Ok(None);
+        };
+        match url.split_once("://").map_or("", |(scheme, _)| scheme) {
+            #[cfg(feature = "redis")]
+            "redis" | "rediss" => Ok(Some(Arc::new(
+                RedisLock::connect(url, "multi_push:lock:").await?,
+            ))),
+            #[allow(unreachable_patterns)]
+            "redis" | "rediss" => Err(PushError::ConfigError(
+                "Redis locks require the `redis` feature".to_string(),
+            )),
+            _ => Err(PushError::ConfigError(format!(
+                "Unsupported lock URL '{}'",
+                url
+            ))),
+        }
+    }
+}
+
+/// 带过期时间的互斥锁
+#[async_trait]
+pub trait Lock: Send + Sync {
+    /// 尝试获取锁，`owner` 已持有时续期，返回是否持有
+    async fn acquire(&self, name: &str, owner: &str, ttl: Duration) -> Result<bool, PushError>;
+
+    /// 释放 `owner` 持有的锁
+    async fn release(&self, name: &str, owner: &str) -> Result<(), PushError>;
+}
+
+/// 进程内的锁，只适合单副本部署
+#[derive(Default)]
+pub struct MemoryLock {
+    locks: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+#[async_trait]
+impl Lock for MemoryLock {
+    async fn acquire(&self, name: &str, owner: &str, ttl: Duration) -> Result<bool, PushError> {
+        let mut locks = self.locks.lock().unwrap();
+        let now = Instant::now();
+        match locks.get(name) {
+            Some((holder, expires_at)) if holder != owner && *expires_at > now => Ok(false),
+            _ => {
+                locks.insert(name.to_string(), (owner.to_string(), now + ttl));
+                Ok(true)
+            }
+        }
+    }
+
+    async fn release(&self, name: &str, owner: &str) -> Result<(), PushError> {
+        let mut locks = self.locks.lock().unwrap();
+        if locks.get(name).is_some_and(|(holder, _)| holder == owner) {
+            locks.remove(name);
+        }
+        Ok(())
+    }
+}
+
+/// 获取锁，持有者相同时续期
+#[cfg(feature = "redis")]
+const ACQUIRE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+    return 1
+end
+return 0
+"#;
+
+/// 只释放自己持有的锁
+#[cfg(feature = "redis")]
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// 基于 Redis 的锁
+#[cfg(feature = "redis")]
+pub struct RedisLock {
+    connection: redis::aio::ConnectionManager,
+    prefix: String,
+}
+
+#[cfg(feature = "redis")]
+impl RedisLock {
+    /// 连接 Redis，键名加上 `prefix` 前缀
+    pub async fn connect(url: &str, prefix: impl Into<String>) -> Result<Self, PushError> {
+        let connection = redis::Client::open(url)
+            .map_err(|e| PushError::ConfigError(format!("Invalid Redis URL: {}", e)))?
+            .get_connection_manager()
+            .await
+            .map_err(redis_error)?;
+        Ok(Self {
+            connection,
+            prefix: prefix.into(),
+        })
+    }
+
+    async fn eval(&self, script: &str, name: &str, args: &[String]) -> Result<i64, PushError> {
+        redis::cmd("EVAL")
+            .arg(script)
+            .arg(1)
+            .arg(format!("{}{}", self.prefix, name))
+            .arg(args)
+            .query_async(&mut self.connection.clone())
+            .await
+            .map_err(redis_error)
+    }
+}
+
+#[cfg(feature = "redis")]
+fn redis_error(e: redis::RedisError) -> PushError {
+    PushError::NetworkError(format!("Redis error: {}", e))
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl Lock for RedisLock {
+    async fn acquire(&self, name: &str, owner: &str, ttl: Duration) -> Result<bool, PushError> {
+        let args = [owner.to_string(), ttl.as_millis().max(1).to_string()];
+        Ok(self.eval(ACQUIRE_SCRIPT, name, &args).await? == 1)
+    }
+
+    async fn release(&self, name: &str, owner: &str) -> Result<(), PushError> {
+        self.eval(RELEASE_SCRIPT, name, &[owner.to_string()])
+            .await
+            .map(|_| ())
+    }
+}
+
+/// 当前副本在集群中的身份，获取锁失败时视为未持有
+#[derive(Clone)]
+pub struct Cluster {
+    lock: Arc<dyn Lock>,
+    instance_id: String,
+    ttl: Duration,
+}
+
+impl Cluster {
+    pub fn new(lock: Arc<dyn Lock>, config: &ClusterConfig) -> Self {
+        Self {
+            lock,
+            instance_id: config.instance_id.clone(),
+            ttl: config.lock_ttl(),
+        }
+    }
+
+    /// 是否由当前副本执行 `task`，每次调用续期，停止调用后其他副本在锁过期后接替
+    pub async fn lead(&self, task: &str) -> bool {
+        self.lock(&format!("leader:{}", task), self.ttl).await
+    }
+
+    /// 在 `ttl` 内独占 `key`
+    pub async fn lock(&self, key: &str, ttl: Duration) -> bool {
+        match self.lock.acquire(key, &self.instance_id, ttl).await {
+            Ok(held) => held,
+            Err(e) => {
+                warn!("Failed to acquire lock '{}': {}", key, e);
+                false
+            }
+        }
+    }
+
+    pub async fn unlock(&self, key: &str) {
+        if let Err(e) = self.lock.release(key, &self.instance_id).await {
+            warn!("Failed to release lock '{}': {}", key, e);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// 各后端共用的检查
+    pub async fn exercise(lock: &dyn Lock) {
+        let ttl = Duration::from_secs(60);
+        assert!(lock.acquire("scheduler", "a", ttl).await.unwrap());
+        assert!(!lock.acquire("scheduler", "b", ttl).await.unwrap());
+        // 持有者重复获取即续期
+        assert!(lock.acquire("scheduler", "a", ttl).await.unwrap());
+        // 非持有者释放无效
+        lock.release("scheduler", "b").await.unwrap();
+        assert!(!lock.acquire("scheduler", "b", ttl).await.unwrap());
+        lock.release("scheduler", "a").await.unwrap();
+        assert!(lock.acquire("scheduler", "b", ttl).await.unwrap());
+
+        assert!(lock.acquire("short", "a", Duration::ZERO).await.unwrap());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(lock.acquire("short", "b", ttl).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_memory_lock() {
+        exercise(&MemoryLock::default()).await;
+    }
+
+    #[tokio::test]
+    async fn test_cluster() {
+        let lock: Arc<dyn Lock> = Arc::new(MemoryLock::default());
+        let config = |id: &str| ClusterConfig {
+            instance_id: id.to_string(),
+            ..Default::default()
+        };
+        let a = Cluster::new(lock.clone(), &config("a"));
+        let b = Cluster::new(lock, &config("b"));
+        assert!(a.lead("scheduler").await);
+        assert!(!b.lead("scheduler").await);
+        assert!(a.lead("scheduler").await);
+        assert!(b.lead("digest").await);
+    }
+}
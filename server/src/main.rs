@@ -1,86 +1,783 @@
-use crate::api::{PushRequest, PushResponse};
+use actix_web::http::StatusCode;
 use actix_web::{
-    App, HttpResponse, HttpServer, Responder, Result, get, http::StatusCode, post, web,
+    App, HttpRequest, HttpResponse, HttpServer, Responder, delete, get, patch, post, web,
+};
+use clap::Parser;
+use common::{
+    Message, MessageType, PlatformRegistry, REQUEST_ID_KEY, Receipt, Redactor, qr_message,
 };
-use common::{PlatformRegistry, PushResult};
 use log::*;
-use wxwork_group_bot::WxWorkPlatformFactory;
-
-mod api;
+use multi_push::default_registry;
+use server::api::{
+    AckResponse, ChannelTestResponse, HistoryQuery, PlatformDescriptor, PreviewRequest, PushQuery,
+    PushRequest, PushResponse, QrRequest, ReceiptWebhookResponse, ReceiptsResponse,
+    ScheduleRequest, ScheduleResponse,
+};
+use server::auth::ApiKeys;
+use server::command::Commands;
+use server::config::{ServerConfig, StartupCheck};
+use server::dispatch::Dispatcher;
+use server::error::{ApiError, ErrorCode};
+use server::ingest::DeliveryReport;
+use server::lock::Cluster;
+use server::request_id::RequestId;
+use server::status::StatusPage;
+use server::validate::ValidationConfig;
+use server::{ack, ingest, queue, quiet, receipt, request_id, schedule, status, validate};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[get("/hello")]
 async fn hello() -> impl Responder {
     HttpResponse::Ok().body("Hello World!")
 }
 
+#[get("/platforms")]
+async fn platforms(registry: web::Data<PlatformRegistry>) -> impl Responder {
+    let mut platforms: Vec<PlatformDescriptor> = registry
+        .iter()
+        .map(|(name, factory)| PlatformDescriptor {
+            name: name.to_string(),
+            info: factory.platform_info(),
+            config_schema: factory.config_schema(),
+        })
+        .collect();
+    platforms.sort_by(|a, b| a.name.cmp(&b.name));
+    HttpResponse::Ok().json(platforms)
+}
+
+/// 调用方提供请求 ID 时按 ID 和推送目标去重，返回去重键；重复的请求返回 409，
+/// 演练的请求不占用去重键
+async fn idempotency(
+    http_req: &HttpRequest,
+    dispatcher: &Dispatcher,
+    target: &str,
+    dry_run: bool,
+) -> Result<Option<String>, ApiError> {
+    let Some(id) = request_id::supplied(http_req).filter(|_| !dry_run) else {
+        return Ok(None);
+    };
+    let key = format!("request:{}:{}", id, target);
+    if !dispatcher.first_seen(&key).await {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            ErrorCode::DuplicateRequest,
+            format!("Request '{}' to '{}' has already been accepted", id, target),
+        ));
+    }
+    Ok(Some(key))
+}
+
+/// 请求要求演练时在演练作用域内执行
+async fn maybe_dry_run<F: std::future::Future>(dry_run: bool, future: F) -> F::Output {
+    if dry_run {
+        common::dry_run::scope(future).await
+    } else {
+        future.await
+    }
+}
+
+/// 推送失败时删除去重记录，允许调用方用相同的请求 ID 重试
+async fn release(dispatcher: &Dispatcher, key: Option<String>, failed: bool) {
+    if let (Some(key), true) = (key, failed) {
+        dispatcher.forget(&key).await;
+    }
+}
+
 #[post("/push")]
-async fn push(req: web::Json<PushRequest>, registry: web::Data<PlatformRegistry>) -> HttpResponse {
-    info!("Received push request for platform: {}", req.platform);
-
-    let factory = match registry.get_factory(&req.platform) {
-        Some(f) => f,
-        None => {
-            let err_resp = PushResponse {
-                result: PushResult {
-                    success: false,
-                    response: Some(format!("Platform '{}' not found", req.platform)),
-                    ..Default::default()
-                },
-            };
-            return HttpResponse::BadRequest().json(err_resp);
-        }
+async fn push(
+    http_req: HttpRequest,
+    req: web::Json<PushRequest>,
+    registry: web::Data<PlatformRegistry>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+    status_page: Option<web::Data<StatusPage>>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let request_id = RequestId::of(&http_req);
+    let mut message = req.to_message();
+    message
+        .metadata
+        .insert(REQUEST_ID_KEY.to_string(), request_id.0.clone());
+    validation.check(&message)?;
+    info!(
+        "[{}] Received push request for platform: {}",
+        request_id.0, req.platform
+    );
+
+    if !registry.contains(&req.platform) {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::PlatformNotFound,
+            format!("Platform '{}' not found", req.platform),
+        ));
+    }
+
+    let dry_run = req.dry_run || dispatcher.dry_run();
+    let key = idempotency(&http_req, &dispatcher, &req.platform, dry_run).await?;
+    let result = maybe_dry_run(
+        req.dry_run,
+        dispatcher.send_to_platform(&req.platform, req.config.clone(), message),
+    )
+    .await;
+    release(&dispatcher, key, result.is_err()).await;
+
+    if let (Some(page), Some(incident), false) = (&status_page, &req.incident, dry_run) {
+        page.record_delivered(incident, &req.message, &result);
+    }
+
+    let mut result = result?;
+    result.request_id = Some(request_id.0);
+    Ok(HttpResponse::Ok().json(PushResponse { result }))
+}
+
+/// 推送到配置文件中的通道，调用方无需持有平台凭据
+#[post("/push/{channel}")]
+async fn push_to_channel(
+    http_req: HttpRequest,
+    channel: web::Path<String>,
+    query: web::Query<PushQuery>,
+    message: web::Json<Message>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let request_id = RequestId::of(&http_req);
+    let mut message = message.into_inner();
+    message
+        .metadata
+        .insert(REQUEST_ID_KEY.to_string(), request_id.0.clone());
+    validation.check(&message)?;
+    info!(
+        "[{}] Received push request for channel: {}",
+        request_id.0, channel
+    );
+
+    if !dispatcher.has_channel(&channel) {
+        return Err(ApiError::channel_not_found(&channel));
+    }
+    let dry_run = query.dry_run || dispatcher.dry_run();
+    let key = idempotency(&http_req, &dispatcher, &channel, dry_run).await?;
+    let result = maybe_dry_run(query.dry_run, dispatcher.send(&channel, message)).await;
+    release(&dispatcher, key, result.is_err()).await;
+    Ok(HttpResponse::Ok().json(PushResponse { result: result? }))
+}
+
+/// 生成二维码图片并推送到通道，适合设备配对、支付链接等需要扫码的通知
+#[post("/qr/{channel}")]
+async fn push_qr(
+    http_req: HttpRequest,
+    channel: web::Path<String>,
+    query: web::Query<PushQuery>,
+    req: web::Json<QrRequest>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let request_id = RequestId::of(&http_req);
+    let req = req.into_inner();
+    // 图片以 data: URL 内联，只校验编码的内容
+    validation.check(&Message::new(MessageType::Text(req.text.clone())))?;
+    info!(
+        "[{}] Received QR code push request for channel: {}",
+        request_id.0, channel
+    );
+
+    if !dispatcher.has_channel(&channel) {
+        return Err(ApiError::channel_not_found(&channel));
+    }
+    let mut message = Message::new(qr_message(&req.text, req.caption)?);
+    message
+        .metadata
+        .insert(REQUEST_ID_KEY.to_string(), request_id.0.clone());
+    let dry_run = query.dry_run || dispatcher.dry_run();
+    let key = idempotency(&http_req, &dispatcher, &channel, dry_run).await?;
+    let result = maybe_dry_run(query.dry_run, dispatcher.send(&channel, message)).await;
+    release(&dispatcher, key, result.is_err()).await;
+    let result = result?;
+    Ok(HttpResponse::Ok().json(PushResponse { result }))
+}
+
+/// 按配置中的路由规则推送，由消息的优先级、标签和元数据决定投递的通道
+#[post("/route")]
+async fn push_routed(
+    http_req: HttpRequest,
+    query: web::Query<PushQuery>,
+    message: web::Json<Message>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let request_id = RequestId::of(&http_req);
+    let mut message = message.into_inner();
+    message
+        .metadata
+        .insert(REQUEST_ID_KEY.to_string(), request_id.0.clone());
+    validation.check(&message)?;
+
+    let channels = dispatcher.route(&message);
+    info!(
+        "[{}] Routing {:?} message to channels: {:?}",
+        request_id.0, message.priority, channels
+    );
+    if channels.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::ChannelNotFound,
+            "No route matches the message",
+        ));
+    }
+    let dry_run = query.dry_run || dispatcher.dry_run();
+    let key = idempotency(&http_req, &dispatcher, "route", dry_run).await?;
+    let results = maybe_dry_run(query.dry_run, dispatcher.send_to_all(&channels, message)).await;
+    let failed = results.iter().all(|(_, result)| result.is_err());
+    release(&dispatcher, key, failed).await;
+    Ok(DeliveryReport::new(results).into_response())
+}
+
+/// 预览消息在各通道上平台会收到的请求载荷，经过模板、Markdown 转换和拆分，但不发送
+#[post("/preview")]
+async fn preview(
+    http_req: HttpRequest,
+    req: web::Json<PreviewRequest>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let PreviewRequest { message, channels } = req.into_inner();
+    validation.check(&message)?;
+    let channels = if channels.is_empty() {
+        dispatcher.route(&message)
+    } else {
+        channels
     };
+    if channels.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::ChannelNotFound,
+            "No route matches the message",
+        ));
+    }
+    if let Some(channel) = channels.iter().find(|c| !dispatcher.has_channel(c)) {
+        return Err(ApiError::channel_not_found(channel));
+    }
+    Ok(HttpResponse::Ok().json(dispatcher.preview(&channels, message).await))
+}
 
-    let platform = match factory.create(req.config.clone()) {
-        Ok(p) => p,
+/// 向通道发送诊断消息并返回详细结果，包括上游响应，用于验证更换凭据后通道是否可用
+#[post("/admin/channels/{name}/test")]
+async fn test_channel(
+    http_req: HttpRequest,
+    name: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    if !dispatcher.has_channel(&name) {
+        return Err(ApiError::channel_not_found(&name));
+    }
+    let channel = name.into_inner();
+    let response = match dispatcher.test_channel(&channel).await {
+        Ok(result) => {
+            info!("Test message to channel '{}' succeeded", channel);
+            ChannelTestResponse {
+                channel,
+                success: result.success,
+                result: Some(result),
+                error: None,
+            }
+        }
         Err(e) => {
-            let err_resp = PushResponse {
-                result: PushResult {
-                    success: false,
-                    response: Some(format!("Failed to create platform: {}", e)),
-                    ..Default::default()
-                },
-            };
-            return HttpResponse::BadRequest().json(err_resp);
+            warn!("Test message to channel '{}' failed: {}", channel, e);
+            ChannelTestResponse {
+                channel,
+                success: false,
+                result: None,
+                error: Some(ApiError::from(e).into_body()),
+            }
         }
     };
+    Ok(HttpResponse::Ok().json(response))
+}
 
-    let result = platform.send(req.message.clone()).await;
+/// 确认 `require_ack` 的消息，停止重复提醒，`id` 为推送结果中的请求 ID
+#[post("/ack/{id}")]
+async fn acknowledge(
+    http_req: HttpRequest,
+    id: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let attempts = dispatcher.acks().ack(&id).ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::AckNotFound,
+            format!("No pending acknowledgement for '{}'", id),
+        )
+    })?;
+    info!("[{}] Acknowledged after {} attempt(s)", id, attempts);
+    Ok(HttpResponse::Ok().json(AckResponse {
+        id: id.into_inner(),
+        attempts,
+    }))
+}
 
-    let response = match result {
-        Ok(push_result) => PushResponse {
-            result: push_result,
-        },
-        Err(push_error) => PushResponse {
-            result: PushResult {
-                success: false,
-                response: Some(push_error.to_string()),
-                ..Default::default()
-            },
-        },
-    };
+fn message_not_found(id: &str) -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        ErrorCode::MessageNotFound,
+        format!("No editable message for request '{}'", id),
+    )
+}
+
+/// 编辑之前推送的消息，`id` 为推送时的请求 ID，如告警恢复后把原消息改为 RESOLVED
+#[patch("/push/{id}")]
+async fn update_pushed(
+    http_req: HttpRequest,
+    id: web::Path<String>,
+    message: web::Json<Message>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let message = message.into_inner();
+    validation.check(&message)?;
+    info!("[{}] Received update request", id);
+    let results = dispatcher
+        .update(&id, message)
+        .await
+        .ok_or_else(|| message_not_found(&id))?;
+    Ok(DeliveryReport::new(results).into_response())
+}
+
+/// 撤回之前推送的消息
+#[delete("/push/{id}")]
+async fn delete_pushed(
+    http_req: HttpRequest,
+    id: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    info!("[{}] Received delete request", id);
+    let results = dispatcher
+        .delete(&id)
+        .await
+        .ok_or_else(|| message_not_found(&id))?;
+    Ok(DeliveryReport::new(results).into_response())
+}
+
+/// 查询推送的投递状态，只包含支持回执的通道
+#[get("/push/{id}/receipts")]
+async fn pushed_receipts(
+    http_req: HttpRequest,
+    id: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let statuses = dispatcher.receipts().statuses(&id);
+    if statuses.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::MessageNotFound,
+            format!("No tracked delivery for request '{}'", id),
+        ));
+    }
+    Ok(HttpResponse::Ok().json(ReceiptsResponse {
+        id: id.into_inner(),
+        statuses,
+    }))
+}
+
+/// 最大可查询的历史条数
+const MAX_HISTORY_LIMIT: usize = 1000;
+
+/// 查询最近的投递记录，按时间倒序
+#[get("/history")]
+async fn history(
+    http_req: HttpRequest,
+    query: web::Query<HistoryQuery>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let labels = query
+        .label_filter()
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, ErrorCode::ValidationFailed, e))?;
+    let records = dispatcher
+        .history(query.limit.min(MAX_HISTORY_LIMIT), &labels)
+        .await?;
+    Ok(HttpResponse::Ok().json(records))
+}
+
+/// 付费通道当日的预算使用情况
+#[get("/budgets")]
+async fn budgets(
+    http_req: HttpRequest,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    Ok(HttpResponse::Ok().json(dispatcher.budgets(chrono::Utc::now())))
+}
+
+/// Prometheus 格式的积压指标
+#[get("/metrics")]
+async fn export_metrics(
+    http_req: HttpRequest,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let gauges = dispatcher.gauges(chrono::Utc::now()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(gauges.render()))
+}
+
+/// 定时推送到通道，到期后按普通通道推送发送
+#[post("/schedule/{channel}")]
+async fn schedule_push(
+    http_req: HttpRequest,
+    channel: web::Path<String>,
+    req: web::Json<ScheduleRequest>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+    validation: web::Data<ValidationConfig>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let request_id = RequestId::of(&http_req);
+    let ScheduleRequest {
+        mut message,
+        send_at,
+    } = req.into_inner();
+    message
+        .metadata
+        .insert(REQUEST_ID_KEY.to_string(), request_id.0.clone());
+    validation.check(&message)?;
+    if !dispatcher.has_channel(&channel) {
+        return Err(ApiError::channel_not_found(&channel));
+    }
+    let schedule = dispatcher.schedule(&channel, message, send_at).await?;
+    info!(
+        "[{}] Scheduled message to channel '{}' at {}",
+        schedule.id, channel, send_at
+    );
+    Ok(HttpResponse::Ok().json(ScheduleResponse {
+        id: schedule.id,
+        channel: schedule.channel,
+        send_at: schedule.send_at,
+    }))
+}
+
+/// 取消未发送的定时消息
+#[delete("/schedule/{id}")]
+async fn cancel_schedule(
+    http_req: HttpRequest,
+    id: web::Path<String>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    if !dispatcher.cancel_schedule(&id).await? {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::MessageNotFound,
+            format!("No pending scheduled message '{}'", id),
+        ));
+    }
+    info!("[{}] Cancelled scheduled message", id);
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// 接收平台或外部系统推送的回执，如邮件退信 Webhook，`platform` 为平台名称
+#[post("/receipts/{platform}")]
+async fn receive_receipts(
+    http_req: HttpRequest,
+    platform: web::Path<String>,
+    receipts: web::Json<Vec<Receipt>>,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let mut applied = 0;
+    for receipt in receipts.into_inner() {
+        if let Some(event) = dispatcher.apply_receipt(&platform, receipt) {
+            info!(
+                "[{}] Message {} on {} is now {:?}",
+                event.request_id, event.message_id, event.target, event.status
+            );
+            applied += 1;
+        }
+    }
+    Ok(HttpResponse::Ok().json(ReceiptWebhookResponse { applied }))
+}
 
-    HttpResponse::Ok().json(response)
+/// 以 Server-Sent Events 推送投递状态变化
+#[get("/events")]
+async fn events(
+    http_req: HttpRequest,
+    dispatcher: web::Data<Dispatcher>,
+    api_keys: web::Data<ApiKeys>,
+) -> Result<HttpResponse, ApiError> {
+    api_keys.check(&http_req)?;
+    let events = futures::stream::unfold(dispatcher.receipts().subscribe(), |mut rx| async {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let data = serde_json::to_string(&event).unwrap_or_default();
+                    let chunk = web::Bytes::from(format!("event: receipt\ndata: {}\n\n", data));
+                    return Some((Ok::<_, actix_web::Error>(chunk), rx));
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event subscriber lagged, skipped {} event(s)", skipped);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(events))
+}
+
+/// 访问日志格式，在默认格式后附加请求 ID
+const LOG_FORMAT: &str =
+    r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T request_id=%{x-request-id}o"#;
+
+/// 命令行参数，优先于配置文件和环境变量
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// 配置文件路径，支持 JSON、TOML 和 YAML
+    #[arg(long, env = "MULTI_PUSH_CONFIG", default_value = "config.json")]
+    config: PathBuf,
+    /// 监听地址
+    #[arg(long)]
+    bind: Option<String>,
+    /// 工作线程数
+    #[arg(long)]
+    workers: Option<usize>,
+    /// 所有路由的前缀
+    #[arg(long)]
+    base_path: Option<String>,
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("debug"));
 
-    let mut registry = PlatformRegistry::new();
-    registry.register(Box::new(WxWorkPlatformFactory));
+    let args = Args::parse();
+    let mut config = ServerConfig::load(&args.config)?;
+    if let Some(bind) = args.bind {
+        config.bind = Some(bind);
+    }
+    if let Some(workers) = args.workers {
+        config.workers = Some(workers);
+    }
+    if let Some(base_path) = args.base_path {
+        config.base_path = base_path;
+    }
+
+    let mut registry = default_registry();
+    #[cfg(feature = "plugins")]
+    for path in &config.plugins {
+        // 插件路径来自服务端配置文件，视为可信
+        let names = unsafe { registry.load_plugin(path) }.map_err(std::io::Error::other)?;
+        info!("Loaded plugin {}: {:?}", path.display(), names);
+    }
+    let backend = config
+        .storage
+        .connect()
+        .await
+        .map_err(std::io::Error::other)?;
+    // 配置中的模板写入存储，共享存储的其他副本写入的模板同样生效，同名时以配置为准
+    let mut templates = backend
+        .storage
+        .list_templates()
+        .await
+        .map_err(std::io::Error::other)?;
+    for (name, template) in &config.templates {
+        backend
+            .storage
+            .save_template(name, template)
+            .await
+            .map_err(std::io::Error::other)?;
+        templates.insert(name.clone(), template.clone());
+    }
+    if !templates.is_empty() {
+        registry.set_templates(templates);
+    }
+    if !config.directory.is_empty() {
+        registry.add_hook(Arc::new(config.directory.clone()));
+    }
     info!("Registered platforms: {:?}", registry.list_platforms());
 
-    let registry_data = web::Data::new(registry);
+    // 在分发器入口脱敏，入队和定时保存的消息不含敏感内容
+    let redactor = Redactor::new(&config.redactions).map_err(std::io::Error::other)?;
+    let registry = Arc::new(registry);
+    let instance_ttl = config.instance_ttl();
+    let instance_cache_size = config.instance_cache_size();
+    let thread_ttl = config.thread_ttl();
+    let sent_ttl = config.sent_ttl();
+    let bind = config.bind_address().to_string();
+    let base_path = config.base_path();
+    let max_body_bytes = config.max_body_bytes();
+    let dedup = config
+        .dedup
+        .connect(backend.dedup.clone())
+        .await
+        .map_err(std::io::Error::other)?;
+    let dispatcher = Arc::new(
+        Dispatcher::new(registry.clone(), config.push.channels, instance_ttl)
+            .with_routes(config.push.routes)
+            .with_instance_cache_size(instance_cache_size)
+            .with_ack_config(config.ack.clone())
+            .with_thread_ttl(thread_ttl)
+            .with_sent_ttl(sent_ttl)
+            .with_storage(backend.storage)
+            .with_queue(backend.queue.clone(), config.queue.clone())
+            .with_dedup(dedup, config.dedup.ttl())
+            .with_redactor(Arc::new(redactor))
+            .with_dry_run(config.dry_run),
+    );
+    if config.dry_run {
+        warn!("Dry run mode is enabled, messages will not be delivered");
+    }
+    let restored = dispatcher.restore().await.map_err(std::io::Error::other)?;
+    if restored > 0 {
+        info!("Restored {} silence(s) from storage", restored);
+    }
+    if config.startup_check != StartupCheck::Off {
+        let failures = dispatcher.check_channels().await;
+        if !failures.is_empty() && config.startup_check == StartupCheck::FailFast {
+            let channels: Vec<&str> = failures.iter().map(|(name, _)| name.as_str()).collect();
+            return Err(std::io::Error::other(format!(
+                "Channels failed startup check: {}",
+                channels.join(", ")
+            )));
+        }
+    }
+    quiet::spawn_digest_task(dispatcher.clone());
+    let lock = match config
+        .cluster
+        .connect()
+        .await
+        .map_err(std::io::Error::other)?
+    {
+        Some(lock) => lock,
+        None => backend.lock,
+    };
+    let cluster = Cluster::new(lock, &config.cluster);
+    schedule::spawn_scheduler(dispatcher.clone(), cluster.clone());
+    if config.queue.worker() {
+        queue::spawn_worker(
+            dispatcher.clone(),
+            backend.queue,
+            config.queue.clone(),
+            cluster,
+        );
+    }
+    ack::spawn_reminder_task(
+        dispatcher.clone(),
+        Duration::from_secs(config.ack.interval_secs.max(1)),
+    );
+    receipt::spawn_poller(dispatcher.clone(), &config.receipts);
+    ingest::rss::spawn_pollers(config.feeds, dispatcher.clone());
+    if let Some(mqtt) = config.mqtt {
+        ingest::mqtt::spawn_subscriber(mqtt, dispatcher.clone());
+    }
+    #[cfg(feature = "kafka")]
+    if let Some(kafka) = config.kafka {
+        ingest::kafka::spawn_consumer(kafka, config.validation.clone(), dispatcher.clone())?;
+    }
+    if let Some(syslog) = config.syslog {
+        ingest::syslog::spawn_listener(syslog, dispatcher.clone()).await?;
+    }
+
+    if config.api_keys.is_empty() {
+        warn!("No API keys configured, push endpoints are unauthenticated");
+    }
+    let api_keys_data = web::Data::new(ApiKeys::new(config.api_keys));
+    let validation_data = web::Data::new(config.validation);
+    let registry_data = web::Data::from(registry);
+    let dispatcher_data = web::Data::from(dispatcher);
+    let alertmanager_data = web::Data::new(config.alertmanager);
+    let jira_data = web::Data::new(config.jira);
+    let harbor_data = web::Data::new(config.harbor);
+    let commands = Commands::new(config.commands).map_err(std::io::Error::other)?;
+    let status_page_data = config
+        .status_page
+        .map(|status_page| web::Data::new(StatusPage::new(status_page)));
 
-    HttpServer::new(move || {
+    let mut server = HttpServer::new(move || {
         App::new()
-            .wrap(actix_web::middleware::Logger::default())
+            .wrap(actix_web::middleware::from_fn(request_id::middleware))
+            .wrap(actix_web::middleware::Logger::new(LOG_FORMAT))
+            .app_data(
+                web::JsonConfig::default()
+                    .limit(max_body_bytes)
+                    .error_handler(validate::json_error_handler),
+            )
+            .app_data(web::PayloadConfig::new(max_body_bytes))
             .app_data(registry_data.clone())
-            .service(hello)
-            .service(push)
-    })
-    .bind("0.0.0.0:8888")?
-    .run()
-    .await
+            .app_data(dispatcher_data.clone())
+            .app_data(api_keys_data.clone())
+            .app_data(validation_data.clone())
+            .app_data(alertmanager_data.clone())
+            .app_data(jira_data.clone())
+            .app_data(harbor_data.clone())
+            .service(
+                web::scope(&base_path)
+                    .service(hello)
+                    .service(platforms)
+                    .service(push)
+                    .service(push_to_channel)
+                    .service(push_routed)
+                    .service(push_qr)
+                    .service(preview)
+                    .service(test_channel)
+                    .service(acknowledge)
+                    .service(update_pushed)
+                    .service(delete_pushed)
+                    .service(pushed_receipts)
+                    .service(receive_receipts)
+                    .service(events)
+                    .service(history)
+                    .service(budgets)
+                    .service(export_metrics)
+                    .service(schedule_push)
+                    .service(cancel_schedule)
+                    .service(ingest::alertmanager::receive)
+                    .service(ingest::alertmanager::receive_for_channel)
+                    .service(ingest::jira::receive)
+                    .service(ingest::harbor::receive)
+                    .configure(|cfg| commands.configure(cfg))
+                    .configure(|cfg| {
+                        if let Some(status_page) = &status_page_data {
+                            cfg.app_data(status_page.clone())
+                                .service(status::status_html)
+                                .service(status::status_json);
+                        }
+                    }),
+            )
+    });
+    if let Some(workers) = config.workers {
+        server = server.workers(workers);
+    }
+    if let Some(keep_alive) = config.keep_alive_secs {
+        server = server.keep_alive(Duration::from_secs(keep_alive));
+    }
+    info!("Listening on {}", bind);
+    server.bind(bind)?.run().await
 }
@@ -0,0 +1,161 @@
+//! Prometheus 文本格式的积压指标：队列深度、定时消息延迟、未确认消息时长、死信数量和付费通道预算
+
+use crate::api::BudgetUsage;
+use crate::queue::QueueStats;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// 一次采集的指标
+#[derive(Debug, Clone, Default)]
+pub struct Gauges {
+    pub queue: QueueStats,
+    /// 最早一条已到期未发送的定时消息的延迟，没有时为 0
+    pub scheduler_lag: Duration,
+    /// 最早一条未确认消息距第一次发送的时间，没有时为 0
+    pub oldest_unacked: Duration,
+    /// 配置了预算的通道当日的使用情况
+    pub budgets: Vec<BudgetUsage>,
+}
+
+impl Gauges {
+    /// 渲染为 Prometheus 文本格式
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        gauge(
+            &mut out,
+            "multi_push_queue_pending",
+            "Queued messages waiting for delivery, including in-flight ones",
+        );
+        for (channel, count) in &self.queue.pending {
+            let _ = writeln!(
+                out,
+                "multi_push_queue_pending{{channel=\"{}\"}} {}",
+                escape(channel),
+                count
+            );
+        }
+        gauge(
+            &mut out,
+            "multi_push_queue_dead_letters",
+            "Queued messages given up after the maximum attempts",
+        );
+        let _ = writeln!(
+            out,
+            "multi_push_queue_dead_letters {}",
+            self.queue.dead_letters
+        );
+        gauge(
+            &mut out,
+            "multi_push_scheduler_lag_seconds",
+            "Delay of the oldest due scheduled message that has not been sent",
+        );
+        let _ = writeln!(
+            out,
+            "multi_push_scheduler_lag_seconds {}",
+            self.scheduler_lag.as_secs_f64()
+        );
+        gauge(
+            &mut out,
+            "multi_push_oldest_unacked_seconds",
+            "Age of the oldest message still waiting for acknowledgement",
+        );
+        let _ = writeln!(
+            out,
+            "multi_push_oldest_unacked_seconds {}",
+            self.oldest_unacked.as_secs_f64()
+        );
+        budget_gauge(
+            &mut out,
+            "multi_push_budget_spent",
+            "Cost spent today on a paid channel",
+            &self.budgets,
+            |usage| usage.spent,
+        );
+        budget_gauge(
+            &mut out,
+            "multi_push_budget_limit",
+            "Daily budget of a paid channel",
+            &self.budgets,
+            |usage| usage.limit,
+        );
+        budget_gauge(
+            &mut out,
+            "multi_push_budget_refused",
+            "Messages refused or downgraded today because the budget was exhausted",
+            &self.budgets,
+            |usage| usage.refused as f64,
+        );
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+}
+
+fn budget_gauge(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    budgets: &[BudgetUsage],
+    value: impl Fn(&BudgetUsage) -> f64,
+) {
+    gauge(out, name, help);
+    for usage in budgets {
+        let _ = writeln!(
+            out,
+            "{}{{channel=\"{}\"}} {}",
+            name,
+            escape(&usage.channel),
+            value(usage)
+        );
+    }
+}
+
+/// 转义标签值中的反斜杠、引号和换行
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_render() {
+        let gauges = Gauges {
+            queue: QueueStats {
+                pending: BTreeMap::from([("ops".to_string(), 3), ("a\"b".to_string(), 1)]),
+                dead_letters: 2,
+            },
+            scheduler_lag: Duration::from_millis(1500),
+            oldest_unacked: Duration::ZERO,
+            budgets: vec![BudgetUsage {
+                channel: "sms".to_string(),
+                date: "2024-01-01".parse().unwrap(),
+                cost_per_message: 0.05,
+                limit: 10.0,
+                spent: 2.5,
+                remaining: 7.5,
+                sent: 50,
+                refused: 0,
+                exhausted: false,
+            }],
+        };
+        let text = gauges.render();
+        assert!(text.contains("# TYPE multi_push_queue_pending gauge\n"));
+        assert!(text.contains("multi_push_queue_pending{channel=\"ops\"} 3\n"));
+        assert!(text.contains("multi_push_queue_pending{channel=\"a\\\"b\"} 1\n"));
+        assert!(text.contains("multi_push_queue_dead_letters 2\n"));
+        assert!(text.contains("multi_push_scheduler_lag_seconds 1.5\n"));
+        assert!(text.contains("multi_push_oldest_unacked_seconds 0\n"));
+        assert!(text.contains("multi_push_budget_spent{channel=\"sms\"} 2.5\n"));
+        assert!(text.contains("multi_push_budget_limit{channel=\"sms\"} 10\n"));
+        assert!(text.contains("multi_push_budget_refused{channel=\"sms\"} 0\n"));
+    }
+}
@@ -0,0 +1,475 @@
+//! 投递队列，通道推送遇到可重试的错误（网络错误、限流、超时）时入队，由后台 worker 退避重试；
+//! 启用 outbox 时所有通道消息先入队再由 worker 投递
+//!
+//! 领取的条目在租约期内为投递中，不会被其他 worker 再次领取；worker 崩溃后由定期清扫
+//! 将租约到期的条目放回队列重新投递，即至少一次投递；投递期间持有条目的锁，
+//! 多个副本不会同时投递同一条目；放弃重试的条目作为死信保留
+
+use crate::dispatch::Dispatcher;
+use crate::lock::Cluster;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{Message, PushError};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// 退避间隔上限
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+/// 每次领取的最大条数
+const BATCH_SIZE: usize = 20;
+
+/// 队列配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueueConfig {
+    /// 是否启用，未启用时可重试的错误直接返回给调用方
+    pub enabled: bool,
+    /// 通道消息先持久化到队列再投递，接受后即返回，进程崩溃不会丢失已接受的消息
+    pub outbox: bool,
+    /// 最多发送次数，包括入队前的第一次发送
+    pub max_attempts: u32,
+    /// 第一次重试的等待时间（秒），之后每次翻倍，最长 1 小时
+    pub backoff_secs: u64,
+    /// 领取后的租约（秒），超过后未完成的条目重新投递
+    pub lease_secs: u64,
+    /// 没有新条目通知时检查队列的间隔（秒）
+    pub poll_interval_secs: u64,
+    /// 清扫租约到期的投递中条目的间隔（秒）
+    pub sweep_interval_secs: u64,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            outbox: false,
+            max_attempts: 5,
+            backoff_secs: 30,
+            lease_secs: 300,
+            poll_interval_secs: 5,
+            sweep_interval_secs: 60,
+        }
+    }
+}
+
+impl QueueConfig {
+    /// 第 `attempts` 次发送失败后的等待时间
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        Duration::from_secs(self.backoff_secs)
+            .saturating_mul(factor)
+            .min(MAX_BACKOFF)
+    }
+
+    pub fn lease(&self) -> Duration {
+        Duration::from_secs(self.lease_secs.max(1))
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_secs.max(1))
+    }
+
+    pub fn sweep_interval(&self) -> Duration {
+        Duration::from_secs(self.sweep_interval_secs.max(1))
+    }
+
+    /// 是否需要运行 worker
+    pub fn worker(&self) -> bool {
+        self.enabled || self.outbox
+    }
+}
+
+/// 队列中等待投递的消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueItem {
+    /// 请求 ID 和通道组成的唯一 ID
+    pub id: String,
+    pub channel: String,
+    pub message: Message,
+    /// 已发送的次数
+    pub attempts: u32,
+    /// 可以领取的时间
+    pub available_at: DateTime<Utc>,
+    /// 投递中条目的租约到期时间
+    pub leased_until: Option<DateTime<Utc>>,
+    /// 放弃重试的时间，不为空时为死信
+    pub failed_at: Option<DateTime<Utc>>,
+    /// 最后一次发送的错误
+    pub last_error: Option<String>,
+    /// 入队时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl QueueItem {
+    /// 通过 outbox 接受、尚未发送的消息
+    pub fn accepted(request_id: &str, channel: &str, message: Message) -> Self {
+        let now = Utc::now();
+        Self {
+            attempts: 0,
+            ..Self::new(request_id, channel, message, now)
+        }
+    }
+
+    /// 第一次发送失败后入队的消息
+    pub fn new(
+        request_id: &str,
+        channel: &str,
+        message: Message,
+        available_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: format!("{}:{}", request_id, channel),
+            channel: channel.to_string(),
+            message,
+            attempts: 1,
+            available_at,
+            leased_until: None,
+            failed_at: None,
+            last_error: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// 投递队列
+#[async_trait]
+pub trait Queue: Send + Sync {
+    /// 入队，相同 ID 时覆盖
+    async fn enqueue(&self, item: &QueueItem) -> Result<(), PushError>;
+
+    /// 领取最多 `limit` 条到期的条目，发送次数加一，标记为投递中直到 `lease` 后
+    async fn claim(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<QueueItem>, PushError>;
+
+    /// 投递完成或放弃，移出队列
+    async fn complete(&self, id: &str) -> Result<(), PushError>;
+
+    /// 放回队列，`available_at` 后重试
+    async fn retry(&self, id: &str, available_at: DateTime<Utc>) -> Result<(), PushError>;
+
+    /// 将 `now` 时租约已到期的投递中条目放回队列，返回条数
+    async fn reclaim(&self, now: DateTime<Utc>) -> Result<usize, PushError>;
+
+    /// 放弃重试，转为死信
+    async fn bury(&self, id: &str, error: &str) -> Result<(), PushError>;
+
+    /// 各通道待投递（含投递中）的条数和死信条数
+    async fn stats(&self) -> Result<QueueStats, PushError>;
+
+    /// 等待新条目入队，最长等待 `timeout`
+    async fn wait(&self, timeout: Duration);
+}
+
+/// 队列统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueueStats {
+    /// 按通道统计的待投递条数
+    pub pending: BTreeMap<String, usize>,
+    /// 死信条数
+    pub dead_letters: usize,
+}
+
+/// `now` 之后 `duration` 的时间点
+pub fn after(now: DateTime<Utc>, duration: Duration) -> DateTime<Utc> {
+    now + chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX)
+}
+
+/// 进程内队列，重启后丢失
+#[derive(Default)]
+pub struct MemoryQueue {
+    items: Mutex<HashMap<String, QueueItem>>,
+    notify: Notify,
+}
+
+#[async_trait]
+impl Queue for MemoryQueue {
+    async fn enqueue(&self, item: &QueueItem) -> Result<(), PushError> {
+        self.items
+            .lock()
+            .unwrap()
+            .insert(item.id.clone(), item.clone());
+        self.notify.notify_waiters();
+        Ok(())
+    }
+
+    async fn claim(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<QueueItem>, PushError> {
+        let mut items = self.items.lock().unwrap();
+        let mut due: Vec<&mut QueueItem> = items
+            .values_mut()
+            .filter(|item| {
+                item.failed_at.is_none() && item.leased_until.is_none() && item.available_at <= now
+            })
+            .collect();
+        due.sort_by_key(|item| item.available_at);
+        Ok(due
+            .into_iter()
+            .take(limit)
+            .map(|item| {
+                item.attempts += 1;
+                item.leased_until = Some(after(now, lease));
+                item.clone()
+            })
+            .collect())
+    }
+
+    async fn complete(&self, id: &str) -> Result<(), PushError> {
+        self.items.lock().unwrap().remove(id);
+        Ok(())
+    }
+
+    async fn retry(&self, id: &str, available_at: DateTime<Utc>) -> Result<(), PushError> {
+        if let Some(item) = self.items.lock().unwrap().get_mut(id) {
+            item.available_at = available_at;
+            item.leased_until = None;
+        }
+        Ok(())
+    }
+
+    async fn reclaim(&self, now: DateTime<Utc>) -> Result<usize, PushError> {
+        let mut count = 0;
+        for item in self.items.lock().unwrap().values_mut() {
+            if item.leased_until.is_some_and(|until| until <= now) {
+                item.leased_until = None;
+                item.available_at = now;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    async fn bury(&self, id: &str, error: &str) -> Result<(), PushError> {
+        if let Some(item) = self.items.lock().unwrap().get_mut(id) {
+            item.failed_at = Some(Utc::now());
+            item.last_error = Some(error.to_string());
+            item.leased_until = None;
+        }
+        Ok(())
+    }
+
+    async fn stats(&self) -> Result<QueueStats, PushError> {
+        let mut stats = QueueStats::default();
+        for item in self.items.lock().unwrap().values() {
+            if item.failed_at.is_some() {
+                stats.dead_letters += 1;
+            } else {
+                *stats.pending.entry(item.channel.clone()).or_default() += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn wait(&self, timeout: Duration) {
+        let _ = tokio::time::timeout(timeout, self.notify.notified()).await;
+    }
+}
+
+/// 领取并投递一批到期的条目，返回领取的条数
+pub async fn process(
+    dispatcher: &Dispatcher,
+    queue: &dyn Queue,
+    config: &QueueConfig,
+    cluster: &Cluster,
+    now: DateTime<Utc>,
+) -> Result<usize, PushError> {
+    let items = queue.claim(now, BATCH_SIZE, config.lease()).await?;
+    let count = items.len();
+    for item in items {
+        let key = format!("queue:{}", item.id);
+        if !cluster.lock(&key, config.lease()).await {
+            // 租约过期后被重新领取，但之前的副本仍在投递
+            debug!("[{}] Queued message is being delivered elsewhere", item.id);
+            continue;
+        }
+        let result = deliver(dispatcher, queue, config, item).await;
+        cluster.unlock(&key).await;
+        result?;
+    }
+    Ok(count)
+}
+
+/// 投递单个条目，按结果移出队列或放回重试
+async fn deliver(
+    dispatcher: &Dispatcher,
+    queue: &dyn Queue,
+    config: &QueueConfig,
+    item: QueueItem,
+) -> Result<(), PushError> {
+    match dispatcher.deliver(&item.channel, item.message).await {
+        Ok(_) => {
+            info!(
+                "[{}] Delivered queued message after {} attempt(s)",
+                item.id, item.attempts
+            );
+            queue.complete(&item.id).await?;
+        }
+        Err(e) if e.is_retryable() && item.attempts < config.max_attempts => {
+            let backoff = e
+                .retry_after()
+                .unwrap_or_else(|| config.backoff(item.attempts));
+            warn!(
+                "[{}] Attempt {} failed, retrying in {:?}: {}",
+                item.id, item.attempts, backoff, e
+            );
+            queue.retry(&item.id, after(Utc::now(), backoff)).await?;
+        }
+        Err(e) => {
+            error!(
+                "[{}] Giving up on queued message after {} attempt(s): {}",
+                item.id, item.attempts, e
+            );
+            queue.bury(&item.id, &e.to_string()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 放回租约到期的投递中条目，即投递期间崩溃或超时的 worker 留下的条目
+pub async fn sweep(queue: &dyn Queue, now: DateTime<Utc>) -> Result<usize, PushError> {
+    let count = queue.reclaim(now).await?;
+    if count > 0 {
+        warn!("Reclaimed {} stuck in-flight queue item(s)", count);
+    }
+    Ok(count)
+}
+
+/// 启动队列 worker，有新条目时立即处理，否则按间隔检查；启动时和之后定期清扫投递中的条目
+pub fn spawn_worker(
+    dispatcher: Arc<Dispatcher>,
+    queue: Arc<dyn Queue>,
+    config: QueueConfig,
+    cluster: Cluster,
+) {
+    tokio::spawn(async move {
+        let mut swept: Option<Instant> = None;
+        loop {
+            if swept.is_none_or(|swept| swept.elapsed() >= config.sweep_interval()) {
+                if let Err(e) = sweep(queue.as_ref(), Utc::now()).await {
+                    error!("Failed to reclaim in-flight queue items: {}", e);
+                }
+                swept = Some(Instant::now());
+            }
+            match process(&dispatcher, queue.as_ref(), &config, &cluster, Utc::now()).await {
+                Ok(0) => queue.wait(config.poll_interval()).await,
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Failed to process delivery queue: {}", e);
+                    tokio::time::sleep(config.poll_interval()).await;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use common::MessageType;
+
+    /// 各后端共用的队列检查
+    pub async fn exercise(queue: &dyn Queue) {
+        let now = Utc::now();
+        let lease = Duration::from_secs(60);
+        let message = Message::new(MessageType::Text("disk full".to_string()));
+        let item = QueueItem::new("r1", "ops", message.clone(), now);
+        assert_eq!(item.id, "r1:ops");
+        assert_eq!(
+            QueueItem::accepted("r1", "ops", message.clone()).attempts,
+            0
+        );
+        queue.enqueue(&item).await.unwrap();
+        queue
+            .enqueue(&QueueItem::new(
+                "r2",
+                "ops",
+                message,
+                now + chrono::Duration::minutes(5),
+            ))
+            .await
+            .unwrap();
+
+        let claimed = queue.claim(now, 10, lease).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, "r1:ops");
+        assert_eq!(claimed[0].attempts, 2);
+        assert!(matches!(&claimed[0].message.content, MessageType::Text(t) if t == "disk full"));
+        // 租约期内不会被再次领取
+        assert!(queue.claim(now, 10, lease).await.unwrap().is_empty());
+
+        // 租约未到期时不清扫
+        assert_eq!(queue.reclaim(now).await.unwrap(), 0);
+        let expired = now + chrono::Duration::minutes(2);
+        assert_eq!(queue.reclaim(expired).await.unwrap(), 1);
+        let claimed = queue.claim(expired, 10, lease).await.unwrap();
+        assert_eq!(claimed[0].attempts, 3);
+
+        queue.retry("r1:ops", now).await.unwrap();
+        let claimed = queue.claim(now, 10, lease).await.unwrap();
+        assert_eq!(claimed[0].attempts, 4);
+        assert!(claimed[0].leased_until.is_some());
+        assert_eq!(
+            queue.stats().await.unwrap().pending,
+            BTreeMap::from([("ops".to_string(), 2)])
+        );
+        queue.complete("r1:ops").await.unwrap();
+
+        let later = now + chrono::Duration::minutes(10);
+        let claimed = queue.claim(later, 10, lease).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, "r2:ops");
+        queue
+            .bury("r2:ops", "Network error: refused")
+            .await
+            .unwrap();
+        let stats = queue.stats().await.unwrap();
+        assert!(stats.pending.is_empty());
+        assert_eq!(stats.dead_letters, 1);
+        // 死信不再领取，也不会被清扫放回
+        let much_later = later + chrono::Duration::hours(1);
+        assert_eq!(queue.reclaim(much_later).await.unwrap(), 0);
+        assert!(queue.claim(much_later, 10, lease).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_queue() {
+        exercise(&MemoryQueue::default()).await;
+
+        let queue = Arc::new(MemoryQueue::default());
+        let waiter = tokio::spawn({
+            let queue = queue.clone();
+            async move { queue.wait(Duration::from_secs(5)).await }
+        });
+        tokio::task::yield_now().await;
+        let item = QueueItem::new(
+            "r3",
+            "ops",
+            Message::new(MessageType::Text("wake".to_string())),
+            Utc::now(),
+        );
+        queue.enqueue(&item).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = QueueConfig::default();
+        assert_eq!(config.backoff(1), Duration::from_secs(30));
+        assert_eq!(config.backoff(3), Duration::from_secs(120));
+        assert_eq!(config.backoff(30), MAX_BACKOFF);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::dispatch::Dispatcher;
+use chrono::{DateTime, Utc};
+use common::{
+    AttachmentSource, BuiltinText, Message, MessageKind, MessageType, PlatformInfo, Priority,
+    PushError, PushResult, degrade, render_template, strip_markdown,
+};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 检查静默时段是否结束的间隔
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 内置的 HTML 摘要布局，适配邮件客户端：表格布局、内联样式、最大宽度 600px
+pub const DIGEST_HTML_TEMPLATE: &str = include_str!("digest.html");
+
+/// 静默时段内暂存的消息，按通道分组
+#[derive(Default)]
+pub struct HeldMessages {
+    channels: Mutex<HashMap<String, Held>>,
+}
+
+/// 一个通道暂存的消息
+#[derive(Default)]
+pub struct Held {
+    /// 第一条消息暂存的时间
+    pub since: Option<DateTime<Utc>>,
+    /// 暂存的消息，按到达顺序
+    pub messages: VecDeque<Message>,
+    /// 超出上限被丢弃的消息数
+    pub dropped: usize,
+}
+
+impl HeldMessages {
+    /// 暂存消息，超出 `max_held` 时丢弃最早的消息
+    pub fn hold(&self, channel: &str, message: Message, max_held: usize) -> PushResult {
+        let mut channels = self.channels.lock().unwrap();
+        let held = channels.entry(channel.to_string()).or_default();
+        held.since.get_or_insert_with(Utc::now);
+        held.messages.push_back(message);
+        while held.messages.len() > max_held.max(1) {
+            held.messages.pop_front();
+            held.dropped += 1;
+        }
+        PushResult {
+            success: true,
+            response: Some("Held for quiet hours digest".to_string()),
+            channel: Some(channel.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// 取出满足条件的通道暂存的全部消息
+    pub fn take(&self, ready: impl Fn(&str) -> bool) -> Vec<(String, Held)> {
+        let mut channels = self.channels.lock().unwrap();
+        let names: Vec<String> = channels
+            .keys()
+            .filter(|name| ready(name))
+            .cloned()
+            .collect();
+        names
+            .into_iter()
+            .filter_map(|name| channels.remove_entry(&name))
+            .collect()
+    }
+}
+
+/// 启动摘要任务，定期将静默时段已结束的通道的暂存消息合并发送
+pub fn spawn_digest_task(dispatcher: Arc<Dispatcher>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            dispatcher.flush_digests(Utc::now()).await;
+        }
+    });
+}
+
+/// 将暂存的消息合并为一条 Markdown 摘要，标题等文字使用 `locale` 语言
+pub fn digest(held: &Held, locale: Option<&str>) -> Message {
+    let count = held.messages.len() + held.dropped;
+    let mut lines = vec![BuiltinText::DigestHeader.localize(locale, &[("count", &count)])];
+    if let Some(since) = held.since {
+        let since = since.format("%Y-%m-%d %H:%M UTC");
+        lines[0].push_str(&BuiltinText::DigestSince.localize(locale, &[("since", &since)]));
+    }
+    for message in &held.messages {
+        lines.push(format!("- {}", summary(message).replace('\n', "\n  ")));
+    }
+    if held.dropped > 0 {
+        lines.push(BuiltinText::DigestOmitted.localize(locale, &[("count", &held.dropped)]));
+    }
+    let message = Message::new(MessageType::Markdown(lines.join("\n")));
+    match locale {
+        Some(locale) => message.with_locale(locale),
+        None => message,
+    }
+}
+
+/// HTML 摘要模板的上下文
+#[derive(Serialize)]
+struct HtmlDigest {
+    lang: String,
+    title: String,
+    since: Option<String>,
+    items: Vec<HtmlDigestItem>,
+    omitted: Option<String>,
+}
+
+#[derive(Serialize)]
+struct HtmlDigestItem {
+    priority: String,
+    color: &'static str,
+    title: String,
+    details: Vec<String>,
+}
+
+/// 将暂存的消息合并为一条 HTML 摘要，用于支持 HTML 的目标（如邮件）
+///
+/// 内置平台都不声明 [`MessageKind::Html`](common::MessageKind::Html)，
+/// 需要注册支持 HTML 的第三方平台（如通过插件接入的邮件平台）才会用到
+///
+/// `template` 为 Jinja 语法的布局，为空时使用 [`DIGEST_HTML_TEMPLATE`]；
+/// 模板中可用 `lang`、`title`、`since`、`omitted` 和 `items`，
+/// 每项包含 `priority`、`color`、`title` 和 `details`（按行拆分的正文）
+pub fn html_digest(
+    held: &Held,
+    locale: Option<&str>,
+    template: Option<&str>,
+) -> Result<Message, PushError> {
+    let count = held.messages.len() + held.dropped;
+    let since = held.since.map(|since| {
+        let since = since.format("%Y-%m-%d %H:%M UTC");
+        BuiltinText::DigestSince.localize(locale, &[("since", &since)])
+    });
+    let items = held
+        .messages
+        .iter()
+        .map(|message| {
+            let summary = summary(message);
+            let mut lines = summary.lines().map(str::to_string);
+            HtmlDigestItem {
+                priority: format!("{:?}", message.priority),
+                color: priority_color(message.priority),
+                title: lines.next().unwrap_or_default(),
+                details: lines.filter(|line| !line.trim().is_empty()).collect(),
+            }
+        })
+        .collect();
+    let omitted = (held.dropped > 0).then(|| {
+        let text = BuiltinText::DigestOmitted.localize(locale, &[("count", &held.dropped)]);
+        text.trim_start_matches("- ").to_string()
+    });
+    let context = HtmlDigest {
+        lang: locale.unwrap_or("en").to_string(),
+        title: strip_markdown(&BuiltinText::DigestHeader.localize(locale, &[("count", &count)])),
+        since: since.map(|since| since.trim().to_string()),
+        items,
+        omitted,
+    };
+    let html = render_template(template.unwrap_or(DIGEST_HTML_TEMPLATE), &context)?;
+    let message = Message::new(MessageType::Html(html));
+    Ok(match locale {
+        Some(locale) => message.with_locale(locale),
+        None => message,
+    })
+}
+
+/// 优先级在 HTML 摘要中的标识色
+fn priority_color(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "#9ca3af",
+        Priority::Normal => "#2563eb",
+        Priority::High => "#d97706",
+        Priority::Urgent => "#dc2626",
+    }
+}
+
+/// 单条消息在摘要中的文字
+fn summary(message: &Message) -> String {
+    // 只支持 Markdown 的目标，富文本、卡片等都会降级为 Markdown
+    let target = PlatformInfo {
+        name: "digest".to_string(),
+        version: String::new(),
+        message_types: [MessageKind::Text, MessageKind::Markdown].into(),
+        mentions: Default::default(),
+        features: Vec::new(),
+        limits: Default::default(),
+        rate_limit: None,
+        markdown_dialect: Default::default(),
+    };
+    match degrade(message.content.clone(), &target) {
+        MessageType::Text(text) => text,
+        MessageType::Markdown(markdown) => strip_markdown(&markdown),
+        MessageType::File { name, source, .. } => match source {
+            AttachmentSource::Url(url) => format!("File: {} ({})", name, url),
+            _ => format!("File: {}", name),
+        },
+        MessageType::Template { name, .. } => format!("Template: {}", name),
+        _ => "(unsupported message)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_and_digest() {
+        let held = HeldMessages::default();
+        for text in ["backup done", "disk 80%", "cert renewed"] {
+            let result = held.hold("ops", MessageType::Text(text.to_string()).into(), 2);
+            assert!(result.success);
+        }
+        assert!(held.take(|channel| channel == "dev").is_empty());
+
+        let mut taken = held.take(|_| true);
+        assert_eq!(taken.len(), 1);
+        let (channel, ops) = taken.remove(0);
+        assert_eq!(channel, "ops");
+        assert_eq!(ops.dropped, 1);
+        let MessageType::Markdown(zh) = digest(&ops, Some("zh-CN")).content else {
+            unreachable!()
+        };
+        assert!(zh.starts_with("**静默时段摘要**：共暂存 3 条消息（自 "));
+        assert!(zh.ends_with("\n- ……另有 1 条较早的消息已省略"));
+        let MessageType::Markdown(digest) = digest(&ops, None).content else {
+            unreachable!()
+        };
+        assert!(digest.starts_with("**Quiet hours digest**: 3 message(s) held since "));
+        assert!(
+            digest.ends_with("\n- disk 80%\n- cert renewed\n- ...and 1 earlier message(s) omitted")
+        );
+        assert!(held.take(|_| true).is_empty());
+    }
+
+    #[test]
+    fn test_html_digest() {
+        let held = HeldMessages::default();
+        held.hold("mail", MessageType::Text("oldest".to_string()).into(), 2);
+        let mut urgent = Message::new(MessageType::Markdown(
+            "**DB <primary> down**\nreplica lag 30s".to_string(),
+        ));
+        urgent.priority = Priority::Urgent;
+        held.hold("mail", urgent, 2);
+        held.hold(
+            "mail",
+            MessageType::Text("backup done".to_string()).into(),
+            2,
+        );
+        let (_, mail) = held.take(|_| true).remove(0);
+
+        let MessageType::Html(html) = html_digest(&mail, None, None).unwrap().content else {
+            unreachable!()
+        };
+        assert!(html.contains("Quiet hours digest: 3 message(s) held"));
+        assert!(html.contains(">Urgent</div>"));
+        assert!(html.contains(">replica lag 30s</div>"));
+        assert!(html.contains(">backup done</div>"));
+        assert!(html.contains("...and 1 earlier message(s) omitted"));
+        assert!(html.contains("DB &lt;primary&gt; down"));
+        assert!(!html.contains("<primary>"));
+
+        // 自定义模板，内容中的 HTML 被转义
+        let held = HeldMessages::default();
+        held.hold(
+            "mail",
+            MessageType::Text("<b>cert</b> renewed".to_string()).into(),
+            10,
+        );
+        let (_, mail) = held.take(|_| true).remove(0);
+        let template =
+            "{% for item in items %}<li>{{ item.priority }}: {{ item.title | e }}</li>{% endfor %}";
+        let message = html_digest(&mail, Some("zh-CN"), Some(template)).unwrap();
+        assert_eq!(message.locale(), Some("zh-CN"));
+        let MessageType::Html(html) = message.content else {
+            unreachable!()
+        };
+        assert_eq!(
+            html,
+            "<li>Normal: &lt;b&gt;cert&lt;&#x2f;b&gt; renewed</li>"
+        );
+    }
+}
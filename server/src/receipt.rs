@@ -0,0 +1,247 @@
+use crate::dispatch::Dispatcher;
+use chrono::{DateTime, Utc};
+use common::{DeliveryStatus, PlatformInfo, PushResult, RECEIPT_FEATURE, Receipt};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// 事件缓冲区大小，订阅方跟不上时丢弃最早的事件
+const EVENT_BUFFER: usize = 256;
+
+/// 投递回执配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReceiptConfig {
+    /// 查询支持回执的通道的间隔（秒），0 表示只接收 `POST /receipts/{platform}` 推送的回执
+    pub poll_interval_secs: u64,
+}
+
+impl Default for ReceiptConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 60,
+        }
+    }
+}
+
+/// 投递状态变化事件，通过 `GET /events` 推送给订阅方
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceiptEvent {
+    /// 推送时的请求 ID
+    pub request_id: String,
+    /// 投递的通道或平台
+    pub target: String,
+    /// 推送时消息的标签
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+    pub message_id: String,
+    pub status: DeliveryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+struct Tracked {
+    request_id: String,
+    target: String,
+    labels: HashMap<String, String>,
+    status: DeliveryStatus,
+    sent_at: Instant,
+}
+
+/// 等待回执的消息，按平台名称和平台消息 ID 索引
+pub struct ReceiptTracker {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, String), Tracked>>,
+    events: broadcast::Sender<ReceiptEvent>,
+}
+
+impl ReceiptTracker {
+    /// 创建跟踪器，超过 `ttl` 的消息不再接收回执
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+            events: broadcast::channel(EVENT_BUFFER).0,
+        }
+    }
+
+    /// 记录发送结果，平台不支持回执或没有返回消息 ID 时忽略
+    pub fn track(
+        &self,
+        request_id: &str,
+        target: &str,
+        labels: &HashMap<String, String>,
+        info: &PlatformInfo,
+        result: &PushResult,
+    ) {
+        if !info.has_feature(RECEIPT_FEATURE) {
+            return;
+        }
+        let Some(message_id) = result.message_id.clone() else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, tracked| now.duration_since(tracked.sent_at) < self.ttl);
+        entries.insert(
+            (info.name.clone(), message_id),
+            Tracked {
+                request_id: request_id.to_string(),
+                target: target.to_string(),
+                labels: labels.clone(),
+                status: DeliveryStatus::Sent,
+                sent_at: now,
+            },
+        );
+    }
+
+    /// 更新消息状态并通知订阅方；消息未跟踪、已过期或已是最终状态时返回 `None`
+    pub fn apply(&self, platform: &str, receipt: Receipt) -> Option<ReceiptEvent> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = (platform.to_string(), receipt.message_id.clone());
+        let tracked = entries
+            .get_mut(&key)
+            .filter(|tracked| tracked.sent_at.elapsed() < self.ttl)?;
+        if tracked.status.is_final() || tracked.status == receipt.status {
+            return None;
+        }
+        tracked.status = receipt.status;
+        let event = ReceiptEvent {
+            request_id: tracked.request_id.clone(),
+            target: tracked.target.clone(),
+            labels: tracked.labels.clone(),
+            message_id: receipt.message_id,
+            status: receipt.status,
+            reason: receipt.reason,
+            timestamp: receipt.timestamp,
+        };
+        // 没有订阅方时发送失败，忽略即可
+        let _ = self.events.send(event.clone());
+        Some(event)
+    }
+
+    /// 该平台在通道上仍在等待回执的消息 ID，供平台按消息逐条查询
+    pub fn pending(&self, platform: &str, target: &str) -> Vec<String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((name, _), tracked)| {
+                name == platform
+                    && tracked.target == target
+                    && !tracked.status.is_final()
+                    && tracked.sent_at.elapsed() < self.ttl
+            })
+            .map(|((_, message_id), _)| message_id.clone())
+            .collect()
+    }
+
+    /// 请求 ID 对应的各通道投递状态
+    pub fn statuses(&self, request_id: &str) -> BTreeMap<String, DeliveryStatus> {
+        self.entries
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|tracked| tracked.request_id == request_id)
+            .map(|tracked| (tracked.target.clone(), tracked.status))
+            .collect()
+    }
+
+    /// 订阅状态变化事件
+    pub fn subscribe(&self) -> broadcast::Receiver<ReceiptEvent> {
+        self.events.subscribe()
+    }
+}
+
+/// 启动回执查询任务，定期查询支持回执的通道
+pub fn spawn_poller(dispatcher: Arc<Dispatcher>, config: &ReceiptConfig) {
+    if config.poll_interval_secs == 0 {
+        return;
+    }
+    let interval = Duration::from_secs(config.poll_interval_secs);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let applied = dispatcher.poll_receipts().await;
+            if applied > 0 {
+                debug!("Applied {} delivery receipt(s)", applied);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MessageKind;
+
+    fn info(features: &[&str]) -> PlatformInfo {
+        PlatformInfo {
+            name: "email".to_string(),
+            version: String::new(),
+            message_types: [MessageKind::Text].into(),
+            mentions: Default::default(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            limits: Default::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let tracker = ReceiptTracker::new(Duration::from_secs(60));
+        let result = |id: &str| PushResult {
+            message_id: Some(id.to_string()),
+            success: true,
+            ..Default::default()
+        };
+        let labels = HashMap::from([("service".to_string(), "billing".to_string())]);
+        let receipts = info(&[RECEIPT_FEATURE]);
+        tracker.track("r1", "ops", &labels, &receipts, &result("m1"));
+        tracker.track("r1", "dev", &labels, &info(&["text"]), &result("m2"));
+        assert_eq!(
+            tracker.statuses("r1"),
+            BTreeMap::from([("ops".to_string(), DeliveryStatus::Sent)])
+        );
+
+        let mut events = tracker.subscribe();
+        let bounced = Receipt::new("m1", DeliveryStatus::Bounced).with_reason("mailbox full");
+        let event = tracker.apply("email", bounced).unwrap();
+        assert_eq!(event.request_id, "r1");
+        assert_eq!(event.target, "ops");
+        // 事件带上推送时的标签，订阅方可按标签分析投递结果
+        assert_eq!(event.labels, labels);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["labels"]["service"],
+            "billing"
+        );
+        assert_eq!(
+            events.try_recv().unwrap().reason.as_deref(),
+            Some("mailbox full")
+        );
+
+        // 最终状态不再被覆盖，其他平台的同名 ID 不受影响
+        assert!(
+            tracker
+                .apply("email", Receipt::new("m1", DeliveryStatus::Delivered))
+                .is_none()
+        );
+        assert!(
+            tracker
+                .apply("telegram", Receipt::new("m1", DeliveryStatus::Read))
+                .is_none()
+        );
+        assert!(
+            tracker
+                .apply("email", Receipt::new("m2", DeliveryStatus::Read))
+                .is_none()
+        );
+        assert_eq!(tracker.statuses("r1")["ops"], DeliveryStatus::Bounced);
+    }
+}
@@ -0,0 +1,133 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest};
+use common::{Message, REQUEST_ID_KEY};
+use std::future::{Ready, ready};
+
+/// 请求 ID 头
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 调用方提供的请求 ID 的最大长度，超出或包含非法字符时重新生成
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// 当前请求的 ID，由 [`middleware`] 放入请求扩展
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// 当前请求的 ID，未经过中间件时生成新的 ID
+    pub fn of(req: &HttpRequest) -> Self {
+        req.extensions()
+            .get::<RequestId>()
+            .cloned()
+            .unwrap_or_else(|| RequestId(generate()))
+    }
+}
+
+impl FromRequest for RequestId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(Self::of(req)))
+    }
+}
+
+/// 读取或生成请求 ID，并在响应头中返回
+pub async fn middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| is_valid(id))
+        .map(str::to_string)
+        .unwrap_or_else(generate);
+    req.extensions_mut().insert(RequestId(id.clone()));
+    let mut res = next.call(req).await?;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    Ok(res)
+}
+
+/// 调用方在请求头中提供的合法请求 ID，用于幂等去重
+pub fn supplied(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|id| is_valid(id))
+}
+
+/// 生成新的请求 ID
+pub fn generate() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 返回消息的请求 ID，没有时生成并写入元数据，重试和降级时随消息一起传递
+pub fn ensure(message: &mut Message) -> String {
+    message
+        .metadata
+        .entry(REQUEST_ID_KEY.to_string())
+        .or_insert_with(generate)
+        .clone()
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::test::{TestRequest, call_service, init_service, read_body};
+    use actix_web::{App, HttpResponse, web};
+    use common::MessageType;
+
+    #[actix_web::test]
+    async fn test_middleware() {
+        let app = init_service(App::new().wrap(from_fn(middleware)).route(
+            "/",
+            web::get().to(|id: RequestId| async move { HttpResponse::Ok().body(id.0) }),
+        ))
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/")
+            .insert_header((REQUEST_ID_HEADER, "abc-123"))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.headers().get(REQUEST_ID_HEADER).unwrap(), "abc-123");
+        assert_eq!(read_body(res).await, "abc-123");
+
+        let req = TestRequest::get()
+            .uri("/")
+            .insert_header((REQUEST_ID_HEADER, "bad id\t"))
+            .to_request();
+        let res = call_service(&app, req).await;
+        let id = res
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(id.len(), 36);
+    }
+
+    #[test]
+    fn test_ensure() {
+        let mut message = Message::new(MessageType::Text("hi".to_string()));
+        let id = ensure(&mut message);
+        assert_eq!(message.request_id(), Some(id.as_str()));
+        assert_eq!(ensure(&mut message), id);
+    }
+}
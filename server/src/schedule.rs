@@ -0,0 +1,42 @@
+use crate::dispatch::Dispatcher;
+use crate::lock::Cluster;
+use chrono::Utc;
+use log::*;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 检查定时消息是否到期的间隔
+const TICK: Duration = Duration::from_secs(10);
+
+/// 清理过期去重记录的间隔
+const DEDUP_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 启动定时任务，定期发送到期的定时消息并清理过期的去重记录；多个副本中只有选举出的一个执行
+pub fn spawn_scheduler(dispatcher: Arc<Dispatcher>, cluster: Cluster) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        let mut leading = false;
+        let mut purged: Option<Instant> = None;
+        loop {
+            interval.tick().await;
+            let lead = cluster.lead("scheduler").await;
+            if lead != leading {
+                info!(
+                    "{} scheduler leadership",
+                    if lead { "Acquired" } else { "Lost" }
+                );
+                leading = lead;
+            }
+            if !lead {
+                continue;
+            }
+            if let Err(e) = dispatcher.send_due(Utc::now()).await {
+                error!("Failed to load scheduled messages: {}", e);
+            }
+            if purged.is_none_or(|purged| purged.elapsed() >= DEDUP_PURGE_INTERVAL) {
+                dispatcher.purge_dedup().await;
+                purged = Some(Instant::now());
+            }
+        }
+    });
+}
@@ -0,0 +1,125 @@
+use common::{DELETE_FEATURE, EDIT_FEATURE, PlatformInfo, PushResult};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 消息的投递目标，编辑或撤回时据此重新获取平台实例
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// 配置文件中的通道
+    Channel(String),
+    /// 直接推送时指定的平台和配置
+    Platform { platform: String, config: Value },
+}
+
+impl Target {
+    /// 在投递结果中使用的名称
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Channel(channel) => channel,
+            Self::Platform { platform, .. } => platform,
+        }
+    }
+}
+
+/// 一次投递在平台上的消息
+#[derive(Debug, Clone)]
+pub struct Sent {
+    pub target: Target,
+    /// 平台返回的消息 ID
+    pub message_id: String,
+}
+
+/// 已发送、可编辑或撤回的消息，按请求 ID 索引
+pub struct SentMessages {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Vec<Sent>)>>,
+}
+
+impl SentMessages {
+    /// 创建记录，超过 `ttl` 的消息不再能编辑或撤回
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// 记录发送结果，平台不支持编辑和撤回或没有返回消息 ID 时忽略
+    pub fn record(&self, id: &str, target: Target, info: &PlatformInfo, result: &PushResult) {
+        let supported = info.has_feature(EDIT_FEATURE) || info.has_feature(DELETE_FEATURE);
+        let Some(message_id) = result.message_id.clone().filter(|_| supported) else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, (sent_at, _)| now.duration_since(*sent_at) < self.ttl);
+        entries
+            .entry(id.to_string())
+            .or_insert_with(|| (now, Vec::new()))
+            .1
+            .push(Sent { target, message_id });
+    }
+
+    /// 请求 ID 对应的全部消息
+    pub fn get(&self, id: &str) -> Option<Vec<Sent>> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(id)
+            .filter(|(sent_at, _)| sent_at.elapsed() < self.ttl)
+            .map(|(_, sent)| sent.clone())
+    }
+
+    /// 删除记录，消息撤回后调用
+    pub fn remove(&self, id: &str) {
+        self.entries.lock().unwrap().remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MessageKind;
+
+    fn info(features: &[&str]) -> PlatformInfo {
+        PlatformInfo {
+            name: "slack".to_string(),
+            version: String::new(),
+            message_types: [MessageKind::Text, MessageKind::Markdown].into(),
+            mentions: Default::default(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            limits: Default::default(),
+            rate_limit: None,
+            markdown_dialect: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let sent = SentMessages::new(Duration::from_secs(60));
+        let result = |id: Option<&str>| PushResult {
+            message_id: id.map(str::to_string),
+            success: true,
+            ..Default::default()
+        };
+        let ops = Target::Channel("ops".to_string());
+        sent.record("r1", ops.clone(), &info(&["text"]), &result(Some("1")));
+        sent.record("r1", ops.clone(), &info(&[EDIT_FEATURE]), &result(None));
+        assert!(sent.get("r1").is_none());
+
+        sent.record("r1", ops, &info(&[EDIT_FEATURE]), &result(Some("1.001")));
+        let direct = Target::Platform {
+            platform: "telegram".to_string(),
+            config: serde_json::json!({"chat_id": 1}),
+        };
+        sent.record("r1", direct, &info(&[DELETE_FEATURE]), &result(Some("42")));
+        let messages = sent.get("r1").unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].message_id, "1.001");
+        assert_eq!(messages[1].target.name(), "telegram");
+
+        sent.remove("r1");
+        assert!(sent.get("r1").is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use common::{Message, PushResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// 静默规则，标签全部匹配的消息在到期前不发送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Silence {
+    pub id: String,
+    /// 需要匹配的标签，消息的 `labels` 或 `metadata` 中的值相同即匹配
+    pub matchers: BTreeMap<String, String>,
+    /// 到期时间
+    pub until: DateTime<Utc>,
+    /// 创建者
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+}
+
+impl Silence {
+    /// 消息是否匹配全部标签
+    pub fn matches(&self, message: &Message) -> bool {
+        self.matchers.iter().all(|(key, value)| {
+            message
+                .labels
+                .get(key)
+                .or_else(|| message.metadata.get(key))
+                == Some(value)
+        })
+    }
+
+    /// 标签的 `key=value` 形式
+    pub fn describe(&self) -> String {
+        self.matchers
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// 生效中的静默规则，由聊天平台的 `/silence` 命令创建
+#[derive(Default)]
+pub struct Silences {
+    entries: Mutex<Vec<Silence>>,
+}
+
+impl Silences {
+    /// 添加静默规则
+    pub fn add(
+        &self,
+        matchers: BTreeMap<String, String>,
+        until: DateTime<Utc>,
+        created_by: Option<String>,
+    ) -> Silence {
+        let silence = Silence {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            matchers,
+            until,
+            created_by,
+        };
+        self.insert(silence.clone());
+        silence
+    }
+
+    /// 加入已有的静默规则，如启动时从存储恢复的规则，相同 ID 时覆盖
+    pub fn insert(&self, silence: Silence) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.id != silence.id);
+        entries.push(silence);
+    }
+
+    /// 删除静默规则，返回是否存在
+    pub fn remove(&self, id: &str) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        let len = entries.len();
+        entries.retain(|silence| silence.id != id);
+        entries.len() != len
+    }
+
+    /// 匹配消息的静默规则，同时清理已到期的规则
+    pub fn find(&self, message: &Message, now: DateTime<Utc>) -> Option<Silence> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|silence| silence.until > now);
+        entries
+            .iter()
+            .find(|silence| silence.matches(message))
+            .cloned()
+    }
+}
+
+/// 被静默的消息在推送结果中的表示
+pub fn silenced(channel: &str, silence: &Silence) -> PushResult {
+    PushResult {
+        success: true,
+        response: Some(format!(
+            "Silenced by {} until {}",
+            silence.id,
+            silence.until.to_rfc3339()
+        )),
+        channel: Some(channel.to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use common::MessageType;
+
+    #[test]
+    fn test_silence() {
+        let silences = Silences::default();
+        let now = Utc::now();
+        let silence = silences.add(
+            BTreeMap::from([("service".to_string(), "db".to_string())]),
+            now + Duration::hours(2),
+            Some("alice".to_string()),
+        );
+        assert_eq!(silence.describe(), "service=db");
+
+        let db = Message::new(MessageType::Text("db down".to_string())).with_label("service", "db");
+        let web =
+            Message::new(MessageType::Text("web down".to_string())).with_metadata("service", "web");
+        assert_eq!(silences.find(&db, now).unwrap().id, silence.id);
+        assert!(silences.find(&web, now).is_none());
+        // 到期后自动清理
+        assert!(silences.find(&db, now + Duration::hours(3)).is_none());
+        assert!(!silences.remove(&silence.id));
+    }
+}
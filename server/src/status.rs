@@ -0,0 +1,362 @@
+use actix_web::{HttpResponse, Responder, get, web};
+use chrono::{DateTime, Utc};
+use common::{MessageType, PushError, PushResult, html_to_markdown};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// 状态页配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPageConfig {
+    /// 页面标题
+    #[serde(default = "default_title")]
+    pub title: String,
+    /// 保留的事件数量上限
+    #[serde(default = "default_max_incidents")]
+    pub max_incidents: usize,
+}
+
+fn default_title() -> String {
+    "Service Status".to_string()
+}
+
+fn default_max_incidents() -> usize {
+    50
+}
+
+/// 事件状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentStatus {
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+impl IncidentStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Investigating => "Investigating",
+            Self::Identified => "Identified",
+            Self::Monitoring => "Monitoring",
+            Self::Resolved => "Resolved",
+        }
+    }
+}
+
+/// 推送请求中携带的事件标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentUpdate {
+    /// 事件 ID，同一 ID 的推送归为同一事件
+    pub id: String,
+    /// 事件标题，为空时沿用之前的标题或使用消息摘要
+    pub title: Option<String>,
+    /// 当前状态
+    pub status: IncidentStatus,
+    /// 受影响的组件
+    pub component: Option<String>,
+}
+
+/// 状态页中的事件
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    pub id: String,
+    pub title: String,
+    pub component: Option<String>,
+    pub status: IncidentStatus,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub updates: Vec<IncidentEntry>,
+}
+
+/// 事件的一次更新
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentEntry {
+    pub status: IncidentStatus,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 状态页 JSON 结构
+#[derive(Debug, Serialize)]
+struct StatusSummary {
+    title: String,
+    operational: bool,
+    incidents: Vec<Incident>,
+}
+
+/// 由推送历史聚合的状态页
+pub struct StatusPage {
+    config: StatusPageConfig,
+    incidents: Mutex<VecDeque<Incident>>,
+}
+
+impl StatusPage {
+    /// 创建状态页
+    pub fn new(config: StatusPageConfig) -> Self {
+        Self {
+            config,
+            incidents: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 推送成功时记录事件更新，发送失败或平台未确认成功的推送不影响状态页
+    pub fn record_delivered(
+        &self,
+        update: &IncidentUpdate,
+        message: &MessageType,
+        result: &Result<PushResult, PushError>,
+    ) {
+        if matches!(result, Ok(result) if result.success) {
+            self.record(update, message);
+        }
+    }
+
+    /// 记录一次带事件标记的推送
+    pub fn record(&self, update: &IncidentUpdate, message: &MessageType) {
+        let now = Utc::now();
+        let text = summarize(message);
+        let entry = IncidentEntry {
+            status: update.status,
+            message: text.clone(),
+            timestamp: now,
+        };
+        let resolved_at = (update.status == IncidentStatus::Resolved).then_some(now);
+
+        let mut incidents = self.incidents.lock().unwrap();
+        if let Some(incident) = incidents.iter_mut().find(|i| i.id == update.id) {
+            if let Some(title) = &update.title {
+                incident.title = title.clone();
+            }
+            if update.component.is_some() {
+                incident.component = update.component.clone();
+            }
+            incident.status = update.status;
+            incident.updated_at = now;
+            incident.resolved_at = resolved_at;
+            incident.updates.push(entry);
+            return;
+        }
+
+        incidents.push_front(Incident {
+            id: update.id.clone(),
+            title: update.title.clone().unwrap_or(text),
+            component: update.component.clone(),
+            status: update.status,
+            started_at: now,
+            updated_at: now,
+            resolved_at,
+            updates: vec![entry],
+        });
+        incidents.truncate(self.config.max_incidents);
+    }
+
+    fn summary(&self) -> StatusSummary {
+        let incidents: Vec<Incident> = self.incidents.lock().unwrap().iter().cloned().collect();
+        StatusSummary {
+            title: self.config.title.clone(),
+            operational: incidents
+                .iter()
+                .all(|i| i.status == IncidentStatus::Resolved),
+            incidents,
+        }
+    }
+}
+
+/// 提取消息的文本摘要
+fn summarize(message: &MessageType) -> String {
+    match message {
+        MessageType::Text(content) | MessageType::Markdown(content) => content.clone(),
+        MessageType::Html(content) => html_to_markdown(content),
+        MessageType::Rich { title, content, .. } => format!("{}\n{}", title, content),
+        MessageType::Image { url, caption } => caption.clone().unwrap_or_else(|| url.clone()),
+        MessageType::Link {
+            title, description, ..
+        } => format!("{}\n{}", title, description),
+        MessageType::File { name, .. } | MessageType::Template { name, .. } => name.clone(),
+        MessageType::Audio { caption, .. } | MessageType::Video { caption, .. } => {
+            caption.clone().unwrap_or_default()
+        }
+        MessageType::Location { lat, lon, label } => match label {
+            Some(label) => format!("{} ({}, {})", label, lat, lon),
+            None => format!("{}, {}", lat, lon),
+        },
+        MessageType::Card {
+            title, sections, ..
+        } => {
+            let mut lines = vec![title.clone()];
+            lines.extend(sections.iter().map(|s| s.content.clone()));
+            lines.join("\n")
+        }
+    }
+}
+
+#[get("/status.json")]
+async fn status_json(page: web::Data<StatusPage>) -> impl Responder {
+    HttpResponse::Ok().json(page.summary())
+}
+
+#[get("/status")]
+async fn status_html(page: web::Data<StatusPage>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(render_html(&page.summary()))
+}
+
+fn render_html(summary: &StatusSummary) -> String {
+    let banner = if summary.operational {
+        r#"<div class="banner ok">All systems operational</div>"#
+    } else {
+        r#"<div class="banner down">Some systems are experiencing issues</div>"#
+    };
+
+    let mut incidents = String::new();
+    for incident in &summary.incidents {
+        let component = incident
+            .component
+            .as_ref()
+            .map(|c| format!(" <small>({})</small>", escape(c)))
+            .unwrap_or_default();
+        let class = if incident.status == IncidentStatus::Resolved {
+            "ok"
+        } else {
+            "down"
+        };
+        incidents.push_str(&format!(
+            "<section><h2>{}{}</h2><p class=\"{}\">{}</p><ul>",
+            escape(&incident.title),
+            component,
+            class,
+            incident.status.label()
+        ));
+        for update in incident.updates.iter().rev() {
+            incidents.push_str(&format!(
+                "<li><b>{}</b> <time>{}</time><br>{}</li>",
+                update.status.label(),
+                update.timestamp.format("%Y-%m-%d %H:%M UTC"),
+                escape(&update.message).replace('\n', "<br>")
+            ));
+        }
+        incidents.push_str("</ul></section>");
+    }
+    if summary.incidents.is_empty() {
+        incidents.push_str("<p>No incidents reported.</p>");
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>{title}</title>
+<style>body{{font-family:sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem}}
+.banner{{padding:1rem;border-radius:4px;color:#fff}}.banner.ok{{background:#2e7d32}}.banner.down{{background:#c62828}}
+p.ok{{color:#2e7d32}}p.down{{color:#c62828}}time{{color:#777}}</style></head>
+<body><h1>{title}</h1>{banner}{incidents}</body></html>"#,
+        title = escape(&summary.title),
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(id: &str, status: IncidentStatus) -> IncidentUpdate {
+        IncidentUpdate {
+            id: id.to_string(),
+            title: None,
+            status,
+            component: Some("api".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_incident_lifecycle() {
+        let page = StatusPage::new(StatusPageConfig {
+            title: "Status".to_string(),
+            max_incidents: 10,
+        });
+        page.record(
+            &update("inc-1", IncidentStatus::Investigating),
+            &MessageType::Text("API errors".to_string()),
+        );
+        assert!(!page.summary().operational);
+
+        page.record(
+            &update("inc-1", IncidentStatus::Resolved),
+            &MessageType::Text("Fixed".to_string()),
+        );
+        let summary = page.summary();
+        assert!(summary.operational);
+        assert_eq!(summary.incidents.len(), 1);
+        assert_eq!(summary.incidents[0].title, "API errors");
+        assert_eq!(summary.incidents[0].updates.len(), 2);
+        assert!(summary.incidents[0].resolved_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_push_not_recorded() {
+        use crate::dispatch::Dispatcher;
+        use common::{MessageKind, PlatformRegistry};
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let mut registry = PlatformRegistry::new();
+        registry.register(Box::new(
+            crate::testing::MockFactory::new(&[MessageKind::Text], &[]).failing(),
+        ));
+        let dispatcher = Dispatcher::new(Arc::new(registry), HashMap::new(), Duration::ZERO);
+        let page = StatusPage::new(StatusPageConfig {
+            title: "Status".to_string(),
+            max_incidents: 10,
+        });
+        let message = MessageType::Text("API errors".to_string());
+        let result = dispatcher
+            .send_to_platform("mock", serde_json::json!({}), message.clone())
+            .await;
+        assert!(result.is_err());
+        page.record_delivered(
+            &update("inc-1", IncidentStatus::Investigating),
+            &message,
+            &result,
+        );
+        assert!(page.summary().operational);
+        assert!(page.summary().incidents.is_empty());
+
+        let queued = Ok(PushResult::default());
+        page.record_delivered(
+            &update("inc-1", IncidentStatus::Investigating),
+            &message,
+            &queued,
+        );
+        assert!(page.summary().incidents.is_empty());
+    }
+
+    #[test]
+    fn test_max_incidents_and_html_escaping() {
+        let page = StatusPage::new(StatusPageConfig {
+            title: "<Status>".to_string(),
+            max_incidents: 1,
+        });
+        for id in ["a", "b"] {
+            page.record(
+                &update(id, IncidentStatus::Identified),
+                &MessageType::Text(format!("incident {}", id)),
+            );
+        }
+        let summary = page.summary();
+        assert_eq!(summary.incidents.len(), 1);
+        assert_eq!(summary.incidents[0].id, "b");
+
+        let html = render_html(&summary);
+        assert!(html.contains("<title>&lt;Status&gt;</title>"));
+        assert!(html.contains("incident b"));
+    }
+}
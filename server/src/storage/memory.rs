@@ -0,0 +1,124 @@
+use super::{MessageRecord, Schedule, Storage};
+use crate::silence::Silence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{AccessToken, MemoryTokenCache, PushError, TemplateDefinition, TokenCache};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// 保留的投递记录条数上限
+const MAX_HISTORY: usize = 10_000;
+
+/// 进程内存储，重启后数据丢失
+#[derive(Default)]
+pub struct MemoryStorage {
+    messages: Mutex<VecDeque<MessageRecord>>,
+    schedules: Mutex<HashMap<String, Schedule>>,
+    silences: Mutex<HashMap<String, Silence>>,
+    templates: Mutex<HashMap<String, TemplateDefinition>>,
+    tokens: MemoryTokenCache,
+}
+
+#[async_trait]
+impl TokenCache for MemoryStorage {
+    async fn get(&self, key: &str) -> Result<Option<AccessToken>, PushError> {
+        self.tokens.get(key).await
+    }
+
+    async fn set(&self, key: &str, token: &AccessToken) -> Result<(), PushError> {
+        self.tokens.set(key, token).await
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), PushError> {
+        self.tokens.remove(key).await
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn save_message(&self, record: &MessageRecord) -> Result<(), PushError> {
+        let mut messages = self.messages.lock().unwrap();
+        messages.push_back(record.clone());
+        if messages.len() > MAX_HISTORY {
+            messages.pop_front();
+        }
+        Ok(())
+    }
+
+    async fn list_messages(
+        &self,
+        limit: usize,
+        labels: &HashMap<String, String>,
+    ) -> Result<Vec<MessageRecord>, PushError> {
+        let mut messages: Vec<MessageRecord> = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|record| record.has_labels(labels))
+            .cloned()
+            .collect();
+        messages.sort_by_key(|record| std::cmp::Reverse(record.created_at));
+        messages.truncate(limit);
+        Ok(messages)
+    }
+
+    async fn save_schedule(&self, schedule: &Schedule) -> Result<(), PushError> {
+        self.schedules
+            .lock()
+            .unwrap()
+            .insert(schedule.id.clone(), schedule.clone());
+        Ok(())
+    }
+
+    async fn due_schedules(&self, now: DateTime<Utc>) -> Result<Vec<Schedule>, PushError> {
+        let mut due: Vec<Schedule> = self
+            .schedules
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|schedule| schedule.send_at <= now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|schedule| schedule.send_at);
+        Ok(due)
+    }
+
+    async fn delete_schedule(&self, id: &str) -> Result<bool, PushError> {
+        Ok(self.schedules.lock().unwrap().remove(id).is_some())
+    }
+
+    async fn save_silence(&self, silence: &Silence) -> Result<(), PushError> {
+        self.silences
+            .lock()
+            .unwrap()
+            .insert(silence.id.clone(), silence.clone());
+        Ok(())
+    }
+
+    async fn delete_silence(&self, id: &str) -> Result<bool, PushError> {
+        Ok(self.silences.lock().unwrap().remove(id).is_some())
+    }
+
+    async fn list_silences(&self, now: DateTime<Utc>) -> Result<Vec<Silence>, PushError> {
+        let mut silences = self.silences.lock().unwrap();
+        silences.retain(|_, silence| silence.until > now);
+        Ok(silences.values().cloned().collect())
+    }
+
+    async fn save_template(
+        &self,
+        name: &str,
+        template: &TemplateDefinition,
+    ) -> Result<(), PushError> {
+        self.templates
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), template.clone());
+        Ok(())
+    }
+
+    async fn list_templates(&self) -> Result<HashMap<String, TemplateDefinition>, PushError> {
+        Ok(self.templates.lock().unwrap().clone())
+    }
+}
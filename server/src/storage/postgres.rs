@@ -0,0 +1,119 @@
+use super::sql::{self, impl_sql_dedup, impl_sql_lock, impl_sql_queue, impl_sql_storage};
+use super::{MessageRecord, Schedule, Storage};
+use crate::queue::{Queue, QueueItem, QueueStats};
+use crate::silence::Silence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{PushError, TemplateDefinition};
+use log::*;
+use sqlx::Row;
+use sqlx::postgres::{PgListener, PgPool, PgPoolOptions};
+use std::time::Duration;
+
+/// 入队通知的频道，由 `push_queue` 表的触发器发出
+const QUEUE_CHANNEL: &str = "push_queue";
+
+/// Postgres 存储，多个副本可以共享；队列领取使用 `FOR UPDATE SKIP LOCKED`，
+/// 入队通过 `LISTEN/NOTIFY` 唤醒所有副本的 worker
+pub struct PostgresStorage {
+    pool: PgPool,
+    listener: tokio::sync::Mutex<Option<PgListener>>,
+}
+
+impl PostgresStorage {
+    /// 连接数据库并执行迁移，迁移期间持有 advisory lock，多副本同时启动时只执行一次
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, PushError> {
+        let pool = PgPoolOptions::new()
+            .max_connections(max_connections.max(1))
+            .connect(url)
+            .await
+            .map_err(sql::storage_error)?;
+        sqlx::migrate!("migrations/postgres")
+            .run(&pool)
+            .await
+            .map_err(|e| PushError::ConfigError(format!("Failed to migrate storage: {}", e)))?;
+        Ok(Self {
+            pool,
+            listener: tokio::sync::Mutex::new(None),
+        })
+    }
+
+    async fn listen(&self) -> Result<PgListener, sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(QUEUE_CHANNEL).await?;
+        Ok(listener)
+    }
+}
+
+impl_sql_storage!(PostgresStorage);
+impl_sql_lock!(PostgresStorage);
+impl_sql_dedup!(PostgresStorage);
+impl_sql_queue!(
+    PostgresStorage,
+    "UPDATE push_queue SET leased_until = $2, attempts = attempts + 1
+     WHERE id IN (SELECT id FROM push_queue
+                  WHERE failed_at IS NULL AND leased_until IS NULL AND available_at <= $1
+                  ORDER BY available_at LIMIT $3 FOR UPDATE SKIP LOCKED)
+     RETURNING id, channel, message, attempts, available_at, leased_until, failed_at,
+               last_error, created_at"
+);
+
+#[async_trait]
+impl Queue for PostgresStorage {
+    async fn enqueue(&self, item: &QueueItem) -> Result<(), PushError> {
+        self.enqueue_item(item).await
+    }
+
+    async fn claim(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<QueueItem>, PushError> {
+        self.claim_items(now, limit, lease).await
+    }
+
+    async fn complete(&self, id: &str) -> Result<(), PushError> {
+        self.complete_item(id).await
+    }
+
+    async fn retry(&self, id: &str, available_at: DateTime<Utc>) -> Result<(), PushError> {
+        self.retry_item(id, available_at).await
+    }
+
+    async fn reclaim(&self, now: DateTime<Utc>) -> Result<usize, PushError> {
+        self.reclaim_items(now).await
+    }
+
+    async fn bury(&self, id: &str, error: &str) -> Result<(), PushError> {
+        self.bury_item(id, error).await
+    }
+
+    async fn stats(&self) -> Result<QueueStats, PushError> {
+        self.queue_stats().await
+    }
+
+    async fn wait(&self, timeout: Duration) {
+        let mut listener = self.listener.lock().await;
+        if listener.is_none() {
+            match self.listen().await {
+                Ok(connected) => *listener = Some(connected),
+                Err(e) => {
+                    warn!("Failed to listen for queue notifications: {}", e);
+                    tokio::time::sleep(timeout).await;
+                    return;
+                }
+            }
+        }
+        let Some(connected) = listener.as_mut() else {
+            return;
+        };
+        match tokio::time::timeout(timeout, connected.recv()).await {
+            Ok(Ok(_)) | Err(_) => {}
+            Ok(Err(e)) => {
+                warn!("Queue notification listener failed: {}", e);
+                *listener = None;
+            }
+        }
+    }
+}
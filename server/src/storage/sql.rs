@@ -0,0 +1,531 @@
+//! SQLite 和 Postgres 共用的查询，表结构见 `migrations` 目录；时间存为毫秒时间戳，结构化字段存为 JSON 文本
+
+use chrono::{DateTime, Utc};
+use common::PushError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub fn storage_error(e: sqlx::Error) -> PushError {
+    PushError::PlatformError(format!("Storage error: {}", e))
+}
+
+pub fn to_json(value: &impl Serialize) -> Result<String, PushError> {
+    serde_json::to_string(value)
+        .map_err(|e| PushError::MessageError(format!("Failed to serialize record: {}", e)))
+}
+
+pub fn from_json<T: DeserializeOwned>(value: &str) -> Result<T, PushError> {
+    serde_json::from_str(value)
+        .map_err(|e| PushError::PlatformError(format!("Corrupted stored record: {}", e)))
+}
+
+pub fn millis(time: DateTime<Utc>) -> i64 {
+    time.timestamp_millis()
+}
+
+pub fn from_millis(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+/// 为带有 `pool` 字段的存储实现 [`Storage`](super::Storage) 和 `TokenCache`
+macro_rules! impl_sql_storage {
+    ($storage:ty) => {
+        #[async_trait::async_trait]
+        impl common::TokenCache for $storage {
+            async fn get(&self, key: &str) -> Result<Option<common::AccessToken>, PushError> {
+                let row =
+                    sqlx::query("SELECT value, expires_at FROM push_tokens WHERE token_key = $1")
+                        .bind(key)
+                        .fetch_optional(&self.pool)
+                        .await
+                        .map_err(sql::storage_error)?;
+                row.map(|row| {
+                    Ok(common::AccessToken {
+                        value: row.try_get("value").map_err(sql::storage_error)?,
+                        expires_at: sql::from_millis(
+                            row.try_get("expires_at").map_err(sql::storage_error)?,
+                        ),
+                    })
+                })
+                .transpose()
+            }
+
+            async fn set(&self, key: &str, token: &common::AccessToken) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_tokens (token_key, value, expires_at) VALUES ($1, $2, $3)
+                     ON CONFLICT (token_key) DO UPDATE
+                     SET value = excluded.value, expires_at = excluded.expires_at",
+                )
+                .bind(key)
+                .bind(&token.value)
+                .bind(sql::millis(token.expires_at))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn remove(&self, key: &str) -> Result<(), PushError> {
+                sqlx::query("DELETE FROM push_tokens WHERE token_key = $1")
+                    .bind(key)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(())
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl Storage for $storage {
+            async fn save_message(&self, record: &MessageRecord) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_messages
+                     (id, target, platform, labels, success, message_id, response, dry_run, created_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                )
+                .bind(&record.id)
+                .bind(&record.target)
+                .bind(&record.platform)
+                .bind(sql::to_json(&record.labels)?)
+                .bind(record.success)
+                .bind(&record.message_id)
+                .bind(&record.response)
+                .bind(record.dry_run)
+                .bind(sql::millis(record.created_at))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn list_messages(
+                &self,
+                limit: usize,
+                labels: &std::collections::HashMap<String, String>,
+            ) -> Result<Vec<MessageRecord>, PushError> {
+                use futures::TryStreamExt;
+
+                // 标签存为 JSON 文本，按时间倒序逐行读取并过滤，取够条数即停止
+                let mut rows = sqlx::query(
+                    "SELECT id, target, platform, labels, success, message_id, response, dry_run,
+                     created_at FROM push_messages ORDER BY created_at DESC",
+                )
+                .fetch(&self.pool);
+                let mut records = Vec::new();
+                while records.len() < limit
+                    && let Some(row) = rows.try_next().await.map_err(sql::storage_error)?
+                {
+                    let stored: String = row.try_get("labels").map_err(sql::storage_error)?;
+                    let record = MessageRecord {
+                        id: row.try_get("id").map_err(sql::storage_error)?,
+                        target: row.try_get("target").map_err(sql::storage_error)?,
+                        platform: row.try_get("platform").map_err(sql::storage_error)?,
+                        labels: sql::from_json(&stored)?,
+                        success: row.try_get("success").map_err(sql::storage_error)?,
+                        message_id: row.try_get("message_id").map_err(sql::storage_error)?,
+                        response: row.try_get("response").map_err(sql::storage_error)?,
+                        dry_run: row.try_get("dry_run").map_err(sql::storage_error)?,
+                        created_at: sql::from_millis(
+                            row.try_get("created_at").map_err(sql::storage_error)?,
+                        ),
+                    };
+                    if record.has_labels(labels) {
+                        records.push(record);
+                    }
+                }
+                Ok(records)
+            }
+
+            async fn save_schedule(&self, schedule: &Schedule) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_schedules (id, channel, message, send_at)
+                     VALUES ($1, $2, $3, $4)
+                     ON CONFLICT (id) DO UPDATE SET channel = excluded.channel,
+                     message = excluded.message, send_at = excluded.send_at",
+                )
+                .bind(&schedule.id)
+                .bind(&schedule.channel)
+                .bind(sql::to_json(&schedule.message)?)
+                .bind(sql::millis(schedule.send_at))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn due_schedules(
+                &self,
+                now: chrono::DateTime<chrono::Utc>,
+            ) -> Result<Vec<Schedule>, PushError> {
+                let rows = sqlx::query(
+                    "SELECT id, channel, message, send_at FROM push_schedules
+                     WHERE send_at <= $1 ORDER BY send_at",
+                )
+                .bind(sql::millis(now))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                rows.iter()
+                    .map(|row| {
+                        let message: String = row.try_get("message").map_err(sql::storage_error)?;
+                        Ok(Schedule {
+                            id: row.try_get("id").map_err(sql::storage_error)?,
+                            channel: row.try_get("channel").map_err(sql::storage_error)?,
+                            message: sql::from_json(&message)?,
+                            send_at: sql::from_millis(
+                                row.try_get("send_at").map_err(sql::storage_error)?,
+                            ),
+                        })
+                    })
+                    .collect()
+            }
+
+            async fn delete_schedule(&self, id: &str) -> Result<bool, PushError> {
+                let result = sqlx::query("DELETE FROM push_schedules WHERE id = $1")
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn save_silence(&self, silence: &Silence) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_silences (id, matchers, until_at, created_by)
+                     VALUES ($1, $2, $3, $4)
+                     ON CONFLICT (id) DO UPDATE SET matchers = excluded.matchers,
+                     until_at = excluded.until_at, created_by = excluded.created_by",
+                )
+                .bind(&silence.id)
+                .bind(sql::to_json(&silence.matchers)?)
+                .bind(sql::millis(silence.until))
+                .bind(&silence.created_by)
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn delete_silence(&self, id: &str) -> Result<bool, PushError> {
+                let result = sqlx::query("DELETE FROM push_silences WHERE id = $1")
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn list_silences(
+                &self,
+                now: chrono::DateTime<chrono::Utc>,
+            ) -> Result<Vec<Silence>, PushError> {
+                sqlx::query("DELETE FROM push_silences WHERE until_at <= $1")
+                    .bind(sql::millis(now))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                let rows =
+                    sqlx::query("SELECT id, matchers, until_at, created_by FROM push_silences")
+                        .fetch_all(&self.pool)
+                        .await
+                        .map_err(sql::storage_error)?;
+                rows.iter()
+                    .map(|row| {
+                        let matchers: String =
+                            row.try_get("matchers").map_err(sql::storage_error)?;
+                        Ok(Silence {
+                            id: row.try_get("id").map_err(sql::storage_error)?,
+                            matchers: sql::from_json(&matchers)?,
+                            until: sql::from_millis(
+                                row.try_get("until_at").map_err(sql::storage_error)?,
+                            ),
+                            created_by: row.try_get("created_by").map_err(sql::storage_error)?,
+                        })
+                    })
+                    .collect()
+            }
+
+            async fn save_template(
+                &self,
+                name: &str,
+                template: &TemplateDefinition,
+            ) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_templates (name, definition) VALUES ($1, $2)
+                     ON CONFLICT (name) DO UPDATE SET definition = excluded.definition",
+                )
+                .bind(name)
+                .bind(sql::to_json(template)?)
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn list_templates(
+                &self,
+            ) -> Result<std::collections::HashMap<String, TemplateDefinition>, PushError> {
+                let rows = sqlx::query("SELECT name, definition FROM push_templates")
+                    .fetch_all(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                rows.iter()
+                    .map(|row| {
+                        let definition: String =
+                            row.try_get("definition").map_err(sql::storage_error)?;
+                        Ok((
+                            row.try_get("name").map_err(sql::storage_error)?,
+                            sql::from_json(&definition)?,
+                        ))
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+/// 为带有 `pool` 字段的存储实现 [`Queue`](crate::queue::Queue) 除 `wait` 以外的方法，
+/// `$claim` 为领取到期条目的语句
+macro_rules! impl_sql_queue {
+    ($storage:ty, $claim:expr) => {
+        impl $storage {
+            async fn enqueue_item(&self, item: &QueueItem) -> Result<(), PushError> {
+                sqlx::query(
+                    "INSERT INTO push_queue
+                     (id, channel, message, attempts, available_at, leased_until, created_at)
+                     VALUES ($1, $2, $3, $4, $5, NULL, $6)
+                     ON CONFLICT (id) DO UPDATE SET message = excluded.message,
+                     attempts = excluded.attempts, available_at = excluded.available_at,
+                     leased_until = NULL, failed_at = NULL, last_error = NULL",
+                )
+                .bind(&item.id)
+                .bind(&item.channel)
+                .bind(sql::to_json(&item.message)?)
+                .bind(item.attempts as i32)
+                .bind(sql::millis(item.available_at))
+                .bind(sql::millis(item.created_at))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn claim_items(
+                &self,
+                now: chrono::DateTime<chrono::Utc>,
+                limit: usize,
+                lease: std::time::Duration,
+            ) -> Result<Vec<QueueItem>, PushError> {
+                let lease = chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+                let rows = sqlx::query($claim)
+                    .bind(sql::millis(now))
+                    .bind(sql::millis(now + lease))
+                    .bind(limit as i64)
+                    .fetch_all(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                let mut items = rows
+                    .iter()
+                    .map(|row| {
+                        let message: String = row.try_get("message").map_err(sql::storage_error)?;
+                        let attempts: i32 = row.try_get("attempts").map_err(sql::storage_error)?;
+                        Ok(QueueItem {
+                            id: row.try_get("id").map_err(sql::storage_error)?,
+                            channel: row.try_get("channel").map_err(sql::storage_error)?,
+                            message: sql::from_json(&message)?,
+                            attempts: attempts as u32,
+                            available_at: sql::from_millis(
+                                row.try_get("available_at").map_err(sql::storage_error)?,
+                            ),
+                            leased_until: row
+                                .try_get::<Option<i64>, _>("leased_until")
+                                .map_err(sql::storage_error)?
+                                .map(sql::from_millis),
+                            failed_at: row
+                                .try_get::<Option<i64>, _>("failed_at")
+                                .map_err(sql::storage_error)?
+                                .map(sql::from_millis),
+                            last_error: row.try_get("last_error").map_err(sql::storage_error)?,
+                            created_at: sql::from_millis(
+                                row.try_get("created_at").map_err(sql::storage_error)?,
+                            ),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, PushError>>()?;
+                // RETURNING 不保证顺序
+                items.sort_by_key(|item| item.created_at);
+                Ok(items)
+            }
+
+            async fn complete_item(&self, id: &str) -> Result<(), PushError> {
+                sqlx::query("DELETE FROM push_queue WHERE id = $1")
+                    .bind(id)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn retry_item(
+                &self,
+                id: &str,
+                available_at: chrono::DateTime<chrono::Utc>,
+            ) -> Result<(), PushError> {
+                sqlx::query(
+                    "UPDATE push_queue SET available_at = $2, leased_until = NULL WHERE id = $1",
+                )
+                .bind(id)
+                .bind(sql::millis(available_at))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn reclaim_items(
+                &self,
+                now: chrono::DateTime<chrono::Utc>,
+            ) -> Result<usize, PushError> {
+                let result = sqlx::query(
+                    "UPDATE push_queue SET available_at = $1, leased_until = NULL
+                     WHERE leased_until <= $1 AND failed_at IS NULL",
+                )
+                .bind(sql::millis(now))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() as usize)
+            }
+
+            async fn bury_item(&self, id: &str, error: &str) -> Result<(), PushError> {
+                sqlx::query(
+                    "UPDATE push_queue SET failed_at = $2, last_error = $3, leased_until = NULL
+                     WHERE id = $1",
+                )
+                .bind(id)
+                .bind(sql::millis(Utc::now()))
+                .bind(error)
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn queue_stats(&self) -> Result<QueueStats, PushError> {
+                let rows = sqlx::query(
+                    "SELECT channel, failed_at IS NOT NULL AS dead, COUNT(*) AS count
+                     FROM push_queue GROUP BY channel, failed_at IS NOT NULL",
+                )
+                .fetch_all(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                let mut stats = QueueStats::default();
+                for row in rows {
+                    let count: i64 = row.try_get("count").map_err(sql::storage_error)?;
+                    if row.try_get("dead").map_err(sql::storage_error)? {
+                        stats.dead_letters += count as usize;
+                    } else {
+                        stats.pending.insert(
+                            row.try_get("channel").map_err(sql::storage_error)?,
+                            count as usize,
+                        );
+                    }
+                }
+                Ok(stats)
+            }
+        }
+    };
+}
+
+/// 为带有 `pool` 字段的存储实现 [`Lock`](crate::lock::Lock)，锁保存在 `push_locks` 表中
+macro_rules! impl_sql_lock {
+    ($storage:ty) => {
+        #[async_trait]
+        impl crate::lock::Lock for $storage {
+            async fn acquire(
+                &self,
+                name: &str,
+                owner: &str,
+                ttl: std::time::Duration,
+            ) -> Result<bool, PushError> {
+                let now = Utc::now();
+                let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+                // 锁已过期或由自己持有时才覆盖
+                let result = sqlx::query(
+                    "INSERT INTO push_locks (name, owner, expires_at) VALUES ($1, $2, $3)
+                     ON CONFLICT (name) DO UPDATE SET owner = excluded.owner,
+                     expires_at = excluded.expires_at
+                     WHERE push_locks.owner = excluded.owner OR push_locks.expires_at <= $4",
+                )
+                .bind(name)
+                .bind(owner)
+                .bind(sql::millis(now + ttl))
+                .bind(sql::millis(now))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn release(&self, name: &str, owner: &str) -> Result<(), PushError> {
+                sqlx::query("DELETE FROM push_locks WHERE name = $1 AND owner = $2")
+                    .bind(name)
+                    .bind(owner)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+/// 为带有 `pool` 字段的存储实现 [`DedupStore`](crate::dedup::DedupStore)，记录保存在 `push_dedup` 表中
+macro_rules! impl_sql_dedup {
+    ($storage:ty) => {
+        #[async_trait]
+        impl crate::dedup::DedupStore for $storage {
+            async fn insert(&self, key: &str, ttl: std::time::Duration) -> Result<bool, PushError> {
+                let now = Utc::now();
+                let ttl = chrono::Duration::from_std(ttl.min(crate::dedup::MAX_TTL))
+                    .unwrap_or(chrono::Duration::MAX);
+                let expires_at = now
+                    .checked_add_signed(ttl)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC);
+                // 已过期的记录视为不存在
+                let result = sqlx::query(
+                    "INSERT INTO push_dedup (key, expires_at) VALUES ($1, $2)
+                     ON CONFLICT (key) DO UPDATE SET expires_at = excluded.expires_at
+                     WHERE push_dedup.expires_at <= $3",
+                )
+                .bind(key)
+                .bind(sql::millis(expires_at))
+                .bind(sql::millis(now))
+                .execute(&self.pool)
+                .await
+                .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn remove(&self, key: &str) -> Result<(), PushError> {
+                sqlx::query("DELETE FROM push_dedup WHERE key = $1")
+                    .bind(key)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(())
+            }
+
+            async fn purge(&self) -> Result<usize, PushError> {
+                let result = sqlx::query("DELETE FROM push_dedup WHERE expires_at <= $1")
+                    .bind(sql::millis(Utc::now()))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(sql::storage_error)?;
+                Ok(result.rows_affected() as usize)
+            }
+        }
+    };
+}
+
+pub(crate) use {impl_sql_dedup, impl_sql_lock, impl_sql_queue, impl_sql_storage};
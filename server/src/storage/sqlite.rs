@@ -0,0 +1,96 @@
+use super::sql::{self, impl_sql_dedup, impl_sql_lock, impl_sql_queue, impl_sql_storage};
+use super::{MessageRecord, Schedule, Storage};
+use crate::queue::{Queue, QueueItem, QueueStats};
+use crate::silence::Silence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::{PushError, TemplateDefinition};
+use sqlx::Row;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// SQLite 存储，适合单副本部署
+pub struct SqliteStorage {
+    pool: SqlitePool,
+    /// 本进程入队时唤醒 worker
+    notify: Notify,
+}
+
+impl SqliteStorage {
+    /// 连接数据库并执行迁移，文件不存在时自动创建
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, PushError> {
+        let options = SqliteConnectOptions::from_str(url)
+            .map_err(|e| PushError::ConfigError(format!("Invalid SQLite URL: {}", e)))?
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections.max(1))
+            .connect_with(options)
+            .await
+            .map_err(sql::storage_error)?;
+        sqlx::migrate!("migrations/sqlite")
+            .run(&pool)
+            .await
+            .map_err(|e| PushError::ConfigError(format!("Failed to migrate storage: {}", e)))?;
+        Ok(Self {
+            pool,
+            notify: Notify::new(),
+        })
+    }
+}
+
+impl_sql_storage!(SqliteStorage);
+impl_sql_lock!(SqliteStorage);
+impl_sql_dedup!(SqliteStorage);
+impl_sql_queue!(
+    SqliteStorage,
+    "UPDATE push_queue SET leased_until = $2, attempts = attempts + 1
+     WHERE id IN (SELECT id FROM push_queue
+                  WHERE failed_at IS NULL AND leased_until IS NULL AND available_at <= $1
+                  ORDER BY available_at LIMIT $3)
+     RETURNING id, channel, message, attempts, available_at, leased_until, failed_at,
+               last_error, created_at"
+);
+
+#[async_trait]
+impl Queue for SqliteStorage {
+    async fn enqueue(&self, item: &QueueItem) -> Result<(), PushError> {
+        self.enqueue_item(item).await?;
+        self.notify.notify_waiters();
+        Ok(())
+    }
+
+    async fn claim(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        lease: Duration,
+    ) -> Result<Vec<QueueItem>, PushError> {
+        self.claim_items(now, limit, lease).await
+    }
+
+    async fn complete(&self, id: &str) -> Result<(), PushError> {
+        self.complete_item(id).await
+    }
+
+    async fn retry(&self, id: &str, available_at: DateTime<Utc>) -> Result<(), PushError> {
+        self.retry_item(id, available_at).await
+    }
+
+    async fn reclaim(&self, now: DateTime<Utc>) -> Result<usize, PushError> {
+        self.reclaim_items(now).await
+    }
+
+    async fn bury(&self, id: &str, error: &str) -> Result<(), PushError> {
+        self.bury_item(id, error).await
+    }
+
+    async fn stats(&self) -> Result<QueueStats, PushError> {
+        self.queue_stats().await
+    }
+
+    async fn wait(&self, timeout: Duration) {
+        let _ = tokio::time::timeout(timeout, self.notify.notified()).await;
+    }
+}
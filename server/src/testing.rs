@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use common::{
+    DeliveryStatus, Mention, MessageKind, MessageType, PlatformContext, PlatformFactory,
+    PlatformInfo, PushError, PushPlatformCapabilities, PushResult, Receipt,
+};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 按声明的平台信息创建模拟平台，所有实例共享发送记录；
+/// 每次创建的都是新实例，可以模拟实例被缓存淘汰后重建
+pub struct MockFactory {
+    info: PlatformInfo,
+    sent: Arc<Mutex<Vec<MessageType>>>,
+    calls: Arc<AtomicU32>,
+    failing: bool,
+}
+
+impl MockFactory {
+    /// 名为 `mock`、支持指定消息类型与特性的平台
+    pub fn new(message_types: &[MessageKind], features: &[&str]) -> Self {
+        Self {
+            info: PlatformInfo {
+                name: "mock".to_string(),
+                version: "0".to_string(),
+                message_types: message_types.iter().copied().collect(),
+                mentions: Default::default(),
+                features: features.iter().map(|f| f.to_string()).collect(),
+                limits: Default::default(),
+                rate_limit: None,
+                markdown_dialect: Default::default(),
+            },
+            sent: Arc::default(),
+            calls: Arc::default(),
+            failing: false,
+        }
+    }
+
+    /// 每次发送都返回平台错误
+    pub fn failing(mut self) -> Self {
+        self.failing = true;
+        self
+    }
+
+    /// 各实例发出的消息
+    pub fn sent(&self) -> Arc<Mutex<Vec<MessageType>>> {
+        self.sent.clone()
+    }
+}
+
+impl PlatformFactory for MockFactory {
+    fn create(
+        &self,
+        _config: Value,
+        _context: &PlatformContext,
+    ) -> Result<Box<dyn PushPlatformCapabilities>, PushError> {
+        Ok(Box::new(MockPlatform {
+            info: self.info.clone(),
+            sent: self.sent.clone(),
+            calls: self.calls.clone(),
+            failing: self.failing,
+        }))
+    }
+
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+}
+
+/// 记录发出的消息；查询回执时把调用方记录的消息都视为接收方已确认
+pub struct MockPlatform {
+    info: PlatformInfo,
+    sent: Arc<Mutex<Vec<MessageType>>>,
+    calls: Arc<AtomicU32>,
+    failing: bool,
+}
+
+#[async_trait]
+impl PushPlatformCapabilities for MockPlatform {
+    async fn init(&mut self) -> Result<(), PushError> {
+        Ok(())
+    }
+
+    async fn send_text(&self, content: &str) -> Result<PushResult, PushError> {
+        self.send(MessageType::Text(content.to_string())).await
+    }
+
+    async fn send_text_with_mention(
+        &self,
+        content: &str,
+        _mentions: Vec<Mention>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(content).await
+    }
+
+    async fn send_markdown(&self, content: &str) -> Result<PushResult, PushError> {
+        self.send(MessageType::Markdown(content.to_string())).await
+    }
+
+    async fn send_rich(
+        &self,
+        title: &str,
+        content: &str,
+        _url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(&format!("{}\n{}", title, content)).await
+    }
+
+    async fn send_image(
+        &self,
+        image_url: &str,
+        _caption: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(image_url).await
+    }
+
+    async fn send_link(
+        &self,
+        title: &str,
+        _description: &str,
+        url: &str,
+        _image_url: Option<&str>,
+    ) -> Result<PushResult, PushError> {
+        self.send_text(&format!("{} {}", title, url)).await
+    }
+
+    async fn send(&self, message: MessageType) -> Result<PushResult, PushError> {
+        if self.failing {
+            return Err(PushError::PlatformError("mock failure".to_string()));
+        }
+        let call = self.calls.fetch_add(1, Ordering::SeqCst);
+        self.sent.lock().unwrap().push(message);
+        Ok(PushResult {
+            message_id: Some(format!("mock-{}", call)),
+            success: true,
+            ..Default::default()
+        })
+    }
+
+    async fn poll_receipts_for(&self, message_ids: &[String]) -> Result<Vec<Receipt>, PushError> {
+        Ok(message_ids
+            .iter()
+            .map(|id| Receipt::new(id.clone(), DeliveryStatus::Acknowledged))
+            .collect())
+    }
+
+    async fn health_check(&self) -> Result<bool, PushError> {
+        Ok(true)
+    }
+
+    fn platform_info(&self) -> PlatformInfo {
+        self.info.clone()
+    }
+}
@@ -0,0 +1,357 @@
+use crate::api::Violation;
+use crate::error::{ApiError, ErrorCode};
+use actix_web::HttpRequest;
+use actix_web::error::JsonPayloadError;
+use actix_web::http::StatusCode;
+use common::{AttachmentSource, CardAction, Message, MessageType};
+use serde::{Deserialize, Serialize};
+
+/// 推送请求校验配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// 文本、Markdown、HTML 等正文的最大字节数
+    pub max_content_bytes: usize,
+    /// 标题、说明、按钮文字等短字段的最大字节数
+    pub max_title_bytes: usize,
+    /// 内联附件的最大字节数
+    pub max_attachment_bytes: usize,
+    /// 消息自带的单次超时上限（毫秒）
+    pub max_timeout_ms: u64,
+    /// 消息自带的重试次数上限，通道重试策略限定了最多发送次数时再按策略截断
+    pub max_retry_count: u32,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_content_bytes: 64 * 1024,
+            max_title_bytes: 1024,
+            max_attachment_bytes: 20 * 1024 * 1024,
+            max_timeout_ms: 5 * 60 * 1000,
+            max_retry_count: 10,
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// 校验消息，返回全部不合法的字段，为空表示通过
+    pub fn validate(&self, message: &Message) -> Vec<Violation> {
+        let mut validator = Validator {
+            config: self,
+            violations: Vec::new(),
+        };
+        validator.content(&message.content);
+        for (i, mention) in message.mentions.iter().enumerate() {
+            validator.title(&format!("mentions[{}]", i), &String::from(mention.clone()));
+        }
+        for (key, value) in &message.metadata {
+            validator.title(&format!("metadata.{}", key), value);
+        }
+        for (key, value) in &message.labels {
+            validator.title(&format!("labels.{}", key), value);
+        }
+        if let Some(thread_key) = &message.thread_key {
+            validator.title("thread_key", thread_key);
+        }
+        if message
+            .timeout_ms
+            .is_some_and(|ms| ms == 0 || ms > self.max_timeout_ms)
+        {
+            validator.violation(
+                "timeout_ms",
+                format!("Must be between 1 and {} ms", self.max_timeout_ms),
+            );
+        }
+        if let Some(retry_count) = message.retry_count.filter(|n| *n > self.max_retry_count) {
+            validator.violation(
+                "retry_count",
+                format!("Exceeds {} retries ({})", self.max_retry_count, retry_count),
+            );
+        }
+        if let Some(chart) = &message.chart {
+            validator.title("chart.name", &chart.name);
+            if let Err(e) = chart.validate() {
+                validator.violation("chart.points", e);
+            }
+        }
+        validator.violations
+    }
+
+    /// 校验失败时返回 422，`details.violations` 中列出全部不合法的字段
+    pub fn check(&self, message: &Message) -> Result<(), ApiError> {
+        let violations = self.validate(message);
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::ValidationFailed,
+            "Request validation failed",
+        )
+        .with_details(serde_json::json!({ "violations": violations })))
+    }
+}
+
+/// 请求体无法解析或超出大小限制时返回结构化的错误响应
+pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    let (status, code) = match &err {
+        JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+            (StatusCode::PAYLOAD_TOO_LARGE, ErrorCode::PayloadTooLarge)
+        }
+        JsonPayloadError::Deserialize(_) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidBody)
+        }
+        _ => (StatusCode::BAD_REQUEST, ErrorCode::InvalidBody),
+    };
+    ApiError::new(status, code, err.to_string()).into()
+}
+
+struct Validator<'a> {
+    config: &'a ValidationConfig,
+    violations: Vec<Violation>,
+}
+
+impl Validator<'_> {
+    fn content(&mut self, content: &MessageType) {
+        match content {
+            MessageType::Text(text) => self.text("message.text", text),
+            MessageType::Markdown(markdown) => self.text("message.markdown", markdown),
+            MessageType::Html(html) => self.text("message.html", html),
+            MessageType::Rich {
+                title,
+                content,
+                url,
+            } => {
+                self.title("message.title", title);
+                self.text("message.content", content);
+                if let Some(url) = url {
+                    self.url("message.url", url);
+                }
+            }
+            MessageType::Image { url, caption } => {
+                self.url("message.url", url);
+                if let Some(caption) = caption {
+                    self.title("message.caption", caption);
+                }
+            }
+            MessageType::Link {
+                title,
+                description,
+                url,
+                image_url,
+            } => {
+                self.title("message.title", title);
+                self.text("message.description", description);
+                self.url("message.url", url);
+                if let Some(image_url) = image_url {
+                    self.url("message.image_url", image_url);
+                }
+            }
+            MessageType::File { name, source, .. } => {
+                self.title("message.name", name);
+                self.source("message.source", source);
+            }
+            MessageType::Card {
+                title,
+                sections,
+                buttons,
+            } => {
+                self.title("message.title", title);
+                for (i, section) in sections.iter().enumerate() {
+                    let field = format!("message.sections[{}]", i);
+                    if let Some(title) = &section.title {
+                        self.title(&format!("{}.title", field), title);
+                    }
+                    self.text(&format!("{}.content", field), &section.content);
+                    if let Some(image_url) = &section.image_url {
+                        self.url(&format!("{}.image_url", field), image_url);
+                    }
+                }
+                for (i, button) in buttons.iter().enumerate() {
+                    let field = format!("message.buttons[{}]", i);
+                    self.title(&format!("{}.label", field), &button.label);
+                    match &button.action {
+                        CardAction::Url(url) => self.url(&format!("{}.action", field), url),
+                        CardAction::Callback(id) => self.title(&format!("{}.action", field), id),
+                    }
+                }
+            }
+            MessageType::Template { name, variables } => {
+                self.title("message.name", name);
+                for (key, value) in variables {
+                    self.text(&format!("message.variables.{}", key), value);
+                }
+            }
+            MessageType::Audio {
+                source, caption, ..
+            }
+            | MessageType::Video {
+                source, caption, ..
+            } => {
+                self.source("message.source", source);
+                if let Some(caption) = caption {
+                    self.title("message.caption", caption);
+                }
+            }
+            MessageType::Location { lat, lon, label } => {
+                if !(-90.0..=90.0).contains(lat) {
+                    self.violation("message.lat", "Latitude must be between -90 and 90");
+                }
+                if !(-180.0..=180.0).contains(lon) {
+                    self.violation("message.lon", "Longitude must be between -180 and 180");
+                }
+                if let Some(label) = label {
+                    self.title("message.label", label);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, field: &str, value: &str) {
+        self.check_len(field, value, self.config.max_content_bytes);
+    }
+
+    fn title(&mut self, field: &str, value: &str) {
+        self.check_len(field, value, self.config.max_title_bytes);
+    }
+
+    fn check_len(&mut self, field: &str, value: &str, max: usize) {
+        if value.len() > max {
+            self.violation(
+                field,
+                format!("Exceeds {} bytes ({} bytes)", max, value.len()),
+            );
+        }
+        // 换行和制表符是正文的正常组成部分
+        if value
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+        {
+            self.violation(field, "Contains control characters");
+        }
+    }
+
+    fn url(&mut self, field: &str, value: &str) {
+        match reqwest::Url::parse(value) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            Ok(url) => self.violation(field, format!("Unsupported URL scheme '{}'", url.scheme())),
+            Err(e) => self.violation(field, format!("Invalid URL: {}", e)),
+        }
+    }
+
+    fn source(&mut self, field: &str, source: &AttachmentSource) {
+        match source {
+            AttachmentSource::Bytes(bytes) if bytes.len() > self.config.max_attachment_bytes => {
+                self.violation(
+                    field,
+                    format!(
+                        "Exceeds {} bytes ({} bytes)",
+                        self.config.max_attachment_bytes,
+                        bytes.len()
+                    ),
+                )
+            }
+            AttachmentSource::Bytes(_) => {}
+            // 服务端不读取调用方指定的本地文件
+            AttachmentSource::Path(_) => self.violation(
+                field,
+                "Local file paths are not accepted, send bytes or a URL",
+            ),
+            AttachmentSource::Url(url) => self.url(field, url),
+        }
+    }
+
+    fn violation(&mut self, field: &str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{CardButton, CardSection};
+
+    fn fields(content: MessageType) -> Vec<String> {
+        ValidationConfig::default()
+            .validate(&Message::new(content))
+            .into_iter()
+            .map(|v| v.field)
+            .collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(fields(MessageType::Markdown("deploy\n**done**\t".to_string())).is_empty());
+        assert_eq!(
+            fields(MessageType::Text("a\u{0}b".to_string())),
+            ["message.text"]
+        );
+        assert_eq!(
+            fields(MessageType::Text("x".repeat(64 * 1024 + 1))),
+            ["message.text"]
+        );
+        assert_eq!(
+            fields(MessageType::Link {
+                title: "t".to_string(),
+                description: String::new(),
+                url: "javascript:alert(1)".to_string(),
+                image_url: Some("not a url".to_string()),
+            }),
+            ["message.url", "message.image_url"]
+        );
+        assert_eq!(
+            fields(MessageType::Card {
+                title: "t".to_string(),
+                sections: vec![CardSection {
+                    content: "c".to_string(),
+                    image_url: Some("ftp://host/a.png".to_string()),
+                    ..Default::default()
+                }],
+                buttons: vec![CardButton {
+                    label: "open".to_string(),
+                    action: CardAction::Url("https://example.com".to_string()),
+                }],
+            }),
+            ["message.sections[0].image_url"]
+        );
+        assert_eq!(
+            fields(MessageType::File {
+                name: "a.txt".to_string(),
+                mime: None,
+                source: AttachmentSource::Path("/etc/passwd".into()),
+            }),
+            ["message.source"]
+        );
+        assert_eq!(
+            fields(MessageType::Location {
+                lat: 91.0,
+                lon: 0.0,
+                label: None,
+            }),
+            ["message.lat"]
+        );
+    }
+
+    #[test]
+    fn test_validate_timeout_and_retries() {
+        let config = ValidationConfig::default();
+        let message = Message::new(MessageType::Text("heartbeat".to_string()))
+            .with_timeout(std::time::Duration::from_secs(2))
+            .with_retry_count(0);
+        assert!(config.validate(&message).is_empty());
+
+        let message = message
+            .with_timeout(std::time::Duration::from_secs(24 * 60 * 60))
+            .with_retry_count(1000);
+        let fields: Vec<_> = config
+            .validate(&message)
+            .into_iter()
+            .map(|v| v.field)
+            .collect();
+        assert_eq!(fields, ["timeout_ms", "retry_count"]);
+    }
+}